@@ -1,7 +1,7 @@
 // TODO: Implement DolaDocumentBuilder, StoryboardBuilder
 use crate::document::DolaDocument;
 use crate::error::DolaError;
-use crate::storyboard::{InterruptionPolicy, Storyboard, StoryboardEntry};
+use crate::storyboard::{InterruptionPolicy, ParamType, Storyboard, StoryboardEntry};
 use crate::transition::TransitionDef;
 use crate::validate::Validate;
 use crate::variable::AnimationVariableDef;
@@ -62,6 +62,7 @@ pub struct StoryboardBuilder {
     time_scale: f64,
     loop_count: Option<u32>,
     interruption_policy: InterruptionPolicy,
+    params: BTreeMap<String, ParamType>,
     entry: Vec<StoryboardEntry>,
 }
 
@@ -72,6 +73,7 @@ impl StoryboardBuilder {
             time_scale: 1.0,
             loop_count: None,
             interruption_policy: InterruptionPolicy::Conclude,
+            params: BTreeMap::new(),
             entry: Vec::new(),
         }
     }
@@ -94,6 +96,12 @@ impl StoryboardBuilder {
         self
     }
 
+    /// パラメータを宣言
+    pub fn param(mut self, name: impl Into<String>, ty: ParamType) -> Self {
+        self.params.insert(name.into(), ty);
+        self
+    }
+
     /// エントリを追加
     pub fn entry(mut self, entry: StoryboardEntry) -> Self {
         self.entry.push(entry);
@@ -106,6 +114,7 @@ impl StoryboardBuilder {
             time_scale: self.time_scale,
             loop_count: self.loop_count,
             interruption_policy: self.interruption_policy,
+            params: self.params,
             entry: self.entry,
         }
     }
@@ -4,7 +4,7 @@ use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 use crate::storyboard::Storyboard;
-use crate::transition::TransitionDef;
+use crate::transition::{TransitionDef, TransitionRef};
 use crate::variable::AnimationVariableDef;
 
 /// Dola ドキュメントのルートコンテナ
@@ -22,3 +22,13 @@ pub struct DolaDocument {
     #[serde(default)]
     pub storyboard: BTreeMap<String, Storyboard>,
 }
+
+impl DolaDocument {
+    /// トランジション参照を定義へ解決（名前参照はテンプレートから検索）
+    pub fn resolve_transition<'a>(&'a self, tref: &'a TransitionRef) -> Option<&'a TransitionDef> {
+        match tref {
+            TransitionRef::Inline(def) => Some(def),
+            TransitionRef::Named(name) => self.transition.get(name),
+        }
+    }
+}
@@ -58,3 +58,173 @@ pub enum ParametricEasing {
     /// 三次ベジェ補間（interpolation::cub_bez 準拠）
     CubicBezier { x0: f64, x1: f64, x2: f64, x3: f64 },
 }
+
+impl EasingFunction {
+    /// 正規化進捗 `t`（0.0〜1.0 にクランプ）をイージング適用後の進捗へ変換
+    pub fn ease(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EasingFunction::Named(name) => name.ease(t),
+            EasingFunction::Parametric(p) => p.ease(t),
+        }
+    }
+}
+
+impl EasingName {
+    /// 正規化進捗 `t`（0.0〜1.0）をイージング適用後の進捗へ変換（interpolation::EaseFunction 準拠）
+    pub fn ease(self, t: f64) -> f64 {
+        use std::f64::consts::PI;
+        let p = t.clamp(0.0, 1.0);
+        match self {
+            EasingName::Linear => p,
+            EasingName::QuadraticIn => p * p,
+            EasingName::QuadraticOut => -(p * (p - 2.0)),
+            EasingName::QuadraticInOut => {
+                if p < 0.5 {
+                    2.0 * p * p
+                } else {
+                    (-2.0 * p * p) + (4.0 * p) - 1.0
+                }
+            }
+            EasingName::CubicIn => p * p * p,
+            EasingName::CubicOut => {
+                let f = p - 1.0;
+                f * f * f + 1.0
+            }
+            EasingName::CubicInOut => {
+                if p < 0.5 {
+                    4.0 * p * p * p
+                } else {
+                    let f = 2.0 * p - 2.0;
+                    0.5 * f * f * f + 1.0
+                }
+            }
+            EasingName::QuarticIn => p * p * p * p,
+            EasingName::QuarticOut => {
+                let f = p - 1.0;
+                f * f * f * (1.0 - p) + 1.0
+            }
+            EasingName::QuarticInOut => {
+                if p < 0.5 {
+                    8.0 * p * p * p * p
+                } else {
+                    let f = p - 1.0;
+                    -8.0 * f * f * f * f + 1.0
+                }
+            }
+            EasingName::QuinticIn => p * p * p * p * p,
+            EasingName::QuinticOut => {
+                let f = p - 1.0;
+                f * f * f * f * f + 1.0
+            }
+            EasingName::QuinticInOut => {
+                if p < 0.5 {
+                    16.0 * p * p * p * p * p
+                } else {
+                    let f = 2.0 * p - 2.0;
+                    0.5 * f * f * f * f * f + 1.0
+                }
+            }
+            EasingName::SineIn => ((p - 1.0) * PI / 2.0).sin() + 1.0,
+            EasingName::SineOut => (p * PI / 2.0).sin(),
+            EasingName::SineInOut => 0.5 * (1.0 - (p * PI).cos()),
+            EasingName::CircularIn => 1.0 - (1.0 - p * p).sqrt(),
+            EasingName::CircularOut => ((2.0 - p) * p).sqrt(),
+            EasingName::CircularInOut => {
+                if p < 0.5 {
+                    0.5 * (1.0 - (1.0 - 4.0 * p * p).sqrt())
+                } else {
+                    0.5 * ((-((2.0 * p) - 3.0) * ((2.0 * p) - 1.0)).sqrt() + 1.0)
+                }
+            }
+            EasingName::ExponentialIn => {
+                if p == 0.0 {
+                    0.0
+                } else {
+                    2.0_f64.powf(10.0 * (p - 1.0))
+                }
+            }
+            EasingName::ExponentialOut => {
+                if p == 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2.0_f64.powf(-10.0 * p)
+                }
+            }
+            EasingName::ExponentialInOut => {
+                if p == 0.0 || p == 1.0 {
+                    p
+                } else if p < 0.5 {
+                    0.5 * 2.0_f64.powf((20.0 * p) - 10.0)
+                } else {
+                    -0.5 * 2.0_f64.powf((-20.0 * p) + 10.0) + 1.0
+                }
+            }
+            EasingName::ElasticIn => (13.0 * PI / 2.0 * p).sin() * 2.0_f64.powf(10.0 * (p - 1.0)),
+            EasingName::ElasticOut => {
+                (-13.0 * PI / 2.0 * (p + 1.0)).sin() * 2.0_f64.powf(-10.0 * p) + 1.0
+            }
+            EasingName::ElasticInOut => {
+                if p < 0.5 {
+                    0.5 * (13.0 * PI / 2.0 * (2.0 * p)).sin()
+                        * 2.0_f64.powf(10.0 * ((2.0 * p) - 1.0))
+                } else {
+                    0.5 * ((-13.0 * PI / 2.0 * ((2.0 * p - 1.0) + 1.0)).sin()
+                        * 2.0_f64.powf(-10.0 * (2.0 * p - 1.0))
+                        + 2.0)
+                }
+            }
+            EasingName::BackIn => p * p * p - p * (p * PI).sin(),
+            EasingName::BackOut => {
+                let f = 1.0 - p;
+                1.0 - (f * f * f - f * (f * PI).sin())
+            }
+            EasingName::BackInOut => {
+                if p < 0.5 {
+                    let f = 2.0 * p;
+                    0.5 * (f * f * f - f * (f * PI).sin())
+                } else {
+                    let f = 1.0 - (2.0 * p - 1.0);
+                    0.5 * (1.0 - (f * f * f - f * (f * PI).sin())) + 0.5
+                }
+            }
+            EasingName::BounceIn => 1.0 - bounce_out(1.0 - p),
+            EasingName::BounceOut => bounce_out(p),
+            EasingName::BounceInOut => {
+                if p < 0.5 {
+                    0.5 * (1.0 - bounce_out(1.0 - p * 2.0))
+                } else {
+                    0.5 * bounce_out(p * 2.0 - 1.0) + 0.5
+                }
+            }
+        }
+    }
+}
+
+impl ParametricEasing {
+    /// 正規化進捗 `t`（0.0〜1.0）におけるベジェ曲線の値を返す
+    pub fn ease(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        let u = 1.0 - t;
+        match *self {
+            ParametricEasing::QuadraticBezier { x0, x1, x2 } => {
+                u * u * x0 + 2.0 * u * t * x1 + t * t * x2
+            }
+            ParametricEasing::CubicBezier { x0, x1, x2, x3 } => {
+                u * u * u * x0 + 3.0 * u * u * t * x1 + 3.0 * u * t * t * x2 + t * t * t * x3
+            }
+        }
+    }
+}
+
+fn bounce_out(p: f64) -> f64 {
+    if p < 4.0 / 11.0 {
+        (121.0 * p * p) / 16.0
+    } else if p < 8.0 / 11.0 {
+        (363.0 / 40.0 * p * p) - (99.0 / 10.0 * p) + 17.0 / 5.0
+    } else if p < 9.0 / 10.0 {
+        (4356.0 / 361.0 * p * p) - (35442.0 / 1805.0 * p) + 16061.0 / 1805.0
+    } else {
+        (54.0 / 5.0 * p * p) - (513.0 / 25.0 * p) + 268.0 / 25.0
+    }
+}
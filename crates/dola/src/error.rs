@@ -55,6 +55,24 @@ pub enum DolaError {
         entry_index: usize,
         reason: String,
     },
+    /// 式の構文・参照エラー (V14)
+    InvalidExpression {
+        storyboard: String,
+        entry_index: usize,
+        reason: String,
+    },
+    /// ストーリーボード引数の不足・型不一致（スケジュール時）
+    InvalidArgument {
+        storyboard: String,
+        name: String,
+        reason: String,
+    },
+    /// 未定義ストーリーボード参照（再生時）
+    UndefinedStoryboard { name: String },
+    /// キーフレーム参照の循環（再生時のタイムライン解決）
+    KeyframeCycle { storyboard: String, name: String },
+    /// 現在時刻より前へのシーク（再生時）
+    InvalidSeek { time: f64, clock: f64 },
 }
 
 impl fmt::Display for DolaError {
@@ -162,6 +180,45 @@ impl fmt::Display for DolaError {
                     storyboard, entry_index, reason
                 )
             }
+            DolaError::InvalidExpression {
+                storyboard,
+                entry_index,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Invalid expression in storyboard '{}' entry {}: {}",
+                    storyboard, entry_index, reason
+                )
+            }
+            DolaError::InvalidArgument {
+                storyboard,
+                name,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Invalid argument '{}' for storyboard '{}': {}",
+                    name, storyboard, reason
+                )
+            }
+            DolaError::UndefinedStoryboard { name } => {
+                write!(f, "Undefined storyboard '{}'", name)
+            }
+            DolaError::KeyframeCycle { storyboard, name } => {
+                write!(
+                    f,
+                    "Cyclic keyframe reference '{}' in storyboard '{}'",
+                    name, storyboard
+                )
+            }
+            DolaError::InvalidSeek { time, clock } => {
+                write!(
+                    f,
+                    "Cannot seek to {} before the current clock {}",
+                    time, clock
+                )
+            }
         }
     }
 }
@@ -0,0 +1,373 @@
+use serde::{Deserialize, Serialize};
+
+/// 式の最大ネスト深度（悪意ある入力によるスタック枯渇防止）
+///
+/// 括弧・単項演算子・関数呼び出しの入れ子に適用する。二項演算の連鎖は
+/// [`Expr::Chain`] に平坦化されるため、`1+1+...+1` は入れ子として数えない。
+const MAX_DEPTH: usize = 64;
+
+/// 式（ソース文字列を保持し、スケジュール時に評価する）
+///
+/// シリアライズ形式: `{ expr = "min(target_x, 800) - x" }`
+///
+/// 文法:
+/// - 四則演算 `+ - * /`、剰余 `%`、単項 `-`、括弧
+/// - 数値リテラル（指数表記 `1e5` 可）、識別子（ストーリーボードパラメータ → 変数の現在値の順に解決）
+/// - 関数 `min(a, ...)`, `max(a, ...)`, `abs(a)`, `clamp(v, lo, hi)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Expression {
+    /// 式のソース文字列
+    pub expr: String,
+}
+
+impl Expression {
+    /// 式を作成（構文検証は行わない）
+    pub fn new(expr: impl Into<String>) -> Self {
+        Self { expr: expr.into() }
+    }
+
+    /// 式を構文解析
+    pub fn parse(&self) -> Result<Expr, String> {
+        let tokens = tokenize(&self.expr)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            pos: 0,
+        };
+        let expr = parser.expr(0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(tok) => Err(format!("unexpected token {:?}", tok)),
+        }
+    }
+
+    /// 識別子を `lookup` で解決して評価
+    pub fn eval(&self, lookup: impl Fn(&str) -> Option<f64>) -> Result<f64, String> {
+        self.parse()?.eval(&lookup)
+    }
+}
+
+/// 構文解析済みの式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Ident(String),
+    Neg(Box<Expr>),
+    /// 同じ優先順位の二項演算の左結合の連鎖（`a + b - c` → `Chain(a, [(Add, b), (Sub, c)])`）
+    Chain(Box<Expr>, Vec<(BinaryOp, Expr)>),
+    Call(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Min,
+    Max,
+    Abs,
+    Clamp,
+}
+
+impl Function {
+    /// 関数名
+    pub fn name(self) -> &'static str {
+        match self {
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Abs => "abs",
+            Function::Clamp => "clamp",
+        }
+    }
+
+    /// 引数の個数を検証
+    fn check_arity(self, len: usize) -> Result<(), String> {
+        let ok = match self {
+            Function::Min | Function::Max => len > 0,
+            Function::Abs => len == 1,
+            Function::Clamp => len == 3,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!(
+                "wrong number of arguments for '{}': {}",
+                self.name(),
+                len
+            ))
+        }
+    }
+}
+
+impl Expr {
+    /// 識別子を `lookup` で解決して評価（結果が有限値でなければエラー）
+    pub fn eval(&self, lookup: &dyn Fn(&str) -> Option<f64>) -> Result<f64, String> {
+        let v = match self {
+            Expr::Number(v) => *v,
+            Expr::Ident(name) => {
+                lookup(name).ok_or_else(|| format!("unknown identifier '{}'", name))?
+            }
+            Expr::Neg(e) => -e.eval(lookup)?,
+            Expr::Chain(first, rest) => {
+                let mut acc = first.eval(lookup)?;
+                for (op, operand) in rest {
+                    let b = operand.eval(lookup)?;
+                    acc = match op {
+                        BinaryOp::Add => acc + b,
+                        BinaryOp::Sub => acc - b,
+                        BinaryOp::Mul => acc * b,
+                        BinaryOp::Div => acc / b,
+                        BinaryOp::Rem => acc % b,
+                    };
+                }
+                acc
+            }
+            Expr::Call(func, args) => {
+                // 構文木は直接組み立てられるため、パーサとは別に検証する
+                func.check_arity(args.len())?;
+                let args = args
+                    .iter()
+                    .map(|a| a.eval(lookup))
+                    .collect::<Result<Vec<f64>, String>>()?;
+                match func {
+                    Function::Min => args.iter().copied().fold(f64::INFINITY, f64::min),
+                    Function::Max => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                    Function::Abs => args[0].abs(),
+                    Function::Clamp => args[0].max(args[1]).min(args[2]),
+                }
+            }
+        };
+        if v.is_finite() {
+            Ok(v)
+        } else {
+            Err("expression result is not finite".to_string())
+        }
+    }
+
+    /// 式中の識別子を出現順に収集
+    pub fn identifiers(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_identifiers(&mut out);
+        out
+    }
+
+    fn collect_identifiers<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Expr::Number(_) => {}
+            Expr::Ident(name) => out.push(name),
+            Expr::Neg(e) => e.collect_identifiers(out),
+            Expr::Chain(first, rest) => {
+                first.collect_identifiers(out);
+                rest.iter().for_each(|(_, e)| e.collect_identifiers(out));
+            }
+            Expr::Call(_, args) => args.iter().for_each(|a| a.collect_identifiers(out)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_ascii_digit() || c == '.' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            // 指数部（`e` の後に数字が続く場合のみ。`2e` は数値 + 識別子）
+            let exponent = exponent_len(&src[end..]);
+            if exponent > 0 {
+                end += exponent;
+                while chars.peek().is_some_and(|&(i, _)| i < end) {
+                    chars.next();
+                }
+            }
+            let text = &src[start..end];
+            let v = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{}'", text))?;
+            tokens.push(Token::Number(v));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(src[start..end].to_string()));
+        } else if "+-*/%(),".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(format!("unexpected character '{}'", c));
+        }
+    }
+    Ok(tokens)
+}
+
+/// 数値直後の指数部 `e[+-]digits` のバイト長（指数部でなければ 0）
+fn exponent_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    if !matches!(bytes.first(), Some(b'e' | b'E')) {
+        return 0;
+    }
+    let sign = usize::from(matches!(bytes.get(1), Some(b'+' | b'-')));
+    let digits = bytes[1 + sign..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits == 0 { 0 } else { 1 + sign + digits }
+}
+
+/// 再帰下降パーサ
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let tok = self.tokens.get(self.pos);
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: char) -> Result<(), String> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}'", op))
+        }
+    }
+
+    /// 連鎖を構文木にする（演算子がなければ先頭の項そのもの）
+    fn chain(first: Expr, rest: Vec<(BinaryOp, Expr)>) -> Expr {
+        if rest.is_empty() {
+            first
+        } else {
+            Expr::Chain(Box::new(first), rest)
+        }
+    }
+
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self, depth: usize) -> Result<Expr, String> {
+        if depth > MAX_DEPTH {
+            return Err("expression nested too deeply".to_string());
+        }
+        let first = self.term(depth)?;
+        let mut rest = Vec::new();
+        loop {
+            let op = if self.eat('+') {
+                BinaryOp::Add
+            } else if self.eat('-') {
+                BinaryOp::Sub
+            } else {
+                return Ok(Self::chain(first, rest));
+            };
+            rest.push((op, self.term(depth)?));
+        }
+    }
+
+    /// term := unary (('*' | '/' | '%') unary)*
+    fn term(&mut self, depth: usize) -> Result<Expr, String> {
+        let first = self.unary(depth)?;
+        let mut rest = Vec::new();
+        loop {
+            let op = if self.eat('*') {
+                BinaryOp::Mul
+            } else if self.eat('/') {
+                BinaryOp::Div
+            } else if self.eat('%') {
+                BinaryOp::Rem
+            } else {
+                return Ok(Self::chain(first, rest));
+            };
+            rest.push((op, self.unary(depth)?));
+        }
+    }
+
+    /// unary := '-' unary | primary
+    fn unary(&mut self, depth: usize) -> Result<Expr, String> {
+        if depth > MAX_DEPTH {
+            return Err("expression nested too deeply".to_string());
+        }
+        if self.eat('-') {
+            Ok(Expr::Neg(Box::new(self.unary(depth + 1)?)))
+        } else {
+            self.primary(depth)
+        }
+    }
+
+    /// primary := number | ident | ident '(' args ')' | '(' expr ')'
+    fn primary(&mut self, depth: usize) -> Result<Expr, String> {
+        match self.next().cloned() {
+            Some(Token::Number(v)) => Ok(Expr::Number(v)),
+            Some(Token::Ident(name)) => {
+                if !self.eat('(') {
+                    return Ok(Expr::Ident(name));
+                }
+                let func = match name.as_str() {
+                    "min" => Function::Min,
+                    "max" => Function::Max,
+                    "abs" => Function::Abs,
+                    "clamp" => Function::Clamp,
+                    _ => return Err(format!("unknown function '{}'", name)),
+                };
+                let mut args = Vec::new();
+                if !self.eat(')') {
+                    loop {
+                        args.push(self.expr(depth + 1)?);
+                        if self.eat(')') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                func.check_arity(args.len())?;
+                Ok(Expr::Call(func, args))
+            }
+            Some(Token::Op('(')) => {
+                let e = self.expr(depth + 1)?;
+                self.expect(')')?;
+                Ok(e)
+            }
+            Some(tok) => Err(format!("unexpected token {:?}", tok)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
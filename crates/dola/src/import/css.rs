@@ -0,0 +1,884 @@
+//! CSS `@keyframes` / `animation` 宣言の取り込み
+//!
+//! 対応範囲:
+//! - `@keyframes` / `@-webkit-keyframes` ブロック（`from` / `to` / パーセント、複数セレクタ）
+//! - `animation` ショートハンドと `animation-*` ロングハンド（カンマ区切りの複数アニメーション）
+//! - プロパティ: `opacity`、`transform`（translate / scale / rotate 系）、
+//!   `left` / `top` / `right` / `bottom` / `width` / `height`（px）
+//!
+//! アニメーションを参照するスタイルルール 1 つにつき 1 ストーリーボードを生成する。
+//! 変数はセレクタ（要素）ごとに `{セレクタ}_{プロパティ}` で名前空間を分ける
+//! （例: `.toast` の opacity → `toast_opacity`）。同じセレクタのアニメーションは変数を共有する。
+//! CSS の 2 次元 `cubic-bezier()` は Dola の 1 次元 3 次ベジェへ近似する。
+
+use std::collections::BTreeMap;
+
+use super::{
+    ImportDiagnostic, ImportResult, Key, easing_fit_diagnostic, fit_cubic_bezier, keyframed_entries,
+};
+use crate::builder::StoryboardBuilder;
+use crate::document::DolaDocument;
+use crate::storyboard::Storyboard;
+use crate::validate::Validate;
+use crate::variable::AnimationVariableDef;
+
+/// `cubic-bezier(x1, y1, x2, y2)` の制御点
+type Bezier = [f64; 4];
+
+const LINEAR: Bezier = [0.0, 0.0, 1.0, 1.0];
+const EASE: Bezier = [0.25, 0.1, 0.25, 1.0];
+
+/// 取り込み対象のプロパティ → Dola 変数名（セレクタの接頭辞を除いた部分）
+const LENGTH_PROPERTIES: [&str; 6] = ["left", "top", "right", "bottom", "width", "height"];
+/// `alternate` の奇数回を展開するサイクル数の上限
+const MAX_UNROLLED_CYCLES: u32 = 64;
+const TRANSFORM_VARIABLES: [&str; 5] =
+    ["translate_x", "translate_y", "scale_x", "scale_y", "rotate"];
+
+/// CSS ソースを Dola ドキュメントへ変換
+///
+/// 変換できないプロパティ・単位・タイミング関数は診断として報告し、スキップまたは近似する。
+pub fn import_css(source: &str) -> ImportResult {
+    let mut importer = CssImporter::default();
+    let source = strip_comments(source);
+    for (prelude, body) in split_blocks(&source) {
+        importer.block(&prelude, &body);
+    }
+    importer.finish()
+}
+
+/// アニメーション方向（`animation-direction`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+/// `animation` 1 件分の設定
+#[derive(Debug, Clone)]
+struct Animation {
+    name: String,
+    duration: f64,
+    delay: f64,
+    /// None = infinite
+    iterations: Option<f64>,
+    direction: Direction,
+    timing: String,
+    fill_mode: Option<String>,
+    paused: bool,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            name: "none".to_string(),
+            duration: 0.0,
+            delay: 0.0,
+            iterations: Some(1.0),
+            direction: Direction::Normal,
+            timing: "ease".to_string(),
+            fill_mode: None,
+            paused: false,
+        }
+    }
+}
+
+/// `@keyframes` 内の 1 キーフレーム（セレクタ展開済み）
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    offset: f64,
+    /// 変数名 → 値
+    values: BTreeMap<String, f64>,
+    /// transform 指定があったか（未指定の transform 成分を恒等値で補う）
+    has_transform: bool,
+    timing: Option<String>,
+}
+
+#[derive(Default)]
+struct CssImporter {
+    keyframes: BTreeMap<String, Vec<Frame>>,
+    /// (セレクタ, アニメーション)
+    usages: Vec<(String, Animation)>,
+    /// セレクタ → 変数名の接頭辞
+    prefixes: BTreeMap<String, String>,
+    diagnostics: Vec<ImportDiagnostic>,
+}
+
+impl CssImporter {
+    fn diag(&mut self, location: &str, message: impl Into<String>) {
+        self.diagnostics
+            .push(ImportDiagnostic::new(location, message));
+    }
+
+    fn block(&mut self, prelude: &str, body: &str) {
+        let prelude = prelude.trim();
+        if let Some(name) = prelude
+            .strip_prefix("@keyframes")
+            .or_else(|| prelude.strip_prefix("@-webkit-keyframes"))
+        {
+            let name = unquote(name.trim()).to_string();
+            let frames = self.keyframes_body(&name, body);
+            // CSS と同様、同名の @keyframes は後勝ち
+            self.keyframes.insert(name, frames);
+        } else if prelude.starts_with('@') {
+            let rule = prelude.split_whitespace().next().unwrap_or(prelude);
+            self.diag(prelude, format!("at-rule '{}' is not supported", rule));
+        } else {
+            self.style_rule(prelude, body);
+        }
+    }
+
+    /// `@keyframes` 本体を解析（オフセット順、同一オフセットはマージ）
+    fn keyframes_body(&mut self, name: &str, body: &str) -> Vec<Frame> {
+        let mut frames: Vec<Frame> = Vec::new();
+        for (selector, decls) in split_blocks(body) {
+            let location = format!("@keyframes {} {}", name, selector.trim());
+            let mut offsets = Vec::new();
+            for sel in selector.split(',').map(str::trim) {
+                match parse_keyframe_selector(sel) {
+                    Some(p) => offsets.push(p),
+                    None => self.diag(&location, format!("invalid keyframe selector '{}'", sel)),
+                }
+            }
+            let parsed = self.frame_declarations(&location, &decls);
+            for offset in offsets {
+                let frame = match frames.iter_mut().find(|f| f.offset == offset) {
+                    Some(f) => f,
+                    None => {
+                        frames.push(Frame {
+                            offset,
+                            ..Frame::default()
+                        });
+                        frames.last_mut().unwrap()
+                    }
+                };
+                frame.values.extend(parsed.values.clone());
+                frame.has_transform |= parsed.has_transform;
+                if parsed.timing.is_some() {
+                    frame.timing = parsed.timing.clone();
+                }
+            }
+        }
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        frames
+    }
+
+    /// キーフレーム内の宣言を変数値へ変換
+    fn frame_declarations(&mut self, location: &str, decls: &str) -> Frame {
+        let mut frame = Frame::default();
+        for (prop, value) in parse_declarations(decls) {
+            match prop.as_str() {
+                "opacity" => match value.parse::<f64>() {
+                    Ok(v) => {
+                        frame
+                            .values
+                            .insert("opacity".to_string(), v.clamp(0.0, 1.0));
+                    }
+                    Err(_) => self.diag(location, format!("invalid opacity '{}'", value)),
+                },
+                "transform" => match parse_transform(&value) {
+                    Ok(components) => {
+                        frame.has_transform = true;
+                        frame.values.extend(components);
+                    }
+                    Err(reason) => self.diag(location, reason),
+                },
+                "animation-timing-function" => frame.timing = Some(value),
+                p if LENGTH_PROPERTIES.contains(&p) => match parse_px(&value) {
+                    Some(v) => {
+                        frame.values.insert(p.to_string(), v);
+                    }
+                    None => self.diag(
+                        location,
+                        format!("unsupported value '{}' for '{}' (px only)", value, p),
+                    ),
+                },
+                p => self.diag(location, format!("property '{}' is not supported", p)),
+            }
+        }
+        frame
+    }
+
+    /// スタイルルールから `animation` / `animation-*` を収集
+    fn style_rule(&mut self, selector: &str, body: &str) {
+        let mut anims: Vec<Animation> = Vec::new();
+        let mut longhands: Vec<(String, Vec<String>)> = Vec::new();
+        for (prop, value) in parse_declarations(body) {
+            if prop == "animation" {
+                anims = split_top_level(&value, ',')
+                    .iter()
+                    .map(|part| parse_shorthand(part))
+                    .collect();
+                longhands.clear();
+            } else if prop.starts_with("animation-") {
+                let values = split_top_level(&value, ',');
+                if values.is_empty() {
+                    self.diag(selector, format!("empty value for '{}'; ignored", prop));
+                    continue;
+                }
+                longhands.retain(|(p, _)| *p != prop);
+                longhands.push((prop, values));
+            }
+        }
+        if let Some((_, names)) = longhands.iter().find(|(p, _)| p == "animation-name") {
+            anims.resize_with(names.len(), Animation::default);
+        }
+        for (prop, values) in &longhands {
+            for (i, anim) in anims.iter_mut().enumerate() {
+                let value = values[i % values.len()].as_str();
+                if let Err(reason) = apply_longhand(anim, prop, value) {
+                    self.diagnostics
+                        .push(ImportDiagnostic::new(selector, reason));
+                }
+            }
+        }
+        for anim in anims {
+            if anim.name != "none" {
+                self.usages.push((selector.to_string(), anim));
+            }
+        }
+    }
+
+    fn finish(mut self) -> ImportResult {
+        let mut doc = DolaDocument {
+            schema_version: "1.0".to_string(),
+            variable: BTreeMap::new(),
+            transition: BTreeMap::new(),
+            storyboard: BTreeMap::new(),
+        };
+
+        let usages = std::mem::take(&mut self.usages);
+        for (selector, anim) in &usages {
+            let location = format!("{} animation '{}'", selector, anim.name);
+            let Some(frames) = self.keyframes.get(&anim.name).cloned() else {
+                self.diag(&location, "no matching @keyframes");
+                continue;
+            };
+            let prefix = self.prefix(selector);
+            let Some((storyboard, variables)) = self.storyboard(&location, &prefix, anim, &frames)
+            else {
+                continue;
+            };
+            for (name, property) in variables {
+                doc.variable
+                    .entry(name)
+                    .or_insert_with(|| variable_def(&property));
+            }
+            let mut name = anim.name.clone();
+            let mut n = 2;
+            while doc.storyboard.contains_key(&name) {
+                name = format!("{}_{}", anim.name, n);
+                n += 1;
+            }
+            doc.storyboard.insert(name, storyboard);
+        }
+
+        for name in self.keyframes.keys() {
+            if !usages.iter().any(|(_, a)| &a.name == name) {
+                self.diagnostics.push(ImportDiagnostic::new(
+                    format!("@keyframes {}", name),
+                    "not referenced by any animation; skipped",
+                ));
+            }
+        }
+
+        if let Err(errors) = doc.validate() {
+            for e in errors {
+                self.diag("document", e.to_string());
+            }
+        }
+        ImportResult {
+            document: doc,
+            diagnostics: self.diagnostics,
+        }
+    }
+
+    /// セレクタの変数名接頭辞（識別子に使えない文字は `_`、別セレクタとの衝突は連番）
+    fn prefix(&mut self, selector: &str) -> String {
+        if let Some(prefix) = self.prefixes.get(selector) {
+            return prefix.clone();
+        }
+        let mut slug = String::new();
+        for c in selector.chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('_') {
+                slug.push('_');
+            }
+        }
+        let slug = slug.trim_end_matches('_');
+        let slug = match slug.chars().next() {
+            None => "element".to_string(),
+            Some(c) if c.is_ascii_digit() => format!("_{}", slug),
+            Some(_) => slug.to_string(),
+        };
+        let mut prefix = slug.clone();
+        let mut n = 2;
+        while self.prefixes.values().any(|p| *p == prefix) {
+            prefix = format!("{}_{}", slug, n);
+            n += 1;
+        }
+        self.prefixes.insert(selector.to_string(), prefix.clone());
+        prefix
+    }
+
+    /// アニメーション 1 件をストーリーボードへ変換（使用変数の (変数名, プロパティ) も返す）
+    ///
+    /// 反復回数が 0 のアニメーションは何も再生しないため、診断を出して None を返す。
+    fn storyboard(
+        &mut self,
+        location: &str,
+        prefix: &str,
+        anim: &Animation,
+        frames: &[Frame],
+    ) -> Option<(Storyboard, Vec<(String, String)>)> {
+        let mut builder = StoryboardBuilder::new();
+
+        let mut delay = anim.delay;
+        if delay < 0.0 {
+            self.diag(
+                location,
+                "negative animation-delay is not supported; clamped to 0",
+            );
+            delay = 0.0;
+        }
+        if anim.paused {
+            self.diag(location, "animation-play-state 'paused' is ignored");
+        }
+        if let Some(mode) = anim
+            .fill_mode
+            .as_deref()
+            .filter(|m| matches!(*m, "none" | "backwards"))
+        {
+            self.diag(
+                location,
+                format!(
+                    "animation-fill-mode '{}' is not supported; final values are held",
+                    mode
+                ),
+            );
+        }
+
+        // 反復回数 → (サイクル方向列, loop_count)
+        let alternate = matches!(
+            anim.direction,
+            Direction::Alternate | Direction::AlternateReverse
+        );
+        let first_reversed = matches!(
+            anim.direction,
+            Direction::Reverse | Direction::AlternateReverse
+        );
+        let iterations = match anim.iterations {
+            None => None,
+            Some(n) if n.fract() != 0.0 => {
+                let rounded = n.round();
+                self.diag(
+                    location,
+                    format!(
+                        "fractional animation-iteration-count {} rounded to {}",
+                        n, rounded
+                    ),
+                );
+                Some(rounded as u32)
+            }
+            Some(n) => Some(n as u32),
+        };
+        let (cycles, loop_count) = match (alternate, iterations) {
+            (_, Some(0)) => {
+                self.diag(
+                    location,
+                    "animation-iteration-count 0 plays nothing; skipped",
+                );
+                return None;
+            }
+            (false, None) => (vec![first_reversed], Some(0)),
+            (false, Some(1)) => (vec![first_reversed], None),
+            (false, Some(n)) => (vec![first_reversed], Some(n)),
+            (true, None) => (vec![first_reversed, !first_reversed], Some(0)),
+            // 偶数回は往復 1 組の繰り返し、奇数回は全サイクルを展開（上限超過は 1 回減らす）
+            (true, Some(n)) if n % 2 == 0 || n > MAX_UNROLLED_CYCLES => {
+                let n = if n % 2 == 0 {
+                    n
+                } else {
+                    self.diag(
+                        location,
+                        format!(
+                            "odd alternate animation-iteration-count {} exceeds {}; played {} times",
+                            n,
+                            MAX_UNROLLED_CYCLES,
+                            n - 1
+                        ),
+                    );
+                    n - 1
+                };
+                (
+                    vec![first_reversed, !first_reversed],
+                    (n > 2).then_some(n / 2),
+                )
+            }
+            (true, Some(n)) => (
+                (0..n).map(|c| first_reversed ^ (c % 2 == 1)).collect(),
+                None,
+            ),
+        };
+        if let Some(n) = loop_count {
+            builder = builder.loop_count(n);
+            if delay > 0.0 {
+                self.diag(location, "animation-delay is repeated on every iteration");
+            }
+        }
+
+        // 変数ごとのキー列（1 サイクル、オフセット 0..1）
+        let used: Vec<String> = {
+            let mut vars: Vec<String> = frames
+                .iter()
+                .flat_map(|f| f.values.keys().cloned())
+                .collect();
+            vars.sort();
+            vars.dedup();
+            vars
+        };
+        let anim_timing = self.bezier(location, &anim.timing);
+        let frame_timings: Vec<Option<Bezier>> = frames
+            .iter()
+            .map(|f| f.timing.as_ref().map(|t| self.bezier(location, t)))
+            .collect();
+
+        let mut tracks = Vec::new();
+        for var in &used {
+            let base = base_value(var);
+            let mut cycle_keys: Vec<(f64, f64, Bezier)> = Vec::new();
+            for (frame, timing) in frames.iter().zip(&frame_timings) {
+                let value = match frame.values.get(var) {
+                    Some(v) => *v,
+                    None if frame.has_transform && TRANSFORM_VARIABLES.contains(&var.as_str()) => {
+                        base
+                    }
+                    None => continue,
+                };
+                cycle_keys.push((frame.offset, value, timing.unwrap_or(anim_timing)));
+            }
+            if cycle_keys.first().is_none_or(|k| k.0 > 0.0) {
+                cycle_keys.insert(0, (0.0, base, anim_timing));
+            }
+            if cycle_keys.last().is_none_or(|k| k.0 < 1.0) {
+                cycle_keys.push((1.0, base, anim_timing));
+            }
+
+            let mut keys: Vec<Key> = Vec::new();
+            for (c, reversed) in cycles.iter().enumerate() {
+                let origin = delay + c as f64 * anim.duration;
+                let oriented: Vec<(f64, f64, Option<Bezier>)> = if *reversed {
+                    cycle_keys
+                        .iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(j, (p, v, _))| {
+                            let easing = j.checked_sub(1).map(|prev| reverse(cycle_keys[prev].2));
+                            (1.0 - p, *v, easing)
+                        })
+                        .collect()
+                } else {
+                    cycle_keys
+                        .iter()
+                        .map(|(p, v, e)| (*p, *v, Some(*e)))
+                        .collect()
+                };
+                // サイクル境界のキーは次サイクルの先頭キーで置き換える
+                keys.pop_if(|k| c > 0 && (k.time - origin).abs() < 1e-9);
+                for (p, value, easing) in oriented {
+                    let easing = easing.map(|b| {
+                        let (easing, err) = fit_cubic_bezier(b[0], b[1], b[2], b[3]);
+                        if let Some(d) = easing_fit_diagnostic(location, &format_bezier(b), err)
+                            && !self.diagnostics.contains(&d)
+                        {
+                            self.diagnostics.push(d);
+                        }
+                        easing
+                    });
+                    keys.push(Key {
+                        time: origin + p * anim.duration,
+                        value,
+                        easing,
+                    });
+                }
+            }
+            if let Some(last) = keys.last_mut() {
+                last.easing = None;
+            }
+            tracks.push((format!("{}_{}", prefix, var), keys));
+        }
+
+        for entry in keyframed_entries(&tracks) {
+            builder = builder.entry(entry);
+        }
+        let variables = tracks.into_iter().map(|(name, _)| name).zip(used).collect();
+        Some((builder.build(), variables))
+    }
+
+    /// タイミング関数を cubic-bezier 制御点へ変換
+    fn bezier(&mut self, location: &str, timing: &str) -> Bezier {
+        let timing = timing.trim();
+        match timing {
+            "linear" => return LINEAR,
+            "ease" => return EASE,
+            "ease-in" => return [0.42, 0.0, 1.0, 1.0],
+            "ease-out" => return [0.0, 0.0, 0.58, 1.0],
+            "ease-in-out" => return [0.42, 0.0, 0.58, 1.0],
+            _ => {}
+        }
+        if let Some(args) = function_args(timing, "cubic-bezier") {
+            let nums: Vec<f64> = args.iter().filter_map(|a| a.parse().ok()).collect();
+            if let [x1, y1, x2, y2] = nums[..] {
+                return [x1, y1, x2, y2];
+            }
+        }
+        let approx = if timing.starts_with("steps(")
+            || timing.starts_with("linear(")
+            || matches!(timing, "step-start" | "step-end")
+        {
+            format!(
+                "timing function '{}' is not supported; using linear",
+                timing
+            )
+        } else {
+            format!("invalid timing function '{}'; using linear", timing)
+        };
+        let d = ImportDiagnostic::new(location, approx);
+        if !self.diagnostics.contains(&d) {
+            self.diagnostics.push(d);
+        }
+        LINEAR
+    }
+}
+
+/// 逆再生用に反転した制御点
+fn reverse(b: Bezier) -> Bezier {
+    [1.0 - b[2], 1.0 - b[3], 1.0 - b[0], 1.0 - b[1]]
+}
+
+fn format_bezier(b: Bezier) -> String {
+    format!("cubic-bezier({}, {}, {}, {})", b[0], b[1], b[2], b[3])
+}
+
+/// 変数の基準値（キーフレーム未指定時・初期値）
+fn base_value(var: &str) -> f64 {
+    match var {
+        "opacity" | "scale_x" | "scale_y" => 1.0,
+        _ => 0.0,
+    }
+}
+
+fn variable_def(var: &str) -> AnimationVariableDef {
+    let (min, max) = if var == "opacity" {
+        (Some(0.0), Some(1.0))
+    } else {
+        (None, None)
+    };
+    AnimationVariableDef::Float {
+        initial: base_value(var),
+        min,
+        max,
+    }
+}
+
+/// ロングハンド 1 値をアニメーション設定へ反映
+fn apply_longhand(anim: &mut Animation, prop: &str, value: &str) -> Result<(), String> {
+    let invalid = || format!("invalid value '{}' for '{}'", value, prop);
+    match prop {
+        "animation-name" => anim.name = unquote(value).to_string(),
+        "animation-duration" => anim.duration = parse_time(value).ok_or_else(invalid)?,
+        "animation-delay" => anim.delay = parse_time(value).ok_or_else(invalid)?,
+        "animation-iteration-count" => {
+            anim.iterations = parse_iterations(value).ok_or_else(invalid)?
+        }
+        "animation-direction" => anim.direction = parse_direction(value).ok_or_else(invalid)?,
+        "animation-timing-function" => anim.timing = value.to_string(),
+        "animation-fill-mode" => anim.fill_mode = Some(value.to_string()),
+        "animation-play-state" => anim.paused = value == "paused",
+        _ => return Err(format!("property '{}' is not supported", prop)),
+    }
+    Ok(())
+}
+
+/// `animation` ショートハンド 1 件を解析（トークン種別で分類）
+fn parse_shorthand(part: &str) -> Animation {
+    let mut anim = Animation::default();
+    let mut times = 0;
+    for token in split_top_level(part, ' ') {
+        if let Some(t) = parse_time(&token) {
+            if times == 0 {
+                anim.duration = t;
+            } else {
+                anim.delay = t;
+            }
+            times += 1;
+        } else if is_timing_function(&token) {
+            anim.timing = token;
+        } else if let Some(n) = parse_iterations(&token) {
+            anim.iterations = n;
+        } else if let Some(d) = parse_direction(&token) {
+            anim.direction = d;
+        } else if matches!(token.as_str(), "none" | "forwards" | "backwards" | "both")
+            && anim.fill_mode.is_none()
+        {
+            anim.fill_mode = Some(token);
+        } else if matches!(token.as_str(), "running" | "paused") {
+            anim.paused = token == "paused";
+        } else {
+            anim.name = unquote(&token).to_string();
+        }
+    }
+    anim
+}
+
+fn is_timing_function(token: &str) -> bool {
+    matches!(
+        token,
+        "linear" | "ease" | "ease-in" | "ease-out" | "ease-in-out" | "step-start" | "step-end"
+    ) || ["cubic-bezier(", "steps(", "linear("]
+        .iter()
+        .any(|f| token.starts_with(f))
+}
+
+/// `1s` / `250ms` → 秒
+fn parse_time(token: &str) -> Option<f64> {
+    if let Some(ms) = token.strip_suffix("ms") {
+        ms.parse::<f64>().ok().map(|v| v / 1000.0)
+    } else {
+        token.strip_suffix('s')?.parse().ok()
+    }
+}
+
+/// `infinite` → Some(None)、数値 → Some(Some(n))
+fn parse_iterations(token: &str) -> Option<Option<f64>> {
+    if token == "infinite" {
+        return Some(None);
+    }
+    token.parse::<f64>().ok().filter(|n| *n >= 0.0).map(Some)
+}
+
+fn parse_direction(token: &str) -> Option<Direction> {
+    match token {
+        "normal" => Some(Direction::Normal),
+        "reverse" => Some(Direction::Reverse),
+        "alternate" => Some(Direction::Alternate),
+        "alternate-reverse" => Some(Direction::AlternateReverse),
+        _ => None,
+    }
+}
+
+/// `from` / `to` / `N%` → 0..1
+fn parse_keyframe_selector(sel: &str) -> Option<f64> {
+    match sel {
+        "from" => Some(0.0),
+        "to" => Some(1.0),
+        _ => sel
+            .strip_suffix('%')?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|p| (0.0..=100.0).contains(p))
+            .map(|p| p / 100.0),
+    }
+}
+
+/// `12px` / `0` → 値
+fn parse_px(value: &str) -> Option<f64> {
+    match value.strip_suffix("px") {
+        Some(v) => v.trim().parse().ok(),
+        None => value.parse::<f64>().ok().filter(|v| *v == 0.0),
+    }
+}
+
+/// 角度 → 度
+fn parse_angle(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let (num, factor) = if let Some(v) = value.strip_suffix("deg") {
+        (v, 1.0)
+    } else if let Some(v) = value.strip_suffix("grad") {
+        (v, 0.9)
+    } else if let Some(v) = value.strip_suffix("rad") {
+        (v, 180.0 / std::f64::consts::PI)
+    } else if let Some(v) = value.strip_suffix("turn") {
+        (v, 360.0)
+    } else if value == "0" {
+        (value, 1.0)
+    } else {
+        return None;
+    };
+    num.trim().parse::<f64>().ok().map(|v| v * factor)
+}
+
+/// `transform` の関数リストを変数値へ分解
+fn parse_transform(value: &str) -> Result<BTreeMap<String, f64>, String> {
+    let mut out = BTreeMap::new();
+    if value == "none" {
+        return Ok(out);
+    }
+    let invalid = |f: &str| format!("invalid transform function '{}'", f);
+    for func in split_top_level(value, ' ') {
+        let Some(open) = func.find('(') else {
+            return Err(invalid(&func));
+        };
+        let name = &func[..open];
+        let args = function_args(&func, name).ok_or_else(|| invalid(&func))?;
+        let px = |i: usize| args.get(i).and_then(|a| parse_px(a));
+        let num = |i: usize| args.get(i).and_then(|a| a.parse::<f64>().ok());
+        let mut set = |var: &str, v: Option<f64>| match v {
+            Some(v) => {
+                out.insert(var.to_string(), v);
+                Ok(())
+            }
+            None => Err(invalid(&func)),
+        };
+        match name {
+            "translateX" => set("translate_x", px(0))?,
+            "translateY" => set("translate_y", px(0))?,
+            "translate" => {
+                set("translate_x", px(0))?;
+                set(
+                    "translate_y",
+                    if args.len() > 1 { px(1) } else { Some(0.0) },
+                )?;
+            }
+            "scaleX" => set("scale_x", num(0))?,
+            "scaleY" => set("scale_y", num(0))?,
+            "scale" => {
+                set("scale_x", num(0))?;
+                set("scale_y", if args.len() > 1 { num(1) } else { num(0) })?;
+            }
+            "rotate" | "rotateZ" => set("rotate", args.first().and_then(|a| parse_angle(a)))?,
+            _ => return Err(format!("transform function '{}' is not supported", name)),
+        }
+    }
+    Ok(out)
+}
+
+/// `name(a, b, ...)` の引数列
+fn function_args(token: &str, name: &str) -> Option<Vec<String>> {
+    let inner = token
+        .strip_prefix(name)?
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(
+        split_top_level(inner, ',')
+            .into_iter()
+            .filter(|a| !a.is_empty())
+            .collect(),
+    )
+}
+
+fn unquote(s: &str) -> &str {
+    s.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// コメント `/* ... */` を除去
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => rest = "",
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// トップレベルの `prelude { body }` ブロックへ分割（文字列リテラル内の括弧は無視）
+fn split_blocks(source: &str) -> Vec<(String, String)> {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut prelude_start = 0;
+    let mut body_start = 0;
+    for (i, c) in source.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' => {
+                if depth == 0 {
+                    body_start = i + 1;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    blocks.push((
+                        source[prelude_start..body_start - 1].trim().to_string(),
+                        source[body_start..i].to_string(),
+                    ));
+                    prelude_start = i + 1;
+                }
+            }
+            // ブロックを持たない文（`@import ...;` など）は読み捨てる
+            ';' if depth == 0 => prelude_start = i + 1,
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// `prop: value; ...` を (小文字プロパティ名, 値) の列へ分解（`!important` は除去）
+fn parse_declarations(body: &str) -> Vec<(String, String)> {
+    split_top_level(body, ';')
+        .into_iter()
+        .filter_map(|decl| {
+            let (prop, value) = decl.split_once(':')?;
+            let value = value.trim();
+            let value = value.strip_suffix("!important").unwrap_or(value).trim();
+            Some((prop.trim().to_ascii_lowercase(), value.to_string()))
+        })
+        .collect()
+}
+
+/// 括弧・引用符の外側にある `sep` で分割（空白区切りの場合は連続空白を 1 つとみなす）
+fn split_top_level(s: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut current = String::new();
+    for c in s.chars() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            current.push(c);
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        let is_sep = if sep == ' ' {
+            c.is_whitespace()
+        } else {
+            c == sep
+        };
+        if is_sep && depth == 0 {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    parts.push(current);
+    parts
+        .into_iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
@@ -0,0 +1,343 @@
+//! Lottie (Bodymovin) JSON の取り込み
+//!
+//! 対応範囲: レイヤーの変形プロパティ（`ks`）のうち
+//! position（`p`、分割形式 `s: true` を含む）/ scale（`s`）/ rotation（`r`）/ opacity（`o`）。
+//! キーフレームの時間ベジェ接線（`o` / `i`）は Dola の 1 次元 3 次ベジェへ近似し、
+//! hold キーフレーム（`h: 1`）は次キー時刻での値切り替えとして表現する。
+//!
+//! コンポジション全体を 1 ストーリーボードとし、変数名は `{レイヤー名}_{プロパティ}` とする。
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use super::{
+    ImportDiagnostic, ImportResult, Key, easing_fit_diagnostic, fit_cubic_bezier, keyframed_entries,
+};
+use crate::builder::StoryboardBuilder;
+use crate::document::DolaDocument;
+use crate::validate::Validate;
+use crate::variable::AnimationVariableDef;
+
+/// Lottie JSON を Dola ドキュメントへ変換
+///
+/// JSON として解析できない場合のみエラーを返す。未対応のレイヤー種別・プロパティは
+/// 診断として報告する。
+pub fn import_lottie(json: &str) -> Result<ImportResult, serde_json::Error> {
+    let root: Value = serde_json::from_str(json)?;
+    let mut importer = LottieImporter {
+        frame_rate: root["fr"].as_f64().filter(|fr| *fr > 0.0).unwrap_or(60.0),
+        in_point: root["ip"].as_f64().unwrap_or(0.0),
+        out_point: root["op"].as_f64(),
+        variables: BTreeMap::new(),
+        tracks: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+    if root["fr"].as_f64().is_none_or(|fr| fr <= 0.0) {
+        importer.diag("composition", "missing frame rate 'fr'; assuming 60");
+    }
+    if root
+        .get("assets")
+        .and_then(Value::as_array)
+        .is_some_and(|a| !a.is_empty())
+    {
+        importer.diag("composition", "assets and precompositions are not imported");
+    }
+
+    let mut used_names = Vec::new();
+    for (i, layer) in root["layers"].as_array().into_iter().flatten().enumerate() {
+        let base = layer["nm"]
+            .as_str()
+            .map(sanitize)
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| format!("layer_{}", i));
+        let mut name = base.clone();
+        let mut n = 2;
+        while used_names.contains(&name) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        used_names.push(name.clone());
+        importer.layer(&name, layer);
+    }
+
+    let storyboard_name = root["nm"]
+        .as_str()
+        .map(sanitize)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "lottie".to_string());
+    let mut builder = StoryboardBuilder::new();
+    for entry in keyframed_entries(&importer.tracks) {
+        builder = builder.entry(entry);
+    }
+    let mut doc = DolaDocument {
+        schema_version: "1.0".to_string(),
+        variable: importer.variables,
+        transition: BTreeMap::new(),
+        storyboard: BTreeMap::new(),
+    };
+    doc.storyboard.insert(storyboard_name, builder.build());
+
+    let mut diagnostics = importer.diagnostics;
+    if let Err(errors) = doc.validate() {
+        diagnostics.extend(
+            errors
+                .into_iter()
+                .map(|e| ImportDiagnostic::new("document", e.to_string())),
+        );
+    }
+    Ok(ImportResult {
+        document: doc,
+        diagnostics,
+    })
+}
+
+/// 取り込むプロパティ（`ks` キー, 変数名の接尾辞, 次元, 値の倍率）
+const PROPERTIES: [(&str, &str, &[&str], f64); 4] = [
+    ("p", "position", &["x", "y"], 1.0),
+    ("s", "scale", &["x", "y"], 0.01),
+    ("r", "rotation", &[""], 1.0),
+    ("o", "opacity", &[""], 0.01),
+];
+
+struct LottieImporter {
+    frame_rate: f64,
+    in_point: f64,
+    out_point: Option<f64>,
+    variables: BTreeMap<String, AnimationVariableDef>,
+    tracks: Vec<(String, Vec<Key>)>,
+    diagnostics: Vec<ImportDiagnostic>,
+}
+
+impl LottieImporter {
+    fn diag(&mut self, location: &str, message: impl Into<String>) {
+        self.diagnostics
+            .push(ImportDiagnostic::new(location, message));
+    }
+
+    fn layer(&mut self, name: &str, layer: &Value) {
+        let location = format!("layer '{}'", name);
+        if layer["ddd"].as_i64() == Some(1) {
+            self.diag(&location, "3D layers are imported as 2D");
+        }
+        if !layer["parent"].is_null() {
+            self.diag(&location, "parenting is not imported; transforms are local");
+        }
+        let layer_in = layer["ip"].as_f64().unwrap_or(self.in_point);
+        let layer_out = layer["op"].as_f64();
+        if layer_in > self.in_point || layer_out.zip(self.out_point).is_some_and(|(l, c)| l < c) {
+            self.diag(
+                &location,
+                "layer in/out points (visibility) are not imported",
+            );
+        }
+        // レイヤー時刻 → コンポジション時刻: frame * sr + st
+        let stretch = layer["sr"].as_f64().unwrap_or(1.0);
+        let start = layer["st"].as_f64().unwrap_or(0.0);
+        let (frame_rate, in_point) = (self.frame_rate, self.in_point);
+        let to_seconds =
+            move |frame: f64| ((frame * stretch + start - in_point) / frame_rate).max(0.0);
+
+        let ks = &layer["ks"];
+        for key in ["a", "sk", "rx", "ry", "or"] {
+            if is_animated(&ks[key]) || has_non_zero(&ks[key]) {
+                self.diag(
+                    &location,
+                    format!("transform property '{}' is not imported", key),
+                );
+            }
+        }
+
+        for (key, suffix, dims, factor) in PROPERTIES {
+            let prop = &ks[key];
+            if prop.is_null() {
+                continue;
+            }
+            // 分割形式の position: { s: true, x: {...}, y: {...} }
+            let components: Vec<(String, &Value, usize)> = if prop["s"].as_bool() == Some(true) {
+                dims.iter()
+                    .map(|d| (format!("{}_{}_{}", name, suffix, d), &prop[*d], 0))
+                    .collect()
+            } else {
+                dims.iter()
+                    .enumerate()
+                    .map(|(i, d)| {
+                        let var = if d.is_empty() {
+                            format!("{}_{}", name, suffix)
+                        } else {
+                            format!("{}_{}_{}", name, suffix, d)
+                        };
+                        (var, prop, i)
+                    })
+                    .collect()
+            };
+            if key == "p" && !prop["s"].as_bool().unwrap_or(false) && has_spatial_tangents(prop) {
+                self.diag(
+                    &location,
+                    "spatial bezier tangents on position are imported as straight paths",
+                );
+            }
+
+            for (var, value, dim) in components {
+                let prop_location = format!("{} {}", location, var);
+                let (initial, keys) = if is_animated(value) {
+                    let keys = self.keys(&prop_location, value, dim, factor, to_seconds);
+                    let Some(first) = keys.first() else {
+                        self.diag(&prop_location, "animated property has no keyframes");
+                        continue;
+                    };
+                    (first.value, Some(keys))
+                } else {
+                    match component(&value["k"], dim) {
+                        Some(v) => (v * factor, None),
+                        None => {
+                            self.diag(&prop_location, "invalid static value");
+                            continue;
+                        }
+                    }
+                };
+                let (min, max) = if suffix == "opacity" {
+                    (Some(0.0), Some(1.0))
+                } else {
+                    (None, None)
+                };
+                self.variables.insert(
+                    var.clone(),
+                    AnimationVariableDef::Float {
+                        initial: if suffix == "opacity" {
+                            initial.clamp(0.0, 1.0)
+                        } else {
+                            initial
+                        },
+                        min,
+                        max,
+                    },
+                );
+                if let Some(keys) = keys {
+                    self.tracks.push((var, keys));
+                }
+            }
+        }
+    }
+
+    /// アニメーションプロパティのキーフレームを Key 列へ変換
+    fn keys(
+        &mut self,
+        location: &str,
+        prop: &Value,
+        dim: usize,
+        factor: f64,
+        to_seconds: impl Fn(f64) -> f64,
+    ) -> Vec<Key> {
+        let frames: Vec<&Value> = prop["k"].as_array().into_iter().flatten().collect();
+        let mut keys: Vec<Key> = Vec::new();
+        for (i, kf) in frames.iter().enumerate() {
+            let Some(frame) = kf["t"].as_f64() else {
+                self.diag(location, format!("keyframe {} has no time 't'", i));
+                continue;
+            };
+            let time = to_seconds(frame);
+            // 旧形式（v5.5 未満）では最終キーに `s` がなく、直前キーの `e` が終了値
+            let value = component(&kf["s"], dim).or_else(|| {
+                i.checked_sub(1)
+                    .and_then(|prev| component(&frames[prev]["e"], dim))
+            });
+            let Some(value) = value.map(|v| v * factor) else {
+                self.diag(location, format!("keyframe {} has no value", i));
+                continue;
+            };
+            let is_last = i + 1 == frames.len();
+            let easing = if is_last || kf["h"].as_i64() == Some(1) {
+                None
+            } else {
+                let tangent = |t: &Value, axis: &str| component(&t[axis], dim);
+                match (
+                    tangent(&kf["o"], "x"),
+                    tangent(&kf["o"], "y"),
+                    tangent(&kf["i"], "x"),
+                    tangent(&kf["i"], "y"),
+                ) {
+                    (Some(x1), Some(y1), Some(x2), Some(y2)) => {
+                        let (easing, err) = fit_cubic_bezier(x1, y1, x2, y2);
+                        let source = format!("bezier({}, {}, {}, {})", x1, y1, x2, y2);
+                        if let Some(d) = easing_fit_diagnostic(location, &source, err) {
+                            self.diagnostics.push(d);
+                        }
+                        Some(easing)
+                    }
+                    _ => None,
+                }
+            };
+            // hold: 次キー時刻まで値を保持し、そこで切り替える
+            if let Some(prev) = keys.last()
+                && i > 0
+                && frames[i - 1]["h"].as_i64() == Some(1)
+            {
+                let held = prev.value;
+                keys.push(Key {
+                    time,
+                    value: held,
+                    easing: None,
+                });
+            }
+            keys.push(Key {
+                time,
+                value,
+                easing,
+            });
+        }
+        keys
+    }
+}
+
+/// アニメーションプロパティか（`a: 1`、または `k` がキーフレーム配列）
+fn is_animated(prop: &Value) -> bool {
+    prop["a"].as_i64() == Some(1)
+        || prop["k"]
+            .as_array()
+            .and_then(|k| k.first())
+            .is_some_and(|first| first.get("t").is_some())
+}
+
+/// 静的値が非ゼロか（アンカー・スキュー等の未対応プロパティ検出用）
+fn has_non_zero(prop: &Value) -> bool {
+    let values: Vec<f64> = match &prop["k"] {
+        Value::Number(n) => n.as_f64().into_iter().collect(),
+        Value::Array(a) => a.iter().filter_map(Value::as_f64).collect(),
+        _ => return false,
+    };
+    values.iter().any(|v| *v != 0.0)
+}
+
+/// position に空間ベジェ接線（`ti` / `to`）が非ゼロで含まれるか
+fn has_spatial_tangents(prop: &Value) -> bool {
+    prop["k"].as_array().into_iter().flatten().any(|kf| {
+        ["ti", "to"].iter().any(|t| {
+            kf[*t]
+                .as_array()
+                .is_some_and(|v| v.iter().any(|c| c.as_f64().is_some_and(|c| c != 0.0)))
+        })
+    })
+}
+
+/// スカラーまたは配列から `dim` 番目の成分を取得（要素が足りなければ先頭）
+fn component(value: &Value, dim: usize) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Array(a) => a.get(dim).or_else(|| a.first()).and_then(Value::as_f64),
+        _ => None,
+    }
+}
+
+/// レイヤー名を変数名に使える文字（英数字・`_`）へ正規化
+fn sanitize(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
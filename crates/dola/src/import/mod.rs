@@ -0,0 +1,218 @@
+//! 外部アニメーション形式からの取り込み
+//!
+//! 各インポーターは [`ImportResult`] を返す。変換できなかった要素は処理を中断せず
+//! [`ImportDiagnostic`] として報告し、変換可能な部分だけでドキュメントを構築する。
+
+mod css;
+#[cfg(feature = "json")]
+mod lottie;
+
+pub use css::import_css;
+#[cfg(feature = "json")]
+pub use lottie::import_lottie;
+
+use std::fmt;
+
+use crate::document::DolaDocument;
+use crate::easing::{EasingFunction, EasingName, ParametricEasing};
+use crate::storyboard::{BetweenKeyframes, KeyframeNames, KeyframeRef, StoryboardEntry};
+use crate::transition::{TransitionDef, TransitionExprs, TransitionRef, TransitionValue};
+
+/// イージング近似の許容誤差（これを超える場合は診断を出す）
+const EASING_FIT_TOLERANCE: f64 = 0.05;
+/// イージング近似のサンプル数
+const EASING_FIT_SAMPLES: usize = 32;
+
+/// 取り込み時の診断メッセージ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportDiagnostic {
+    /// 発生箇所（セレクタ・キーフレーム名・レイヤー名など）
+    pub location: String,
+    /// 内容
+    pub message: String,
+}
+
+impl ImportDiagnostic {
+    pub(crate) fn new(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ImportDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// 取り込み結果（変換済みドキュメント + 診断）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportResult {
+    /// 変換済みドキュメント（バリデーション済み）
+    pub document: DolaDocument,
+    /// 未対応プロパティ・近似変換などの診断
+    pub diagnostics: Vec<ImportDiagnostic>,
+}
+
+/// 変数 1 つ分のキー（ストーリーボードローカル時刻）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Key {
+    pub time: f64,
+    pub value: f64,
+    /// このキーから次のキーまでの区間に適用するイージング
+    pub easing: Option<EasingFunction>,
+}
+
+/// 変数ごとのキー列からキーフレームベースのエントリ列を構築
+///
+/// 全トラックの時刻ごとに純粋KFエントリ（名前は時刻 `"{t:.3}s"`）を生成し、
+/// 隣接キー間を `between` 配置のトランジションで結ぶ。
+pub(crate) fn keyframed_entries(tracks: &[(String, Vec<Key>)]) -> Vec<StoryboardEntry> {
+    let mut times: Vec<f64> = tracks
+        .iter()
+        .flat_map(|(_, keys)| keys.iter().map(|k| k.time))
+        .collect();
+    times.sort_by(f64::total_cmp);
+    times.dedup_by(|a, b| keyframe_name(*a) == keyframe_name(*b));
+
+    let mut entries: Vec<StoryboardEntry> = times
+        .iter()
+        .map(|t| StoryboardEntry {
+            variable: None,
+            transition: None,
+            at: Some(KeyframeRef::WithOffset {
+                keyframes: KeyframeNames::Single("start".to_string()),
+                offset: *t,
+            }),
+            between: None,
+            keyframe: Some(keyframe_name(*t)),
+        })
+        .collect();
+
+    for (variable, keys) in tracks {
+        if let [only] = keys.as_slice() {
+            entries.push(StoryboardEntry {
+                variable: Some(variable.clone()),
+                transition: Some(TransitionRef::Inline(TransitionDef {
+                    from: None,
+                    to: Some(TransitionValue::Scalar(only.value)),
+                    relative_to: None,
+                    easing: None,
+                    delay: 0.0,
+                    duration: None,
+                    exprs: TransitionExprs::default(),
+                })),
+                at: Some(KeyframeRef::Single(keyframe_name(only.time))),
+                between: None,
+                keyframe: None,
+            });
+            continue;
+        }
+        for pair in keys.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            entries.push(StoryboardEntry {
+                variable: Some(variable.clone()),
+                transition: Some(TransitionRef::Inline(TransitionDef {
+                    from: Some(TransitionValue::Scalar(a.value)),
+                    to: Some(TransitionValue::Scalar(b.value)),
+                    relative_to: None,
+                    easing: a.easing.clone(),
+                    delay: 0.0,
+                    duration: Some(b.time - a.time),
+                    exprs: TransitionExprs::default(),
+                })),
+                at: None,
+                between: Some(BetweenKeyframes {
+                    from: keyframe_name(a.time),
+                    to: keyframe_name(b.time),
+                }),
+                keyframe: None,
+            });
+        }
+    }
+    entries
+}
+
+/// 時刻からキーフレーム名を生成（ミリ秒精度）
+pub(crate) fn keyframe_name(t: f64) -> String {
+    format!("{:.3}s", t)
+}
+
+/// 2 次元 3 次ベジェイージング（CSS `cubic-bezier(x1, y1, x2, y2)` / Lottie 接線）を
+/// Dola の 1 次元 3 次ベジェへ最小二乗近似する。戻り値は (イージング, 最大誤差)
+pub(crate) fn fit_cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> (EasingFunction, f64) {
+    if (x1 - y1).abs() < 1e-9 && (x2 - y2).abs() < 1e-9 {
+        return (EasingFunction::Named(EasingName::Linear), 0.0);
+    }
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let bez = |p1: f64, p2: f64, t: f64| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+
+    // 進捗 s ごとの目標値 y(x = s) を求める（x(t) は単調なので二分探索）
+    let samples: Vec<(f64, f64)> = (0..=EASING_FIT_SAMPLES)
+        .map(|i| {
+            let s = i as f64 / EASING_FIT_SAMPLES as f64;
+            let (mut lo, mut hi) = (0.0, 1.0);
+            for _ in 0..48 {
+                let mid = (lo + hi) / 2.0;
+                if bez(x1, x2, mid) < s {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            (s, bez(y1, y2, (lo + hi) / 2.0))
+        })
+        .collect();
+
+    // f(s) = 3(1-s)^2 s c1 + 3(1-s) s^2 c2 + s^3 の c1, c2 を正規方程式で解く
+    let (mut aa, mut ab, mut bb, mut ar, mut br) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for &(s, y) in &samples {
+        let u = 1.0 - s;
+        let (a, b, r) = (3.0 * u * u * s, 3.0 * u * s * s, y - s * s * s);
+        aa += a * a;
+        ab += a * b;
+        bb += b * b;
+        ar += a * r;
+        br += b * r;
+    }
+    let det = aa * bb - ab * ab;
+    let (c1, c2) = if det.abs() < 1e-12 {
+        (y1, y2)
+    } else {
+        ((ar * bb - br * ab) / det, (aa * br - ab * ar) / det)
+    };
+
+    let max_error = samples
+        .iter()
+        .map(|&(s, y)| (bez(c1, c2, s) - y).abs())
+        .fold(0.0, f64::max);
+    let easing = EasingFunction::Parametric(ParametricEasing::CubicBezier {
+        x0: 0.0,
+        x1: c1,
+        x2: c2,
+        x3: 1.0,
+    });
+    (easing, max_error)
+}
+
+/// 近似誤差が許容値を超える場合の診断
+pub(crate) fn easing_fit_diagnostic(
+    location: &str,
+    source: &str,
+    max_error: f64,
+) -> Option<ImportDiagnostic> {
+    (max_error > EASING_FIT_TOLERANCE).then(|| {
+        ImportDiagnostic::new(
+            location,
+            format!(
+                "timing function '{}' approximated with max error {:.3}",
+                source, max_error
+            ),
+        )
+    })
+}
@@ -8,22 +8,36 @@ mod builder;
 mod document;
 mod easing;
 mod error;
+mod expr;
+mod import;
 mod playback;
+mod player;
+mod replay;
 mod storyboard;
+mod timeline;
 mod transition;
 mod validate;
 mod value;
 mod variable;
+mod visualize;
 
 pub use builder::{DolaDocumentBuilder, StoryboardBuilder};
 pub use document::DolaDocument;
 pub use easing::{EasingFunction, EasingName, ParametricEasing};
 pub use error::DolaError;
+pub use expr::{BinaryOp, Expr, Expression, Function};
+#[cfg(feature = "json")]
+pub use import::import_lottie;
+pub use import::{import_css, ImportDiagnostic, ImportResult};
 pub use playback::{PlaybackState, ScheduleRequest};
+pub use player::DolaPlayer;
+pub use replay::{PlayerInput, ReplayFrame, ReplayLog, ReplayTrace, Replayer};
 pub use storyboard::{
-    BetweenKeyframes, InterruptionPolicy, KeyframeNames, KeyframeRef, Storyboard, StoryboardEntry,
+    BetweenKeyframes, InterruptionPolicy, KeyframeNames, KeyframeRef, ParamType, Storyboard,
+    StoryboardEntry,
 };
-pub use transition::{TransitionDef, TransitionRef, TransitionValue};
+pub use timeline::{ResolvedStoryboard, ResolvedTransition};
+pub use transition::{TransitionDef, TransitionExprs, TransitionRef, TransitionValue};
 pub use validate::Validate;
-pub use value::DynamicValue;
+pub use value::{AnimationValue, DynamicValue};
 pub use variable::AnimationVariableDef;
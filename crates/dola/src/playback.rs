@@ -1,4 +1,6 @@
 // TODO: Implement PlaybackState, ScheduleRequest
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// 再生状態列挙型
@@ -23,4 +25,7 @@ pub struct ScheduleRequest {
     pub storyboard: String,
     /// 開始時刻（f64秒、相対時間）
     pub start_time: f64,
+    /// ストーリーボードパラメータへの引数（要求発行時に式へ束縛）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, f64>,
 }
@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+
+use crate::document::DolaDocument;
+use crate::easing::EasingFunction;
+use crate::error::DolaError;
+use crate::playback::{PlaybackState, ScheduleRequest};
+use crate::replay::{PlayerInput, ReplayLog};
+use crate::storyboard::InterruptionPolicy;
+use crate::timeline::ResolvedStoryboard;
+use crate::transition::TransitionValue;
+use crate::value::AnimationValue;
+use crate::variable::AnimationVariableDef;
+
+/// Compress で中断されたストーリーボードの残りを再生する最大時間（f64秒）
+const COMPRESSED_REMAINDER: f64 = 0.25;
+
+/// 決定的ストーリーボードプレイヤー
+///
+/// すべての状態変化は [`PlayerInput`] を通じてのみ発生する。
+/// 同じドキュメントに同じ入力列を与えれば、同じ変数値が再現される。
+/// 時刻は前進のみで、巻き戻しは [`Replayer`](crate::replay::Replayer) で入力列を再実行して行う。
+#[derive(Clone)]
+pub struct DolaPlayer {
+    document: DolaDocument,
+    clock: f64,
+    values: BTreeMap<String, AnimationValue>,
+    states: BTreeMap<String, PlaybackState>,
+    active: Vec<ActiveStoryboard>,
+    pending: Vec<PendingStoryboard>,
+    sequence: u64,
+    log: Option<ReplayLog>,
+}
+
+/// 開始待ちのストーリーボード
+#[derive(Clone)]
+struct PendingStoryboard {
+    name: String,
+    start: f64,
+    sequence: u64,
+    timeline: ResolvedStoryboard,
+}
+
+/// 再生中のストーリーボード（開始時点の変数値で区間値を確定済み）
+#[derive(Clone)]
+struct ActiveStoryboard {
+    name: String,
+    start: f64,
+    timeline: ResolvedStoryboard,
+    tracks: Vec<Track>,
+    compression: Option<Compression>,
+}
+
+/// Compress による時間圧縮（時刻 `at` 以降を `speed` 倍速で再生）
+#[derive(Clone, Copy)]
+struct Compression {
+    at: f64,
+    speed: f64,
+}
+
+/// 1 変数分の区間列
+#[derive(Clone)]
+struct Track {
+    variable: String,
+    base: AnimationValue,
+    segments: Vec<Segment>,
+}
+
+/// 値が確定したトランジション区間（ローカル時刻）
+#[derive(Clone)]
+struct Segment {
+    begin: f64,
+    end: f64,
+    from: AnimationValue,
+    to: AnimationValue,
+    easing: Option<EasingFunction>,
+}
+
+impl DolaPlayer {
+    /// 全変数を初期値に設定したプレイヤーを作成（時刻 0.0）
+    pub fn new(document: DolaDocument) -> Self {
+        let values = document
+            .variable
+            .iter()
+            .map(|(name, def)| (name.clone(), def.initial_value()))
+            .collect();
+        Self {
+            document,
+            clock: 0.0,
+            values,
+            states: BTreeMap::new(),
+            active: Vec::new(),
+            pending: Vec::new(),
+            sequence: 0,
+            log: None,
+        }
+    }
+
+    /// 入力記録を有効化したプレイヤーに変換
+    pub fn with_recording(mut self) -> Self {
+        self.log = Some(ReplayLog::default());
+        self
+    }
+
+    /// 記録中の入力ログ
+    pub fn log(&self) -> Option<&ReplayLog> {
+        self.log.as_ref()
+    }
+
+    /// 入力ログを取り出し、記録を終了
+    pub fn take_log(&mut self) -> Option<ReplayLog> {
+        self.log.take()
+    }
+
+    /// 再生対象のドキュメント
+    pub fn document(&self) -> &DolaDocument {
+        &self.document
+    }
+
+    /// 現在のプレイヤー時刻（f64秒）
+    pub fn clock(&self) -> f64 {
+        self.clock
+    }
+
+    /// 変数の現在値
+    pub fn value(&self, variable: &str) -> Option<&AnimationValue> {
+        self.values.get(variable)
+    }
+
+    /// 全変数の現在値
+    pub fn values(&self) -> &BTreeMap<String, AnimationValue> {
+        &self.values
+    }
+
+    /// ストーリーボードの最新の再生状態（未スケジュールなら Idle）
+    pub fn state(&self, storyboard: &str) -> PlaybackState {
+        self.states
+            .get(storyboard)
+            .copied()
+            .unwrap_or(PlaybackState::Idle)
+    }
+
+    /// ストーリーボードの再生をスケジュール
+    pub fn schedule(&mut self, request: ScheduleRequest) -> Result<(), DolaError> {
+        self.apply(&PlayerInput::Schedule(request))
+    }
+
+    /// ストーリーボード自身の割り込み終了戦略に従って中断
+    pub fn interrupt(&mut self, storyboard: &str) -> Result<(), DolaError> {
+        self.apply(&PlayerInput::Interrupt {
+            storyboard: storyboard.to_string(),
+        })
+    }
+
+    /// 絶対時刻へシーク（現在時刻より前へのシークは [`DolaError::InvalidSeek`]）
+    pub fn seek(&mut self, time: f64) -> Result<(), DolaError> {
+        self.apply(&PlayerInput::Seek { time })
+    }
+
+    /// クロックを `delta` 秒進める
+    pub fn tick(&mut self, delta: f64) -> Result<(), DolaError> {
+        self.apply(&PlayerInput::Tick { delta })
+    }
+
+    /// 入力を 1 件適用（記録有効時はログへ追記）
+    pub fn apply(&mut self, input: &PlayerInput) -> Result<(), DolaError> {
+        if let Some(log) = &mut self.log {
+            log.inputs.push(input.clone());
+        }
+        match input {
+            PlayerInput::Schedule(request) => self.apply_schedule(request),
+            PlayerInput::Interrupt { storyboard } => {
+                self.apply_interrupt(storyboard);
+                Ok(())
+            }
+            PlayerInput::Seek { time } => {
+                if time.is_nan() || *time < self.clock {
+                    return Err(DolaError::InvalidSeek {
+                        time: *time,
+                        clock: self.clock,
+                    });
+                }
+                self.advance_to(*time);
+                Ok(())
+            }
+            PlayerInput::Tick { delta } => {
+                self.advance_to(self.clock + delta.max(0.0));
+                Ok(())
+            }
+        }
+    }
+
+    fn apply_schedule(&mut self, request: &ScheduleRequest) -> Result<(), DolaError> {
+        let timeline = ResolvedStoryboard::bind(
+            &self.document,
+            &request.storyboard,
+            &request.args,
+            &self.values,
+        )?;
+
+        // Cancel は要求発行時点で即座に破棄（値はその瞬間で凍結）
+        let variables: Vec<String> = timeline.variables().map(str::to_string).collect();
+        let clock = self.clock;
+        self.interrupt_conflicts(&variables, clock, |policy| {
+            policy == InterruptionPolicy::Cancel
+        });
+
+        self.sequence += 1;
+        self.pending.push(PendingStoryboard {
+            name: request.storyboard.clone(),
+            start: self.clock + request.start_time.max(0.0),
+            sequence: self.sequence,
+            timeline,
+        });
+        self.states
+            .insert(request.storyboard.clone(), PlaybackState::Idle);
+        self.advance_to(self.clock);
+        Ok(())
+    }
+
+    fn apply_interrupt(&mut self, storyboard: &str) {
+        let before = self.pending.len();
+        self.pending.retain(|p| p.name != storyboard);
+        if self.pending.len() != before {
+            self.states
+                .insert(storyboard.to_string(), PlaybackState::Cancelled);
+        }
+
+        let clock = self.clock;
+        let mut idx = 0;
+        while idx < self.active.len() {
+            let policy = self.active[idx].timeline.interruption_policy;
+            if self.active[idx].name == storyboard && policy != InterruptionPolicy::Never {
+                if policy == InterruptionPolicy::Compress && self.active[idx].compress(clock) {
+                    idx += 1;
+                    continue;
+                }
+                let active = self.active.remove(idx);
+                self.finish_interrupted(active, clock);
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    /// 開始時刻を迎えた待機中ストーリーボードを順に起動しつつ `target` まで進める
+    fn advance_to(&mut self, target: f64) {
+        loop {
+            let next = self
+                .pending
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.start <= target)
+                .min_by(|(_, a), (_, b)| {
+                    a.start
+                        .total_cmp(&b.start)
+                        .then(a.sequence.cmp(&b.sequence))
+                })
+                .map(|(idx, _)| idx);
+            let Some(idx) = next else { break };
+
+            let pending = self.pending.remove(idx);
+            let start = pending.start.max(self.clock);
+            self.evaluate_at(start);
+            self.activate(pending, start);
+        }
+        self.evaluate_at(target);
+    }
+
+    fn activate(&mut self, mut pending: PendingStoryboard, at: f64) {
+        let variables: Vec<String> = pending.timeline.variables().map(str::to_string).collect();
+
+        // Compress: 競合するストーリーボードの残りを圧縮して再生
+        for active in self.active.iter_mut() {
+            if active.timeline.interruption_policy == InterruptionPolicy::Compress
+                && active.shares_variable(&variables)
+            {
+                active.compress(at);
+            }
+        }
+
+        // Never・圧縮中の Compress: 競合するストーリーボードの完了まで待機
+        let blocked_until = self
+            .active
+            .iter()
+            .filter(|a| match a.timeline.interruption_policy {
+                InterruptionPolicy::Never => true,
+                InterruptionPolicy::Compress => a.compression.is_some(),
+                _ => false,
+            })
+            .filter(|a| a.shares_variable(&variables))
+            .map(|a| a.end_time())
+            .fold(None, |acc: Option<f64>, t| {
+                Some(acc.map_or(t, |acc| acc.max(t)))
+            });
+        if let Some(until) = blocked_until {
+            pending.start = until;
+            self.pending.push(pending);
+            return;
+        }
+
+        self.interrupt_conflicts(&variables, at, |_| true);
+
+        let tracks = self.build_tracks(&pending.timeline);
+        self.states
+            .insert(pending.name.clone(), PlaybackState::Playing);
+        self.active.push(ActiveStoryboard {
+            name: pending.name,
+            start: at,
+            timeline: pending.timeline,
+            tracks,
+            compression: None,
+        });
+        self.evaluate_at(at);
+    }
+
+    /// 変数を共有する再生中ストーリーボードのうち `filter` に合致する戦略のものを終了
+    ///
+    /// Trim は競合する変数のトラックだけを切り離し、残りの変数がなくなった場合のみ終了する。
+    fn interrupt_conflicts(
+        &mut self,
+        variables: &[String],
+        at: f64,
+        filter: impl Fn(InterruptionPolicy) -> bool,
+    ) {
+        let mut idx = 0;
+        while idx < self.active.len() {
+            let active = &self.active[idx];
+            let policy = active.timeline.interruption_policy;
+            if policy != InterruptionPolicy::Never
+                && filter(policy)
+                && active.shares_variable(variables)
+            {
+                if policy == InterruptionPolicy::Trim {
+                    let tracks = &mut self.active[idx].tracks;
+                    tracks.retain(|t| !variables.contains(&t.variable));
+                    if !tracks.is_empty() {
+                        idx += 1;
+                        continue;
+                    }
+                }
+                let active = self.active.remove(idx);
+                self.finish_interrupted(active, at);
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    /// 割り込み終了戦略に従って変数値を確定
+    fn finish_interrupted(&mut self, active: ActiveStoryboard, at: f64) {
+        let local = active.timeline.local_time(active.elapsed(at));
+        let state = match active.timeline.interruption_policy {
+            // 値はその瞬間で凍結（評価済み）
+            InterruptionPolicy::Cancel | InterruptionPolicy::Trim => PlaybackState::Cancelled,
+            InterruptionPolicy::Conclude => {
+                for track in &active.tracks {
+                    if let Some(seg) = track
+                        .segments
+                        .iter()
+                        .find(|s| s.begin <= local && local < s.end)
+                    {
+                        self.values.insert(track.variable.clone(), seg.to.clone());
+                    }
+                }
+                PlaybackState::Completed
+            }
+            // 終了しない（圧縮できない）場合のみ: 残りの最終値へジャンプ
+            InterruptionPolicy::Compress => {
+                for track in &active.tracks {
+                    if let Some(seg) = track.segments.last() {
+                        self.values.insert(track.variable.clone(), seg.to.clone());
+                    }
+                }
+                PlaybackState::Completed
+            }
+            InterruptionPolicy::Never => return,
+        };
+        self.states.insert(active.name, state);
+    }
+
+    /// 再生中ストーリーボードを時刻 `time` で評価し、完了したものを除去
+    fn evaluate_at(&mut self, time: f64) {
+        self.clock = time;
+        for active in &self.active {
+            let local = active.timeline.local_time(active.elapsed(time));
+            for track in &active.tracks {
+                self.values
+                    .insert(track.variable.clone(), track.sample(local));
+            }
+        }
+        let states = &mut self.states;
+        self.active.retain(|active| {
+            if time >= active.end_time() {
+                states.insert(active.name.clone(), PlaybackState::Completed);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// 現在値を起点に各変数の区間値を確定
+    fn build_tracks(&self, timeline: &ResolvedStoryboard) -> Vec<Track> {
+        let mut order: Vec<&crate::timeline::ResolvedTransition> =
+            timeline.transitions.iter().collect();
+        order.sort_by(|a, b| {
+            a.begin
+                .total_cmp(&b.begin)
+                .then(a.entry_index.cmp(&b.entry_index))
+        });
+
+        let mut tracks: Vec<Track> = Vec::new();
+        for trans in order {
+            let Some(def) = self.document.variable.get(&trans.variable) else {
+                continue;
+            };
+            let pos = match tracks.iter().position(|t| t.variable == trans.variable) {
+                Some(pos) => pos,
+                None => {
+                    let base = self
+                        .values
+                        .get(&trans.variable)
+                        .cloned()
+                        .unwrap_or_else(|| def.initial_value());
+                    tracks.push(Track {
+                        variable: trans.variable.clone(),
+                        base,
+                        segments: Vec::new(),
+                    });
+                    tracks.len() - 1
+                }
+            };
+            let track = &mut tracks[pos];
+            let current = track
+                .segments
+                .last()
+                .map(|s| s.to.clone())
+                .unwrap_or_else(|| track.base.clone());
+
+            let from = match (&trans.def.from, def) {
+                (Some(TransitionValue::Scalar(v)), AnimationVariableDef::Float { .. }) => {
+                    AnimationValue::Float(*v)
+                }
+                (Some(TransitionValue::Scalar(v)), AnimationVariableDef::Integer { .. }) => {
+                    AnimationValue::Integer(v.round() as i64)
+                }
+                _ => current,
+            };
+            let to = match (&trans.def.to, trans.def.relative_to, def) {
+                (Some(TransitionValue::Scalar(v)), _, AnimationVariableDef::Float { .. }) => {
+                    AnimationValue::Float(*v)
+                }
+                (Some(TransitionValue::Scalar(v)), _, AnimationVariableDef::Integer { .. }) => {
+                    AnimationValue::Integer(v.round() as i64)
+                }
+                (Some(TransitionValue::Dynamic(v)), _, AnimationVariableDef::Object { .. }) => {
+                    AnimationValue::Object(v.clone())
+                }
+                (None, Some(rel), AnimationVariableDef::Float { .. }) => {
+                    AnimationValue::Float(from.as_f64().unwrap_or(0.0) + rel)
+                }
+                (None, Some(rel), AnimationVariableDef::Integer { .. }) => {
+                    AnimationValue::Integer((from.as_f64().unwrap_or(0.0) + rel).round() as i64)
+                }
+                // タイプライター: 終了値省略時は文字列長
+                (
+                    None,
+                    None,
+                    AnimationVariableDef::Integer {
+                        typewriter: Some(text),
+                        ..
+                    },
+                ) => AnimationValue::Integer(text.chars().count() as i64),
+                _ => from.clone(),
+            };
+
+            track.segments.push(Segment {
+                begin: trans.begin,
+                end: trans.end,
+                from,
+                to,
+                easing: trans.def.easing.clone(),
+            });
+        }
+        tracks
+    }
+}
+
+impl ActiveStoryboard {
+    /// 時刻 `time` における開始からの経過時間（圧縮後は加速する）
+    fn elapsed(&self, time: f64) -> f64 {
+        match self.compression {
+            Some(c) if time > c.at => (c.at - self.start) + (time - c.at) * c.speed,
+            _ => time - self.start,
+        }
+    }
+
+    fn end_time(&self) -> f64 {
+        let end = self.start + self.timeline.playback_length();
+        match self.compression {
+            Some(c) => c.at + (end - c.at) / c.speed,
+            None => end,
+        }
+    }
+
+    /// 時刻 `at` 以降の残りを [`COMPRESSED_REMAINDER`] 以内に圧縮
+    ///
+    /// 終了しない（無限ループ・time_scale 0）ストーリーボードは圧縮できず `false`。
+    fn compress(&mut self, at: f64) -> bool {
+        if self.compression.is_some() {
+            return true;
+        }
+        let remaining = self.end_time() - at;
+        if !remaining.is_finite() {
+            return false;
+        }
+        self.compression = Some(Compression {
+            at,
+            speed: (remaining / COMPRESSED_REMAINDER).max(1.0),
+        });
+        true
+    }
+
+    fn shares_variable(&self, variables: &[String]) -> bool {
+        self.tracks.iter().any(|t| variables.contains(&t.variable))
+    }
+}
+
+impl Track {
+    /// ローカル時刻 `t` における値（最初の区間開始前は開始時点の値を保持）
+    fn sample(&self, t: f64) -> AnimationValue {
+        match self.segments.iter().rev().find(|s| s.begin <= t) {
+            Some(seg) => seg.sample(t),
+            None => self.base.clone(),
+        }
+    }
+}
+
+impl Segment {
+    fn sample(&self, t: f64) -> AnimationValue {
+        if t >= self.end {
+            return self.to.clone();
+        }
+        let progress = if self.end > self.begin {
+            (t - self.begin) / (self.end - self.begin)
+        } else {
+            1.0
+        };
+        let eased = match &self.easing {
+            Some(easing) => easing.ease(progress),
+            None => progress.clamp(0.0, 1.0),
+        };
+        match (&self.from, &self.to) {
+            (AnimationValue::Float(a), AnimationValue::Float(b)) => {
+                AnimationValue::Float(a + (b - a) * eased)
+            }
+            (AnimationValue::Integer(a), AnimationValue::Integer(b)) => {
+                let (a, b) = (*a as f64, *b as f64);
+                AnimationValue::Integer((a + (b - a) * eased).round() as i64)
+            }
+            // Object は補間なし（区間終了時に切り替え）
+            _ => self.from.clone(),
+        }
+    }
+}
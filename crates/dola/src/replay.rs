@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::document::DolaDocument;
+use crate::error::DolaError;
+use crate::playback::ScheduleRequest;
+use crate::player::DolaPlayer;
+use crate::value::AnimationValue;
+
+/// プレイヤーへの入力（記録・再生の単位）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlayerInput {
+    /// ストーリーボードのスケジュール要求
+    Schedule(ScheduleRequest),
+    /// ストーリーボードの中断要求
+    Interrupt { storyboard: String },
+    /// 絶対時刻へのシーク（f64秒）
+    Seek { time: f64 },
+    /// クロックの進行（f64秒）
+    Tick { delta: f64 },
+}
+
+/// シリアライズ可能な入力ログ
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    /// 記録順の入力列
+    #[serde(default)]
+    pub inputs: Vec<PlayerInput>,
+}
+
+impl ReplayLog {
+    /// ドキュメントに対して全入力を再生し、入力ごとの変数値を記録したトレースを返す
+    pub fn replay(&self, document: &DolaDocument) -> ReplayTrace {
+        let mut replayer = Replayer::new(document.clone(), self.clone());
+        let mut frames = vec![ReplayFrame::capture(&replayer.player, None, None)];
+        while let Some(result) = replayer.step() {
+            let index = replayer.position() - 1;
+            frames.push(ReplayFrame::capture(
+                &replayer.player,
+                Some(index),
+                result.err(),
+            ));
+        }
+        ReplayTrace {
+            frames,
+            document: document.clone(),
+            log: self.clone(),
+        }
+    }
+}
+
+/// 入力適用直後のプレイヤー状態スナップショット
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// 適用した入力のインデックス（初期状態は None）
+    pub input_index: Option<usize>,
+    /// 適用後のプレイヤー時刻
+    pub clock: f64,
+    /// 適用後の全変数値
+    pub values: BTreeMap<String, AnimationValue>,
+    /// 入力適用時のエラー（記録時と同じく再生時も再現される）
+    #[serde(skip)]
+    pub error: Option<DolaError>,
+}
+
+impl ReplayFrame {
+    fn capture(player: &DolaPlayer, input_index: Option<usize>, error: Option<DolaError>) -> Self {
+        Self {
+            input_index,
+            clock: player.clock(),
+            values: player.values().clone(),
+            error,
+        }
+    }
+}
+
+/// 再生結果のトレース（デバッグ用クエリ API）
+///
+/// 時刻指定のクエリは入力間の時刻もプレイヤーを再評価して求める。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayTrace {
+    /// 初期状態 + 入力ごとのスナップショット
+    pub frames: Vec<ReplayFrame>,
+    document: DolaDocument,
+    log: ReplayLog,
+}
+
+impl ReplayTrace {
+    /// 時刻 `time` における変数値
+    ///
+    /// 複数の入力が同じ時刻を通過した場合は、ログ順で最後の入力を採用する。
+    /// 記録された時間範囲外（最後の入力より後を含む）なら None。
+    pub fn value_at(&self, variable: &str, time: f64) -> Option<AnimationValue> {
+        let mut replayer = Replayer::new(self.document.clone(), self.log.clone());
+        self.sample(&mut replayer, variable, time)
+    }
+
+    /// 時刻 `t0`〜`t1`（両端含む）を `step` 秒間隔で標本化した変数値の列（時刻, 値）
+    ///
+    /// `step` が正でない場合や `t1 < t0` の場合は空。
+    pub fn value_between(
+        &self,
+        variable: &str,
+        t0: f64,
+        t1: f64,
+        step: f64,
+    ) -> Vec<(f64, AnimationValue)> {
+        if step.is_nan() || step <= 0.0 || t1.is_nan() || t1 < t0 {
+            return Vec::new();
+        }
+        // 浮動小数点誤差で終端の標本を落とさない
+        let count = ((t1 - t0) / step + 1e-9).floor() as usize;
+        let mut replayer = Replayer::new(self.document.clone(), self.log.clone());
+        (0..=count)
+            .map(|i| (t0 + step * i as f64).min(t1))
+            .filter_map(|t| {
+                self.sample(&mut replayer, variable, t)
+                    .map(|value| (t, value))
+            })
+            .collect()
+    }
+
+    /// `time` を通過したログ順で最後のフレーム
+    ///
+    /// フレーム k は入力 k-1 の適用直後。入力がクロックを進めた場合は
+    /// 直前フレームの時刻から k の時刻までの区間を通過したとみなす。
+    fn covering_frame(&self, time: f64) -> Option<usize> {
+        (0..self.frames.len()).rev().find(|&k| {
+            let clock = self.frames[k].clock;
+            clock == time || (k > 0 && self.frames[k - 1].clock < time && time < clock)
+        })
+    }
+
+    fn sample(&self, replayer: &mut Replayer, variable: &str, time: f64) -> Option<AnimationValue> {
+        let k = self.covering_frame(time)?;
+        let frame = &self.frames[k];
+        if frame.clock == time {
+            return frame.values.get(variable).cloned();
+        }
+        // 入力 k-1 の適用前の状態から time まで進める
+        replayer.seek_to(k - 1);
+        let mut player = replayer.player.clone();
+        player.seek(time).ok()?;
+        player.value(variable).cloned()
+    }
+}
+
+/// ステップ実行・巻き戻し可能なリプレイヤー（タイムトラベルデバッグ用）
+pub struct Replayer {
+    document: DolaDocument,
+    log: ReplayLog,
+    player: DolaPlayer,
+    position: usize,
+}
+
+impl Replayer {
+    /// 初期状態のリプレイヤーを作成
+    pub fn new(document: DolaDocument, log: ReplayLog) -> Self {
+        let player = DolaPlayer::new(document.clone());
+        Self {
+            document,
+            log,
+            player,
+            position: 0,
+        }
+    }
+
+    /// 次の入力を 1 件適用（全入力適用済みなら None）
+    pub fn step(&mut self) -> Option<Result<(), DolaError>> {
+        let input = self.log.inputs.get(self.position)?;
+        self.position += 1;
+        Some(self.player.apply(input))
+    }
+
+    /// 先頭から `position` 件の入力を適用した状態へ移動（巻き戻しは先頭から再実行）
+    pub fn seek_to(&mut self, position: usize) {
+        let position = position.min(self.log.inputs.len());
+        if position < self.position {
+            self.player = DolaPlayer::new(self.document.clone());
+            self.position = 0;
+        }
+        while self.position < position {
+            self.step();
+        }
+    }
+
+    /// 適用済み入力数
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 再生対象のログ
+    pub fn log(&self) -> &ReplayLog {
+        &self.log
+    }
+
+    /// 現在のプレイヤー状態
+    pub fn player(&self) -> &DolaPlayer {
+        &self.player
+    }
+}
@@ -1,4 +1,6 @@
 // TODO: Implement Storyboard, StoryboardEntry, KeyframeRef, KeyframeNames, BetweenKeyframes, InterruptionPolicy
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::transition::TransitionRef;
@@ -17,14 +19,28 @@ pub enum InterruptionPolicy {
     Cancel,
     /// 現在のトランジションを最終値へジャンプさせて完了（デフォルト）
     Conclude,
-    /// 割り込み開始時点まで再生して切断
+    /// 競合する変数のみ割り込み開始時点まで再生して切断（他の変数は再生を継続）
     Trim,
     /// 残りを圧縮（高速再生）して完了
+    ///
+    /// 残りは最大0.25秒に短縮され、割り込んだストーリーボードはその完了を待って開始する。
+    /// 終了しない（無限ループ）場合は残りの最終値へジャンプして完了。
     Compress,
     /// 中断不可。このストーリーボードが未完了なら新ストーリーボードの開始を待機
     Never,
 }
 
+/// ストーリーボードパラメータの型（ScheduleRequest.args で値を与える）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParamType {
+    /// 連続値
+    #[serde(rename = "f64")]
+    Float,
+    /// 整数値（引数は整数でなければならない）
+    #[serde(rename = "i64")]
+    Integer,
+}
+
 fn default_time_scale() -> f64 {
     1.0
 }
@@ -45,6 +61,9 @@ pub struct Storyboard {
     /// 割り込み終了戦略（デフォルト: Conclude）
     #[serde(default = "default_interruption_policy")]
     pub interruption_policy: InterruptionPolicy,
+    /// パラメータ宣言（名前 → 型）。式から名前で参照できる
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, ParamType>,
     /// エントリ配列
     #[serde(default)]
     pub entry: Vec<StoryboardEntry>,
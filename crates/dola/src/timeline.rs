@@ -0,0 +1,377 @@
+use std::collections::BTreeMap;
+
+use crate::document::DolaDocument;
+use crate::error::DolaError;
+use crate::storyboard::{InterruptionPolicy, KeyframeNames, KeyframeRef, ParamType, Storyboard};
+use crate::transition::{TransitionDef, TransitionRef, TransitionValue};
+use crate::value::AnimationValue;
+
+/// 解決済みトランジション配置（ストーリーボードローカル時刻）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedTransition {
+    /// 元エントリのインデックス
+    pub entry_index: usize,
+    /// 対象変数名
+    pub variable: String,
+    /// 配置時刻（delay 適用前）
+    pub placed: f64,
+    /// 遷移開始時刻（delay 適用後）
+    pub begin: f64,
+    /// 遷移終了時刻
+    pub end: f64,
+    /// 参照解決・式評価済みトランジション定義（数値はすべてリテラル）
+    pub def: TransitionDef,
+}
+
+/// 解決済みストーリーボード（キーフレーム時刻とトランジション配置を確定したタイムライン）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedStoryboard {
+    /// キーフレーム名 → ローカル時刻（"start" と暗黙的KFを含む）
+    pub keyframes: BTreeMap<String, f64>,
+    /// トランジション配置（エントリ順）
+    pub transitions: Vec<ResolvedTransition>,
+    /// 1 周期分の長さ（f64秒、time_scale 適用前）
+    pub duration: f64,
+    /// 再生速度倍率
+    pub time_scale: f64,
+    /// ループ回数
+    pub loop_count: Option<u32>,
+    /// 割り込み終了戦略
+    pub interruption_policy: InterruptionPolicy,
+}
+
+impl ResolvedStoryboard {
+    /// ドキュメント内の名前付きストーリーボードを解決（引数なし、変数は初期値で式を評価）
+    pub fn resolve(doc: &DolaDocument, name: &str) -> Result<Self, DolaError> {
+        let values = doc
+            .variable
+            .iter()
+            .map(|(name, def)| (name.clone(), def.initial_value()))
+            .collect();
+        Self::bind(doc, name, &BTreeMap::new(), &values)
+    }
+
+    /// 引数と変数の現在値を式へ束縛してストーリーボードを解決
+    ///
+    /// 識別子はパラメータ → 変数の順に解決される（パラメータが変数を隠す）。
+    pub fn bind(
+        doc: &DolaDocument,
+        name: &str,
+        args: &BTreeMap<String, f64>,
+        values: &BTreeMap<String, AnimationValue>,
+    ) -> Result<Self, DolaError> {
+        let sb = doc
+            .storyboard
+            .get(name)
+            .ok_or_else(|| DolaError::UndefinedStoryboard {
+                name: name.to_string(),
+            })?;
+        check_args(name, sb, args)?;
+
+        let lookup = |ident: &str| -> Option<f64> {
+            if sb.params.contains_key(ident) {
+                args.get(ident).copied()
+            } else {
+                values.get(ident).and_then(AnimationValue::as_f64)
+            }
+        };
+        let mut defs = Vec::with_capacity(sb.entry.len());
+        for (idx, entry) in sb.entry.iter().enumerate() {
+            let Some(tref) = &entry.transition else {
+                defs.push(None);
+                continue;
+            };
+            let def =
+                doc.resolve_transition(tref)
+                    .ok_or_else(|| DolaError::UndefinedTransition {
+                        storyboard: name.to_string(),
+                        entry_index: idx,
+                        name: match tref {
+                            TransitionRef::Named(name) => name.clone(),
+                            TransitionRef::Inline(_) => String::new(),
+                        },
+                    })?;
+            let bound = def
+                .bind(lookup)
+                .map_err(|reason| DolaError::InvalidExpression {
+                    storyboard: name.to_string(),
+                    entry_index: idx,
+                    reason,
+                })?;
+            if let Some(variable) = &entry.variable {
+                check_range(doc, variable, def, &bound)?;
+            }
+            defs.push(Some(bound));
+        }
+
+        Resolver::new(name, sb, defs).resolve()
+    }
+
+    /// ループを含む総再生長（ローカル時刻）。無限ループは `f64::INFINITY`
+    pub fn total_duration(&self) -> f64 {
+        match self.loop_count {
+            None => self.duration,
+            Some(_) if self.duration <= 0.0 => 0.0,
+            Some(0) => f64::INFINITY,
+            Some(n) => self.duration * n as f64,
+        }
+    }
+
+    /// 経過時間（f64秒、time_scale 適用前）から 1 周期内のローカル時刻へ変換
+    pub fn local_time(&self, elapsed: f64) -> f64 {
+        let t = (elapsed * self.time_scale).max(0.0);
+        if t >= self.total_duration() {
+            self.duration
+        } else if self.loop_count.is_some() && self.duration > 0.0 {
+            t % self.duration
+        } else {
+            t
+        }
+    }
+
+    /// 再生開始からの終了までの経過時間（f64秒、time_scale 適用後）
+    pub fn playback_length(&self) -> f64 {
+        let total = self.total_duration();
+        if total <= 0.0 {
+            0.0
+        } else if self.time_scale > 0.0 {
+            total / self.time_scale
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// このストーリーボードが操作する変数名（重複なし）
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        let mut names: Vec<&str> = self
+            .transitions
+            .iter()
+            .map(|t| t.variable.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names.into_iter()
+    }
+}
+
+/// エントリ終了時刻の解決状態（循環検出用）
+#[derive(Clone, Copy)]
+enum Slot {
+    Unresolved,
+    Visiting,
+    Done(f64),
+}
+
+/// 引数の過不足・型を検証
+fn check_args(name: &str, sb: &Storyboard, args: &BTreeMap<String, f64>) -> Result<(), DolaError> {
+    let invalid = |param: &str, reason: &str| DolaError::InvalidArgument {
+        storyboard: name.to_string(),
+        name: param.to_string(),
+        reason: reason.to_string(),
+    };
+    for (param, ty) in &sb.params {
+        match args.get(param) {
+            None => return Err(invalid(param, "missing argument")),
+            Some(v) if !v.is_finite() => return Err(invalid(param, "argument is not finite")),
+            Some(v) if *ty == ParamType::Integer && v.fract() != 0.0 => {
+                return Err(invalid(param, "expected integer"));
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(param) = args.keys().find(|k| !sb.params.contains_key(*k)) {
+        return Err(invalid(param, "undeclared parameter"));
+    }
+    Ok(())
+}
+
+/// 式から評価した from/to が変数の値域内か検証（リテラルは V12 の対象外）
+fn check_range(
+    doc: &DolaDocument,
+    variable: &str,
+    def: &TransitionDef,
+    bound: &TransitionDef,
+) -> Result<(), DolaError> {
+    let Some((min, max)) = doc.variable.get(variable).and_then(|v| v.range()) else {
+        return Ok(());
+    };
+    let fields = [("from", &def.from, &bound.from), ("to", &def.to, &bound.to)];
+    for (field, source, value) in fields {
+        let evaluated = match (source, value) {
+            (Some(TransitionValue::Expression(_)), Some(TransitionValue::Scalar(value))) => *value,
+            _ => continue,
+        };
+        if !(min..=max).contains(&evaluated) {
+            return Err(DolaError::ValueOutOfRange {
+                variable: variable.to_string(),
+                field: field.to_string(),
+                value: evaluated,
+                min,
+                max,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// キーフレーム参照を辿ってエントリ時刻を確定するリゾルバ
+struct Resolver<'a> {
+    name: &'a str,
+    sb: &'a Storyboard,
+    defs: Vec<Option<TransitionDef>>,
+    ends: Vec<Slot>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(name: &'a str, sb: &'a Storyboard, defs: Vec<Option<TransitionDef>>) -> Self {
+        Self {
+            name,
+            sb,
+            defs,
+            ends: vec![Slot::Unresolved; sb.entry.len()],
+        }
+    }
+
+    fn resolve(mut self) -> Result<ResolvedStoryboard, DolaError> {
+        let mut keyframes = BTreeMap::new();
+        keyframes.insert("start".to_string(), 0.0);
+        let mut transitions = Vec::new();
+        let mut duration: f64 = 0.0;
+
+        for (idx, entry) in self.sb.entry.iter().enumerate() {
+            let end = self.entry_end(idx)?;
+            duration = duration.max(end);
+            keyframes.insert(keyframe_name(idx, entry.keyframe.as_deref()), end);
+
+            if let (Some(variable), Some(def)) = (&entry.variable, self.defs[idx].clone()) {
+                let placed = self.placement(idx)?;
+                let begin = placed + delay_of(&def);
+                transitions.push(ResolvedTransition {
+                    entry_index: idx,
+                    variable: variable.clone(),
+                    placed,
+                    begin,
+                    end: end.max(begin),
+                    def,
+                });
+            }
+        }
+
+        Ok(ResolvedStoryboard {
+            keyframes,
+            transitions,
+            duration,
+            time_scale: self.sb.time_scale,
+            loop_count: self.sb.loop_count,
+            interruption_policy: self.sb.interruption_policy,
+        })
+    }
+
+    /// エントリの配置時刻（at / between / 前エントリ連結）
+    fn placement(&mut self, idx: usize) -> Result<f64, DolaError> {
+        let entry = &self.sb.entry[idx];
+        if let Some(at) = &entry.at {
+            let (names, offset) = match at {
+                KeyframeRef::Single(name) => (std::slice::from_ref(name), 0.0),
+                KeyframeRef::Multiple(names) => (names.as_slice(), 0.0),
+                KeyframeRef::WithOffset { keyframes, offset } => match keyframes {
+                    KeyframeNames::Single(name) => (std::slice::from_ref(name), *offset),
+                    KeyframeNames::Multiple(names) => (names.as_slice(), *offset),
+                },
+            };
+            // 複数指定は全KF完了待機（最遅）
+            let mut latest: f64 = 0.0;
+            for name in names {
+                latest = latest.max(self.keyframe_time(name)?);
+            }
+            Ok((latest + offset).max(0.0))
+        } else if let Some(between) = &entry.between {
+            self.keyframe_time(&between.from)
+        } else if idx == 0 {
+            Ok(0.0)
+        } else {
+            self.entry_end(idx - 1)
+        }
+    }
+
+    /// エントリ終了時刻（= このエントリのキーフレーム時刻）
+    fn entry_end(&mut self, idx: usize) -> Result<f64, DolaError> {
+        match self.ends[idx] {
+            Slot::Done(t) => return Ok(t),
+            Slot::Visiting => {
+                return Err(DolaError::KeyframeCycle {
+                    storyboard: self.name.to_string(),
+                    name: keyframe_name(idx, self.sb.entry[idx].keyframe.as_deref()),
+                });
+            }
+            Slot::Unresolved => {}
+        }
+        self.ends[idx] = Slot::Visiting;
+
+        let placed = self.placement(idx)?;
+        let timing = self.defs[idx]
+            .as_ref()
+            .map(|def| (delay_of(def), duration_of(def)));
+        let end = match timing {
+            Some((delay, duration)) => {
+                let begin = placed + delay;
+                match &self.sb.entry[idx].between {
+                    // duration はKF間時間差で上書き
+                    Some(between) => self.keyframe_time(&between.to)?.max(begin),
+                    None => begin + duration,
+                }
+            }
+            None => placed,
+        };
+
+        self.ends[idx] = Slot::Done(end);
+        Ok(end)
+    }
+
+    fn keyframe_time(&mut self, name: &str) -> Result<f64, DolaError> {
+        if name == "start" {
+            return Ok(0.0);
+        }
+        let idx = self
+            .keyframe_index(name)
+            .ok_or_else(|| DolaError::UndefinedKeyframe {
+                storyboard: self.name.to_string(),
+                name: name.to_string(),
+            })?;
+        self.entry_end(idx)
+    }
+
+    fn keyframe_index(&self, name: &str) -> Option<usize> {
+        if let Some(idx) = self
+            .sb
+            .entry
+            .iter()
+            .position(|e| e.keyframe.as_deref() == Some(name))
+        {
+            return Some(idx);
+        }
+        let idx: usize = name.strip_prefix("__implicit_")?.parse().ok()?;
+        match self.sb.entry.get(idx) {
+            Some(entry) if entry.keyframe.is_none() => Some(idx),
+            _ => None,
+        }
+    }
+}
+
+/// 束縛済み定義の delay（負値は 0 扱い）
+fn delay_of(def: &TransitionDef) -> f64 {
+    def.delay.max(0.0)
+}
+
+/// 束縛済み定義の duration（省略時・負値は 0 扱い）
+fn duration_of(def: &TransitionDef) -> f64 {
+    def.duration.unwrap_or(0.0).max(0.0)
+}
+
+/// エントリのキーフレーム名（省略時は暗黙的KF名 `__implicit_{index}`）
+fn keyframe_name(idx: usize, explicit: Option<&str>) -> String {
+    match explicit {
+        Some(name) => name.to_string(),
+        None => format!("__implicit_{}", idx),
+    }
+}
@@ -2,16 +2,19 @@
 use serde::{Deserialize, Serialize};
 
 use crate::easing::EasingFunction;
+use crate::expr::Expression;
 use crate::value::DynamicValue;
 
 /// トランジションの開始値・終了値を表す型
-/// serde 動作: #[serde(untagged)] により Scalar(f64) を先に試行。
-/// 数値は Scalar、オブジェクト構造は Dynamic にマッピング。
+/// serde 動作: #[serde(untagged)] により Scalar(f64) → Expression → Dynamic の順に試行。
+/// 数値は Scalar、`{ expr = "..." }` は Expression、その他のオブジェクト構造は Dynamic にマッピング。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TransitionValue {
     /// スカラー値（f64/i64 変数向け）
     Scalar(f64),
+    /// 式（f64/i64 変数向け、スケジュール時に評価）
+    Expression(Expression),
     /// オブジェクト値（Object 型変数向け、補間なし）
     Dynamic(DynamicValue),
 }
@@ -23,6 +26,7 @@ pub enum TransitionValue {
 /// - f64/i64 型変数: from/to は TransitionValue::Scalar のみ（V13）。relative_to 使用可
 /// - Object 型変数: to（TransitionValue::Dynamic）のみ。from/relative_to/easing は不可（V10）
 /// - 総時間 = delay + duration（duration 省略時は即時 = delay 後即座に切り替え）
+/// - from/to は式を、relative_to/delay/duration は `exprs` で式を受け付ける（スケジュール時に評価。V14）
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransitionDef {
     /// 開始値（省略時は配置時点の変数の現在値）
@@ -43,6 +47,24 @@ pub struct TransitionDef {
     /// 遷移持続時間（f64秒、省略時は即時遷移）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    /// relative_to/delay/duration の式（指定時は数値フィールドより優先）
+    #[serde(flatten)]
+    pub exprs: TransitionExprs,
+}
+
+/// 数値フィールドに対応する式（`relative_to_expr = { expr = "..." }` 形式）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransitionExprs {
+    /// `relative_to` の式（to と排他。V11）
+    #[serde(rename = "relative_to_expr", skip_serializing_if = "Option::is_none")]
+    pub relative_to: Option<Expression>,
+    /// `delay` の式
+    #[serde(rename = "delay_expr", skip_serializing_if = "Option::is_none")]
+    pub delay: Option<Expression>,
+    /// `duration` の式
+    #[serde(rename = "duration_expr", skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Expression>,
 }
 
 /// トランジション参照（ハイブリッド: 名前文字列 or インライン定義）
@@ -55,3 +77,67 @@ pub enum TransitionRef {
     /// インライントランジション定義
     Inline(TransitionDef),
 }
+
+impl TransitionDef {
+    /// 式をすべて評価し、数値リテラルのみで構成された定義を返す
+    ///
+    /// `lookup` は識別子（パラメータ名・変数名）を値へ解決する。
+    /// エラー時はフィールド名を含む理由文字列を返す。
+    pub fn bind(&self, lookup: impl Fn(&str) -> Option<f64>) -> Result<TransitionDef, String> {
+        let eval = |field: &str, e: &Expression| -> Result<f64, String> {
+            e.eval(&lookup)
+                .map_err(|reason| format!("field '{}': {}", field, reason))
+        };
+        let value = |field: &str, v: &Option<TransitionValue>| -> Result<_, String> {
+            match v {
+                Some(TransitionValue::Expression(e)) => {
+                    eval(field, e).map(|v| Some(TransitionValue::Scalar(v)))
+                }
+                other => Ok(other.clone()),
+            }
+        };
+        let scalar = |field: &str, e: &Option<Expression>, literal: Option<f64>| match e {
+            Some(e) => eval(field, e).map(Some),
+            None => Ok(literal),
+        };
+        Ok(TransitionDef {
+            from: value("from", &self.from)?,
+            to: value("to", &self.to)?,
+            relative_to: scalar(
+                "relative_to_expr",
+                &self.exprs.relative_to,
+                self.relative_to,
+            )?,
+            easing: self.easing.clone(),
+            delay: scalar("delay_expr", &self.exprs.delay, Some(self.delay))?.unwrap_or(0.0),
+            duration: scalar("duration_expr", &self.exprs.duration, self.duration)?,
+            exprs: TransitionExprs::default(),
+        })
+    }
+
+    /// 相対終了値が指定されているか（数値または式）
+    pub fn has_relative_to(&self) -> bool {
+        self.relative_to.is_some() || self.exprs.relative_to.is_some()
+    }
+
+    /// 定義中の式をフィールド名と共に列挙
+    pub fn expressions(&self) -> Vec<(&'static str, &Expression)> {
+        let mut out = Vec::new();
+        if let Some(TransitionValue::Expression(e)) = &self.from {
+            out.push(("from", e));
+        }
+        if let Some(TransitionValue::Expression(e)) = &self.to {
+            out.push(("to", e));
+        }
+        if let Some(e) = &self.exprs.relative_to {
+            out.push(("relative_to_expr", e));
+        }
+        if let Some(e) = &self.exprs.delay {
+            out.push(("delay_expr", e));
+        }
+        if let Some(e) = &self.exprs.duration {
+            out.push(("duration_expr", e));
+        }
+        out
+    }
+}
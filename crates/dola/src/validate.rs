@@ -97,13 +97,16 @@ impl Validate for DolaDocument {
 
                 if let Some(trans_def) = resolved_transition {
                     // V11: to と relative_to 排他
-                    if trans_def.to.is_some() && trans_def.relative_to.is_some() {
+                    if trans_def.to.is_some() && trans_def.has_relative_to() {
                         errors.push(DolaError::MutuallyExclusive {
                             storyboard: sb_name.clone(),
                             entry_index: entry_idx,
                         });
                     }
 
+                    // V14: 式の構文・識別子参照
+                    validate_expressions(self, sb_name, sb, entry_idx, trans_def, &mut errors);
+
                     // V10, V13: 変数型に基づくトランジション制約
                     if let Some(ref var_name) = entry.variable {
                         if let Some(var_def) = self.variable.get(var_name) {
@@ -283,6 +286,47 @@ fn validate_keyframe_references(
     }
 }
 
+/// V14: 式の構文検証と識別子参照（パラメータまたは数値変数）の存在確認
+fn validate_expressions(
+    doc: &DolaDocument,
+    sb_name: &str,
+    sb: &crate::storyboard::Storyboard,
+    entry_idx: usize,
+    trans_def: &crate::transition::TransitionDef,
+    errors: &mut Vec<DolaError>,
+) {
+    for (field, expression) in trans_def.expressions() {
+        let parsed = match expression.parse() {
+            Ok(parsed) => parsed,
+            Err(reason) => {
+                errors.push(DolaError::InvalidExpression {
+                    storyboard: sb_name.to_string(),
+                    entry_index: entry_idx,
+                    reason: format!("field '{}': {}", field, reason),
+                });
+                continue;
+            }
+        };
+        for ident in parsed.identifiers() {
+            let resolvable = sb.params.contains_key(ident)
+                || matches!(
+                    doc.variable.get(ident),
+                    Some(AnimationVariableDef::Float { .. } | AnimationVariableDef::Integer { .. })
+                );
+            if !resolvable {
+                errors.push(DolaError::InvalidExpression {
+                    storyboard: sb_name.to_string(),
+                    entry_index: entry_idx,
+                    reason: format!(
+                        "field '{}': unknown identifier '{}' (expected parameter or numeric variable)",
+                        field, ident
+                    ),
+                });
+            }
+        }
+    }
+}
+
 /// KeyframeRef からキーフレーム名を収集
 fn collect_keyframe_names_from_ref(kf_ref: &KeyframeRef) -> Vec<String> {
     match kf_ref {
@@ -314,7 +358,7 @@ fn validate_transition_type_constraints(
                     field: "from".to_string(),
                 });
             }
-            if trans_def.relative_to.is_some() {
+            if trans_def.has_relative_to() {
                 errors.push(DolaError::ObjectTransitionViolation {
                     storyboard: sb_name.to_string(),
                     entry_index: entry_idx,
@@ -329,7 +373,7 @@ fn validate_transition_type_constraints(
                 });
             }
             if let Some(ref to) = trans_def.to {
-                if matches!(to, TransitionValue::Scalar(_)) {
+                if !matches!(to, TransitionValue::Dynamic(_)) {
                     errors.push(DolaError::TypeMismatch {
                         storyboard: sb_name.to_string(),
                         entry_index: entry_idx,
@@ -16,3 +16,29 @@ pub enum DynamicValue {
     Array(Vec<DynamicValue>),
     Map(BTreeMap<String, DynamicValue>),
 }
+
+/// 再生時のアニメーション変数の実値（変数型ごとの値表現）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum AnimationValue {
+    /// f64 変数の値
+    #[serde(rename = "f64")]
+    Float(f64),
+    /// i64 変数の値
+    #[serde(rename = "i64")]
+    Integer(i64),
+    /// Object 変数の値
+    #[serde(rename = "object")]
+    Object(DynamicValue),
+}
+
+impl AnimationValue {
+    /// 数値として取得（Object の場合は None）
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AnimationValue::Float(v) => Some(*v),
+            AnimationValue::Integer(v) => Some(*v as f64),
+            AnimationValue::Object(_) => None,
+        }
+    }
+}
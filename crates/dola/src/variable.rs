@@ -1,7 +1,7 @@
 // TODO: Implement AnimationVariableDef
 use serde::{Deserialize, Serialize};
 
-use crate::value::DynamicValue;
+use crate::value::{AnimationValue, DynamicValue};
 
 /// アニメーション変数定義（内部タグ方式: "type" フィールドで判別）
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -32,3 +32,29 @@ pub enum AnimationVariableDef {
     #[serde(rename = "object")]
     Object { initial: DynamicValue },
 }
+
+impl AnimationVariableDef {
+    /// 初期値を実値として取得
+    pub fn initial_value(&self) -> AnimationValue {
+        match self {
+            AnimationVariableDef::Float { initial, .. } => AnimationValue::Float(*initial),
+            AnimationVariableDef::Integer { initial, .. } => AnimationValue::Integer(*initial),
+            AnimationVariableDef::Object { initial } => AnimationValue::Object(initial.clone()),
+        }
+    }
+
+    /// 数値変数の値域（min/max 省略時は無限大）。Object 型は None
+    pub fn range(&self) -> Option<(f64, f64)> {
+        match self {
+            AnimationVariableDef::Float { min, max, .. } => Some((
+                min.unwrap_or(f64::NEG_INFINITY),
+                max.unwrap_or(f64::INFINITY),
+            )),
+            AnimationVariableDef::Integer { min, max, .. } => Some((
+                min.map_or(f64::NEG_INFINITY, |v| v as f64),
+                max.map_or(f64::INFINITY, |v| v as f64),
+            )),
+            AnimationVariableDef::Object { .. } => None,
+        }
+    }
+}
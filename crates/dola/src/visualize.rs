@@ -0,0 +1,283 @@
+use std::fmt::Write;
+
+use crate::timeline::{ResolvedStoryboard, ResolvedTransition};
+use crate::transition::TransitionValue;
+
+/// SVG 出力のレイアウト定数
+const SVG_LABEL_WIDTH: f64 = 120.0;
+const SVG_PX_PER_SECOND: f64 = 100.0;
+const SVG_LANE_HEIGHT: f64 = 24.0;
+const SVG_HEADER_HEIGHT: f64 = 40.0;
+const SVG_MARGIN: f64 = 8.0;
+/// イージング曲線のサンプル数
+const SVG_CURVE_SAMPLES: usize = 24;
+/// 時間軸の目盛りの最大数（長いストーリーボードは目盛り間隔を広げる）
+const SVG_MAX_TICKS: usize = 50;
+
+impl ResolvedStoryboard {
+    /// 変数ごとのレーン名（名前順）
+    fn lanes(&self) -> Vec<&str> {
+        self.variables().collect()
+    }
+
+    /// 表示対象のキーフレーム（暗黙的KFを除き時刻順）
+    fn visible_keyframes(&self) -> Vec<(&str, f64)> {
+        let mut kfs: Vec<(&str, f64)> = self
+            .keyframes
+            .iter()
+            .filter(|(name, _)| !name.starts_with("__implicit_"))
+            .map(|(name, t)| (name.as_str(), *t))
+            .collect();
+        kfs.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(b.0)));
+        kfs
+    }
+
+    /// タイムラインを SVG として出力
+    ///
+    /// 変数ごとに 1 レーン、トランジションは矩形バー（delay は破線）、
+    /// イージングはバー内の進捗曲線、キーフレームはヘッダ上の菱形で描画する。
+    pub fn to_svg(&self) -> String {
+        let lanes = self.lanes();
+        let x = |t: f64| SVG_LABEL_WIDTH + t * SVG_PX_PER_SECOND;
+        let lane_y = |i: usize| SVG_HEADER_HEIGHT + i as f64 * SVG_LANE_HEIGHT;
+        let width = x(self.duration) + SVG_MARGIN;
+        let height = lane_y(lanes.len()) + SVG_MARGIN;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.1}" height="{:.1}" viewBox="0 0 {:.1} {:.1}" font-family="sans-serif" font-size="11">"#,
+            width, height, width, height
+        );
+
+        // 時間軸（1・2・5×10^n 秒刻み）
+        let step = svg_tick_step(self.duration);
+        let ticks = ((self.duration / step).ceil() as usize).min(SVG_MAX_TICKS);
+        for i in 0..=ticks {
+            let s = i as f64 * step;
+            let tx = x(s);
+            let _ = writeln!(
+                svg,
+                r##"  <line x1="{tx:.1}" y1="{:.1}" x2="{tx:.1}" y2="{:.1}" stroke="#ddd"/>"##,
+                SVG_HEADER_HEIGHT - 4.0,
+                height - SVG_MARGIN
+            );
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{tx:.1}" y="12" text-anchor="middle">{}s</text>"#,
+                s
+            );
+        }
+
+        // キーフレーム（菱形）
+        for (name, t) in self.visible_keyframes() {
+            let kx = x(t);
+            let ky = SVG_HEADER_HEIGHT - 14.0;
+            let _ = writeln!(
+                svg,
+                r##"  <polygon class="keyframe" points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="#c33"><title>{} @ {:.3}s</title></polygon>"##,
+                kx,
+                ky - 5.0,
+                kx + 5.0,
+                ky,
+                kx,
+                ky + 5.0,
+                kx - 5.0,
+                ky,
+                escape_xml(name),
+                t
+            );
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{:.1}" y="{:.1}">{}</text>"#,
+                kx + 7.0,
+                ky + 4.0,
+                escape_xml(name)
+            );
+        }
+
+        // 変数レーン
+        for (i, lane) in lanes.iter().enumerate() {
+            let y = lane_y(i);
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{:.1}" y="{:.1}">{}</text>"#,
+                SVG_MARGIN,
+                y + SVG_LANE_HEIGHT / 2.0 + 4.0,
+                escape_xml(lane)
+            );
+            for trans in self.transitions.iter().filter(|t| t.variable == *lane) {
+                write_svg_transition(&mut svg, trans, x, y);
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// タイムラインを ASCII ガントチャートとして出力（`columns` は時間軸の文字数）
+    ///
+    /// 凡例: `.` delay / `=` 補間区間 / `#` 値切り替え（Object） / `|` 即時遷移 / `*` キーフレーム
+    pub fn to_ascii_gantt(&self, columns: usize) -> String {
+        let columns = columns.max(1);
+        let lanes = self.lanes();
+        let label_width = lanes
+            .iter()
+            .map(|l| l.chars().count())
+            .chain(std::iter::once("keyframes".len()))
+            .max()
+            .unwrap_or(0);
+        let col = |t: f64| -> usize {
+            if self.duration <= 0.0 {
+                0
+            } else {
+                ((t / self.duration * columns as f64).floor() as usize).min(columns - 1)
+            }
+        };
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:label_width$} |0{:>width$}",
+            "",
+            format!("{:.3}s", self.duration),
+            width = columns.saturating_sub(1)
+        );
+
+        for lane in &lanes {
+            let mut row = vec![' '; columns];
+            for trans in self.transitions.iter().filter(|t| t.variable == *lane) {
+                let (placed, begin, end) = (col(trans.placed), col(trans.begin), col(trans.end));
+                for cell in row.iter_mut().take(begin).skip(placed) {
+                    *cell = '.';
+                }
+                if trans.end <= trans.begin {
+                    row[begin] = '|';
+                } else {
+                    let fill = if is_object_transition(trans) {
+                        '#'
+                    } else {
+                        '='
+                    };
+                    let last = if end > begin { end - 1 } else { begin };
+                    for cell in row.iter_mut().take(last + 1).skip(begin) {
+                        *cell = fill;
+                    }
+                }
+            }
+            let _ = writeln!(
+                out,
+                "{:label_width$} |{}",
+                lane,
+                row.iter().collect::<String>().trim_end()
+            );
+        }
+
+        let keyframes = self.visible_keyframes();
+        let mut row = vec![' '; columns];
+        for (_, t) in &keyframes {
+            row[col(*t)] = '*';
+        }
+        let _ = writeln!(
+            out,
+            "{:label_width$} |{}",
+            "keyframes",
+            row.iter().collect::<String>().trim_end()
+        );
+        for (name, t) in keyframes {
+            let _ = writeln!(out, "{:label_width$}  * {} @ {:.3}s", "", name, t);
+        }
+        out
+    }
+}
+
+/// 目盛りが [`SVG_MAX_TICKS`] 以下になる最小の目盛り間隔（1・2・5×10^n 秒）
+fn svg_tick_step(duration: f64) -> f64 {
+    if !duration.is_finite() {
+        return 1.0;
+    }
+    let mut magnitude = 1.0;
+    loop {
+        for m in [1.0, 2.0, 5.0] {
+            let step = m * magnitude;
+            if duration / step <= SVG_MAX_TICKS as f64 {
+                return step;
+            }
+        }
+        magnitude *= 10.0;
+    }
+}
+
+/// 1 トランジションを SVG 要素として出力
+fn write_svg_transition(
+    svg: &mut String,
+    trans: &ResolvedTransition,
+    x: impl Fn(f64) -> f64,
+    y: f64,
+) {
+    let top = y + 4.0;
+    let bottom = y + SVG_LANE_HEIGHT - 4.0;
+    let mid = (top + bottom) / 2.0;
+
+    if trans.begin > trans.placed {
+        let _ = writeln!(
+            svg,
+            r##"  <line class="delay" x1="{:.1}" y1="{mid:.1}" x2="{:.1}" y2="{mid:.1}" stroke="#888" stroke-dasharray="3,2"/>"##,
+            x(trans.placed),
+            x(trans.begin)
+        );
+    }
+
+    let (bx, ex) = (x(trans.begin), x(trans.end));
+    if trans.end <= trans.begin {
+        let _ = writeln!(
+            svg,
+            r##"  <line class="instant" x1="{bx:.1}" y1="{top:.1}" x2="{bx:.1}" y2="{bottom:.1}" stroke="#36c" stroke-width="2"/>"##
+        );
+        return;
+    }
+    let _ = writeln!(
+        svg,
+        r##"  <rect class="transition" x="{bx:.1}" y="{top:.1}" width="{:.1}" height="{:.1}" fill="#cde" stroke="#36c"><title>entry {} ({:.3}s - {:.3}s)</title></rect>"##,
+        ex - bx,
+        bottom - top,
+        trans.entry_index,
+        trans.begin,
+        trans.end
+    );
+    if is_object_transition(trans) {
+        return;
+    }
+
+    // イージング曲線（進捗 0→1 をバー下端→上端に対応）
+    let points: Vec<String> = (0..=SVG_CURVE_SAMPLES)
+        .map(|i| {
+            let p = i as f64 / SVG_CURVE_SAMPLES as f64;
+            let eased = match &trans.def.easing {
+                Some(easing) => easing.ease(p),
+                None => p,
+            };
+            format!(
+                "{:.1},{:.1}",
+                bx + (ex - bx) * p,
+                bottom - (bottom - top) * eased
+            )
+        })
+        .collect();
+    let _ = writeln!(
+        svg,
+        r##"  <polyline class="easing" points="{}" fill="none" stroke="#c63"/>"##,
+        points.join(" ")
+    );
+}
+
+fn is_object_transition(trans: &ResolvedTransition) -> bool {
+    matches!(trans.def.to, Some(TransitionValue::Dynamic(_)))
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -39,6 +39,7 @@ mod document_builder_tests {
                     easing: Some(EasingFunction::Named(EasingName::QuadraticInOut)),
                     delay: 0.0,
                     duration: Some(1.5),
+                    exprs: TransitionExprs::default(),
                 },
             )
             .storyboard(
@@ -77,6 +78,7 @@ mod document_builder_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -122,6 +124,7 @@ mod storyboard_builder_tests {
                     easing: None,
                     delay: 0.0,
                     duration: Some(1.0),
+                    exprs: TransitionExprs::default(),
                 })),
                 at: None,
                 between: None,
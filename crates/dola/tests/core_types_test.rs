@@ -363,6 +363,7 @@ mod transition_tests {
             easing: Some(EasingFunction::Named(EasingName::QuadraticInOut)),
             delay: 0.5,
             duration: Some(2.0),
+            exprs: TransitionExprs::default(),
         };
         let json = serde_json::to_string(&def).unwrap();
         let deserialized: TransitionDef = serde_json::from_str(&json).unwrap();
@@ -378,6 +379,7 @@ mod transition_tests {
             easing: Some(EasingFunction::Named(EasingName::Linear)),
             delay: 0.0,
             duration: Some(1.0),
+            exprs: TransitionExprs::default(),
         };
         let json = serde_json::to_string(&def).unwrap();
         let deserialized: TransitionDef = serde_json::from_str(&json).unwrap();
@@ -410,6 +412,7 @@ mod transition_tests {
             easing: None,
             delay: 0.0,
             duration: Some(1.5),
+            exprs: TransitionExprs::default(),
         });
         let json = serde_json::to_string(&tref).unwrap();
         let deserialized: TransitionRef = serde_json::from_str(&json).unwrap();
@@ -505,6 +508,7 @@ mod storyboard_tests {
                 easing: Some(EasingFunction::Named(EasingName::Linear)),
                 delay: 0.0,
                 duration: None,
+                exprs: TransitionExprs::default(),
             })),
             at: None,
             between: Some(BetweenKeyframes {
@@ -594,6 +598,7 @@ mod playback_tests {
         let req = ScheduleRequest {
             storyboard: "greeting".to_string(),
             start_time: 1.5,
+            args: BTreeMap::new(),
         };
         let json = serde_json::to_string(&req).unwrap();
         let deserialized: ScheduleRequest = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,368 @@
+//! CSS import tests — @keyframes/animation conversion, easing approximation and diagnostics
+
+use dola::*;
+
+const FADE_SLIDE: &str = r#"
+/* フェードしながらスライド */
+@keyframes fade-slide {
+    from { opacity: 0; transform: translateX(-40px); }
+    50% { opacity: 0.8; }
+    to { opacity: 1; transform: translateX(0); }
+}
+.toast { animation: fade-slide 400ms linear; }
+"#;
+
+fn value_at(doc: &DolaDocument, storyboard: &str, variable: &str, t: f64) -> f64 {
+    let mut player = DolaPlayer::new(doc.clone());
+    player
+        .schedule(ScheduleRequest {
+            storyboard: storyboard.to_string(),
+            start_time: 0.0,
+            args: Default::default(),
+        })
+        .unwrap();
+    player.tick(t).unwrap();
+    player.value(variable).unwrap().as_f64().unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+mod conversion_tests {
+    use super::*;
+
+    #[test]
+    fn keyframes_become_storyboard() {
+        let result = import_css(FADE_SLIDE);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let doc = &result.document;
+        assert!(doc.validate().is_ok());
+        assert_eq!(
+            doc.variable["toast_opacity"],
+            AnimationVariableDef::Float {
+                initial: 1.0,
+                min: Some(0.0),
+                max: Some(1.0),
+            }
+        );
+        assert!(doc.variable.contains_key("toast_translate_x"));
+
+        let tl = ResolvedStoryboard::resolve(doc, "fade-slide").unwrap();
+        assert_close(tl.duration, 0.4);
+        assert_close(tl.keyframes["0.200s"], 0.2);
+    }
+
+    #[test]
+    fn values_follow_keyframes() {
+        let doc = import_css(FADE_SLIDE).document;
+        assert_close(value_at(&doc, "fade-slide", "toast_opacity", 0.1), 0.4);
+        assert_close(value_at(&doc, "fade-slide", "toast_opacity", 0.3), 0.9);
+        assert_close(
+            value_at(&doc, "fade-slide", "toast_translate_x", 0.2),
+            -20.0,
+        );
+        assert_close(value_at(&doc, "fade-slide", "toast_translate_x", 1.0), 0.0);
+    }
+
+    #[test]
+    fn missing_endpoints_use_base_values() {
+        let css = "@keyframes pulse { 50% { transform: scale(1.5) rotate(0.25turn); } }
+                   .a { animation: pulse 1s linear; }";
+        let doc = import_css(css).document;
+        assert_close(value_at(&doc, "pulse", "a_scale_x", 0.25), 1.25);
+        assert_close(value_at(&doc, "pulse", "a_scale_y", 0.5), 1.5);
+        assert_close(value_at(&doc, "pulse", "a_rotate", 0.5), 90.0);
+        assert_close(value_at(&doc, "pulse", "a_rotate", 1.0), 0.0);
+    }
+
+    #[test]
+    fn longhands_and_multiple_animations() {
+        let css = "@keyframes a { to { left: 100px; } }
+                   @keyframes b { to { top: 50px; } }
+                   .x {
+                       animation-name: a, b;
+                       animation-duration: 1s, 2s;
+                       animation-timing-function: linear;
+                       animation-delay: 500ms;
+                   }";
+        let result = import_css(css);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let doc = &result.document;
+        assert_close(ResolvedStoryboard::resolve(doc, "a").unwrap().duration, 1.5);
+        assert_close(ResolvedStoryboard::resolve(doc, "b").unwrap().duration, 2.5);
+        assert_close(value_at(doc, "b", "x_top", 1.5), 25.0);
+    }
+
+    #[test]
+    fn name_collisions_get_suffix() {
+        let css = "@keyframes spin { to { transform: rotate(360deg); } }
+                   .a { animation: spin 1s; }
+                   .b { animation: spin 2s; }";
+        let doc = import_css(css).document;
+        assert!(doc.storyboard.contains_key("spin"));
+        assert!(doc.storyboard.contains_key("spin_2"));
+    }
+
+    #[test]
+    fn selectors_get_separate_variables() {
+        let css = "@keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+                   @keyframes hide { from { opacity: 1; } to { opacity: 0; } }
+                   .toast { animation: fade 1s linear; }
+                   #main .dialog { animation: hide 1s linear; }
+                   .toast { animation: hide 2s linear; }";
+        let result = import_css(css);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let doc = &result.document;
+        let mut names: Vec<&str> = doc.variable.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["main_dialog_opacity", "toast_opacity"]);
+        assert_close(value_at(doc, "fade", "toast_opacity", 0.25), 0.25);
+        assert_close(value_at(doc, "hide", "main_dialog_opacity", 0.25), 0.75);
+        assert_close(value_at(doc, "hide_2", "toast_opacity", 0.5), 0.75);
+    }
+}
+
+mod iteration_tests {
+    use super::*;
+
+    #[test]
+    fn infinite_and_counted_loops() {
+        let css = "@keyframes spin { to { transform: rotate(360deg); } }
+                   .a { animation: spin 1s linear infinite; }
+                   .b { animation: spin 1s linear 3; }";
+        let doc = import_css(css).document;
+        assert_eq!(doc.storyboard["spin"].loop_count, Some(0));
+        assert_eq!(doc.storyboard["spin_2"].loop_count, Some(3));
+    }
+
+    #[test]
+    fn reverse_mirrors_keyframes() {
+        let css = "@keyframes move { from { left: 0px; } 25% { left: 80px; } to { left: 100px; } }
+                   .a { animation: move 1s linear reverse; }";
+        let doc = import_css(css).document;
+        assert_close(value_at(&doc, "move", "a_left", 0.0), 100.0);
+        assert_close(value_at(&doc, "move", "a_left", 0.75), 80.0);
+        assert_close(value_at(&doc, "move", "a_left", 1.0), 0.0);
+    }
+
+    #[test]
+    fn alternate_builds_two_cycles() {
+        let css = "@keyframes move { to { left: 100px; } }
+                   .a { animation: move 1s linear alternate infinite; }";
+        let doc = import_css(css).document;
+        let tl = ResolvedStoryboard::resolve(&doc, "move").unwrap();
+        assert_close(tl.duration, 2.0);
+        assert_eq!(tl.loop_count, Some(0));
+        assert_close(value_at(&doc, "move", "a_left", 1.0), 100.0);
+        assert_close(value_at(&doc, "move", "a_left", 1.5), 50.0);
+    }
+
+    #[test]
+    fn alternate_odd_count_is_unrolled() {
+        let css = "@keyframes move { to { left: 100px; } }
+                   .a { animation: move 1s linear alternate 3; }";
+        let result = import_css(css);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let doc = &result.document;
+        let tl = ResolvedStoryboard::resolve(doc, "move").unwrap();
+        assert_close(tl.duration, 3.0);
+        assert_eq!(tl.loop_count, None);
+        assert_close(value_at(doc, "move", "a_left", 1.5), 50.0);
+        assert_close(value_at(doc, "move", "a_left", 2.5), 50.0);
+        assert_close(value_at(doc, "move", "a_left", 3.0), 100.0);
+    }
+
+    #[test]
+    fn large_odd_alternate_count_is_not_unrolled() {
+        let css = "@keyframes move { to { left: 100px; } }
+                   .a { animation: move 1s linear alternate 2000001; }";
+        let result = import_css(css);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("played 2000000 times"))
+        );
+        let tl = ResolvedStoryboard::resolve(&result.document, "move").unwrap();
+        assert_close(tl.duration, 2.0);
+        assert_eq!(tl.loop_count, Some(1_000_000));
+    }
+
+    #[test]
+    fn zero_iterations_play_nothing() {
+        let css = "@keyframes move { to { left: 100px; } }
+                   .a { animation: move 1s 0; }
+                   .b { animation: move 1s 0.4; }";
+        let result = import_css(css);
+        assert!(result.document.storyboard.is_empty());
+        assert!(result.document.variable.is_empty());
+        assert_eq!(
+            result
+                .diagnostics
+                .iter()
+                .filter(|d| d.message.contains("iteration-count 0 plays nothing"))
+                .count(),
+            2
+        );
+    }
+}
+
+mod easing_tests {
+    use super::*;
+
+    fn easing_of(doc: &DolaDocument, storyboard: &str) -> Option<EasingFunction> {
+        doc.storyboard[storyboard]
+            .entry
+            .iter()
+            .find_map(|e| match &e.transition {
+                Some(TransitionRef::Inline(def)) => Some(def.easing.clone()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    #[test]
+    fn linear_maps_to_named_linear() {
+        let doc = import_css(FADE_SLIDE).document;
+        assert_eq!(
+            easing_of(&doc, "fade-slide"),
+            Some(EasingFunction::Named(EasingName::Linear))
+        );
+    }
+
+    #[test]
+    fn ease_keyword_is_approximated_closely() {
+        let css = "@keyframes f { to { opacity: 0; } } .a { animation: f 1s ease-in-out; }";
+        let result = import_css(css);
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let easing = easing_of(&result.document, "f").unwrap();
+        assert!(matches!(
+            easing,
+            EasingFunction::Parametric(ParametricEasing::CubicBezier { .. })
+        ));
+        assert_close(easing.ease(0.0), 0.0);
+        assert_close(easing.ease(1.0), 1.0);
+        assert!((easing.ease(0.5) - 0.5).abs() < 0.01);
+        assert!(easing.ease(0.2) < 0.15);
+    }
+
+    #[test]
+    fn per_keyframe_timing_function_overrides() {
+        let css = "@keyframes f {
+                       from { left: 0px; animation-timing-function: linear; }
+                       to { left: 10px; }
+                   }
+                   .a { animation: f 1s ease; }";
+        let doc = import_css(css).document;
+        assert_close(value_at(&doc, "f", "a_left", 0.5), 5.0);
+    }
+
+    #[test]
+    fn overshooting_bezier_is_reported() {
+        let css = "@keyframes f { to { left: 10px; } }
+                   .a { animation: f 1s cubic-bezier(0.9, -0.6, 0.1, 1.6); }";
+        let result = import_css(css);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("approximated"))
+        );
+    }
+
+    #[test]
+    fn steps_fall_back_to_linear() {
+        let css = "@keyframes f { to { left: 10px; } } .a { animation: f 1s steps(4); }";
+        let result = import_css(css);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("'steps(4)' is not supported"))
+        );
+        assert_eq!(
+            easing_of(&result.document, "f"),
+            Some(EasingFunction::Named(EasingName::Linear))
+        );
+    }
+}
+
+mod diagnostic_tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_input_is_reported_not_fatal() {
+        let css = r#"
+@import "base.css";
+@media (min-width: 600px) { .a { color: red; } }
+@keyframes glow {
+    from { color: red; width: 10%; opacity: 0; }
+    to { filter: blur(2px); transform: skewX(10deg); opacity: 1; }
+}
+@keyframes unused { to { opacity: 0; } }
+.a { animation: glow 1s linear backwards paused; }
+.b { animation: missing 1s; }
+"#;
+        let result = import_css(css);
+        assert!(result.document.validate().is_ok());
+        assert!(result.document.storyboard.contains_key("glow"));
+        let messages: Vec<String> = result.diagnostics.iter().map(|d| d.to_string()).collect();
+        let expect = [
+            "at-rule '@media' is not supported",
+            "property 'color' is not supported",
+            "unsupported value '10%' for 'width'",
+            "property 'filter' is not supported",
+            "transform function 'skewX' is not supported",
+            "animation-fill-mode 'backwards' is not supported",
+            "animation-play-state 'paused' is ignored",
+            ".b animation 'missing': no matching @keyframes",
+            "@keyframes unused: not referenced",
+        ];
+        for e in expect {
+            assert!(
+                messages.iter().any(|m| m.contains(e)),
+                "missing '{}' in {:#?}",
+                e,
+                messages
+            );
+        }
+    }
+
+    #[test]
+    fn empty_longhand_is_reported_not_fatal() {
+        let css = "@keyframes f { to { left: 10px; } }
+                   .a { animation: f 1s linear; animation-delay: ; }";
+        let result = import_css(css);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("empty value for 'animation-delay'"))
+        );
+        assert!(result.document.storyboard.contains_key("f"));
+    }
+
+    #[test]
+    fn negative_delay_is_clamped() {
+        let css = "@keyframes f { to { left: 10px; } } .a { animation: f 1s linear -0.5s; }";
+        let result = import_css(css);
+        assert!(
+            result
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("negative animation-delay"))
+        );
+        assert_close(
+            ResolvedStoryboard::resolve(&result.document, "f")
+                .unwrap()
+                .duration,
+            1.0,
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Expression tests — parsing/evaluation, parameterized storyboards and schedule-time binding
+
+use dola::*;
+use std::collections::BTreeMap;
+
+fn expr(src: &str) -> Expression {
+    Expression::new(src)
+}
+
+fn float_var(initial: f64) -> AnimationVariableDef {
+    AnimationVariableDef::Float {
+        initial,
+        min: None,
+        max: None,
+    }
+}
+
+/// 「カーソル位置へ移動」: to = target_x、duration は距離に比例
+fn move_to_document() -> DolaDocument {
+    DolaDocumentBuilder::new("1.0")
+        .variable("x", float_var(100.0))
+        .storyboard(
+            "move_to",
+            StoryboardBuilder::new()
+                .param("target_x", ParamType::Float)
+                .entry(StoryboardEntry {
+                    variable: Some("x".to_string()),
+                    transition: Some(TransitionRef::Inline(TransitionDef {
+                        from: None,
+                        to: Some(TransitionValue::Expression(expr("target_x"))),
+                        relative_to: None,
+                        easing: None,
+                        delay: 0.0,
+                        duration: None,
+                        exprs: TransitionExprs {
+                            duration: Some(expr("max(abs(target_x - x) / 100, 0.1)")),
+                            ..Default::default()
+                        },
+                    })),
+                    at: None,
+                    between: None,
+                    keyframe: None,
+                })
+                .build(),
+        )
+        .build()
+        .unwrap()
+}
+
+fn move_to(target_x: f64) -> ScheduleRequest {
+    let mut args = BTreeMap::new();
+    args.insert("target_x".to_string(), target_x);
+    ScheduleRequest {
+        storyboard: "move_to".to_string(),
+        start_time: 0.0,
+        args,
+    }
+}
+
+mod evaluation_tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_precedence_and_unary_minus() {
+        let lookup = |_: &str| None;
+        assert_eq!(expr("1 + 2 * 3").eval(lookup), Ok(7.0));
+        assert_eq!(expr("(1 + 2) * 3").eval(lookup), Ok(9.0));
+        assert_eq!(expr("-2 * -3 - 1").eval(lookup), Ok(5.0));
+        assert_eq!(expr("7 % 4 / 2").eval(lookup), Ok(1.5));
+    }
+
+    #[test]
+    fn functions_and_identifiers() {
+        let lookup = |name: &str| match name {
+            "a" => Some(3.0),
+            "b" => Some(-8.0),
+            _ => None,
+        };
+        assert_eq!(expr("min(a, b, 0)").eval(lookup), Ok(-8.0));
+        assert_eq!(expr("max(a, 10)").eval(lookup), Ok(10.0));
+        assert_eq!(expr("abs(b)").eval(lookup), Ok(8.0));
+        assert_eq!(expr("clamp(b, -1, 1)").eval(lookup), Ok(-1.0));
+        assert!(expr("unknown + 1").eval(lookup).is_err());
+    }
+
+    #[test]
+    fn syntax_errors_are_reported() {
+        for src in ["1 +", "(1", "min()", "abs(1, 2)", "foo(1)", "1 $ 2", "1 2"] {
+            assert!(expr(src).parse().is_err(), "{}", src);
+        }
+    }
+
+    #[test]
+    fn non_finite_result_is_error() {
+        assert!(expr("1 / 0").eval(|_| None).is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let src = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+        assert!(expr(&src).parse().is_err());
+    }
+
+    #[test]
+    fn long_flat_chain_is_not_nesting() {
+        // 二項演算の連鎖は平坦化されるため、入れ子の上限に関係なく評価できる
+        let src = vec!["1"; 100_000].join("+");
+        assert_eq!(expr(&src).eval(|_| None), Ok(100_000.0));
+        let src = vec!["x"; 100_000].join(" * ");
+        assert_eq!(expr(&src).eval(|_| Some(1.0)), Ok(1.0));
+        let src = format!("{} - 1", vec!["2 * 3"; 66].join(" + "));
+        assert_eq!(expr(&src).eval(|_| None), Ok(395.0));
+    }
+
+    #[test]
+    fn hand_built_call_checks_arity() {
+        let lookup = |_: &str| None;
+        assert!(Expr::Call(Function::Abs, vec![]).eval(&lookup).is_err());
+        assert!(
+            Expr::Call(Function::Clamp, vec![Expr::Number(1.0)])
+                .eval(&lookup)
+                .is_err()
+        );
+        assert!(Expr::Call(Function::Min, vec![]).eval(&lookup).is_err());
+        assert_eq!(
+            Expr::Call(Function::Abs, vec![Expr::Number(-2.0)]).eval(&lookup),
+            Ok(2.0)
+        );
+    }
+
+    #[test]
+    fn exponent_literals() {
+        let lookup = |name: &str| (name == "e").then_some(2.0);
+        assert_eq!(expr("1e5").eval(lookup), Ok(100000.0));
+        assert_eq!(expr("2.5E-1 + 1e+1").eval(lookup), Ok(10.25));
+        assert_eq!(expr("3 * e").eval(lookup), Ok(6.0));
+        assert!(expr("2e").parse().is_err());
+    }
+
+    #[test]
+    fn identifiers_are_collected() {
+        let parsed = expr("min(target_x, x) + y * 2").parse().unwrap();
+        assert_eq!(parsed.identifiers(), vec!["target_x", "x", "y"]);
+    }
+}
+
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn expression_json_forms() {
+        let def: TransitionDef = serde_json::from_str(
+            r#"{"to":{"expr":"target_x"},"delay_expr":{"expr":"0.1 * 2"},"duration":1.5}"#,
+        )
+        .unwrap();
+        assert_eq!(def.to, Some(TransitionValue::Expression(expr("target_x"))));
+        assert_eq!(def.delay, 0.0);
+        assert_eq!(def.exprs.delay, Some(expr("0.1 * 2")));
+        assert_eq!(def.duration, Some(1.5));
+        assert_eq!(def.exprs.duration, None);
+
+        // 式なしの定義は従来どおりの形式で出力される
+        let json = serde_json::to_string(&TransitionDef {
+            exprs: TransitionExprs::default(),
+            ..def
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"to":{"expr":"target_x"},"delay":0.0,"duration":1.5}"#
+        );
+    }
+
+    #[test]
+    fn params_and_args_json_roundtrip() {
+        let doc = move_to_document();
+        let json = serde_json::to_string(&doc).unwrap();
+        assert!(json.contains(r#""params":{"target_x":"f64"}"#));
+        let restored: DolaDocument = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, doc);
+
+        let json = serde_json::to_string(&move_to(5.0)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"storyboard":"move_to","start_time":0.0,"args":{"target_x":5.0}}"#
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_parameterized_storyboard() {
+        let doc: DolaDocument = toml::from_str(
+            r#"
+schema_version = "1.0"
+
+[variable.x]
+type = "f64"
+initial = 0.0
+
+[storyboard.move_to]
+params = { target_x = "f64" }
+
+[[storyboard.move_to.entry]]
+variable = "x"
+transition = { to = { expr = "target_x" }, duration_expr = { expr = "abs(target_x - x) / 500" } }
+"#,
+        )
+        .unwrap();
+        assert!(doc.validate().is_ok());
+        assert_eq!(
+            doc.storyboard["move_to"].params["target_x"],
+            ParamType::Float
+        );
+    }
+}
+
+mod validation_tests {
+    use super::*;
+
+    fn doc_with(to: &str) -> DolaDocument {
+        let mut doc = move_to_document();
+        let sb = doc.storyboard.get_mut("move_to").unwrap();
+        if let Some(TransitionRef::Inline(def)) = &mut sb.entry[0].transition {
+            def.to = Some(TransitionValue::Expression(expr(to)));
+        }
+        doc
+    }
+
+    #[test]
+    fn valid_expression_ok() {
+        assert!(doc_with("target_x + x").validate().is_ok());
+    }
+
+    #[test]
+    fn syntax_error_detected_v14() {
+        let errors = doc_with("target_x +").validate().unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            DolaError::InvalidExpression { reason, .. } if reason.starts_with("field 'to'")
+        ));
+    }
+
+    #[test]
+    fn unknown_identifier_detected_v14() {
+        let errors = doc_with("target_y").validate().unwrap_err();
+        assert!(matches!(
+            &errors[0],
+            DolaError::InvalidExpression { reason, .. } if reason.contains("'target_y'")
+        ));
+    }
+
+    #[test]
+    fn to_and_relative_to_expr_are_exclusive_v11() {
+        let mut doc = doc_with("target_x");
+        let sb = doc.storyboard.get_mut("move_to").unwrap();
+        if let Some(TransitionRef::Inline(def)) = &mut sb.entry[0].transition {
+            def.exprs.relative_to = Some(expr("target_x - x"));
+        }
+        let errors = doc.validate().unwrap_err();
+        assert!(matches!(errors[0], DolaError::MutuallyExclusive { .. }));
+    }
+
+    #[test]
+    fn object_variable_rejects_expression() {
+        let doc = DolaDocument {
+            schema_version: "1.0".to_string(),
+            variable: {
+                let mut m = BTreeMap::new();
+                m.insert(
+                    "face".to_string(),
+                    AnimationVariableDef::Object {
+                        initial: DynamicValue::Null,
+                    },
+                );
+                m
+            },
+            transition: BTreeMap::new(),
+            storyboard: {
+                let mut m = BTreeMap::new();
+                m.insert(
+                    "sb".to_string(),
+                    StoryboardBuilder::new()
+                        .entry(StoryboardEntry {
+                            variable: Some("face".to_string()),
+                            transition: Some(TransitionRef::Inline(TransitionDef {
+                                from: None,
+                                to: Some(TransitionValue::Expression(expr("1"))),
+                                relative_to: None,
+                                easing: None,
+                                delay: 0.0,
+                                duration: None,
+                                exprs: TransitionExprs::default(),
+                            })),
+                            at: None,
+                            between: None,
+                            keyframe: None,
+                        })
+                        .build(),
+                );
+                m
+            },
+        };
+        let errors = doc.validate().unwrap_err();
+        assert!(matches!(errors[0], DolaError::TypeMismatch { .. }));
+    }
+}
+
+mod binding_tests {
+    use super::*;
+
+    #[test]
+    fn request_args_bind_params_and_current_values() {
+        let mut player = DolaPlayer::new(move_to_document());
+        player.schedule(move_to(400.0)).unwrap();
+        // duration = |400 - 100| / 100 = 3.0
+        player.tick(1.5).unwrap();
+        assert_eq!(player.value("x"), Some(&AnimationValue::Float(250.0)));
+        player.tick(1.5).unwrap();
+        assert_eq!(player.value("x"), Some(&AnimationValue::Float(400.0)));
+
+        // 2回目は現在値 400 を起点に評価される: duration = |200 - 400| / 100 = 2.0
+        player.schedule(move_to(200.0)).unwrap();
+        player.tick(1.0).unwrap();
+        assert_eq!(player.value("x"), Some(&AnimationValue::Float(300.0)));
+    }
+
+    #[test]
+    fn bound_timeline_contains_literals() {
+        let doc = move_to_document();
+        let values = BTreeMap::from([("x".to_string(), AnimationValue::Float(0.0))]);
+        let args = BTreeMap::from([("target_x".to_string(), 50.0)]);
+        let tl = ResolvedStoryboard::bind(&doc, "move_to", &args, &values).unwrap();
+        assert_eq!(
+            tl.transitions[0].def.to,
+            Some(TransitionValue::Scalar(50.0))
+        );
+        assert_eq!(tl.transitions[0].end, 0.5);
+    }
+
+    #[test]
+    fn evaluated_value_is_checked_against_range() {
+        let mut doc = move_to_document();
+        doc.variable.insert(
+            "x".to_string(),
+            AnimationVariableDef::Float {
+                initial: 100.0,
+                min: Some(0.0),
+                max: Some(500.0),
+            },
+        );
+        let mut player = DolaPlayer::new(doc);
+        assert!(matches!(
+            player.schedule(move_to(800.0)),
+            Err(DolaError::ValueOutOfRange { ref field, value, .. }) if field == "to" && value == 800.0
+        ));
+        assert_eq!(player.state("move_to"), PlaybackState::Idle);
+        assert!(player.schedule(move_to(500.0)).is_ok());
+    }
+
+    #[test]
+    fn expression_overrides_literal_field() {
+        let mut doc = move_to_document();
+        let sb = doc.storyboard.get_mut("move_to").unwrap();
+        if let Some(TransitionRef::Inline(def)) = &mut sb.entry[0].transition {
+            def.delay = 5.0;
+            def.exprs.delay = Some(expr("0.5"));
+        }
+        let values = BTreeMap::from([("x".to_string(), AnimationValue::Float(0.0))]);
+        let args = BTreeMap::from([("target_x".to_string(), 50.0)]);
+        let tl = ResolvedStoryboard::bind(&doc, "move_to", &args, &values).unwrap();
+        assert_eq!(tl.transitions[0].begin, 0.5);
+        assert_eq!(tl.transitions[0].def.delay, 0.5);
+        assert_eq!(tl.transitions[0].def.exprs, TransitionExprs::default());
+    }
+
+    #[test]
+    fn missing_and_undeclared_args_are_errors() {
+        let mut player = DolaPlayer::new(move_to_document());
+        let mut request = move_to(1.0);
+        request.args.clear();
+        assert!(matches!(
+            player.schedule(request.clone()),
+            Err(DolaError::InvalidArgument { ref reason, .. }) if reason == "missing argument"
+        ));
+
+        request.args.insert("target_x".to_string(), 1.0);
+        request.args.insert("extra".to_string(), 1.0);
+        assert!(matches!(
+            player.schedule(request),
+            Err(DolaError::InvalidArgument { ref name, .. }) if name == "extra"
+        ));
+    }
+
+    #[test]
+    fn integer_param_requires_integral_arg() {
+        let mut doc = move_to_document();
+        doc.storyboard
+            .get_mut("move_to")
+            .unwrap()
+            .params
+            .insert("target_x".to_string(), ParamType::Integer);
+        let mut player = DolaPlayer::new(doc);
+        assert!(matches!(
+            player.schedule(move_to(1.5)),
+            Err(DolaError::InvalidArgument { ref reason, .. }) if reason == "expected integer"
+        ));
+        assert!(player.schedule(move_to(2.0)).is_ok());
+    }
+
+    #[test]
+    fn args_are_recorded_and_replayed() {
+        let mut player = DolaPlayer::new(move_to_document()).with_recording();
+        player.schedule(move_to(300.0)).unwrap();
+        player.tick(1.0).unwrap();
+        let log = player.take_log().unwrap();
+        let trace = log.replay(&move_to_document());
+        assert_eq!(
+            trace.frames.last().unwrap().values["x"],
+            AnimationValue::Float(200.0)
+        );
+    }
+}
@@ -48,6 +48,7 @@ fn build_complete_document() -> DolaDocument {
                 easing: Some(EasingFunction::Named(EasingName::QuadraticInOut)),
                 delay: 0.0,
                 duration: Some(1.5),
+                exprs: TransitionExprs::default(),
             },
         )
         .transition(
@@ -59,6 +60,7 @@ fn build_complete_document() -> DolaDocument {
                 easing: Some(EasingFunction::Named(EasingName::Linear)),
                 delay: 0.0,
                 duration: Some(3.0),
+                exprs: TransitionExprs::default(),
             },
         )
         // SB1: greeting — 3つの配置パターン
@@ -99,6 +101,7 @@ fn build_complete_document() -> DolaDocument {
                         easing: None,
                         delay: 0.0,
                         duration: None,
+                        exprs: TransitionExprs::default(),
                     })),
                     at: Some(KeyframeRef::Single("text_done".to_string())),
                     between: None,
@@ -128,6 +131,7 @@ fn build_complete_document() -> DolaDocument {
                         easing: Some(EasingFunction::Named(EasingName::Linear)),
                         delay: 0.0,
                         duration: Some(2.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -143,6 +147,7 @@ fn build_complete_document() -> DolaDocument {
                         easing: Some(EasingFunction::Named(EasingName::Linear)),
                         delay: 0.0,
                         duration: None,
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: Some(BetweenKeyframes {
@@ -280,6 +285,7 @@ mod e2e_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -294,6 +300,7 @@ mod e2e_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -370,6 +377,7 @@ mod edge_case_tests {
                             easing: Some(EasingFunction::Named(EasingName::Linear)),
                             delay: 0.0,
                             duration: Some(3.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -412,6 +420,7 @@ mod edge_case_tests {
                             )),
                             delay: 0.0,
                             duration: Some(2.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -451,6 +460,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 2.0,
                             duration: None, // instant transition after delay
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -486,6 +496,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::Single("start".to_string())),
                         between: None,
@@ -521,6 +532,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -535,6 +547,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -549,6 +562,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::Multiple(vec![
                             "a".to_string(),
@@ -587,6 +601,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -601,6 +616,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::WithOffset {
                             keyframes: KeyframeNames::Single("visible".to_string()),
@@ -651,6 +667,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: None,
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -707,6 +724,7 @@ mod edge_case_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -0,0 +1,183 @@
+//! Lottie import tests — transform/opacity keyframes, bezier tangents, hold keys and diagnostics
+#![cfg(feature = "json")]
+
+use dola::*;
+
+/// 30fps・60フレームのコンポジション（1 レイヤー）
+const BOUNCE: &str = r#"{
+  "v": "5.7.4", "fr": 30, "ip": 0, "op": 60, "w": 200, "h": 200, "nm": "bounce",
+  "layers": [{
+    "ty": 4, "nm": "Ball Shape", "ind": 1, "ip": 0, "op": 60, "st": 0,
+    "ks": {
+      "o": { "a": 1, "k": [
+        { "t": 0, "s": [0], "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+        { "t": 15, "s": [100] }
+      ]},
+      "p": { "a": 1, "k": [
+        { "t": 0, "s": [0, 100, 0], "o": { "x": 0.42, "y": 0 }, "i": { "x": 0.58, "y": 1 } },
+        { "t": 30, "s": [100, 0, 0], "o": { "x": 0, "y": 0 }, "i": { "x": 1, "y": 1 } },
+        { "t": 60, "s": [200, 100, 0] }
+      ]},
+      "s": { "a": 0, "k": [50, 200, 100] },
+      "r": { "a": 1, "k": [
+        { "t": 0, "s": [0], "h": 1 },
+        { "t": 30, "s": [90] }
+      ]},
+      "a": { "a": 0, "k": [0, 0, 0] }
+    }
+  }]
+}"#;
+
+fn value_at(doc: &DolaDocument, variable: &str, t: f64) -> f64 {
+    let mut player = DolaPlayer::new(doc.clone());
+    player
+        .schedule(ScheduleRequest {
+            storyboard: "bounce".to_string(),
+            start_time: 0.0,
+            args: Default::default(),
+        })
+        .unwrap();
+    player.tick(t).unwrap();
+    player.value(variable).unwrap().as_f64().unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn layers_become_variables_and_storyboard() {
+    let result = import_lottie(BOUNCE).unwrap();
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let doc = &result.document;
+    assert!(doc.validate().is_ok());
+    let names: Vec<&str> = doc.variable.keys().map(String::as_str).collect();
+    assert_eq!(
+        names,
+        vec![
+            "Ball_Shape_opacity",
+            "Ball_Shape_position_x",
+            "Ball_Shape_position_y",
+            "Ball_Shape_rotation",
+            "Ball_Shape_scale_x",
+            "Ball_Shape_scale_y",
+        ]
+    );
+    assert_eq!(
+        doc.variable["Ball_Shape_scale_y"],
+        AnimationVariableDef::Float {
+            initial: 2.0,
+            min: None,
+            max: None,
+        }
+    );
+    let tl = ResolvedStoryboard::resolve(doc, "bounce").unwrap();
+    assert_close(tl.duration, 2.0);
+    assert_close(tl.keyframes["0.500s"], 0.5);
+}
+
+#[test]
+fn values_follow_keyframes() {
+    let doc = import_lottie(BOUNCE).unwrap().document;
+    // opacity: 0..100 → 0..1（線形接線）
+    assert_close(value_at(&doc, "Ball_Shape_opacity", 0.25), 0.5);
+    assert_close(value_at(&doc, "Ball_Shape_opacity", 1.0), 1.0);
+    // position: 2 区間目は線形
+    assert_close(value_at(&doc, "Ball_Shape_position_x", 1.5), 150.0);
+    assert_close(value_at(&doc, "Ball_Shape_position_y", 1.5), 50.0);
+    assert_close(value_at(&doc, "Ball_Shape_position_x", 2.0), 200.0);
+}
+
+#[test]
+fn bezier_tangents_become_easing() {
+    let doc = import_lottie(BOUNCE).unwrap().document;
+    // ease-in-out 相当: 中点は対称、序盤は遅い
+    assert!((value_at(&doc, "Ball_Shape_position_x", 0.5) - 50.0).abs() < 1.0);
+    assert!(value_at(&doc, "Ball_Shape_position_x", 0.2) < 20.0);
+}
+
+#[test]
+fn hold_keyframe_switches_at_next_key() {
+    let doc = import_lottie(BOUNCE).unwrap().document;
+    assert_close(value_at(&doc, "Ball_Shape_rotation", 0.9), 0.0);
+    assert_close(value_at(&doc, "Ball_Shape_rotation", 1.0), 90.0);
+}
+
+#[test]
+fn split_position_and_layer_timing() {
+    let json = r#"{
+      "fr": 10, "ip": 0, "op": 20,
+      "layers": [{
+        "nm": "a", "st": 5, "sr": 2,
+        "ks": { "p": { "s": true,
+          "x": { "a": 1, "k": [ { "t": 0, "s": [0] }, { "t": 5, "s": [10] } ] },
+          "y": { "a": 0, "k": 7 }
+        } }
+      }]
+    }"#;
+    let result = import_lottie(json).unwrap();
+    let doc = &result.document;
+    assert_eq!(
+        doc.variable["a_position_y"],
+        AnimationVariableDef::Float {
+            initial: 7.0,
+            min: None,
+            max: None,
+        }
+    );
+    // フレーム 0..5 → (0*2+5)/10 .. (5*2+5)/10 = 0.5s .. 1.5s
+    let tl = ResolvedStoryboard::resolve(doc, "lottie").unwrap();
+    assert_close(tl.keyframes["0.500s"], 0.5);
+    assert_close(tl.keyframes["1.500s"], 1.5);
+}
+
+#[test]
+fn unsupported_features_are_reported() {
+    let json = r#"{
+      "fr": 30, "ip": 0, "op": 30,
+      "assets": [{ "id": "comp_0", "layers": [] }],
+      "layers": [
+        { "nm": "child", "parent": 2, "ddd": 1, "ip": 10, "op": 30,
+          "ks": {
+            "a": { "a": 0, "k": [50, 50, 0] },
+            "sk": { "a": 1, "k": [ { "t": 0, "s": [0] }, { "t": 10, "s": [20] } ] },
+            "p": { "a": 1, "k": [
+              { "t": 0, "s": [0, 0], "to": [10, 0, 0], "ti": [0, 0, 0] },
+              { "t": 10, "s": [100, 0] }
+            ] }
+          } },
+        { "nm": "child" }
+      ]
+    }"#;
+    let result = import_lottie(json).unwrap();
+    assert!(result.document.validate().is_ok());
+    assert!(result.document.variable.contains_key("child_position_x"));
+    let messages: Vec<String> = result.diagnostics.iter().map(|d| d.to_string()).collect();
+    let expect = [
+        "composition: assets and precompositions are not imported",
+        "layer 'child': 3D layers are imported as 2D",
+        "layer 'child': parenting is not imported",
+        "layer 'child': layer in/out points",
+        "layer 'child': transform property 'a' is not imported",
+        "layer 'child': transform property 'sk' is not imported",
+        "layer 'child': spatial bezier tangents",
+    ];
+    for e in expect {
+        assert!(
+            messages.iter().any(|m| m.contains(e)),
+            "missing '{}' in {:#?}",
+            e,
+            messages
+        );
+    }
+}
+
+#[test]
+fn invalid_json_is_error() {
+    assert!(import_lottie("{ not json").is_err());
+}
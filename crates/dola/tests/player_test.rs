@@ -0,0 +1,438 @@
+//! Player tests — timeline resolution, easing evaluation and deterministic playback
+
+use dola::*;
+use std::collections::BTreeMap;
+
+fn float_var(initial: f64) -> AnimationVariableDef {
+    AnimationVariableDef::Float {
+        initial,
+        min: None,
+        max: None,
+    }
+}
+
+fn linear_to(to: f64, duration: f64) -> TransitionRef {
+    TransitionRef::Inline(TransitionDef {
+        from: None,
+        to: Some(TransitionValue::Scalar(to)),
+        relative_to: None,
+        easing: Some(EasingFunction::Named(EasingName::Linear)),
+        delay: 0.0,
+        duration: Some(duration),
+        exprs: TransitionExprs::default(),
+    })
+}
+
+fn entry(variable: &str, transition: TransitionRef) -> StoryboardEntry {
+    StoryboardEntry {
+        variable: Some(variable.to_string()),
+        transition: Some(transition),
+        at: None,
+        between: None,
+        keyframe: None,
+    }
+}
+
+fn float(player: &DolaPlayer, name: &str) -> f64 {
+    player.value(name).and_then(AnimationValue::as_f64).unwrap()
+}
+
+fn request(storyboard: &str, start_time: f64) -> ScheduleRequest {
+    ScheduleRequest {
+        storyboard: storyboard.to_string(),
+        start_time,
+        args: BTreeMap::new(),
+    }
+}
+
+// =============================================================
+// Easing
+// =============================================================
+
+mod easing_tests {
+    use super::*;
+
+    #[test]
+    fn named_easings_hit_endpoints() {
+        let names = [
+            EasingName::Linear,
+            EasingName::QuadraticInOut,
+            EasingName::CubicOut,
+            EasingName::QuarticIn,
+            EasingName::QuinticInOut,
+            EasingName::SineInOut,
+            EasingName::CircularInOut,
+            EasingName::ExponentialInOut,
+            EasingName::ElasticOut,
+            EasingName::BackInOut,
+            EasingName::BounceInOut,
+        ];
+        for name in names {
+            assert!(name.ease(0.0).abs() < 1e-9, "{:?} at 0", name);
+            assert!((name.ease(1.0) - 1.0).abs() < 1e-9, "{:?} at 1", name);
+        }
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        let easing = EasingFunction::Named(EasingName::Linear);
+        assert_eq!(easing.ease(-1.0), 0.0);
+        assert_eq!(easing.ease(2.0), 1.0);
+    }
+
+    #[test]
+    fn cubic_bezier_evaluates_control_points() {
+        let easing = EasingFunction::Parametric(ParametricEasing::CubicBezier {
+            x0: 0.0,
+            x1: 0.0,
+            x2: 1.0,
+            x3: 1.0,
+        });
+        assert_eq!(easing.ease(0.0), 0.0);
+        assert_eq!(easing.ease(0.5), 0.5);
+        assert_eq!(easing.ease(1.0), 1.0);
+    }
+}
+
+// =============================================================
+// Timeline resolution
+// =============================================================
+
+mod timeline_tests {
+    use super::*;
+
+    #[test]
+    fn chained_entries_follow_previous_end() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .storyboard(
+                "sb",
+                StoryboardBuilder::new()
+                    .entry(entry("x", linear_to(1.0, 1.0)))
+                    .entry(entry("x", linear_to(2.0, 0.5)))
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let tl = ResolvedStoryboard::resolve(&doc, "sb").unwrap();
+        assert_eq!(tl.transitions[0].begin, 0.0);
+        assert_eq!(tl.transitions[1].begin, 1.0);
+        assert_eq!(tl.transitions[1].end, 1.5);
+        assert_eq!(tl.keyframes["__implicit_1"], 1.5);
+        assert_eq!(tl.duration, 1.5);
+    }
+
+    #[test]
+    fn at_with_offset_and_between_use_keyframe_times() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .variable("y", float_var(0.0))
+            .storyboard(
+                "sb",
+                StoryboardBuilder::new()
+                    .entry(StoryboardEntry {
+                        keyframe: Some("a".to_string()),
+                        ..entry("x", linear_to(1.0, 2.0))
+                    })
+                    .entry(StoryboardEntry {
+                        at: Some(KeyframeRef::WithOffset {
+                            keyframes: KeyframeNames::Single("a".to_string()),
+                            offset: 0.5,
+                        }),
+                        keyframe: Some("b".to_string()),
+                        ..entry("y", linear_to(1.0, 1.0))
+                    })
+                    .entry(StoryboardEntry {
+                        between: Some(BetweenKeyframes {
+                            from: "start".to_string(),
+                            to: "b".to_string(),
+                        }),
+                        ..entry("y", linear_to(5.0, 99.0))
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let tl = ResolvedStoryboard::resolve(&doc, "sb").unwrap();
+        assert_eq!(tl.keyframes["b"], 3.5);
+        assert_eq!(tl.transitions[2].begin, 0.0);
+        assert_eq!(tl.transitions[2].end, 3.5);
+    }
+
+    #[test]
+    fn cyclic_keyframe_reference_is_error() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .storyboard(
+                "sb",
+                StoryboardBuilder::new()
+                    .entry(StoryboardEntry {
+                        at: Some(KeyframeRef::Single("b".to_string())),
+                        keyframe: Some("a".to_string()),
+                        ..entry("x", linear_to(1.0, 1.0))
+                    })
+                    .entry(StoryboardEntry {
+                        at: Some(KeyframeRef::Single("a".to_string())),
+                        keyframe: Some("b".to_string()),
+                        ..entry("x", linear_to(2.0, 1.0))
+                    })
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let err = ResolvedStoryboard::resolve(&doc, "sb").unwrap_err();
+        assert!(matches!(err, DolaError::KeyframeCycle { .. }));
+    }
+
+    #[test]
+    fn undefined_storyboard_is_error() {
+        let doc = DolaDocumentBuilder::new("1.0").build().unwrap();
+        let err = ResolvedStoryboard::resolve(&doc, "missing").unwrap_err();
+        assert_eq!(
+            err,
+            DolaError::UndefinedStoryboard {
+                name: "missing".to_string()
+            }
+        );
+    }
+}
+
+// =============================================================
+// Playback
+// =============================================================
+
+mod playback_tests {
+    use super::*;
+
+    fn single_move(policy: InterruptionPolicy) -> DolaDocument {
+        DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .storyboard(
+                "move",
+                StoryboardBuilder::new()
+                    .interruption_policy(policy)
+                    .entry(entry("x", linear_to(10.0, 1.0)))
+                    .entry(entry("x", linear_to(20.0, 1.0)))
+                    .build(),
+            )
+            .storyboard(
+                "reset",
+                StoryboardBuilder::new()
+                    .entry(entry("x", linear_to(0.0, 1.0)))
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn linear_transition_interpolates_and_completes() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Conclude));
+        player.schedule(request("move", 0.0)).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Playing);
+        player.tick(0.5).unwrap();
+        assert_eq!(float(&player, "x"), 5.0);
+        player.tick(1.0).unwrap();
+        assert_eq!(float(&player, "x"), 15.0);
+        player.tick(1.0).unwrap();
+        assert_eq!(float(&player, "x"), 20.0);
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+    }
+
+    #[test]
+    fn delayed_request_waits_for_start_time() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Conclude));
+        player.schedule(request("move", 1.0)).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Idle);
+        player.tick(1.5).unwrap();
+        assert_eq!(float(&player, "x"), 5.0);
+    }
+
+    #[test]
+    fn conclude_jumps_current_transition_to_final_value() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Conclude));
+        player.schedule(request("move", 0.0)).unwrap();
+        player.tick(0.5).unwrap();
+        player.schedule(request("reset", 0.0)).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+        assert_eq!(float(&player, "x"), 10.0);
+    }
+
+    #[test]
+    fn compress_plays_remainder_fast_before_interrupter_starts() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Compress));
+        player.schedule(request("move", 0.0)).unwrap();
+        player.tick(0.5).unwrap();
+        // 残り1.5秒を0.25秒（6倍速）に圧縮、resetはその完了を待つ
+        player.schedule(request("reset", 0.0)).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Playing);
+        assert_eq!(player.state("reset"), PlaybackState::Idle);
+        assert_eq!(float(&player, "x"), 5.0);
+        player.tick(0.125).unwrap();
+        assert_eq!(float(&player, "x"), 12.5);
+        player.tick(0.125).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+        assert_eq!(player.state("reset"), PlaybackState::Playing);
+        assert_eq!(float(&player, "x"), 20.0);
+        player.tick(0.5).unwrap();
+        assert_eq!(float(&player, "x"), 10.0);
+    }
+
+    #[test]
+    fn compress_interrupt_finishes_remainder_quickly() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Compress));
+        player.schedule(request("move", 0.0)).unwrap();
+        player.tick(1.5).unwrap();
+        // 残り0.5秒を0.25秒に圧縮
+        player.interrupt("move").unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Playing);
+        player.tick(0.125).unwrap();
+        assert_eq!(float(&player, "x"), 17.5);
+        player.tick(0.125).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+        assert_eq!(float(&player, "x"), 20.0);
+    }
+
+    #[test]
+    fn cancel_freezes_at_request_time_and_trim_at_start_time() {
+        let mut cancel = DolaPlayer::new(single_move(InterruptionPolicy::Cancel));
+        cancel.schedule(request("move", 0.0)).unwrap();
+        cancel.tick(0.5).unwrap();
+        cancel.schedule(request("reset", 1.0)).unwrap();
+        cancel.tick(0.5).unwrap();
+        assert_eq!(cancel.state("move"), PlaybackState::Cancelled);
+        assert_eq!(float(&cancel, "x"), 5.0);
+
+        let mut trim = DolaPlayer::new(single_move(InterruptionPolicy::Trim));
+        trim.schedule(request("move", 0.0)).unwrap();
+        trim.tick(0.5).unwrap();
+        trim.schedule(request("reset", 1.0)).unwrap();
+        trim.tick(0.5).unwrap();
+        assert_eq!(float(&trim, "x"), 10.0);
+        trim.tick(0.5).unwrap();
+        assert_eq!(trim.state("move"), PlaybackState::Cancelled);
+        assert_eq!(float(&trim, "x"), 15.0);
+    }
+
+    #[test]
+    fn trim_cuts_only_conflicting_variables() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .variable("y", float_var(0.0))
+            .storyboard(
+                "move",
+                StoryboardBuilder::new()
+                    .interruption_policy(InterruptionPolicy::Trim)
+                    .entry(entry("x", linear_to(10.0, 2.0)))
+                    .entry(StoryboardEntry {
+                        at: Some(KeyframeRef::Single("start".to_string())),
+                        ..entry("y", linear_to(10.0, 2.0))
+                    })
+                    .build(),
+            )
+            .storyboard(
+                "reset_x",
+                StoryboardBuilder::new()
+                    .entry(entry("x", linear_to(0.0, 1.0)))
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let mut player = DolaPlayer::new(doc);
+        player.schedule(request("move", 0.0)).unwrap();
+        player.tick(1.0).unwrap();
+        player.schedule(request("reset_x", 0.0)).unwrap();
+        // y は継続、x は reset_x が引き継ぐ
+        assert_eq!(player.state("move"), PlaybackState::Playing);
+        player.tick(0.5).unwrap();
+        assert_eq!(float(&player, "x"), 2.5);
+        assert_eq!(float(&player, "y"), 7.5);
+        player.tick(0.5).unwrap();
+        assert_eq!(float(&player, "x"), 0.0);
+        assert_eq!(float(&player, "y"), 10.0);
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+    }
+
+    #[test]
+    fn never_delays_conflicting_storyboard() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Never));
+        player.schedule(request("move", 0.0)).unwrap();
+        player.tick(0.5).unwrap();
+        player.schedule(request("reset", 0.0)).unwrap();
+        assert_eq!(player.state("reset"), PlaybackState::Idle);
+        player.tick(1.5).unwrap();
+        assert_eq!(player.state("move"), PlaybackState::Completed);
+        assert_eq!(player.state("reset"), PlaybackState::Playing);
+        assert_eq!(float(&player, "x"), 20.0);
+        player.tick(0.5).unwrap();
+        assert_eq!(float(&player, "x"), 10.0);
+    }
+
+    #[test]
+    fn loop_count_repeats_timeline() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable("x", float_var(0.0))
+            .storyboard(
+                "blink",
+                StoryboardBuilder::new()
+                    .loop_count(2)
+                    .entry(entry("x", linear_to(1.0, 1.0)))
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let mut player = DolaPlayer::new(doc);
+        player.schedule(request("blink", 0.0)).unwrap();
+        player.tick(1.25).unwrap();
+        assert_eq!(float(&player, "x"), 0.25);
+        player.tick(1.0).unwrap();
+        assert_eq!(float(&player, "x"), 1.0);
+        assert_eq!(player.state("blink"), PlaybackState::Completed);
+    }
+
+    #[test]
+    fn typewriter_defaults_to_text_length() {
+        let doc = DolaDocumentBuilder::new("1.0")
+            .variable(
+                "chars",
+                AnimationVariableDef::Integer {
+                    initial: 0,
+                    min: Some(0),
+                    max: None,
+                    typewriter: Some("こんにちは".to_string()),
+                },
+            )
+            .storyboard(
+                "type",
+                StoryboardBuilder::new()
+                    .entry(entry(
+                        "chars",
+                        TransitionRef::Inline(TransitionDef {
+                            from: None,
+                            to: None,
+                            relative_to: None,
+                            easing: None,
+                            delay: 0.0,
+                            duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
+                        }),
+                    ))
+                    .build(),
+            )
+            .build()
+            .unwrap();
+        let mut player = DolaPlayer::new(doc);
+        player.schedule(request("type", 0.0)).unwrap();
+        player.tick(0.4).unwrap();
+        assert_eq!(player.value("chars"), Some(&AnimationValue::Integer(2)));
+        player.tick(1.0).unwrap();
+        assert_eq!(player.value("chars"), Some(&AnimationValue::Integer(5)));
+    }
+
+    #[test]
+    fn undefined_storyboard_request_is_error() {
+        let mut player = DolaPlayer::new(single_move(InterruptionPolicy::Conclude));
+        let err = player.schedule(request("missing", 0.0)).unwrap_err();
+        assert!(matches!(err, DolaError::UndefinedStoryboard { .. }));
+    }
+}
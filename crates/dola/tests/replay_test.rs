@@ -0,0 +1,226 @@
+//! Replay tests — input recording, deterministic replay and time-travel queries
+
+use dola::*;
+use std::collections::BTreeMap;
+
+fn document() -> DolaDocument {
+    DolaDocumentBuilder::new("1.0")
+        .variable(
+            "x",
+            AnimationVariableDef::Float {
+                initial: 0.0,
+                min: None,
+                max: None,
+            },
+        )
+        .transition(
+            "ease",
+            TransitionDef {
+                from: None,
+                relative_to: Some(100.0),
+                to: None,
+                easing: Some(EasingFunction::Named(EasingName::CubicInOut)),
+                delay: 0.0,
+                duration: Some(2.0),
+                exprs: TransitionExprs::default(),
+            },
+        )
+        .storyboard(
+            "move",
+            StoryboardBuilder::new()
+                .entry(StoryboardEntry {
+                    variable: Some("x".to_string()),
+                    transition: Some(TransitionRef::Named("ease".to_string())),
+                    at: None,
+                    between: None,
+                    keyframe: None,
+                })
+                .build(),
+        )
+        .build()
+        .unwrap()
+}
+
+/// 記録付きプレイヤーで入力列を実行し、各入力後の x を返す
+fn record_session() -> (ReplayLog, Vec<AnimationValue>) {
+    let mut player = DolaPlayer::new(document()).with_recording();
+    let mut observed = Vec::new();
+    player
+        .schedule(ScheduleRequest {
+            storyboard: "move".to_string(),
+            start_time: 0.0,
+            args: BTreeMap::new(),
+        })
+        .unwrap();
+    observed.push(player.value("x").unwrap().clone());
+    for _ in 0..3 {
+        player.tick(0.3).unwrap();
+        observed.push(player.value("x").unwrap().clone());
+    }
+    player
+        .schedule(ScheduleRequest {
+            storyboard: "move".to_string(),
+            start_time: 0.0,
+            args: BTreeMap::new(),
+        })
+        .unwrap();
+    observed.push(player.value("x").unwrap().clone());
+    player.interrupt("move").unwrap();
+    observed.push(player.value("x").unwrap().clone());
+    player.seek(5.0).unwrap();
+    observed.push(player.value("x").unwrap().clone());
+    (player.take_log().unwrap(), observed)
+}
+
+#[test]
+fn recording_captures_every_input() {
+    let (log, _) = record_session();
+    assert_eq!(log.inputs.len(), 7);
+    assert!(matches!(log.inputs[0], PlayerInput::Schedule(_)));
+    assert_eq!(log.inputs[1], PlayerInput::Tick { delta: 0.3 });
+    assert_eq!(
+        log.inputs[5],
+        PlayerInput::Interrupt {
+            storyboard: "move".to_string()
+        }
+    );
+    assert_eq!(log.inputs[6], PlayerInput::Seek { time: 5.0 });
+}
+
+#[test]
+fn replay_reproduces_exact_values() {
+    let (log, observed) = record_session();
+    let trace = log.replay(&document());
+    let replayed: Vec<AnimationValue> = trace.frames[1..]
+        .iter()
+        .map(|f| f.values["x"].clone())
+        .collect();
+    assert_eq!(replayed, observed);
+}
+
+#[test]
+fn log_json_roundtrip_replays_identically() {
+    let (log, observed) = record_session();
+    let json = serde_json::to_string(&log).unwrap();
+    let restored: ReplayLog = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, log);
+    let trace = restored.replay(&document());
+    assert_eq!(trace.frames.last().unwrap().values["x"], observed[6]);
+}
+
+#[test]
+fn input_json_format() {
+    let json = serde_json::to_string(&PlayerInput::Tick { delta: 0.5 }).unwrap();
+    assert_eq!(json, r#"{"type":"tick","delta":0.5}"#);
+    let json = serde_json::to_string(&PlayerInput::Schedule(ScheduleRequest {
+        storyboard: "move".to_string(),
+        start_time: 0.0,
+        args: BTreeMap::new(),
+    }))
+    .unwrap();
+    assert_eq!(
+        json,
+        r#"{"type":"schedule","storyboard":"move","start_time":0.0}"#
+    );
+}
+
+#[test]
+fn replayer_steps_and_travels_back() {
+    let (log, observed) = record_session();
+    let mut replayer = Replayer::new(document(), log);
+    replayer.seek_to(4);
+    assert_eq!(replayer.player().value("x"), Some(&observed[3]));
+    replayer.seek_to(2);
+    assert_eq!(replayer.position(), 2);
+    assert_eq!(replayer.player().value("x"), Some(&observed[1]));
+    assert!(replayer.step().unwrap().is_ok());
+    assert_eq!(replayer.player().value("x"), Some(&observed[2]));
+    replayer.seek_to(usize::MAX);
+    assert!(replayer.step().is_none());
+}
+
+/// 記録なしのプレイヤーで move を開始し `time` まで進めた x
+fn direct_value(time: f64) -> AnimationValue {
+    let mut player = DolaPlayer::new(document());
+    player
+        .schedule(ScheduleRequest {
+            storyboard: "move".to_string(),
+            start_time: 0.0,
+            args: BTreeMap::new(),
+        })
+        .unwrap();
+    player.seek(time).unwrap();
+    player.value("x").unwrap().clone()
+}
+
+#[test]
+fn value_at_reevaluates_between_inputs() {
+    let (log, _) = record_session();
+    let trace = log.replay(&document());
+    // 入力境界（0.6）ではなく 0.65 時点の補間値
+    assert_eq!(trace.value_at("x", 0.65), Some(direct_value(0.65)));
+    assert_ne!(trace.value_at("x", 0.65), Some(direct_value(0.6)));
+    assert_eq!(
+        trace.value_at("x", 0.6).as_ref(),
+        Some(&trace.frames[3].values["x"])
+    );
+    // 同時刻の入力はログ順で最後の状態（0.9 の再スケジュール → 中断後）
+    assert_eq!(
+        trace.value_at("x", 0.9).as_ref(),
+        Some(&trace.frames[6].values["x"])
+    );
+    assert!(trace.value_at("x", -1.0).is_none());
+    assert!(trace.value_at("x", 6.0).is_none());
+    assert!(trace.value_at("missing", 0.5).is_none());
+}
+
+#[test]
+fn value_between_samples_at_fixed_step() {
+    let (log, _) = record_session();
+    let trace = log.replay(&document());
+    let samples = trace.value_between("x", 0.2, 0.7, 0.1);
+    assert_eq!(samples.len(), 6);
+    for (t, value) in &samples {
+        assert_eq!(value, &direct_value(*t), "t = {}", t);
+    }
+    assert!((samples.last().unwrap().0 - 0.7).abs() < 1e-9);
+    assert!(trace.value_between("x", 0.2, 0.7, 0.0).is_empty());
+    assert!(trace.value_between("x", 0.7, 0.2, 0.1).is_empty());
+    assert!(trace.value_between("missing", 0.0, 10.0, 0.5).is_empty());
+}
+
+#[test]
+fn backward_seek_is_rejected_and_replayed() {
+    let mut player = DolaPlayer::new(document()).with_recording();
+    player.seek(1.0).unwrap();
+    assert!(matches!(
+        player.seek(0.5),
+        Err(DolaError::InvalidSeek { time, clock }) if time == 0.5 && clock == 1.0
+    ));
+    assert_eq!(player.clock(), 1.0);
+    let trace = player.take_log().unwrap().replay(&document());
+    assert!(matches!(
+        trace.frames[2].error,
+        Some(DolaError::InvalidSeek { .. })
+    ));
+    assert_eq!(trace.frames[2].clock, 1.0);
+}
+
+#[test]
+fn failed_input_is_replayed_as_failure() {
+    let mut player = DolaPlayer::new(document()).with_recording();
+    assert!(
+        player
+            .schedule(ScheduleRequest {
+                storyboard: "missing".to_string(),
+                start_time: 0.0,
+                args: BTreeMap::new(),
+            })
+            .is_err()
+    );
+    let trace = player.take_log().unwrap().replay(&document());
+    assert!(matches!(
+        trace.frames[1].error,
+        Some(DolaError::UndefinedStoryboard { .. })
+    ));
+}
@@ -85,6 +85,7 @@ mod v2_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![
                     StoryboardEntry {
                         variable: Some("x".to_string()),
@@ -95,6 +96,7 @@ mod v2_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -109,6 +111,7 @@ mod v2_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -156,6 +159,7 @@ mod v3_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -165,6 +169,7 @@ mod v3_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -198,6 +203,7 @@ mod v4_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("undefined_var".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -207,6 +213,7 @@ mod v4_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -253,6 +260,7 @@ mod v5_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Named("undefined_trans".to_string())),
@@ -302,6 +310,7 @@ mod v6_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![
                     StoryboardEntry {
                         variable: Some("x".to_string()),
@@ -312,6 +321,7 @@ mod v6_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::Single("kf_from_entry_1".to_string())),
                         between: None,
@@ -326,6 +336,7 @@ mod v6_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -360,6 +371,7 @@ mod v6_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -369,6 +381,7 @@ mod v6_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: Some(KeyframeRef::Single("nonexistent".to_string())),
                     between: None,
@@ -408,6 +421,7 @@ mod v6_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![
                     StoryboardEntry {
                         variable: Some("x".to_string()),
@@ -418,6 +432,7 @@ mod v6_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::Single("__implicit_1".to_string())),
                         between: None,
@@ -432,6 +447,7 @@ mod v6_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -467,6 +483,7 @@ mod v6_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -476,6 +493,7 @@ mod v6_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: Some(KeyframeRef::Single("start".to_string())),
                     between: None,
@@ -506,6 +524,7 @@ mod v7_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: None, // missing!
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -515,6 +534,7 @@ mod v7_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -561,6 +581,7 @@ mod v8_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![
                     // Need a KF first
                     StoryboardEntry {
@@ -572,6 +593,7 @@ mod v8_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: None,
                         between: None,
@@ -586,6 +608,7 @@ mod v8_tests {
                             easing: None,
                             delay: 0.0,
                             duration: Some(1.0),
+                            exprs: TransitionExprs::default(),
                         })),
                         at: Some(KeyframeRef::Single("kf1".to_string())),
                         between: Some(BetweenKeyframes {
@@ -625,6 +648,7 @@ mod v9_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: None,
                     transition: None,
@@ -654,6 +678,7 @@ mod v9_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: None,
                     transition: None,
@@ -695,6 +720,7 @@ mod v10_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("bg".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -706,6 +732,7 @@ mod v10_tests {
                         easing: None,
                         delay: 0.0,
                         duration: None,
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -742,6 +769,7 @@ mod v10_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("bg".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -751,6 +779,7 @@ mod v10_tests {
                         easing: None,
                         delay: 0.0,
                         duration: None,
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -797,6 +826,7 @@ mod v11_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -806,6 +836,7 @@ mod v11_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -861,6 +892,7 @@ mod v12_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -870,6 +902,7 @@ mod v12_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -928,6 +961,7 @@ mod v13_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("x".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -939,6 +973,7 @@ mod v13_tests {
                         easing: None,
                         delay: 0.0,
                         duration: Some(1.0),
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -975,6 +1010,7 @@ mod v13_tests {
                 time_scale: 1.0,
                 loop_count: None,
                 interruption_policy: InterruptionPolicy::Conclude,
+                params: BTreeMap::new(),
                 entry: vec![StoryboardEntry {
                     variable: Some("bg".to_string()),
                     transition: Some(TransitionRef::Inline(TransitionDef {
@@ -984,6 +1020,7 @@ mod v13_tests {
                         easing: None,
                         delay: 0.0,
                         duration: None,
+                        exprs: TransitionExprs::default(),
                     })),
                     at: None,
                     between: None,
@@ -0,0 +1,184 @@
+//! Visualization tests — ASCII Gantt snapshot and SVG structure
+
+use dola::*;
+
+fn document() -> DolaDocument {
+    let linear = |to: f64, delay: f64, duration: f64| {
+        TransitionRef::Inline(TransitionDef {
+            from: None,
+            to: Some(TransitionValue::Scalar(to)),
+            relative_to: None,
+            easing: Some(EasingFunction::Named(EasingName::QuadraticInOut)),
+            delay,
+            duration: Some(duration),
+            exprs: TransitionExprs::default(),
+        })
+    };
+    DolaDocumentBuilder::new("1.0")
+        .variable(
+            "opacity",
+            AnimationVariableDef::Float {
+                initial: 0.0,
+                min: Some(0.0),
+                max: Some(1.0),
+            },
+        )
+        .variable(
+            "x",
+            AnimationVariableDef::Float {
+                initial: 0.0,
+                min: None,
+                max: None,
+            },
+        )
+        .variable(
+            "face",
+            AnimationVariableDef::Object {
+                initial: DynamicValue::String("normal".to_string()),
+            },
+        )
+        .storyboard(
+            "greeting",
+            StoryboardBuilder::new()
+                .entry(StoryboardEntry {
+                    variable: Some("opacity".to_string()),
+                    transition: Some(linear(1.0, 0.0, 1.0)),
+                    at: None,
+                    between: None,
+                    keyframe: Some("visible".to_string()),
+                })
+                .entry(StoryboardEntry {
+                    variable: Some("x".to_string()),
+                    transition: Some(linear(100.0, 0.5, 1.5)),
+                    at: Some(KeyframeRef::Single("visible".to_string())),
+                    between: None,
+                    keyframe: Some("moved".to_string()),
+                })
+                .entry(StoryboardEntry {
+                    variable: Some("face".to_string()),
+                    transition: Some(TransitionRef::Inline(TransitionDef {
+                        from: None,
+                        to: Some(TransitionValue::Dynamic(DynamicValue::String(
+                            "smile".to_string(),
+                        ))),
+                        relative_to: None,
+                        easing: None,
+                        delay: 0.0,
+                        duration: None,
+                        exprs: TransitionExprs::default(),
+                    })),
+                    at: Some(KeyframeRef::Single("moved".to_string())),
+                    between: None,
+                    keyframe: None,
+                })
+                .build(),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn ascii_gantt_snapshot() {
+    let tl = ResolvedStoryboard::resolve(&document(), "greeting").unwrap();
+    let expected = [
+        "          |0              3.000s",
+        "face      |                    |",
+        "opacity   |=======",
+        "x         |       ...==========",
+        "keyframes |*      *            *",
+        "           * start @ 0.000s",
+        "           * visible @ 1.000s",
+        "           * moved @ 3.000s",
+        "",
+    ]
+    .join("\n");
+    assert_eq!(tl.to_ascii_gantt(21), expected);
+}
+
+#[test]
+fn ascii_gantt_handles_empty_storyboard() {
+    let doc = DolaDocumentBuilder::new("1.0")
+        .storyboard("empty", StoryboardBuilder::new().build())
+        .build()
+        .unwrap();
+    let tl = ResolvedStoryboard::resolve(&doc, "empty").unwrap();
+    let gantt = tl.to_ascii_gantt(10);
+    assert!(gantt.contains("keyframes |*"));
+    assert!(gantt.contains("* start @ 0.000s"));
+}
+
+#[test]
+fn svg_contains_lanes_bars_curves_and_keyframes() {
+    let tl = ResolvedStoryboard::resolve(&document(), "greeting").unwrap();
+    let svg = tl.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("class=\"transition\"").count(), 2);
+    assert_eq!(svg.matches("class=\"easing\"").count(), 2);
+    assert_eq!(svg.matches("class=\"instant\"").count(), 1);
+    assert_eq!(svg.matches("class=\"delay\"").count(), 1);
+    assert_eq!(svg.matches("class=\"keyframe\"").count(), 3);
+    assert!(svg.contains(">opacity</text>"));
+    assert!(svg.contains("moved @ 3.000s"));
+}
+
+#[test]
+fn svg_escapes_names() {
+    let doc = DolaDocumentBuilder::new("1.0")
+        .storyboard(
+            "sb",
+            StoryboardBuilder::new()
+                .entry(StoryboardEntry {
+                    variable: None,
+                    transition: None,
+                    at: None,
+                    between: None,
+                    keyframe: Some("a<b&c".to_string()),
+                })
+                .build(),
+        )
+        .build()
+        .unwrap();
+    let svg = ResolvedStoryboard::resolve(&doc, "sb").unwrap().to_svg();
+    assert!(svg.contains("a&lt;b&amp;c"));
+    assert!(!svg.contains("a<b"));
+}
+
+#[test]
+fn svg_widens_tick_step_for_long_storyboards() {
+    let doc = DolaDocumentBuilder::new("1.0")
+        .variable(
+            "x",
+            AnimationVariableDef::Float {
+                initial: 0.0,
+                min: None,
+                max: None,
+            },
+        )
+        .storyboard(
+            "long",
+            StoryboardBuilder::new()
+                .entry(StoryboardEntry {
+                    variable: Some("x".to_string()),
+                    transition: Some(TransitionRef::Inline(TransitionDef {
+                        from: None,
+                        to: Some(TransitionValue::Scalar(1.0)),
+                        relative_to: None,
+                        easing: None,
+                        delay: 0.0,
+                        duration: Some(1.0e9),
+                        exprs: TransitionExprs::default(),
+                    })),
+                    at: None,
+                    between: None,
+                    keyframe: None,
+                })
+                .build(),
+        )
+        .build()
+        .unwrap();
+    let svg = ResolvedStoryboard::resolve(&doc, "long").unwrap().to_svg();
+    let ticks = svg.matches("stroke=\"#ddd\"").count();
+    assert!((2..=51).contains(&ticks), "{ticks} ticks");
+    assert!(svg.contains(">1000000000s</text>"));
+}
@@ -1,5 +1,6 @@
 use windows::core::*;
 use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
 use windows::Win32::Graphics::Direct2D::*;
 use windows::Win32::Graphics::DirectComposition::*;
 use windows::Win32::Graphics::Dxgi::Common::*;
@@ -147,6 +148,10 @@ pub trait DCompositionVisualExt {
     fn set_effect<P0>(&self, effect: P0) -> Result<()>
     where
         P0: Param<IDCompositionEffect>;
+    /// SetClip - 矩形クリップを設定（ローカル座標、物理ピクセル）
+    fn set_clip_rect(&self, rect: &D2D_RECT_F) -> Result<()>;
+    /// SetClip(NULL) - クリップを解除
+    fn clear_clip(&self) -> Result<()>;
 }
 
 impl DCompositionVisualExt for IDCompositionVisual3 {
@@ -207,6 +212,16 @@ impl DCompositionVisualExt for IDCompositionVisual3 {
     {
         unsafe { self.SetEffect(effect) }
     }
+
+    #[inline(always)]
+    fn set_clip_rect(&self, rect: &D2D_RECT_F) -> Result<()> {
+        unsafe { self.SetClip2(rect) }
+    }
+
+    #[inline(always)]
+    fn clear_clip(&self) -> Result<()> {
+        unsafe { self.SetClip(None::<&IDCompositionClip>) }
+    }
 }
 
 pub trait DCompositionSurfaceExt {
@@ -134,6 +134,14 @@ pub struct HitTestResult {
     pub metrics: DWRITE_HIT_TEST_METRICS,
 }
 
+/// HitTestPoint の結果
+#[derive(Debug, Clone)]
+pub struct PointHitTestResult {
+    pub is_trailing_hit: bool,
+    pub is_inside: bool,
+    pub metrics: DWRITE_HIT_TEST_METRICS,
+}
+
 pub trait DWriteTextLayoutExt {
     /// クラスタメトリクス取得
     fn get_cluster_metrics(&self) -> Result<Vec<DWRITE_CLUSTER_METRICS>>;
@@ -147,6 +155,18 @@ pub trait DWriteTextLayoutExt {
         text_position: u32,
         is_trailing_hit: bool,
     ) -> Result<HitTestResult>;
+
+    /// 座標からヒットテスト（クリック位置のテキスト位置取得用）
+    fn hit_test_point(&self, point_x: f32, point_y: f32) -> Result<PointHitTestResult>;
+
+    /// テキスト範囲のヒットテスト（選択範囲の矩形取得用、行ごとに1要素）
+    fn hit_test_text_range(
+        &self,
+        text_position: u32,
+        text_length: u32,
+        origin_x: f32,
+        origin_y: f32,
+    ) -> Result<Vec<DWRITE_HIT_TEST_METRICS>>;
 }
 
 impl DWriteTextLayoutExt for IDWriteTextLayout {
@@ -203,4 +223,63 @@ impl DWriteTextLayoutExt for IDWriteTextLayout {
             })
         }
     }
+
+    fn hit_test_point(&self, point_x: f32, point_y: f32) -> Result<PointHitTestResult> {
+        unsafe {
+            let mut is_trailing_hit = BOOL::default();
+            let mut is_inside = BOOL::default();
+            let mut metrics = DWRITE_HIT_TEST_METRICS::default();
+            self.HitTestPoint(
+                point_x,
+                point_y,
+                &mut is_trailing_hit,
+                &mut is_inside,
+                &mut metrics,
+            )?;
+
+            Ok(PointHitTestResult {
+                is_trailing_hit: is_trailing_hit.as_bool(),
+                is_inside: is_inside.as_bool(),
+                metrics,
+            })
+        }
+    }
+
+    fn hit_test_text_range(
+        &self,
+        text_position: u32,
+        text_length: u32,
+        origin_x: f32,
+        origin_y: f32,
+    ) -> Result<Vec<DWRITE_HIT_TEST_METRICS>> {
+        unsafe {
+            // まず必要な要素数を取得（バッファ不足エラーは想定内）
+            let mut actual_count = 0u32;
+            let _ = self.HitTestTextRange(
+                text_position,
+                text_length,
+                origin_x,
+                origin_y,
+                None,
+                &mut actual_count,
+            );
+
+            if actual_count == 0 {
+                return Ok(Vec::new());
+            }
+
+            let mut metrics = vec![DWRITE_HIT_TEST_METRICS::default(); actual_count as usize];
+            self.HitTestTextRange(
+                text_position,
+                text_length,
+                origin_x,
+                origin_y,
+                Some(&mut metrics),
+                &mut actual_count,
+            )?;
+            metrics.truncate(actual_count as usize);
+
+            Ok(metrics)
+        }
+    }
 }
@@ -50,8 +50,17 @@ pub fn format_entity_name(entity: Entity, name: Option<&Name>) -> String {
 pub fn calculate_surface_size_from_global_arrangement(
     global_arrangement: &GlobalArrangement,
 ) -> Option<(u32, u32)> {
-    let width = global_arrangement.bounds.right - global_arrangement.bounds.left;
-    let height = global_arrangement.bounds.bottom - global_arrangement.bounds.top;
+    calculate_surface_size_from_bounds(&global_arrangement.bounds)
+}
+
+/// 物理ピクセル矩形からSurfaceサイズを計算する
+///
+/// ピクセルスナップ済みの矩形（`SnappedArrangement.bounds`）では幅・高さが整数になる。
+pub fn calculate_surface_size_from_bounds(
+    bounds: &crate::ecs::layout::D2DRect,
+) -> Option<(u32, u32)> {
+    let width = bounds.right - bounds.left;
+    let height = bounds.bottom - bounds.top;
 
     // サイズが0以下の場合はNone
     if width <= 0.0 || height <= 0.0 {
@@ -194,6 +203,7 @@ fn draw_recursive(
 /// - 自分のGraphicsCommandListのみを描画（子は描画しない）
 /// - 子の描画は各子が自分のSurfaceで行う
 /// - GlobalArrangementのスケール成分を適用（DPIスケール対応）
+/// - `DisplayHidden`を持つEntityはクリアのみ行い、コマンドリストを描画しない
 pub fn render_surface(
     surfaces: Query<
         (
@@ -202,6 +212,7 @@ pub fn render_surface(
             &GlobalArrangement,
             Option<&GraphicsCommandList>,
             Option<&Name>,
+            Has<crate::ecs::layout::DisplayHidden>,
         ),
         Changed<super::components::SurfaceGraphicsDirty>,
     >,
@@ -212,7 +223,7 @@ pub fn render_surface(
     use windows::Win32::Graphics::Direct2D::Common::D2D1_COMPOSITE_MODE_SOURCE_OVER;
     use windows::Win32::Graphics::Direct2D::D2D1_INTERPOLATION_MODE_LINEAR;
 
-    for (entity, surface, global_arrangement, cmd_list_opt, name) in surfaces.iter() {
+    for (entity, surface, global_arrangement, cmd_list_opt, name, is_hidden) in surfaces.iter() {
         let entity_name = format_entity_name(entity, name);
         // 正常パスのログは抑制（毎フレーム出力されるため）
         // eprintln!("[Frame {}] [render_surface] === Self-rendering Entity={} ===", _frame_count.0, entity_name);
@@ -282,7 +293,8 @@ pub fn render_surface(
 
         // 自己描画: 自分のGraphicsCommandListのみを描画
         // 子の描画は行わない（各子が自分のSurfaceで行う）
-        if let Some(cmd_list) = cmd_list_opt {
+        // display:none のサブツリーは透明クリアのまま
+        if let Some(cmd_list) = cmd_list_opt.filter(|_| !is_hidden) {
             if let Some(command_list) = cmd_list.command_list() {
                 // 正常パスのログは抑制
                 // eprintln!("[render_surface] Drawing own CommandList for Entity={}", entity_name);
@@ -877,6 +889,10 @@ pub fn mark_dirty_surfaces(
 /// 重要: 親→子の順序でAddVisualを呼ぶ必要がある（DirectComposition要件）。
 /// 深さでソートして親が先に処理されるようにする。
 ///
+/// 兄弟Visual間の順序は`ZIndex`を考慮した描画順（`stacking_order`）に従い、
+/// 追加済みの兄弟を基準に挿入位置を決める。トップレイヤー（`ZIndex::Global`）の
+/// Visualは最も近いWindowのVisualに、通常の子より前面に追加する。
+///
 /// 注意: エラーは無視する（親が先に削除されている場合など）
 pub fn visual_hierarchy_sync_system(
     mut vg_queries: ParamSet<(
@@ -886,8 +902,16 @@ pub fn visual_hierarchy_sync_system(
         Query<(&VisualGraphics, Option<&Name>)>,
     )>,
     child_of_query: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    z_indices: Query<&crate::ecs::layout::ZIndex>,
+    windows: Query<(), With<crate::ecs::window::Window>>,
 ) {
     use crate::com::dcomp::DCompositionVisualExt;
+    use crate::ecs::layout::{StackPosition, ZIndex, stack_position};
+    use std::collections::HashMap;
+
+    let z_index_of = |entity: Entity| z_indices.get(entity).ok().copied();
+    let has_top_layer = z_indices.iter().any(ZIndex::is_global);
 
     // 1. まず未同期（parent_visual==None）のエンティティと親情報とName情報を収集
     // (child_entity, parent_entity, child_name, parent_name, depth)
@@ -905,7 +929,14 @@ pub fn visual_hierarchy_sync_system(
                     depth += 1;
                     current = co.parent();
                 }
-                updates.push((entity, child_of.parent(), child_name_str, None, depth));
+                // トップレイヤーはWindowのVisualへ
+                let parent = if z_index_of(entity).is_some_and(|z| z.is_global()) {
+                    nearest_window_ancestor(entity, &child_of_query, &windows)
+                        .unwrap_or(child_of.parent())
+                } else {
+                    child_of.parent()
+                };
+                updates.push((entity, parent, child_name_str, None, depth));
             }
         }
     }
@@ -931,6 +962,9 @@ pub fn visual_hierarchy_sync_system(
         );
     }
 
+    // 親ごとの兄弟Visualの描画順（背面→前面）
+    let mut sibling_orders: HashMap<Entity, Vec<Entity>> = HashMap::new();
+
     // 3. 各子エンティティに対して処理
     for (child_entity, parent_entity, child_name_str, parent_name_str, depth) in updates {
         // フォーマット済み名前を生成
@@ -948,6 +982,33 @@ pub fn visual_hierarchy_sync_system(
                 .and_then(|(pv, _)| pv.visual().cloned())
         };
 
+        // 追加済みの兄弟を基準に挿入位置を決める
+        let reference = parent_visual.as_ref().and_then(|parent_visual| {
+            let siblings = sibling_orders.entry(parent_entity).or_insert_with(|| {
+                visual_sibling_order(
+                    parent_entity,
+                    has_top_layer,
+                    &children_query,
+                    &z_index_of,
+                    &child_of_query,
+                    &windows,
+                )
+            });
+            let parent_query = vg_queries.p1();
+            let visual_of = |entity: Entity| {
+                parent_query
+                    .get(entity)
+                    .ok()
+                    .filter(|(vg, _)| vg.parent_visual() == Some(parent_visual))
+                    .and_then(|(vg, _)| vg.visual().cloned())
+            };
+            match stack_position(siblings, child_entity, |e| visual_of(e).is_some()) {
+                StackPosition::Behind(e) => visual_of(e).map(|v| (v, false)),
+                StackPosition::Above(e) => visual_of(e).map(|v| (v, true)),
+                StackPosition::Top => None,
+            }
+        });
+
         // 子のVisualGraphicsを更新
         let mut child_query = vg_queries.p0();
         if let Ok((_, _, mut child_vg, _)) = child_query.get_mut(child_entity) {
@@ -960,7 +1021,15 @@ pub fn visual_hierarchy_sync_system(
             // 新しい親に追加
             if let Some(ref parent_visual) = parent_visual {
                 // 親がVisualGraphicsを持つ場合: 親Visualに追加
-                if let Err(e) = parent_visual.add_visual(&child_visual, false, None) {
+                let result = match &reference {
+                    // insert_above=false: 基準の背面、true: 基準の前面
+                    Some((reference, insert_above)) => {
+                        parent_visual.add_visual(&child_visual, *insert_above, reference)
+                    }
+                    // 兄弟なし: 最前面
+                    None => parent_visual.add_visual(&child_visual, false, None),
+                };
+                if let Err(e) = result {
                     error!(
                         child = %child_display,
                         depth = depth,
@@ -992,11 +1061,91 @@ pub fn visual_hierarchy_sync_system(
     }
 }
 
+/// 最も近いWindow祖先（自身は含まない）
+fn nearest_window_ancestor(
+    entity: Entity,
+    child_of_query: &Query<&ChildOf>,
+    windows: &Query<(), With<crate::ecs::window::Window>>,
+) -> Option<Entity> {
+    let mut current = entity;
+    while let Ok(child_of) = child_of_query.get(current) {
+        current = child_of.parent();
+        if windows.contains(current) {
+            return Some(current);
+        }
+    }
+    None
+}
+
+/// `parent`のVisual直下に並ぶ子の描画順（背面→前面）
+///
+/// 通常フローの子を`ZIndex::Local`で並べ、Windowの場合はそのWindowに属する
+/// トップレイヤーを描画順で後ろ（前面）に続ける。
+fn visual_sibling_order(
+    parent: Entity,
+    has_top_layer: bool,
+    children_query: &Query<&Children>,
+    z_index_of: &impl Fn(Entity) -> Option<crate::ecs::layout::ZIndex>,
+    child_of_query: &Query<&ChildOf>,
+    windows: &Query<(), With<crate::ecs::window::Window>>,
+) -> Vec<Entity> {
+    use crate::ecs::layout::{sorted_children, stacking_order_with};
+
+    let mut siblings = children_query
+        .get(parent)
+        .map(|children| sorted_children(children, z_index_of))
+        .unwrap_or_default();
+    if has_top_layer && windows.contains(parent) {
+        let order = stacking_order_with(
+            parent,
+            |entity| children_query.get(entity).ok().map(|c| &**c),
+            z_index_of,
+        );
+        siblings.extend(order.into_iter().filter(|&entity| {
+            z_index_of(entity).is_some_and(|z| z.is_global())
+                && nearest_window_ancestor(entity, child_of_query, windows) == Some(parent)
+        }));
+    }
+    siblings
+}
+
+/// `ZIndex`の変更・削除時にVisualを親から外して再挿入させるシステム
+///
+/// `parent_visual`を`None`に戻すことで、`visual_hierarchy_sync_system`が
+/// 新しい描画順（トップレイヤーの場合はWindowのVisual）で追加し直す。
+/// 子孫のVisualは外したVisualに付いたまま移動する。
+pub fn visual_restack_system(
+    changed: Query<Entity, Changed<crate::ecs::layout::ZIndex>>,
+    mut removed: RemovedComponents<crate::ecs::layout::ZIndex>,
+    mut visuals: Query<&mut VisualGraphics>,
+) {
+    use crate::com::dcomp::DCompositionVisualExt;
+
+    for entity in changed.iter().chain(removed.read()) {
+        let Ok(vg) = visuals.get(entity) else {
+            continue;
+        };
+        let (Some(parent), Some(visual)) = (vg.parent_visual().cloned(), vg.visual().cloned())
+        else {
+            continue;
+        };
+        // 階層ルート（parent_visual == 自身）は外す親がない
+        if parent != visual {
+            let _ = parent.remove_visual(&visual); // エラー無視
+        }
+        if let Ok(mut vg) = visuals.get_mut(entity) {
+            vg.set_parent_visual(None);
+        }
+        debug!(entity = ?entity, "[visual_restack] Visual detached for restacking");
+    }
+}
+
 /// Visual プロパティ同期システム (R8)
 ///
 /// ArrangementまたはOpacity変更を検知してVisualのプロパティを同期する。
 /// - Arrangement.offset → Visual.SetOffsetX/SetOffsetY
 /// - Opacity → Visual.SetOpacity
+/// - ClipToBounds → Visual.SetClip（自身のサイズの矩形。削除時は解除）
 ///
 /// DirectCompositionのVisual Offsetは物理ピクセル単位で指定する必要がある。
 /// Arrangement.offset（論理座標）にGlobalArrangement.transform（累積DPIスケール）を適用して物理座標に変換。
@@ -1012,18 +1161,51 @@ pub fn visual_property_sync_system(
             &VisualGraphics,
             Option<&Name>,
             Has<crate::ecs::window::Window>,
+            Has<crate::ecs::layout::ClipToBounds>,
+            Option<&crate::ecs::layout::SnappedArrangement>,
+            Option<&ChildOf>,
+            Option<&crate::ecs::layout::ZIndex>,
         ),
         Or<(
             Changed<crate::ecs::layout::Arrangement>,
             Changed<crate::ecs::layout::GlobalArrangement>,
+            Changed<crate::ecs::layout::SnappedArrangement>,
             Changed<crate::ecs::layout::Opacity>,
+            Changed<crate::ecs::layout::ClipToBounds>,
+            Changed<crate::ecs::layout::ZIndex>,
         )>,
     >,
+    mut removed_clips: RemovedComponents<crate::ecs::layout::ClipToBounds>,
+    visuals: Query<&VisualGraphics>,
+    snapped_parents: Query<&crate::ecs::layout::SnappedArrangement>,
+    child_of_query: Query<&ChildOf>,
+    windows: Query<(), With<crate::ecs::window::Window>>,
+    global_arrangements: Query<&GlobalArrangement>,
 ) {
     use crate::com::dcomp::DCompositionVisualExt;
 
-    for (entity, arrangement, global_arrangement, opacity_opt, vg, name, is_window) in
-        changed_entities.iter()
+    // ClipToBounds削除: クリップ解除
+    for entity in removed_clips.read() {
+        if let Some(visual) = visuals.get(entity).ok().and_then(|vg| vg.visual()) {
+            if let Err(e) = visual.clear_clip() {
+                error!(entity = ?entity, error = ?e, "[visual_property_sync] ClearClip failed");
+            }
+        }
+    }
+
+    for (
+        entity,
+        arrangement,
+        global_arrangement,
+        opacity_opt,
+        vg,
+        name,
+        is_window,
+        clip,
+        snapped,
+        child_of,
+        z_index,
+    ) in changed_entities.iter()
     {
         let Some(visual) = vg.visual() else {
             continue;
@@ -1035,12 +1217,34 @@ pub fn visual_property_sync_system(
         // ただし、WindowエンティティはWin32がCompositionTargetを通じて位置を管理するため、
         // offsetを設定すると二重にオフセットが適用されてしまう。Windowはスキップする。
         if !is_window {
-            // GlobalArrangementから累積スケールを取得（親からのDPIスケールを含む）
-            let scale_x = global_arrangement.scale_x();
-            let scale_y = global_arrangement.scale_y();
-            // 論理座標 × 累積スケール = 物理ピクセル座標
-            let offset_x = arrangement.offset.x * scale_x;
-            let offset_y = arrangement.offset.y * scale_y;
+            // トップレイヤーのVisualはWindowのVisual直下にあるため、Windowからの差分を使う
+            let top_layer_window = z_index
+                .filter(|z| z.is_global())
+                .and_then(|_| nearest_window_ancestor(entity, &child_of_query, &windows));
+            let visual_parent = top_layer_window.or(child_of.map(ChildOf::parent));
+            // ピクセルスナップ済みなら親との整数ピクセル差分を使う（境界線のにじみ防止）
+            let parent_snapped = visual_parent.and_then(|p| snapped_parents.get(p).ok());
+            let window_global = top_layer_window.and_then(|w| global_arrangements.get(w).ok());
+            let (offset_x, offset_y) = match (snapped, parent_snapped, window_global) {
+                (Some(snapped), Some(parent), _) => {
+                    let offset = snapped.offset_from(parent);
+                    (offset.x, offset.y)
+                }
+                (_, _, Some(window)) => (
+                    global_arrangement.bounds.left - window.bounds.left,
+                    global_arrangement.bounds.top - window.bounds.top,
+                ),
+                _ => {
+                    // GlobalArrangementから累積スケールを取得（親からのDPIスケールを含む）
+                    let scale_x = global_arrangement.scale_x();
+                    let scale_y = global_arrangement.scale_y();
+                    // 論理座標 × 累積スケール = 物理ピクセル座標
+                    (
+                        arrangement.offset.x * scale_x,
+                        arrangement.offset.y * scale_y,
+                    )
+                }
+            };
 
             if let Err(e) = visual.set_offset_x(offset_x) {
                 error!(
@@ -1088,6 +1292,30 @@ pub fn visual_property_sync_system(
             // #[cfg(debug_assertions)]
             // eprintln!("[visual_property_sync] Entity={}, opacity={}", entity_name, opacity_value);
         }
+
+        // Clip同期: 自身の矩形（ピクセルスナップ済みサイズ、なければローカル座標 × 累積スケール）
+        if clip {
+            let (width, height) = snapped.map_or(
+                (
+                    arrangement.size.width * global_arrangement.scale_x(),
+                    arrangement.size.height * global_arrangement.scale_y(),
+                ),
+                |s| (s.width(), s.height()),
+            );
+            let rect = windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F {
+                left: 0.0,
+                top: 0.0,
+                right: width,
+                bottom: height,
+            };
+            if let Err(e) = visual.set_clip_rect(&rect) {
+                error!(
+                    entity = %entity_name,
+                    error = ?e,
+                    "[visual_property_sync] SetClip failed"
+                );
+            }
+        }
     }
 }
 
@@ -1126,8 +1354,13 @@ pub fn deferred_surface_creation_system(
             &mut SurfaceGraphics,
             &mut SurfaceGraphicsDirty,
             Option<&Name>,
+            Option<&crate::ecs::layout::SnappedArrangement>,
         ),
-        Or<(Changed<GlobalArrangement>, Changed<GraphicsCommandList>)>,
+        Or<(
+            Changed<GlobalArrangement>,
+            Changed<crate::ecs::layout::SnappedArrangement>,
+            Changed<GraphicsCommandList>,
+        )>,
     >,
     mut stats: ResMut<SurfaceCreationStats>,
 ) {
@@ -1150,14 +1383,14 @@ pub fn deferred_surface_creation_system(
         mut surface_graphics,
         mut dirty,
         name,
+        snapped,
     ) in query.iter_mut()
     {
         let entity_name = format_entity_name(entity, name);
 
-        // GlobalArrangementからサイズを計算
-        let Some((width, height)) =
-            calculate_surface_size_from_global_arrangement(global_arrangement)
-        else {
+        // ピクセルスナップ済み矩形（なければGlobalArrangement）からサイズを計算
+        let bounds = snapped.map_or(&global_arrangement.bounds, |s| &s.bounds);
+        let Some((width, height)) = calculate_surface_size_from_bounds(bounds) else {
             // Req 5.1: スキップ理由ログ
             trace!(
                 entity = %entity_name,
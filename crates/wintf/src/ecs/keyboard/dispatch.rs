@@ -0,0 +1,108 @@
+//! キーイベントディスパッチ
+//!
+//! `KeyboardInput`のイベントをフォーカス中のエンティティへTunnel/Bubbleで配信する。
+
+use super::{
+    FocusNavigation, KeyEvent, KeyState, KeyboardInput, KeyboardInputRecord, KeyboardState,
+    TextInputComposer, TextInputMessage, move_focus, text_input::dispatch_text_input,
+};
+use crate::ecs::pointer::{EventHandler, build_bubble_path, dispatch_event_for_handler};
+use bevy_ecs::prelude::*;
+
+/// キーイベントハンドラ型エイリアス
+pub type KeyEventHandler = EventHandler<KeyEvent>;
+
+/// キー押下ハンドラコンポーネント（WinUI3 KeyDown / PreviewKeyDown 相当）
+///
+/// オートリピートも押下として配信される（`KeyEvent::repeat`で判別）。
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnKeyDown(pub KeyEventHandler);
+
+/// キー解放ハンドラコンポーネント（WinUI3 KeyUp / PreviewKeyUp 相当）
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnKeyUp(pub KeyEventHandler);
+
+/// キーボードイベントディスパッチシステム
+///
+/// Input スケジュールで実行される排他システム。
+///
+/// # Algorithm
+/// `KeyboardInput`の入力を発生順に処理する:
+/// 1. `KeyboardState`を更新（フォーカス喪失時は全キーを解放）
+/// 2. 送信先 = ウィンドウ内の`Focused`エンティティ（なければウィンドウ自身）
+/// 3. `ChildOf`の親チェーンで Tunnel（root → 送信先）/ Bubble（送信先 → root）
+/// 4. ハンドラが `true` を返したら伝播停止
+/// 5. 押下が未処理なら既定動作（Tab / Shift+Tab・矢印キーによるフォーカス移動）
+///
+/// テキスト入力（WM_CHAR / WM_IME_*）も同じキューで発生順に`dispatch_text_input`へ渡す。
+pub fn dispatch_keyboard_events(world: &mut World) {
+    let Some(records) = world
+        .get_resource_mut::<KeyboardInput>()
+        .map(|mut input| input.drain())
+    else {
+        return;
+    };
+
+    for record in records {
+        match record {
+            KeyboardInputRecord::FocusLost { window } => {
+                if let Some(mut state) = world.get_resource_mut::<KeyboardState>() {
+                    state.release_all();
+                }
+                // 変換中なら終了させる
+                if world.get::<TextInputComposer>(window).is_some() {
+                    dispatch_text_input(world, window, TextInputMessage::FocusLost);
+                }
+                tracing::debug!(window = ?window, "[dispatch_keyboard_events] Keys released");
+            }
+            KeyboardInputRecord::Text { window, message } => {
+                dispatch_text_input(world, window, message);
+            }
+            KeyboardInputRecord::Key { window, event } => {
+                if let Some(mut state) = world.get_resource_mut::<KeyboardState>() {
+                    state.apply(&event);
+                }
+                if world.get_entity(window).is_err() {
+                    continue;
+                }
+
+                let sender = super::focused_entity(world, window).unwrap_or(window);
+                let path = build_bubble_path(world, sender);
+                tracing::trace!(
+                    sender = ?sender,
+                    key = event.virtual_key,
+                    state = ?event.state,
+                    "[dispatch_keyboard_events] Dispatching"
+                );
+                match event.state {
+                    KeyState::Pressed => {
+                        let handled = dispatch_event_for_handler::<KeyEvent, OnKeyDown>(
+                            world,
+                            sender,
+                            &path,
+                            &event,
+                            |h| h.0,
+                        );
+                        // 未処理のTab・矢印キーは既定動作としてフォーカスを移動
+                        let navigation =
+                            FocusNavigation::from_key_event(&event).filter(|_| !handled);
+                        if let Some(navigation) = navigation {
+                            move_focus(world, sender, navigation);
+                        }
+                    }
+                    KeyState::Released => {
+                        dispatch_event_for_handler::<KeyEvent, OnKeyUp>(
+                            world,
+                            sender,
+                            &path,
+                            &event,
+                            |h| h.0,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! フォーカス管理
+//!
+//! キーボード入力を受け取るエンティティ（`Focused`）をウィンドウごとに1つ管理する。
+
+use crate::ecs::pointer::{
+    EventHandler, PointerState, build_bubble_path, dispatch_event_for_handler,
+};
+use crate::ecs::window::Window;
+use bevy_ecs::message::{Message, Messages};
+use bevy_ecs::prelude::*;
+
+/// フォーカスを受け取れるエンティティのマーカー
+///
+/// 外すとフォーカスも外れる（`validate_focus_system`）。
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Focusable;
+
+/// フォーカス中のエンティティのマーカー
+///
+/// 直接挿入せず`set_focus`/`clear_focus`で操作する（ウィンドウごとに1つを保つため）。
+///
+/// メモリ戦略: SparseSet - 頻繁な挿入/削除
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[component(storage = "SparseSet")]
+pub struct Focused;
+
+/// フォーカス変更イベント
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct FocusChangedEvent {
+    /// フォーカスを管理するルート（ウィンドウ）
+    pub root: Entity,
+    /// 直前のフォーカス
+    pub previous: Option<Entity>,
+    /// 新しいフォーカス
+    pub current: Option<Entity>,
+}
+
+/// フォーカス取得ハンドラコンポーネント（WinUI3 GotFocus 相当）
+///
+/// 新しいフォーカスから`ChildOf`の祖先へTunnel/Bubbleで配信される。
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnGotFocus(pub EventHandler<FocusChangedEvent>);
+
+/// フォーカス喪失ハンドラコンポーネント（WinUI3 LostFocus 相当）
+///
+/// 直前のフォーカスから`ChildOf`の祖先へTunnel/Bubbleで配信される。
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnLostFocus(pub EventHandler<FocusChangedEvent>);
+
+/// フォーカスを管理するルート
+///
+/// 自身を含む最も近い`Window`の祖先。`Window`がなければ最上位の祖先。
+pub fn focus_root(world: &World, entity: Entity) -> Entity {
+    let mut current = entity;
+    loop {
+        if world.get::<Window>(current).is_some() {
+            return current;
+        }
+        match world.get::<ChildOf>(current) {
+            Some(child_of) => current = child_of.parent(),
+            None => return current,
+        }
+    }
+}
+
+/// `entity`と同じルート（ウィンドウ）内でフォーカス中のエンティティ
+pub fn focused_entity(world: &World, entity: Entity) -> Option<Entity> {
+    let root = focus_root(world, entity);
+    let mut query = world.try_query_filtered::<Entity, With<Focused>>()?;
+    query
+        .iter(world)
+        .find(|&focused| focus_root(world, focused) == root)
+}
+
+/// `entity`にフォーカスを移す
+///
+/// 同じウィンドウ内の直前のフォーカスは外れ、`OnLostFocus` → `OnGotFocus`の順に配信される。
+///
+/// # Returns
+/// - `true`: フォーカス中（既にフォーカスを持っていた場合も含む）
+/// - `false`: エンティティが存在しない、または`Focusable`を持たない
+pub fn set_focus(world: &mut World, entity: Entity) -> bool {
+    if world.get::<Focusable>(entity).is_none() {
+        return false;
+    }
+    let previous = focused_entity(world, entity);
+    if previous == Some(entity) {
+        return true;
+    }
+
+    if let Some(previous) = previous {
+        world.entity_mut(previous).remove::<Focused>();
+    }
+    world.entity_mut(entity).insert(Focused);
+    notify_focus_changed(world, focus_root(world, entity), previous, Some(entity));
+    true
+}
+
+/// `entity`がフォーカスを持っていれば外す
+///
+/// # Returns
+/// フォーカスを外した場合 `true`
+pub fn clear_focus(world: &mut World, entity: Entity) -> bool {
+    if world.get::<Focused>(entity).is_none() {
+        return false;
+    }
+    world.entity_mut(entity).remove::<Focused>();
+    notify_focus_changed(world, focus_root(world, entity), Some(entity), None);
+    true
+}
+
+/// フォーカス変更を通知（Messages送信 + ハンドラ配信）
+fn notify_focus_changed(
+    world: &mut World,
+    root: Entity,
+    previous: Option<Entity>,
+    current: Option<Entity>,
+) {
+    let event = FocusChangedEvent {
+        root,
+        previous,
+        current,
+    };
+    tracing::debug!(
+        ?root,
+        ?previous,
+        ?current,
+        "[FocusChangedEvent] Dispatching"
+    );
+
+    if let Some(mut messages) = world.get_resource_mut::<Messages<FocusChangedEvent>>() {
+        messages.write(event.clone());
+    }
+    if let Some(previous) = previous {
+        if world.get_entity(previous).is_ok() {
+            let path = build_bubble_path(world, previous);
+            dispatch_event_for_handler::<FocusChangedEvent, OnLostFocus>(
+                world,
+                previous,
+                &path,
+                &event,
+                |h| h.0,
+            );
+        }
+    }
+    if let Some(current) = current {
+        if world.get_entity(current).is_ok() {
+            let path = build_bubble_path(world, current);
+            dispatch_event_for_handler::<FocusChangedEvent, OnGotFocus>(
+                world,
+                current,
+                &path,
+                &event,
+                |h| h.0,
+            );
+        }
+    }
+}
+
+/// クリックによるフォーカス移動システム
+///
+/// Input スケジュールで`dispatch_pointer_events`（ボタン状態をクリアする）より前に実行される。
+/// ボタンが押されたエンティティ自身または最も近い`Focusable`の祖先にフォーカスを移す。
+/// `Focusable`の祖先がなければフォーカスは変わらない。
+pub fn focus_on_click_system(world: &mut World) {
+    let pressed: Vec<Entity> = {
+        let mut query = world.query::<(Entity, &PointerState)>();
+        query
+            .iter(world)
+            .filter(|(_, s)| s.left_down || s.right_down || s.middle_down)
+            .map(|(e, _)| e)
+            .collect()
+    };
+
+    for entity in pressed {
+        let target = build_bubble_path(world, entity)
+            .into_iter()
+            .find(|&e| world.get::<Focusable>(e).is_some());
+        if let Some(target) = target {
+            set_focus(world, target);
+        }
+    }
+}
+
+/// `Focusable`を失ったエンティティのフォーカスを外すシステム
+pub fn validate_focus_system(world: &mut World) {
+    let invalid: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Focused>, Without<Focusable>)>()
+        .iter(world)
+        .collect();
+    for entity in invalid {
+        clear_focus(world, entity);
+    }
+}
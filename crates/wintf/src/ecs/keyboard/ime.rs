@@ -0,0 +1,164 @@
+//! IMM（Input Method Manager）連携
+//!
+//! WM_IME_COMPOSITIONからの変換中文字列の読み出しと、候補ウィンドウの配置。
+
+use super::{CandidateWindowRequest, Composition, TextInputMessage};
+use crate::ecs::window::{WindowHandle, WindowPos};
+use bevy_ecs::message::MessageReader;
+use bevy_ecs::prelude::*;
+use windows::Win32::Foundation::{HWND, LPARAM, POINT, RECT};
+use windows::Win32::UI::Input::Ime::{
+    CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT, COMPOSITIONFORM, GCS_COMPATTR, GCS_COMPCLAUSE,
+    GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, HIMC, IME_COMPOSITION_STRING,
+    ImmGetCompositionStringW, ImmGetContext, ImmReleaseContext, ImmSetCandidateWindow,
+    ImmSetCompositionWindow,
+};
+
+/// 入力コンテキスト（スコープを抜けると解放）
+struct InputContext {
+    hwnd: HWND,
+    himc: HIMC,
+}
+
+impl InputContext {
+    fn get(hwnd: HWND) -> Option<Self> {
+        let himc = unsafe { ImmGetContext(hwnd) };
+        (!himc.is_invalid()).then_some(Self { hwnd, himc })
+    }
+
+    /// 変換文字列データをバイト列で取得
+    fn bytes(&self, index: IME_COMPOSITION_STRING) -> Vec<u8> {
+        let len = unsafe { ImmGetCompositionStringW(self.himc, index, None, 0) };
+        if len <= 0 {
+            return Vec::new();
+        }
+        let mut buffer = vec![0u8; len as usize];
+        let read = unsafe {
+            ImmGetCompositionStringW(
+                self.himc,
+                index,
+                Some(buffer.as_mut_ptr().cast()),
+                buffer.len() as u32,
+            )
+        };
+        buffer.truncate(read.max(0) as usize);
+        buffer
+    }
+
+    /// UTF-16文字列を取得
+    fn utf16(&self, index: IME_COMPOSITION_STRING) -> Vec<u16> {
+        self.bytes(index)
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect()
+    }
+
+    /// 文節境界（UTF-16オフセット）を取得
+    fn offsets(&self, index: IME_COMPOSITION_STRING) -> Vec<u32> {
+        self.bytes(index)
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    /// キャレット位置（UTF-16オフセット）を取得
+    fn cursor(&self) -> Option<u32> {
+        let pos = unsafe { ImmGetCompositionStringW(self.himc, GCS_CURSORPOS, None, 0) };
+        (pos >= 0).then_some(pos as u32 & 0xFFFF)
+    }
+}
+
+impl Drop for InputContext {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = ImmReleaseContext(self.hwnd, self.himc);
+        }
+    }
+}
+
+/// WM_IME_COMPOSITIONの内容を読み出す（lParamで示された内容のみ）
+pub(crate) fn read_composition(hwnd: HWND, lparam: LPARAM) -> TextInputMessage {
+    let flags = lparam.0 as u32;
+    let Some(context) = InputContext::get(hwnd) else {
+        return TextInputMessage::Composition {
+            result: None,
+            composition: None,
+        };
+    };
+
+    let result = (flags & GCS_RESULTSTR.0 != 0)
+        .then(|| String::from_utf16_lossy(&context.utf16(GCS_RESULTSTR)));
+    let composition = (flags & GCS_COMPSTR.0 != 0).then(|| {
+        let text = context.utf16(GCS_COMPSTR);
+        let attributes = if flags & GCS_COMPATTR.0 != 0 {
+            context.bytes(GCS_COMPATTR)
+        } else {
+            Vec::new()
+        };
+        let clauses = if flags & GCS_COMPCLAUSE.0 != 0 {
+            context.offsets(GCS_COMPCLAUSE)
+        } else {
+            Vec::new()
+        };
+        let cursor = if flags & GCS_CURSORPOS.0 != 0 {
+            context.cursor()
+        } else {
+            None
+        };
+        Composition::from_utf16(&text, &attributes, &clauses, cursor)
+    });
+
+    TextInputMessage::Composition {
+        result,
+        composition,
+    }
+}
+
+/// 候補ウィンドウ配置システム
+///
+/// `CandidateWindowRequest`の矩形をクライアント座標に変換し、
+/// 変換中文字列ウィンドウをその左上、候補ウィンドウをその下（矩形を避ける位置）に設定する。
+pub fn apply_candidate_window_system(
+    mut requests: MessageReader<CandidateWindowRequest>,
+    windows: Query<(&WindowHandle, Option<&WindowPos>)>,
+) {
+    for request in requests.read() {
+        let Ok((handle, window_pos)) = windows.get(request.window) else {
+            continue;
+        };
+        let Some(context) = InputContext::get(handle.hwnd) else {
+            continue;
+        };
+
+        // スクリーン座標 → クライアント座標
+        let origin = window_pos.and_then(|pos| pos.position).unwrap_or_default();
+        let rect = RECT {
+            left: request.exclude.left.floor() as i32 - origin.x,
+            top: request.exclude.top.floor() as i32 - origin.y,
+            right: request.exclude.right.ceil() as i32 - origin.x,
+            bottom: request.exclude.bottom.ceil() as i32 - origin.y,
+        };
+        let composition = COMPOSITIONFORM {
+            dwStyle: CFS_POINT,
+            ptCurrentPos: POINT {
+                x: rect.left,
+                y: rect.top,
+            },
+            rcArea: RECT::default(),
+        };
+        let candidate = CANDIDATEFORM {
+            dwIndex: 0,
+            dwStyle: CFS_EXCLUDE,
+            ptCurrentPos: POINT {
+                x: rect.left,
+                y: rect.bottom,
+            },
+            rcArea: rect,
+        };
+        unsafe {
+            let _ = ImmSetCompositionWindow(context.himc, &composition);
+            let _ = ImmSetCandidateWindow(context.himc, &candidate);
+        }
+        tracing::trace!(window = ?request.window, ?rect, "[apply_candidate_window_system] Positioned");
+    }
+}
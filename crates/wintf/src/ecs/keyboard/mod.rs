@@ -0,0 +1,484 @@
+//! キーボード入力・フォーカスモジュール
+//!
+//! Win32キーボードメッセージ（WM_KEYDOWN / WM_KEYUP / WM_SYSKEYDOWN / WM_SYSKEYUP）を
+//! `KeyEvent`に正規化し、フォーカスを持つエンティティ（`Focused`）へ
+//! ポインターイベントと同じTunnel/Bubbleで配信する。
+//!
+//! # パイプライン
+//! 1. WndProc: メッセージを`decode_key_message`で`KeyEvent`に変換し、thread_localバッファへ
+//! 2. `transfer_keyboard_buffers_to_world`: バッファを`KeyboardInput`リソースへ転送
+//! 3. `dispatch_keyboard_events`（Input）: `KeyboardState`を更新し、`OnKeyDown`/`OnKeyUp`を配信
+//!
+//! `KeyboardInput`に直接イベントを積めば、ウィンドウなしの`World`でも配信を検証できる。
+//!
+//! # フォーカス
+//! フォーカスはウィンドウごとに1つで、`Focusable`を持つエンティティだけが受け取れる。
+//! `set_focus`・クリック（`focus_on_click_system`）で移動し、`OnGotFocus`/`OnLostFocus`と
+//! `FocusChangedEvent`が発生する。
+//!
+//! どのハンドラも処理しなかったTab / Shift+Tab・矢印キーは、`FocusScope`内でフォーカスを移動する
+//! （`TabIndex`順・`GlobalArrangement`の配置による方向移動）。
+//!
+//! # テキスト入力
+//! WM_CHAR / WM_IME_* は`TextInputMessage`としてキーイベントと同じキューに積まれ、
+//! ウィンドウごとの`TextInputComposer`が`OnTextInput`（確定文字列）・`OnComposition`
+//! （IME変換中文字列）に変換してフォーカス中のエンティティへ配信する。
+
+mod dispatch;
+mod focus;
+mod ime;
+mod navigation;
+mod text_input;
+
+pub use dispatch::{KeyEventHandler, OnKeyDown, OnKeyUp, dispatch_keyboard_events};
+pub use focus::{
+    FocusChangedEvent, Focusable, Focused, OnGotFocus, OnLostFocus, clear_focus,
+    focus_on_click_system, focus_root, focused_entity, set_focus, validate_focus_system,
+};
+pub use ime::apply_candidate_window_system;
+pub(crate) use ime::read_composition;
+pub use navigation::{
+    FocusNavigation, FocusScope, TabIndex, find_in_direction, focus_scope, move_focus,
+    navigation_target, next_tab_stop, tab_order,
+};
+pub use text_input::{
+    CandidateWindowRequest, ClauseAttribute, Composition, CompositionClause, CompositionEvent,
+    ImeCaretRect, OnComposition, OnTextInput, TextInputComposer, TextInputEvent, TextInputMessage,
+    TextInputOutput,
+};
+
+use bevy_ecs::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::time::Instant;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+use windows::Win32::UI::WindowsAndMessaging::{WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP};
+
+// ============================================================================
+// 基本型定義
+// ============================================================================
+
+/// 修飾キー状態
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Windowsキー
+    pub win: bool,
+}
+
+impl Modifiers {
+    /// 修飾キーが1つも押されていないか
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 現在の修飾キー状態を取得（WndProcスレッドで呼ぶ）
+    pub(crate) fn current() -> Self {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            GetKeyState, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+        };
+
+        // 上位ビットが立っていれば押下中
+        let down = |key: VIRTUAL_KEY| unsafe { GetKeyState(key.0 as i32) } < 0;
+        Self {
+            shift: down(VK_SHIFT),
+            ctrl: down(VK_CONTROL),
+            alt: down(VK_MENU),
+            win: down(VK_LWIN) || down(VK_RWIN),
+        }
+    }
+}
+
+/// キーの押下・解放
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+/// キーイベント
+///
+/// Win32メッセージの情報を透過的に保持する。文字入力（WM_CHAR）は含まない（`OnTextInput`で配信）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    /// 仮想キーコード（`VK_*`）
+    pub virtual_key: u16,
+    /// スキャンコード（lParam bit 16-23）
+    pub scan_code: u16,
+    /// 拡張キー（右Ctrl・テンキー以外の矢印キー等、lParam bit 24）
+    pub extended: bool,
+    /// 押下・解放
+    pub state: KeyState,
+    /// オートリピートによる押下（lParam bit 30 = 直前も押下中）
+    pub repeat: bool,
+    /// システムキー（WM_SYSKEYDOWN / WM_SYSKEYUP、Alt併用・F10）
+    pub system: bool,
+    /// イベント発生時の修飾キー状態
+    pub modifiers: Modifiers,
+    /// タイムスタンプ
+    pub timestamp: Instant,
+}
+
+impl KeyEvent {
+    /// 押下イベントを作成（テスト・合成入力用）
+    pub fn pressed(key: VIRTUAL_KEY) -> Self {
+        Self {
+            virtual_key: key.0,
+            scan_code: 0,
+            extended: false,
+            state: KeyState::Pressed,
+            repeat: false,
+            system: false,
+            modifiers: Modifiers::default(),
+            timestamp: Instant::now(),
+        }
+    }
+
+    /// 解放イベントを作成（テスト・合成入力用）
+    pub fn released(key: VIRTUAL_KEY) -> Self {
+        Self {
+            state: KeyState::Released,
+            ..Self::pressed(key)
+        }
+    }
+
+    /// 修飾キー状態を設定
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// 指定の仮想キーか
+    pub fn is_key(&self, key: VIRTUAL_KEY) -> bool {
+        self.virtual_key == key.0
+    }
+
+    /// 押下イベントか
+    pub fn is_pressed(&self) -> bool {
+        self.state == KeyState::Pressed
+    }
+}
+
+/// Win32キーボードメッセージを`KeyEvent`に変換
+///
+/// WM_KEYDOWN / WM_KEYUP / WM_SYSKEYDOWN / WM_SYSKEYUP 以外は`None`。
+pub fn decode_key_message(
+    message: u32,
+    wparam: usize,
+    lparam: isize,
+    modifiers: Modifiers,
+) -> Option<KeyEvent> {
+    let (state, system) = match message {
+        WM_KEYDOWN => (KeyState::Pressed, false),
+        WM_KEYUP => (KeyState::Released, false),
+        WM_SYSKEYDOWN => (KeyState::Pressed, true),
+        WM_SYSKEYUP => (KeyState::Released, true),
+        _ => return None,
+    };
+    let bits = lparam as u32;
+    Some(KeyEvent {
+        virtual_key: wparam as u16,
+        scan_code: ((bits >> 16) & 0xFF) as u16,
+        extended: bits & (1 << 24) != 0,
+        state,
+        // 解放時は常に bit 30 = 1 のため、押下時のみリピートとみなす
+        repeat: state == KeyState::Pressed && bits & (1 << 30) != 0,
+        system,
+        modifiers,
+        timestamp: Instant::now(),
+    })
+}
+
+// ============================================================================
+// KeyboardState リソース
+// ============================================================================
+
+/// キーボード状態リソース
+///
+/// `dispatch_keyboard_events`がイベントごとに更新する。ポーリング用。
+/// `just_pressed`/`just_released`は1フレームのみ有効（FrameFinalizeでクリア）。
+#[derive(Resource, Debug, Default)]
+pub struct KeyboardState {
+    pressed: HashSet<u16>,
+    just_pressed: HashSet<u16>,
+    just_released: HashSet<u16>,
+    modifiers: Modifiers,
+}
+
+impl KeyboardState {
+    /// 押下中か
+    pub fn is_pressed(&self, key: VIRTUAL_KEY) -> bool {
+        self.pressed.contains(&key.0)
+    }
+
+    /// このフレームで押されたか（リピートは含まない）
+    pub fn just_pressed(&self, key: VIRTUAL_KEY) -> bool {
+        self.just_pressed.contains(&key.0)
+    }
+
+    /// このフレームで離されたか
+    pub fn just_released(&self, key: VIRTUAL_KEY) -> bool {
+        self.just_released.contains(&key.0)
+    }
+
+    /// 押下中の仮想キーコード
+    pub fn pressed_keys(&self) -> impl Iterator<Item = u16> + '_ {
+        self.pressed.iter().copied()
+    }
+
+    /// 最新の修飾キー状態
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// イベントを反映
+    pub fn apply(&mut self, event: &KeyEvent) {
+        self.modifiers = event.modifiers;
+        match event.state {
+            KeyState::Pressed => {
+                if self.pressed.insert(event.virtual_key) {
+                    self.just_pressed.insert(event.virtual_key);
+                }
+            }
+            KeyState::Released => {
+                if self.pressed.remove(&event.virtual_key) {
+                    self.just_released.insert(event.virtual_key);
+                }
+            }
+        }
+    }
+
+    /// 全キーを解放（ウィンドウのフォーカス喪失時、離されたキーのWM_KEYUPが届かないため）
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+        self.modifiers = Modifiers::default();
+    }
+
+    /// 1フレームのみ有効な状態をクリア
+    pub fn clear_transient(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+// ============================================================================
+// KeyboardInput リソース（未配信の入力キュー）
+// ============================================================================
+
+/// 未配信のキーボード入力
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyboardInputRecord {
+    /// ウィンドウで発生したキーイベント
+    Key { window: Entity, event: KeyEvent },
+    /// ウィンドウで発生したテキスト入力（WM_CHAR / WM_IME_*）
+    Text {
+        window: Entity,
+        message: TextInputMessage,
+    },
+    /// ウィンドウがキーボードフォーカスを失った（WM_KILLFOCUS）
+    FocusLost { window: Entity },
+}
+
+/// 未配信のキーボード入力キュー
+///
+/// WndProcの入力は`transfer_keyboard_buffers_to_world`でここに積まれ、
+/// `dispatch_keyboard_events`が発生順に配信する。
+#[derive(Resource, Debug, Default)]
+pub struct KeyboardInput {
+    queue: VecDeque<KeyboardInputRecord>,
+}
+
+impl KeyboardInput {
+    /// キーイベントを積む
+    pub fn push(&mut self, window: Entity, event: KeyEvent) {
+        self.queue
+            .push_back(KeyboardInputRecord::Key { window, event });
+    }
+
+    /// テキスト入力メッセージを積む
+    pub fn push_text(&mut self, window: Entity, message: TextInputMessage) {
+        self.queue
+            .push_back(KeyboardInputRecord::Text { window, message });
+    }
+
+    /// ウィンドウのフォーカス喪失を積む
+    pub fn push_focus_lost(&mut self, window: Entity) {
+        self.queue
+            .push_back(KeyboardInputRecord::FocusLost { window });
+    }
+
+    /// 未配信の入力があるか
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// 未配信の入力をすべて取り出す
+    pub fn drain(&mut self) -> Vec<KeyboardInputRecord> {
+        self.queue.drain(..).collect()
+    }
+}
+
+// ============================================================================
+// thread_local! バッファ（WndProcスレッド → World）
+// ============================================================================
+
+thread_local! {
+    /// WndProcで受け取った未転送の入力
+    static KEYBOARD_BUFFER: RefCell<Vec<KeyboardInputRecord>> = const { RefCell::new(Vec::new()) };
+}
+
+/// キーイベントをバッファに記録（handlers.rs から使用）
+#[inline]
+pub(crate) fn record_key_event(window: Entity, event: KeyEvent) {
+    KEYBOARD_BUFFER.with(|buffer| {
+        buffer
+            .borrow_mut()
+            .push(KeyboardInputRecord::Key { window, event });
+    });
+}
+
+/// テキスト入力メッセージをバッファに記録（handlers.rs から使用）
+#[inline]
+pub(crate) fn record_text_input(window: Entity, message: TextInputMessage) {
+    KEYBOARD_BUFFER.with(|buffer| {
+        buffer
+            .borrow_mut()
+            .push(KeyboardInputRecord::Text { window, message });
+    });
+}
+
+/// ウィンドウのフォーカス喪失をバッファに記録（handlers.rs から使用）
+#[inline]
+pub(crate) fn record_focus_lost(window: Entity) {
+    KEYBOARD_BUFFER.with(|buffer| {
+        buffer
+            .borrow_mut()
+            .push(KeyboardInputRecord::FocusLost { window });
+    });
+}
+
+/// WndProcスレッドのthread_localバッファから`KeyboardInput`へ転送
+///
+/// `try_tick_world()`の冒頭（Inputスケジュール実行前）で呼ばれる。
+pub(crate) fn transfer_keyboard_buffers_to_world(world: &mut World) {
+    let records = KEYBOARD_BUFFER.with(|buffer| std::mem::take(&mut *buffer.borrow_mut()));
+    if records.is_empty() {
+        return;
+    }
+    let Some(mut input) = world.get_resource_mut::<KeyboardInput>() else {
+        return;
+    };
+    input.queue.extend(records);
+}
+
+// ============================================================================
+// システム
+// ============================================================================
+
+/// 一時的キーボード状態クリアシステム（FrameFinalize）
+pub fn clear_transient_keyboard_state(state: Option<ResMut<KeyboardState>>) {
+    if let Some(mut state) = state {
+        state.clear_transient();
+    }
+}
+
+// ============================================================================
+// テスト
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{VK_A, VK_RIGHT, VK_SHIFT};
+
+    /// lParam を組み立てる（リピート回数1）
+    fn lparam(scan_code: u32, extended: bool, previous_down: bool, released: bool) -> isize {
+        let mut bits = 1 | (scan_code << 16);
+        if extended {
+            bits |= 1 << 24;
+        }
+        if previous_down {
+            bits |= 1 << 30;
+        }
+        if released {
+            bits |= 1 << 31;
+        }
+        bits as i32 as isize
+    }
+
+    #[test]
+    fn test_decode_key_down() {
+        let event = decode_key_message(
+            WM_KEYDOWN,
+            VK_A.0 as usize,
+            lparam(0x1E, false, false, false),
+            Modifiers::default(),
+        )
+        .unwrap();
+        assert!(event.is_key(VK_A));
+        assert_eq!(event.scan_code, 0x1E);
+        assert_eq!(event.state, KeyState::Pressed);
+        assert!(!event.repeat);
+        assert!(!event.extended);
+        assert!(!event.system);
+    }
+
+    #[test]
+    fn test_decode_repeat_and_extended() {
+        let event = decode_key_message(
+            WM_KEYDOWN,
+            VK_RIGHT.0 as usize,
+            lparam(0x4D, true, true, false),
+            Modifiers::default(),
+        )
+        .unwrap();
+        assert!(event.repeat);
+        assert!(event.extended);
+    }
+
+    #[test]
+    fn test_decode_key_up_is_not_repeat() {
+        let event = decode_key_message(
+            WM_SYSKEYUP,
+            VK_A.0 as usize,
+            lparam(0x1E, false, true, true),
+            Modifiers {
+                alt: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(event.state, KeyState::Released);
+        assert!(!event.repeat);
+        assert!(event.system);
+        assert!(event.modifiers.alt);
+    }
+
+    #[test]
+    fn test_decode_ignores_other_messages() {
+        assert!(decode_key_message(0x0102, 0, 0, Modifiers::default()).is_none());
+    }
+
+    #[test]
+    fn test_keyboard_state_tracks_edges() {
+        let mut state = KeyboardState::default();
+        state.apply(&KeyEvent::pressed(VK_SHIFT));
+        state.apply(&KeyEvent::pressed(VK_A));
+        // リピートは just_pressed にならない
+        state.clear_transient();
+        state.apply(&KeyEvent::pressed(VK_A));
+        assert!(state.is_pressed(VK_A));
+        assert!(!state.just_pressed(VK_A));
+
+        state.apply(&KeyEvent::released(VK_A));
+        assert!(!state.is_pressed(VK_A));
+        assert!(state.just_released(VK_A));
+
+        state.release_all();
+        assert!(!state.is_pressed(VK_SHIFT));
+        assert!(state.just_released(VK_SHIFT));
+        assert_eq!(state.pressed_keys().count(), 0);
+    }
+}
@@ -0,0 +1,246 @@
+//! フォーカスナビゲーション
+//!
+//! Tab / Shift+Tab によるTab順序での移動と、矢印キーによる方向移動
+//! （`GlobalArrangement`の配置から指定方向で最も近いエンティティ）。
+//! 移動先の計算はECSツリーを読むだけの純粋関数で、フォーカスの変更は`move_focus`が行う。
+
+use super::{Focusable, KeyEvent, set_focus};
+use crate::ecs::layout::{D2DRect, GlobalArrangement};
+use crate::ecs::window::Window;
+use bevy_ecs::prelude::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::{VK_DOWN, VK_LEFT, VK_RIGHT, VK_TAB, VK_UP};
+
+/// Tab順序の指定（HTML `tabindex` 相当）
+///
+/// - 正の値: 値の小さい順に、0のエンティティより先に巡回
+/// - 0（未指定と同じ）: ドキュメント順（ツリーの前順）
+/// - 負の値: Tab・矢印キーの移動先にならない（クリック・`set_focus`ではフォーカス可能）
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TabIndex(pub i32);
+
+/// フォーカススコープ
+///
+/// ナビゲーションはスコープ内で循環し、外へ出ない。ポップアップ・ダイアログのルートに付与する。
+/// `Window`は暗黙のスコープ。ネストしたスコープの中身は外側のTab順序に含まれない。
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FocusScope;
+
+/// フォーカスの移動方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusNavigation {
+    /// 次のTabストップ（Tab）
+    Next,
+    /// 前のTabストップ（Shift+Tab）
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusNavigation {
+    /// キー押下に対応する既定のナビゲーション
+    ///
+    /// Tab（Shift併用で逆順）と修飾キーなしの矢印キー。Ctrl/Alt/Win併用時は`None`。
+    pub fn from_key_event(event: &KeyEvent) -> Option<Self> {
+        if !event.is_pressed() {
+            return None;
+        }
+        let modifiers = event.modifiers;
+        if modifiers.ctrl || modifiers.alt || modifiers.win {
+            return None;
+        }
+        if event.is_key(VK_TAB) {
+            return Some(if modifiers.shift {
+                Self::Previous
+            } else {
+                Self::Next
+            });
+        }
+        if modifiers.shift {
+            return None;
+        }
+        [
+            (VK_UP, Self::Up),
+            (VK_DOWN, Self::Down),
+            (VK_LEFT, Self::Left),
+            (VK_RIGHT, Self::Right),
+        ]
+        .into_iter()
+        .find_map(|(key, navigation)| event.is_key(key).then_some(navigation))
+    }
+
+    /// 方向移動か
+    pub fn is_directional(&self) -> bool {
+        !matches!(self, Self::Next | Self::Previous)
+    }
+}
+
+/// スコープのルートか（`FocusScope`または`Window`）
+fn is_scope_root(world: &World, entity: Entity) -> bool {
+    world.get::<FocusScope>(entity).is_some() || world.get::<Window>(entity).is_some()
+}
+
+/// `entity`が属するフォーカススコープ
+///
+/// 自身を含む最も近い`FocusScope`/`Window`の祖先。どちらもなければ最上位の祖先。
+pub fn focus_scope(world: &World, entity: Entity) -> Entity {
+    let mut current = entity;
+    loop {
+        if is_scope_root(world, current) {
+            return current;
+        }
+        match world.get::<ChildOf>(current) {
+            Some(child_of) => current = child_of.parent(),
+            None => return current,
+        }
+    }
+}
+
+/// スコープ内のTab順序
+///
+/// `Focusable`かつ`TabIndex`が負でないエンティティを、正の`TabIndex`の昇順 → ドキュメント順で並べる。
+/// ネストしたスコープの中身は含まない。
+pub fn tab_order(world: &World, scope: Entity) -> Vec<Entity> {
+    let mut stops = Vec::new();
+    collect_tab_stops(world, scope, &mut stops);
+    // 安定ソートのため同じ値はドキュメント順を保つ
+    stops.sort_by_key(|&(index, _)| if index > 0 { (0, index) } else { (1, 0) });
+    stops.into_iter().map(|(_, entity)| entity).collect()
+}
+
+fn collect_tab_stops(world: &World, entity: Entity, stops: &mut Vec<(i32, Entity)>) {
+    if world.get::<Focusable>(entity).is_some() {
+        let index = world.get::<TabIndex>(entity).map_or(0, |t| t.0);
+        if index >= 0 {
+            stops.push((index, entity));
+        }
+    }
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.iter() {
+            if !is_scope_root(world, child) {
+                collect_tab_stops(world, child, stops);
+            }
+        }
+    }
+}
+
+/// `from`の次（`reverse`なら前）のTabストップ
+///
+/// スコープの端では反対側へ循環する。`from`がTabストップでない場合（スコープのルート、
+/// 負の`TabIndex`等）は先頭（`reverse`なら末尾）。Tabストップがなければ`None`。
+pub fn next_tab_stop(world: &World, from: Entity, reverse: bool) -> Option<Entity> {
+    let order = tab_order(world, focus_scope(world, from));
+    if order.is_empty() {
+        return None;
+    }
+    let len = order.len();
+    let index = match order.iter().position(|&e| e == from) {
+        Some(i) if reverse => (i + len - 1) % len,
+        Some(i) => (i + 1) % len,
+        None if reverse => len - 1,
+        None => 0,
+    };
+    Some(order[index])
+}
+
+/// `from`から`direction`方向で最も近いTabストップ
+///
+/// 候補は`from`と同じスコープのTabストップのうち、中心と遠い側の辺が`from`より進行方向にあるもの。
+/// 進行方向の隙間 + 直交方向の隙間 × 2 が最小のものを選ぶ（同点は直交方向の中心のずれ → Tab順序）。
+/// スコープのルート自身や`GlobalArrangement`のないエンティティからは移動しない。
+pub fn find_in_direction(
+    world: &World,
+    from: Entity,
+    direction: FocusNavigation,
+) -> Option<Entity> {
+    if !direction.is_directional() {
+        return None;
+    }
+    let scope = focus_scope(world, from);
+    if scope == from {
+        return None;
+    }
+    let origin = world.get::<GlobalArrangement>(from)?.bounds;
+
+    tab_order(world, scope)
+        .into_iter()
+        .filter(|&e| e != from)
+        .filter_map(|e| {
+            let bounds = world.get::<GlobalArrangement>(e)?.bounds;
+            directional_score(&origin, &bounds, direction).map(|score| (score, e))
+        })
+        .min_by(|(a, _), (b, _)| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)))
+        .map(|(_, e)| e)
+}
+
+/// 方向移動のスコア（小さいほど近い）。進行方向にない場合は`None`。
+fn directional_score(
+    from: &D2DRect,
+    to: &D2DRect,
+    direction: FocusNavigation,
+) -> Option<(f32, f32)> {
+    // 進行方向を正とする主軸・直交軸の区間に変換
+    let (from_main, to_main, from_cross, to_cross) = match direction {
+        FocusNavigation::Right => (
+            (from.left, from.right),
+            (to.left, to.right),
+            (from.top, from.bottom),
+            (to.top, to.bottom),
+        ),
+        FocusNavigation::Left => (
+            (-from.right, -from.left),
+            (-to.right, -to.left),
+            (from.top, from.bottom),
+            (to.top, to.bottom),
+        ),
+        FocusNavigation::Down => (
+            (from.top, from.bottom),
+            (to.top, to.bottom),
+            (from.left, from.right),
+            (to.left, to.right),
+        ),
+        FocusNavigation::Up => (
+            (-from.bottom, -from.top),
+            (-to.bottom, -to.top),
+            (from.left, from.right),
+            (to.left, to.right),
+        ),
+        FocusNavigation::Next | FocusNavigation::Previous => return None,
+    };
+
+    let center = |(start, end): (f32, f32)| (start + end) / 2.0;
+    if center(to_main) <= center(from_main) || to_main.1 <= from_main.1 {
+        return None;
+    }
+    let main_gap = (to_main.0 - from_main.1).max(0.0);
+    let cross_gap = (to_cross.0 - from_cross.1)
+        .max(from_cross.0 - to_cross.1)
+        .max(0.0);
+    let cross_offset = (center(to_cross) - center(from_cross)).abs();
+    Some((main_gap + cross_gap * 2.0, cross_offset))
+}
+
+/// `from`からのナビゲーション先
+pub fn navigation_target(
+    world: &World,
+    from: Entity,
+    navigation: FocusNavigation,
+) -> Option<Entity> {
+    match navigation {
+        FocusNavigation::Next => next_tab_stop(world, from, false),
+        FocusNavigation::Previous => next_tab_stop(world, from, true),
+        direction => find_in_direction(world, from, direction),
+    }
+}
+
+/// `from`からナビゲーションしてフォーカスを移す
+///
+/// # Returns
+/// フォーカスを移した場合 `true`（移動先が`from`自身の場合も含む）
+pub fn move_focus(world: &mut World, from: Entity, navigation: FocusNavigation) -> bool {
+    match navigation_target(world, from, navigation) {
+        Some(target) => set_focus(world, target),
+        None => false,
+    }
+}
@@ -0,0 +1,464 @@
+//! テキスト入力・IME変換
+//!
+//! WM_CHAR / WM_IME_* を`TextInputMessage`に正規化し、ウィンドウごとの`TextInputComposer`
+//! （Win32に依存しない状態機械）で確定文字列・変換中文字列のイベントに変換して、
+//! フォーカス中のエンティティへTunnel/Bubbleで配信する。
+
+use super::focused_entity;
+use crate::ecs::layout::{D2DRect, GlobalArrangement};
+use crate::ecs::pointer::{EventHandler, build_bubble_path, dispatch_event_for_handler};
+use bevy_ecs::message::{Message, Messages};
+use bevy_ecs::prelude::*;
+use std::ops::Range;
+
+// ============================================================================
+// 変換中文字列
+// ============================================================================
+
+/// 文節の属性（IMM `ATTR_*`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ClauseAttribute {
+    /// 未変換の入力 (ATTR_INPUT)
+    #[default]
+    Input,
+    /// 変換対象の変換済み文節 (ATTR_TARGET_CONVERTED)
+    TargetConverted,
+    /// 変換済み (ATTR_CONVERTED)
+    Converted,
+    /// 変換対象の未変換文節 (ATTR_TARGET_NOTCONVERTED)
+    TargetNotConverted,
+    /// 入力エラー (ATTR_INPUT_ERROR)
+    InputError,
+    /// 確定済み (ATTR_FIXEDCONVERTED)
+    FixedConverted,
+}
+
+impl ClauseAttribute {
+    /// IMMの属性値から変換（未知の値は`Input`）
+    pub fn from_ime(attribute: u8) -> Self {
+        match attribute {
+            1 => Self::TargetConverted,
+            2 => Self::Converted,
+            3 => Self::TargetNotConverted,
+            4 => Self::InputError,
+            5 => Self::FixedConverted,
+            _ => Self::Input,
+        }
+    }
+
+    /// 変換対象（候補ウィンドウの対象）の文節か
+    pub fn is_target(&self) -> bool {
+        matches!(self, Self::TargetConverted | Self::TargetNotConverted)
+    }
+}
+
+/// 変換中文字列の文節
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositionClause {
+    /// `Composition::text`内の範囲（バイトオフセット）
+    pub range: Range<usize>,
+    pub attribute: ClauseAttribute,
+}
+
+/// 変換中文字列（未確定文字列）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Composition {
+    pub text: String,
+    /// 文節（先頭から順に`text`全体を覆う）
+    pub clauses: Vec<CompositionClause>,
+    /// キャレット位置（`text`のバイトオフセット）
+    pub caret: usize,
+}
+
+impl Composition {
+    /// IMMの変換中文字列データから作成
+    ///
+    /// # Arguments
+    /// - `text`: GCS_COMPSTR（UTF-16）
+    /// - `attributes`: GCS_COMPATTR（UTF-16コードユニットごとの属性）
+    /// - `clause_offsets`: GCS_COMPCLAUSE（文節境界のUTF-16オフセット、先頭0・末尾は文字列長）
+    /// - `caret`: GCS_CURSORPOS（UTF-16オフセット、`None`なら末尾）
+    ///
+    /// オフセットはバイトオフセットに変換し、範囲外は文字列長に丸める。
+    pub fn from_utf16(
+        text: &[u16],
+        attributes: &[u8],
+        clause_offsets: &[u32],
+        caret: Option<u32>,
+    ) -> Self {
+        // UTF-16オフセット → バイトオフセット（サロゲートペアの後半は文字の先頭に対応）
+        let mut string = String::with_capacity(text.len());
+        let mut byte_offsets = Vec::with_capacity(text.len() + 1);
+        for decoded in char::decode_utf16(text.iter().copied()) {
+            let c = decoded.unwrap_or(char::REPLACEMENT_CHARACTER);
+            let units = decoded.map_or(1, char::len_utf16);
+            byte_offsets.extend(std::iter::repeat_n(string.len(), units));
+            string.push(c);
+        }
+        byte_offsets.push(string.len());
+        let to_byte = |offset: u32| byte_offsets[(offset as usize).min(text.len())];
+        let attribute_at = |offset: u32| {
+            attributes
+                .get(offset as usize)
+                .map_or(ClauseAttribute::Input, |&a| ClauseAttribute::from_ime(a))
+        };
+
+        let clauses = if clause_offsets.len() >= 2 {
+            clause_offsets
+                .windows(2)
+                .filter(|w| to_byte(w[0]) < to_byte(w[1]))
+                .map(|w| CompositionClause {
+                    range: to_byte(w[0])..to_byte(w[1]),
+                    attribute: attribute_at(w[0]),
+                })
+                .collect()
+        } else if string.is_empty() {
+            Vec::new()
+        } else {
+            vec![CompositionClause {
+                range: 0..string.len(),
+                attribute: attribute_at(0),
+            }]
+        };
+
+        Self {
+            caret: caret.map_or(string.len(), to_byte),
+            text: string,
+            clauses,
+        }
+    }
+
+    /// 変換中文字列が空か
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// 変換対象の文節
+    pub fn target_clause(&self) -> Option<&CompositionClause> {
+        self.clauses.iter().find(|c| c.attribute.is_target())
+    }
+}
+
+// ============================================================================
+// イベント
+// ============================================================================
+
+/// 確定文字列イベント（WM_CHAR・IME確定）
+///
+/// 制御文字（Backspace・Enter・Tab等）は含まない。それらは`OnKeyDown`で扱う。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextInputEvent {
+    pub text: String,
+}
+
+/// IME変換イベント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompositionEvent {
+    /// 変換開始
+    Started,
+    /// 変換中文字列の更新（確定時は空の文字列で更新した後に`TextInputEvent`が届く）
+    Updated(Composition),
+    /// 変換終了（確定・取り消し・フォーカス喪失）
+    Ended,
+}
+
+/// 確定文字列ハンドラコンポーネント
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnTextInput(pub EventHandler<TextInputEvent>);
+
+/// IME変換ハンドラコンポーネント
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+pub struct OnComposition(pub EventHandler<CompositionEvent>);
+
+/// IMEのキャレット矩形（スクリーン座標・物理ピクセル、`GlobalArrangement`と同じ座標系）
+///
+/// テキスト入力を受け付けるエンティティが`OnComposition`等で更新する。
+/// 変換の開始・更新のたびに、フォーカス中のエンティティのこの矩形
+/// （なければ`GlobalArrangement`の境界）で`CandidateWindowRequest`が発行される。
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ImeCaretRect(pub D2DRect);
+
+/// 候補ウィンドウの配置要求
+///
+/// `apply_candidate_window_system`がクライアント座標に変換してIMEに設定する。
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct CandidateWindowRequest {
+    pub window: Entity,
+    /// 避けるべき矩形（スクリーン座標・物理ピクセル）。候補ウィンドウはこの下に表示される
+    pub exclude: D2DRect,
+}
+
+// ============================================================================
+// 状態機械
+// ============================================================================
+
+/// Win32テキスト入力メッセージ（正規化済み）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputMessage {
+    /// WM_CHAR（UTF-16コードユニット）
+    Char(u16),
+    /// WM_IME_STARTCOMPOSITION
+    StartComposition,
+    /// WM_IME_COMPOSITION（lParamで示された内容のみ`Some`）
+    Composition {
+        result: Option<String>,
+        composition: Option<Composition>,
+    },
+    /// WM_IME_ENDCOMPOSITION
+    EndComposition,
+    /// ウィンドウのフォーカス喪失（WM_KILLFOCUS）
+    FocusLost,
+}
+
+/// `TextInputComposer`の出力
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputOutput {
+    Text(TextInputEvent),
+    Composition(CompositionEvent),
+}
+
+/// テキスト入力の状態機械（ウィンドウごと）
+///
+/// Win32に依存せず、メッセージ列を与えてイベント列を得る。
+/// - WM_CHARのサロゲートペアを1文字に結合し、制御文字を除く
+/// - WM_IME_STARTCOMPOSITIONなしの変換更新は暗黙に開始する
+/// - 確定は「変換中文字列の更新 → 確定文字列」の順に出力する
+/// - 変換中のフォーカス喪失で変換を終了する
+#[derive(Component, Debug, Clone, Default)]
+pub struct TextInputComposer {
+    high_surrogate: Option<u16>,
+    composition: Option<Composition>,
+}
+
+impl TextInputComposer {
+    /// 変換中か
+    pub fn is_composing(&self) -> bool {
+        self.composition.is_some()
+    }
+
+    /// 現在の変換中文字列
+    pub fn composition(&self) -> Option<&Composition> {
+        self.composition.as_ref()
+    }
+
+    /// メッセージを処理してイベントを出力
+    pub fn feed(&mut self, message: TextInputMessage) -> Vec<TextInputOutput> {
+        let mut out = Vec::new();
+        match message {
+            TextInputMessage::Char(unit) => {
+                if let Some(c) = self.decode_char(unit).filter(|c| !c.is_control()) {
+                    out.push(TextInputOutput::Text(TextInputEvent {
+                        text: c.to_string(),
+                    }));
+                }
+            }
+            TextInputMessage::StartComposition => self.start(&mut out),
+            TextInputMessage::Composition {
+                result,
+                composition,
+            } => {
+                let result = result.filter(|r| !r.is_empty());
+                if composition.as_ref().is_some_and(|c| !c.is_empty()) {
+                    self.start(&mut out);
+                }
+                if self.composition.is_some() {
+                    // 確定のみの場合、変換中文字列は空になる
+                    let next = match composition {
+                        Some(composition) => Some(composition),
+                        None if result.is_some() => Some(Composition::default()),
+                        None => None,
+                    }
+                    .filter(|next| self.composition.as_ref() != Some(next));
+                    if let Some(next) = next {
+                        self.composition = Some(next.clone());
+                        out.push(TextInputOutput::Composition(CompositionEvent::Updated(
+                            next,
+                        )));
+                    }
+                }
+                if let Some(text) = result {
+                    out.push(TextInputOutput::Text(TextInputEvent { text }));
+                }
+            }
+            TextInputMessage::EndComposition => self.end(&mut out),
+            TextInputMessage::FocusLost => {
+                self.high_surrogate = None;
+                self.end(&mut out);
+            }
+        }
+        out
+    }
+
+    fn start(&mut self, out: &mut Vec<TextInputOutput>) {
+        if self.composition.is_none() {
+            self.composition = Some(Composition::default());
+            out.push(TextInputOutput::Composition(CompositionEvent::Started));
+        }
+    }
+
+    fn end(&mut self, out: &mut Vec<TextInputOutput>) {
+        if self.composition.take().is_some() {
+            out.push(TextInputOutput::Composition(CompositionEvent::Ended));
+        }
+    }
+
+    /// UTF-16コードユニットを文字に復号（上位サロゲートは下位を待つ）
+    fn decode_char(&mut self, unit: u16) -> Option<char> {
+        match unit {
+            0xD800..=0xDBFF => {
+                self.high_surrogate = Some(unit);
+                None
+            }
+            0xDC00..=0xDFFF => {
+                let high = self.high_surrogate.take()?;
+                char::decode_utf16([high, unit]).next()?.ok()
+            }
+            _ => {
+                self.high_surrogate = None;
+                char::from_u32(unit as u32)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 配信
+// ============================================================================
+
+/// テキスト入力メッセージを処理し、フォーカス中のエンティティへ配信
+///
+/// `dispatch_keyboard_events`から呼ばれる。`TextInputComposer`はウィンドウに自動で付与される。
+pub(super) fn dispatch_text_input(world: &mut World, window: Entity, message: TextInputMessage) {
+    if world.get_entity(window).is_err() {
+        return;
+    }
+    let outputs = match world.get_mut::<TextInputComposer>(window) {
+        Some(mut composer) => composer.feed(message),
+        None => {
+            let mut composer = TextInputComposer::default();
+            let outputs = composer.feed(message);
+            world.entity_mut(window).insert(composer);
+            outputs
+        }
+    };
+    if outputs.is_empty() {
+        return;
+    }
+
+    let sender = focused_entity(world, window).unwrap_or(window);
+    let path = build_bubble_path(world, sender);
+    for output in outputs {
+        match output {
+            TextInputOutput::Text(event) => {
+                tracing::trace!(sender = ?sender, text = %event.text, "[dispatch_text_input] Text");
+                dispatch_event_for_handler::<TextInputEvent, OnTextInput>(
+                    world,
+                    sender,
+                    &path,
+                    &event,
+                    |h| h.0,
+                );
+            }
+            TextInputOutput::Composition(event) => {
+                tracing::trace!(sender = ?sender, event = ?event, "[dispatch_text_input] Composition");
+                dispatch_event_for_handler::<CompositionEvent, OnComposition>(
+                    world,
+                    sender,
+                    &path,
+                    &event,
+                    |h| h.0,
+                );
+                if event != CompositionEvent::Ended {
+                    request_candidate_window(world, window, sender);
+                }
+            }
+        }
+    }
+}
+
+/// フォーカス中のエンティティのキャレット矩形で候補ウィンドウの配置を要求
+fn request_candidate_window(world: &mut World, window: Entity, sender: Entity) {
+    let exclude = world.get::<ImeCaretRect>(sender).map(|r| r.0).or_else(|| {
+        world
+            .get::<GlobalArrangement>(sender)
+            .map(|arrangement| arrangement.bounds)
+    });
+    let Some(exclude) = exclude else {
+        return;
+    };
+    if let Some(mut messages) = world.get_resource_mut::<Messages<CandidateWindowRequest>>() {
+        messages.write(CandidateWindowRequest { window, exclude });
+    }
+}
+
+// ============================================================================
+// テスト
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn test_composition_from_utf16_converts_offsets() {
+        // 𠮷はサロゲートペア（UTF-16で2単位・UTF-8で4バイト）
+        let text = utf16("へんかん𠮷");
+        let composition = Composition::from_utf16(&text, &[1, 1, 2, 2, 2, 2], &[0, 2, 6], Some(2));
+        assert_eq!(composition.text, "へんかん𠮷");
+        assert_eq!(
+            composition.clauses,
+            vec![
+                CompositionClause {
+                    range: 0..6,
+                    attribute: ClauseAttribute::TargetConverted,
+                },
+                CompositionClause {
+                    range: 6..16,
+                    attribute: ClauseAttribute::Converted,
+                },
+            ]
+        );
+        assert_eq!(composition.caret, 6);
+        assert_eq!(composition.target_clause().unwrap().range, 0..6);
+    }
+
+    #[test]
+    fn test_composition_without_clauses() {
+        let composition = Composition::from_utf16(&utf16("かな"), &[], &[], None);
+        assert_eq!(composition.caret, 6);
+        assert_eq!(composition.clauses.len(), 1);
+        assert_eq!(composition.clauses[0].attribute, ClauseAttribute::Input);
+        assert!(
+            Composition::from_utf16(&[], &[], &[], None)
+                .clauses
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_char_surrogate_pair_and_controls() {
+        let mut composer = TextInputComposer::default();
+        let units = utf16("𠮷");
+        assert!(composer.feed(TextInputMessage::Char(units[0])).is_empty());
+        assert_eq!(
+            composer.feed(TextInputMessage::Char(units[1])),
+            vec![TextInputOutput::Text(TextInputEvent {
+                text: "𠮷".to_string(),
+            })]
+        );
+        // 単独の下位サロゲート・制御文字は出力しない
+        assert!(composer.feed(TextInputMessage::Char(units[1])).is_empty());
+        assert!(composer.feed(TextInputMessage::Char(0x08)).is_empty());
+        assert!(
+            composer
+                .feed(TextInputMessage::Char(b'\r' as u16))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! モニター作業領域へのウィンドウのアンカー（ドッキング）
+//!
+//! `Anchor`を持つWindowは、対象モニターの`work_area`（タスクバーを除いた領域）を基準に
+//! 位置を解決する。解決結果は`WindowPos.position`に書き込まれ、以降は通常の
+//! `WindowPos` → `Arrangement.offset`の経路で反映される。
+//!
+//! 再解決のタイミング:
+//! - `Anchor`の変更（`detect_display_change_system`はディスプレイ構成変更時に全Anchorを変更扱いにする）
+//! - ウィンドウサイズ（`Arrangement`）の変更
+//! - `Monitor`の追加・変更・削除
+//!
+//! ```rust,ignore
+//! // 作業領域の右下（タスクバーの上）から右20px・下10px離して配置
+//! commands.spawn((
+//!     Window::default(),
+//!     Anchor::bottom_right(20.0, 10.0),
+//! ));
+//! ```
+
+use super::Arrangement;
+use crate::ecs::monitor::Monitor;
+use crate::ecs::window::{Window, WindowPos};
+use bevy_ecs::prelude::*;
+use tracing::debug;
+use windows::Win32::Foundation::{POINT, RECT};
+
+/// 軸ごとの揃え位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnchorAlign {
+    /// 左端・上端
+    #[default]
+    Start,
+    /// 中央
+    Center,
+    /// 右端・下端
+    End,
+}
+
+/// アンカーの基準モニター
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnchorMonitor {
+    /// プライマリモニター
+    #[default]
+    Primary,
+    /// ウィンドウの中心を含むモニター（なければプライマリ）
+    Containing,
+    /// 指定したMonitorエンティティ（削除された場合はプライマリ）
+    Entity(Entity),
+}
+
+/// モニター作業領域に対するウィンドウの配置
+///
+/// `margin`は作業領域の各辺からの距離（DIP）で、対象モニターのDPIで物理ピクセルに変換する。
+/// `Start`は`left`/`top`、`End`は`right`/`bottom`を使い、`Center`は両方の差分だけずらす。
+/// ウィンドウをドラッグ等で自由に移動させる場合は`Anchor`を削除すること。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Anchor {
+    /// 水平方向の揃え位置
+    pub horizontal: AnchorAlign,
+    /// 垂直方向の揃え位置
+    pub vertical: AnchorAlign,
+    /// 作業領域の各辺からの余白（DIP）
+    pub margin: super::Rect<f32>,
+    /// 基準モニター
+    pub monitor: AnchorMonitor,
+}
+
+impl Anchor {
+    /// 揃え位置を指定して作成（余白0、プライマリモニター）
+    pub fn new(horizontal: AnchorAlign, vertical: AnchorAlign) -> Self {
+        Self {
+            horizontal,
+            vertical,
+            ..Default::default()
+        }
+    }
+
+    /// 作業領域の右下に、右端から`right`・下端から`bottom`離して配置
+    pub fn bottom_right(right: f32, bottom: f32) -> Self {
+        Self::new(AnchorAlign::End, AnchorAlign::End).with_margin(super::Rect {
+            left: 0.0,
+            right,
+            top: 0.0,
+            bottom,
+        })
+    }
+
+    /// 作業領域の中央に配置
+    pub fn center() -> Self {
+        Self::new(AnchorAlign::Center, AnchorAlign::Center)
+    }
+
+    /// 余白を設定
+    pub fn with_margin(mut self, margin: super::Rect<f32>) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// 基準モニターを設定
+    pub fn with_monitor(mut self, monitor: AnchorMonitor) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// 作業領域・DPI・ウィンドウの物理サイズから左上位置（物理ピクセル）を解決
+    pub fn resolve(&self, work_area: &RECT, dpi: u32, size: (f32, f32)) -> POINT {
+        let scale = dpi as f32 / 96.0;
+        let axis = |align: AnchorAlign, start: i32, end: i32, before: f32, after: f32, len: f32| {
+            let (start, end) = (start as f32, end as f32);
+            let (before, after) = (before * scale, after * scale);
+            let position = match align {
+                AnchorAlign::Start => start + before,
+                AnchorAlign::Center => start + (end - start - len + before - after) / 2.0,
+                AnchorAlign::End => end - after - len,
+            };
+            position.round() as i32
+        };
+        POINT {
+            x: axis(
+                self.horizontal,
+                work_area.left,
+                work_area.right,
+                self.margin.left,
+                self.margin.right,
+                size.0,
+            ),
+            y: axis(
+                self.vertical,
+                work_area.top,
+                work_area.bottom,
+                self.margin.top,
+                self.margin.bottom,
+                size.1,
+            ),
+        }
+    }
+}
+
+/// アンカーの基準モニターを選択
+///
+/// `center`はウィンドウ中心の物理座標。該当がなければプライマリ、それもなければ先頭を返す。
+pub fn select_anchor_monitor<'a>(
+    target: AnchorMonitor,
+    center: (f32, f32),
+    monitors: impl IntoIterator<Item = (Entity, &'a Monitor)>,
+) -> Option<&'a Monitor> {
+    let mut primary = None;
+    let mut first = None;
+    for (entity, monitor) in monitors {
+        let matched = match target {
+            AnchorMonitor::Primary => false,
+            AnchorMonitor::Containing => monitor.contains_point(center.0, center.1),
+            AnchorMonitor::Entity(e) => e == entity,
+        };
+        if matched {
+            return Some(monitor);
+        }
+        if monitor.is_primary && primary.is_none() {
+            primary = Some(monitor);
+        }
+        first.get_or_insert(monitor);
+    }
+    primary.or(first)
+}
+
+/// `Anchor`を持つWindowの位置を解決し、`WindowPos.position`に書き込む
+///
+/// ウィンドウの物理サイズは`Arrangement.size × Arrangement.scale`
+/// （WindowはLayoutRoot直下でscale=DPIスケール）。サイズ未確定（0）の間は解決しない。
+///
+/// PostLayoutスケジュールで`sync_window_arrangement_from_window_pos`の前に実行し、
+/// 同じフレームで`Arrangement.offset`まで反映させる。
+pub fn resolve_window_anchors_system(
+    mut windows: Query<(Entity, Ref<Anchor>, Ref<Arrangement>, &mut WindowPos), With<Window>>,
+    monitors: Query<(Entity, &Monitor)>,
+    changed_monitors: Query<(), Changed<Monitor>>,
+    mut removed_monitors: RemovedComponents<Monitor>,
+) {
+    let monitors_changed = !changed_monitors.is_empty() || removed_monitors.read().next().is_some();
+
+    for (entity, anchor, arrangement, mut window_pos) in windows.iter_mut() {
+        if !monitors_changed && !anchor.is_changed() && !arrangement.is_changed() {
+            continue;
+        }
+        let size = (
+            arrangement.size.width * arrangement.scale.x,
+            arrangement.size.height * arrangement.scale.y,
+        );
+        if size.0 <= 0.0 || size.1 <= 0.0 {
+            continue;
+        }
+        let center = (
+            arrangement.offset.x + size.0 / 2.0,
+            arrangement.offset.y + size.1 / 2.0,
+        );
+        let Some(monitor) = select_anchor_monitor(anchor.monitor, center, monitors.iter()) else {
+            continue;
+        };
+
+        let position = anchor.resolve(&monitor.work_area, monitor.dpi, size);
+        if window_pos.position != Some(position) {
+            debug!(
+                entity = ?entity,
+                x = position.x,
+                y = position.y,
+                monitor = monitor.handle,
+                "[resolve_window_anchors_system] Updating WindowPos.position"
+            );
+            window_pos.position = Some(position);
+        }
+    }
+}
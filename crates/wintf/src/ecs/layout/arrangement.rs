@@ -1,7 +1,7 @@
 use bevy_ecs::prelude::*;
 use windows_numerics::Matrix3x2;
 
-use super::{transform_rect_axis_aligned, D2DRect, LayoutScale, Offset, Size};
+use super::{D2DRect, LayoutScale, Offset, Size, snap_rect_to_pixels, transform_rect_axis_aligned};
 
 /// ローカルレイアウト配置（親からの相対位置とサイズ）
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
@@ -40,10 +40,11 @@ fn on_arrangement_add(
     mut world: bevy_ecs::world::DeferredWorld,
     hook: bevy_ecs::lifecycle::HookContext,
 ) {
-    world
-        .commands()
-        .entity(hook.entity)
-        .insert((GlobalArrangement::default(), ArrangementTreeChanged));
+    world.commands().entity(hook.entity).insert((
+        GlobalArrangement::default(),
+        SnappedArrangement::default(),
+        ArrangementTreeChanged,
+    ));
 }
 
 /// グローバルレイアウト変換（親からの累積変換とバウンディングボックス）
@@ -135,6 +136,19 @@ impl GlobalArrangement {
     pub fn size(&self) -> (f32, f32) {
         (self.width(), self.height())
     }
+
+    /// ローカル座標（DIP）の矩形をスクリーン座標（物理ピクセル）に変換
+    ///
+    /// 描画と同じく、スケールを掛けてからboundsの左上へ平行移動する。
+    pub fn to_screen_rect(&self, local: D2DRect) -> D2DRect {
+        let (scale_x, scale_y) = self.scale();
+        D2DRect {
+            left: self.bounds.left + local.left * scale_x,
+            top: self.bounds.top + local.top * scale_y,
+            right: self.bounds.left + local.right * scale_x,
+            bottom: self.bounds.top + local.bottom * scale_y,
+        }
+    }
 }
 
 impl Default for GlobalArrangement {
@@ -152,6 +166,58 @@ impl Default for GlobalArrangement {
     }
 }
 
+/// ピクセルスナップ済みのグローバル配置
+///
+/// `GlobalArrangement.bounds`（物理ピクセル、小数を含む）の各辺を整数ピクセルに丸めた矩形。
+/// 累積変換（DPIスケール・入れ子の`LayoutScale`）適用後の絶対座標で丸めるため、
+/// どのDPIでも辺を共有する兄弟要素は丸め後も辺を共有する。
+///
+/// Visualのオフセット・Surfaceサイズ・クリップはこの矩形を基準にすることで
+/// 1px の境界線がにじまない。`pixel_snap_system`により更新される。
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SnappedArrangement {
+    /// スクリーン座標系での整数ピクセル矩形
+    pub bounds: D2DRect,
+}
+
+impl SnappedArrangement {
+    /// GlobalArrangementからスナップ済み矩形を計算
+    pub fn from_global(global: &GlobalArrangement) -> Self {
+        Self {
+            bounds: snap_rect_to_pixels(&global.bounds),
+        }
+    }
+
+    /// 幅（整数ピクセル）
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.bounds.right - self.bounds.left
+    }
+
+    /// 高さ（整数ピクセル）
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.bounds.bottom - self.bounds.top
+    }
+
+    /// 親のスナップ済み矩形からの相対位置（整数ピクセル）
+    #[inline]
+    pub fn offset_from(&self, parent: &SnappedArrangement) -> Offset {
+        Offset {
+            x: self.bounds.left - parent.bounds.left,
+            y: self.bounds.top - parent.bounds.top,
+        }
+    }
+}
+
+impl Default for SnappedArrangement {
+    fn default() -> Self {
+        Self {
+            bounds: GlobalArrangement::default().bounds,
+        }
+    }
+}
+
 /// Arrangementツリー変更マーカー（ダーティビット伝播用）
 #[derive(Component, Debug, Clone, Copy, Default)]
 pub struct ArrangementTreeChanged;
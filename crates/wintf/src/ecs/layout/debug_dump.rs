@@ -0,0 +1,422 @@
+//! # Layout Debug Dump - レイアウトツリーのテキストダンプとスナップショット比較
+//!
+//! LayoutRoot → Monitor → Window → ウィジェットのツリーをインデント付きテキストに変換し、
+//! `Arrangement`/`GlobalArrangement`の不整合やレイアウトの退行を差分として読めるようにする。
+//!
+//! # 例
+//!
+//! ```rust,ignore
+//! use wintf::ecs::layout::*;
+//!
+//! let since = layout_change_tick(world);
+//! ecs_world.try_tick_world();
+//! println!("{}", dump_layout_tree(world, root, LayoutDumpOptions::default().dirty_since(since)));
+//!
+//! assert_layout_snapshot(
+//!     &dump_layout_tree(world, root, LayoutDumpOptions::compact()),
+//!     r#"
+//!     Root [LayoutRoot] local=(0, 0) 800x600
+//!       Item local=(0, 0) 100x50
+//!     "#,
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use bevy_ecs::name::Name;
+use bevy_ecs::prelude::*;
+
+use super::taffy::{TaffyComputedLayout, TaffyStyle};
+use super::{
+    Arrangement, ArrangementTreeChanged, BoxStyle, Dimension, GlobalArrangement, LayoutRoot,
+    LengthPercentage, LengthPercentageAuto, Rect,
+};
+use crate::ecs::graphics::format_entity_name;
+use crate::ecs::monitor::Monitor;
+use crate::ecs::window::Window;
+
+/// ダンプに含める項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutDumpOptions {
+    /// BoxStyleの指定済みフィールドの要約
+    pub style: bool,
+    /// GlobalArrangementのスクリーン座標バウンディングボックス
+    pub global: bool,
+    /// 指定した変更ティック以降に変更されたコンポーネント（`layout_change_tick`で取得）
+    pub dirty_since: Option<u32>,
+}
+
+impl Default for LayoutDumpOptions {
+    fn default() -> Self {
+        Self {
+            style: true,
+            global: true,
+            dirty_since: None,
+        }
+    }
+}
+
+impl LayoutDumpOptions {
+    /// ローカル配置のみ（スナップショット比較向け）
+    pub fn compact() -> Self {
+        Self {
+            style: false,
+            global: false,
+            dirty_since: None,
+        }
+    }
+
+    /// 変更フラグの基準ティックを設定
+    pub fn dirty_since(mut self, tick: u32) -> Self {
+        self.dirty_since = Some(tick);
+        self
+    }
+}
+
+/// 現在の変更ティック（`LayoutDumpOptions::dirty_since`の基準に使う）
+pub fn layout_change_tick(world: &World) -> u32 {
+    world.read_change_tick().get()
+}
+
+/// 指定エンティティ以下のツリーをインデント付きテキストに変換
+///
+/// 1行1エンティティで、子は2スペースずつ字下げされる。
+/// `名前 [種別] local=(x, y) WxH scale=S global=[l, t, r, b] style={..} dirty={..}`
+pub fn dump_layout_tree(world: &World, root: Entity, options: LayoutDumpOptions) -> String {
+    let mut out = String::new();
+    dump_entity(world, root, 0, &options, &mut out);
+    out
+}
+
+/// 全LayoutRoot以下のツリーをテキストに変換
+pub fn dump_layout_roots(world: &World, options: LayoutDumpOptions) -> String {
+    let Some(mut roots) = world.try_query_filtered::<Entity, With<LayoutRoot>>() else {
+        return String::new();
+    };
+    let mut roots: Vec<Entity> = roots.iter(world).collect();
+    roots.sort();
+    roots
+        .into_iter()
+        .map(|root| dump_layout_tree(world, root, options))
+        .collect()
+}
+
+/// ダンプ文字列をスナップショットと比較し、不一致なら行差分付きでパニック
+///
+/// `expected`は共通の字下げ・前後の空行・行末空白を無視して比較する
+/// （テスト内で字下げした生文字列リテラルをそのまま渡せる）。
+#[track_caller]
+pub fn assert_layout_snapshot(actual: &str, expected: &str) {
+    let actual = normalize_snapshot(actual);
+    let expected = normalize_snapshot(expected);
+    if actual == expected {
+        return;
+    }
+    panic!(
+        "layout snapshot mismatch (-expected +actual):\n{}",
+        diff_lines(&expected, &actual)
+    );
+}
+
+fn dump_entity(
+    world: &World,
+    entity: Entity,
+    depth: usize,
+    options: &LayoutDumpOptions,
+    out: &mut String,
+) {
+    let entity_ref = world.entity(entity);
+    let _ = write!(
+        out,
+        "{}{}",
+        "  ".repeat(depth),
+        format_entity_name(entity, entity_ref.get::<Name>())
+    );
+
+    if entity_ref.contains::<LayoutRoot>() {
+        out.push_str(" [LayoutRoot]");
+    } else if entity_ref.contains::<Monitor>() {
+        out.push_str(" [Monitor]");
+    } else if entity_ref.contains::<Window>() {
+        out.push_str(" [Window]");
+    }
+
+    if let Some(arrangement) = entity_ref.get::<Arrangement>() {
+        let _ = write!(
+            out,
+            " local=({}, {}) {}x{}",
+            num(arrangement.offset.x),
+            num(arrangement.offset.y),
+            num(arrangement.size.width),
+            num(arrangement.size.height)
+        );
+        if arrangement.scale.x != 1.0 || arrangement.scale.y != 1.0 {
+            if arrangement.scale.x == arrangement.scale.y {
+                let _ = write!(out, " scale={}", num(arrangement.scale.x));
+            } else {
+                let _ = write!(
+                    out,
+                    " scale=({}, {})",
+                    num(arrangement.scale.x),
+                    num(arrangement.scale.y)
+                );
+            }
+        }
+    }
+
+    if options.global {
+        if let Some(global) = entity_ref.get::<GlobalArrangement>() {
+            let b = &global.bounds;
+            let _ = write!(
+                out,
+                " global=[{}, {}, {}, {}]",
+                num(b.left),
+                num(b.top),
+                num(b.right),
+                num(b.bottom)
+            );
+        }
+    }
+
+    if options.style {
+        if let Some(style) = entity_ref.get::<BoxStyle>() {
+            let summary = summarize_box_style(style);
+            if !summary.is_empty() {
+                let _ = write!(out, " style={{{}}}", summary.join(" "));
+            }
+        }
+    }
+
+    if let Some(since) = options.dirty_since {
+        let dirty = dirty_components(world, entity, since);
+        if !dirty.is_empty() {
+            let _ = write!(out, " dirty={{{}}}", dirty.join(", "));
+        }
+    }
+
+    out.push('\n');
+
+    if let Some(children) = entity_ref.get::<Children>() {
+        for child in children.iter() {
+            dump_entity(world, child, depth + 1, options, out);
+        }
+    }
+}
+
+/// `since`以降（`since`を含む）に変更されたレイアウト関連コンポーネント名
+///
+/// ティックの周回を考慮するため、生の値ではなく`Tick::is_newer_than`で比較する。
+fn dirty_components(world: &World, entity: Entity, since: u32) -> Vec<&'static str> {
+    fn changed<T: Component>(world: &World, entity: Entity, since: u32) -> bool {
+        // `since`自身で変更されたものも含めるため、基準を1つ前のティックにする
+        let this_run = world.read_change_tick();
+        let mut last_run = this_run;
+        last_run.set(since.wrapping_sub(1));
+        world
+            .entity(entity)
+            .get_ref::<T>()
+            .is_some_and(|r| r.last_changed().is_newer_than(last_run, this_run))
+    }
+
+    let mut dirty = Vec::new();
+    if changed::<BoxStyle>(world, entity, since) {
+        dirty.push("BoxStyle");
+    }
+    if changed::<TaffyStyle>(world, entity, since) {
+        dirty.push("TaffyStyle");
+    }
+    if changed::<TaffyComputedLayout>(world, entity, since) {
+        dirty.push("TaffyComputedLayout");
+    }
+    if changed::<Arrangement>(world, entity, since) {
+        dirty.push("Arrangement");
+    }
+    if changed::<GlobalArrangement>(world, entity, since) {
+        dirty.push("GlobalArrangement");
+    }
+    if changed::<ArrangementTreeChanged>(world, entity, since) {
+        dirty.push("ArrangementTreeChanged");
+    }
+    dirty
+}
+
+/// BoxStyleの指定済み（Some）フィールドを`name=value`形式で列挙
+fn summarize_box_style(style: &BoxStyle) -> Vec<String> {
+    let mut parts = Vec::new();
+
+    macro_rules! debug_fields {
+        ($($field:ident),* $(,)?) => {
+            $(
+                if let Some(value) = &style.$field {
+                    parts.push(format!(concat!(stringify!($field), "={:?}"), value));
+                }
+            )*
+        };
+    }
+
+    debug_fields!(display, position, flex_direction, logical_flex_direction);
+    for (label, size) in [
+        ("size", &style.size),
+        ("min_size", &style.min_size),
+        ("max_size", &style.max_size),
+    ] {
+        if let Some(size) = size {
+            parts.push(format!(
+                "{}=({}, {})",
+                label,
+                opt_dimension(size.width),
+                opt_dimension(size.height)
+            ));
+        }
+    }
+    if let Some(margin) = &style.margin {
+        parts.push(format!("margin={}", rect(&margin.0, lpa)));
+    }
+    if let Some(padding) = &style.padding {
+        parts.push(format!("padding={}", rect(&padding.0, lp)));
+    }
+    if let Some(inset) = &style.inset {
+        parts.push(format!("inset={}", rect(&inset.0, lpa)));
+    }
+    if let Some(grow) = style.flex_grow {
+        parts.push(format!("grow={}", num(grow)));
+    }
+    if let Some(shrink) = style.flex_shrink {
+        parts.push(format!("shrink={}", num(shrink)));
+    }
+    if let Some(basis) = style.flex_basis {
+        parts.push(format!("basis={}", dimension(basis)));
+    }
+    if let Some(gap) = style.row_gap {
+        parts.push(format!("row_gap={}", lp(gap)));
+    }
+    if let Some(gap) = style.column_gap {
+        parts.push(format!("column_gap={}", lp(gap)));
+    }
+    debug_fields!(
+        justify_content,
+        align_items,
+        align_self,
+        align_content,
+        flex_wrap,
+        aspect_ratio,
+        overflow,
+        logical_size,
+        min_logical_size,
+        max_logical_size,
+        logical_margin,
+        logical_padding,
+        logical_inset,
+        grid_template_rows,
+        grid_template_columns,
+        grid_template_areas,
+        grid_auto_rows,
+        grid_auto_columns,
+        grid_auto_flow,
+        grid_row,
+        grid_column,
+    );
+    parts
+}
+
+/// 小数第2位で丸め、末尾の0を省いた数値表記
+fn num(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    // -0 を 0 に揃える
+    let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+    format!("{}", rounded)
+}
+
+fn dimension(value: Dimension) -> String {
+    match value {
+        Dimension::Auto => "auto".to_string(),
+        Dimension::Px(v) => format!("{}px", num(v)),
+        Dimension::Percent(v) => format!("{}%", num(v)),
+    }
+}
+
+fn opt_dimension(value: Option<Dimension>) -> String {
+    value.map_or_else(|| "-".to_string(), dimension)
+}
+
+fn lpa(value: LengthPercentageAuto) -> String {
+    match value {
+        LengthPercentageAuto::Auto => "auto".to_string(),
+        LengthPercentageAuto::Px(v) => format!("{}px", num(v)),
+        LengthPercentageAuto::Percent(v) => format!("{}%", num(v)),
+    }
+}
+
+fn lp(value: LengthPercentage) -> String {
+    match value {
+        LengthPercentage::Px(v) => format!("{}px", num(v)),
+        LengthPercentage::Percent(v) => format!("{}%", num(v)),
+    }
+}
+
+/// CSSと同じ上・右・下・左の順
+fn rect<T: Copy>(value: &Rect<T>, f: fn(T) -> String) -> String {
+    format!(
+        "({} {} {} {})",
+        f(value.top),
+        f(value.right),
+        f(value.bottom),
+        f(value.left)
+    )
+}
+
+/// 共通の字下げ・前後の空行・行末空白を除去
+fn normalize_snapshot(text: &str) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let start = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(start, |i| i + 1);
+    let lines = &lines[start..end];
+    let indent = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").to_string())
+        .collect()
+}
+
+/// 最長共通部分列による行差分（` `共通、`-`期待値のみ、`+`実際のみ）
+fn diff_lines(expected: &[String], actual: &[String]) -> String {
+    let (n, m) = (expected.len(), actual.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            let _ = writeln!(out, "  {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            let _ = writeln!(out, "+ {}", actual[j]);
+            j += 1;
+        } else {
+            let _ = writeln!(out, "- {}", expected[i]);
+            i += 1;
+        }
+    }
+    out
+}
@@ -325,6 +325,65 @@ pub enum BoxPosition {
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct BoxInset(pub Rect<LengthPercentageAuto>);
 
+/// 表示タイプ（値オブジェクト）
+///
+/// 未指定時はFlex/Gridコンテナープロパティの有無から自動決定される。
+/// 明示指定は自動決定より優先される。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BoxDisplay {
+    /// Flexコンテナー
+    #[default]
+    Flex,
+    /// Gridコンテナー
+    Grid,
+    /// ブロックレイアウト
+    Block,
+    /// レイアウトから除外（サイズ0、子孫も同様）。エンティティは削除されない
+    None,
+}
+
+impl From<BoxDisplay> for taffy::Display {
+    fn from(val: BoxDisplay) -> Self {
+        match val {
+            BoxDisplay::Flex => taffy::Display::Flex,
+            BoxDisplay::Grid => taffy::Display::Grid,
+            BoxDisplay::Block => taffy::Display::Block,
+            BoxDisplay::None => taffy::Display::None,
+        }
+    }
+}
+
+/// `BoxDisplay::None` のエンティティとその子孫に付与される非表示マーカー
+///
+/// `update_display_hidden_system` が維持する。描画（`render_surface`）と
+/// ヒットテストはこのマーカーを持つエンティティを対象外とする。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[component(storage = "SparseSet")]
+pub struct DisplayHidden;
+
+/// はみ出し時の挙動（値オブジェクト、軸ごと）
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoxOverflow {
+    pub x: Overflow,
+    pub y: Overflow,
+}
+
+impl BoxOverflow {
+    /// 両軸に同じ挙動を指定
+    pub const fn all(overflow: Overflow) -> Self {
+        Self {
+            x: overflow,
+            y: overflow,
+        }
+    }
+}
+
+impl From<BoxOverflow> for taffy::Point<taffy::Overflow> {
+    fn from(val: BoxOverflow) -> Self {
+        taffy::Point { x: val.x, y: val.y }
+    }
+}
+
 /// Flexコンテナ（値オブジェクト）
 ///
 /// 注: BoxStyleのflex_direction, justify_content, align_itemsを直接使用することを推奨。
@@ -370,7 +429,474 @@ impl Default for FlexItem {
 }
 
 // taffy の Flex 関連型を re-export（テストと外部利用のため）
-pub use taffy::{AlignContent, AlignItems, AlignSelf, FlexDirection, JustifyContent};
+pub use taffy::{
+    AlignContent, AlignItems, AlignSelf, FlexDirection, FlexWrap, JustifyContent, Overflow,
+};
+
+// ===== Grid系の値オブジェクト =====
+
+/// グリッドトラックのサイズ指定の基本単位（`minmax()` の引数として使用）
+///
+/// パーセント値は**0.0～100.0**の範囲で指定します。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackBreadth {
+    /// ピクセル値
+    Px(f32),
+    /// パーセント値（0.0～100.0）
+    Percent(f32),
+    /// 残り領域の比率（`fr`）。最小値側では `Auto` として扱う（CSS仕様）
+    Fr(f32),
+    /// 自動
+    Auto,
+    /// 最小コンテンツサイズ
+    MinContent,
+    /// 最大コンテンツサイズ
+    MaxContent,
+}
+
+impl From<TrackBreadth> for taffy::MinTrackSizingFunction {
+    fn from(val: TrackBreadth) -> Self {
+        use taffy::style_helpers::*;
+        match val {
+            TrackBreadth::Px(v) => length(v),
+            TrackBreadth::Percent(v) => percent(v / 100.0),
+            TrackBreadth::Fr(_) | TrackBreadth::Auto => auto(),
+            TrackBreadth::MinContent => min_content(),
+            TrackBreadth::MaxContent => max_content(),
+        }
+    }
+}
+
+impl From<TrackBreadth> for taffy::MaxTrackSizingFunction {
+    fn from(val: TrackBreadth) -> Self {
+        use taffy::style_helpers::*;
+        match val {
+            TrackBreadth::Px(v) => length(v),
+            TrackBreadth::Percent(v) => percent(v / 100.0),
+            TrackBreadth::Fr(v) => fr(v),
+            TrackBreadth::Auto => auto(),
+            TrackBreadth::MinContent => min_content(),
+            TrackBreadth::MaxContent => max_content(),
+        }
+    }
+}
+
+/// グリッドトラック1本のサイズ指定
+///
+/// # 例
+///
+/// ```ignore
+/// use wintf::ecs::layout::{GridTrack, TrackBreadth};
+///
+/// let sidebar = GridTrack::Px(200.0);
+/// let main = GridTrack::Fr(1.0);
+/// let cell = GridTrack::MinMax(TrackBreadth::Px(120.0), TrackBreadth::Fr(1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+    /// ピクセル値
+    Px(f32),
+    /// パーセント値（0.0～100.0）
+    Percent(f32),
+    /// 残り領域の比率（`fr`）
+    Fr(f32),
+    /// 自動
+    Auto,
+    /// 最小コンテンツサイズ
+    MinContent,
+    /// 最大コンテンツサイズ
+    MaxContent,
+    /// `fit-content(limit)`
+    FitContent(LengthPercentage),
+    /// `minmax(min, max)`
+    MinMax(TrackBreadth, TrackBreadth),
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl From<GridTrack> for taffy::TrackSizingFunction {
+    fn from(val: GridTrack) -> Self {
+        use taffy::style_helpers::*;
+        match val {
+            GridTrack::Px(v) => length(v),
+            GridTrack::Percent(v) => percent(v / 100.0),
+            GridTrack::Fr(v) => fr(v),
+            GridTrack::Auto => auto(),
+            GridTrack::MinContent => min_content(),
+            GridTrack::MaxContent => max_content(),
+            GridTrack::FitContent(limit) => fit_content(limit.into()),
+            GridTrack::MinMax(min, max) => minmax(min.into(), max.into()),
+        }
+    }
+}
+
+/// `repeat()` の繰り返し回数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridRepeat {
+    /// 固定回数
+    Count(u16),
+    /// `auto-fill`: コンテナに収まるだけ繰り返す（空トラックも残す）
+    AutoFill,
+    /// `auto-fit`: `auto-fill` と同様だが空トラックを潰す
+    AutoFit,
+}
+
+impl From<GridRepeat> for taffy::RepetitionCount {
+    fn from(val: GridRepeat) -> Self {
+        match val {
+            GridRepeat::Count(n) => taffy::RepetitionCount::Count(n),
+            GridRepeat::AutoFill => taffy::RepetitionCount::AutoFill,
+            GridRepeat::AutoFit => taffy::RepetitionCount::AutoFit,
+        }
+    }
+}
+
+/// `grid_template_rows` / `grid_template_columns` の1要素
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridTemplate {
+    /// 単一トラック
+    Track(GridTrack),
+    /// `repeat(count, tracks...)`
+    Repeat(GridRepeat, Vec<GridTrack>),
+}
+
+impl From<GridTrack> for GridTemplate {
+    fn from(track: GridTrack) -> Self {
+        Self::Track(track)
+    }
+}
+
+impl From<&GridTemplate> for taffy::GridTemplateComponent<String> {
+    fn from(val: &GridTemplate) -> Self {
+        match val {
+            GridTemplate::Track(track) => taffy::GridTemplateComponent::Single((*track).into()),
+            GridTemplate::Repeat(count, tracks) => taffy::style_helpers::repeat(
+                taffy::RepetitionCount::from(*count),
+                tracks.iter().map(|t| (*t).into()).collect(),
+            ),
+        }
+    }
+}
+
+/// グリッド線の指定（`grid_row` / `grid_column` の start / end）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum GridLine {
+    /// 自動配置
+    #[default]
+    Auto,
+    /// 線番号（1始まり、負数は末尾から）
+    Line(i16),
+    /// トラック数でのスパン
+    Span(u16),
+    /// 名前付き線、または `grid_template_areas` のエリア名
+    /// （エリア名は start 側で `{name}-start`、end 側で `{name}-end` に解決される）
+    Named(String),
+}
+
+impl From<&GridLine> for taffy::GridPlacement<String> {
+    fn from(val: &GridLine) -> Self {
+        match val {
+            GridLine::Auto => taffy::GridPlacement::Auto,
+            GridLine::Line(n) => taffy::style_helpers::line(*n),
+            GridLine::Span(n) => taffy::GridPlacement::Span(*n),
+            GridLine::Named(name) => taffy::GridPlacement::NamedLine(name.clone(), 0),
+        }
+    }
+}
+
+/// グリッドアイテムの行または列方向の配置（CSSの `grid-row: start / end` に相当）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+impl GridPlacement {
+    /// 線 `start` から線 `end` まで（`start / end`）
+    pub const fn lines(start: i16, end: i16) -> Self {
+        Self {
+            start: GridLine::Line(start),
+            end: GridLine::Line(end),
+        }
+    }
+
+    /// 線 `start` から `count` トラック分（`start / span count`）
+    pub const fn span(start: i16, count: u16) -> Self {
+        Self {
+            start: GridLine::Line(start),
+            end: GridLine::Span(count),
+        }
+    }
+
+    /// 名前付きエリアに配置（`grid-area: name` の行/列成分）
+    pub fn area(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            start: GridLine::Named(name.clone()),
+            end: GridLine::Named(name),
+        }
+    }
+}
+
+impl From<&GridPlacement> for taffy::Line<taffy::GridPlacement<String>> {
+    fn from(val: &GridPlacement) -> Self {
+        taffy::Line {
+            start: (&val.start).into(),
+            end: (&val.end).into(),
+        }
+    }
+}
+
+/// グリッドの自動配置方向（`grid-auto-flow`）
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GridAutoFlow {
+    /// 行方向に埋める
+    #[default]
+    Row,
+    /// 列方向に埋める
+    Column,
+    /// 行方向 + 隙間を詰める
+    RowDense,
+    /// 列方向 + 隙間を詰める
+    ColumnDense,
+}
+
+impl From<GridAutoFlow> for taffy::GridAutoFlow {
+    fn from(val: GridAutoFlow) -> Self {
+        match val {
+            GridAutoFlow::Row => taffy::GridAutoFlow::Row,
+            GridAutoFlow::Column => taffy::GridAutoFlow::Column,
+            GridAutoFlow::RowDense => taffy::GridAutoFlow::RowDense,
+            GridAutoFlow::ColumnDense => taffy::GridAutoFlow::ColumnDense,
+        }
+    }
+}
+
+/// `grid_template_areas` の行文字列（例: `["header header", "side main"]`）を
+/// taffy のエリア定義へ変換する。`.` は空セル。
+///
+/// 長方形にならないエリアや列数が揃わない行は無視して警告を出す（CSSでは宣言全体が無効）。
+pub fn parse_grid_template_areas(rows: &[String]) -> Vec<taffy::GridTemplateArea<String>> {
+    let cells: Vec<Vec<&str>> = rows
+        .iter()
+        .map(|r| r.split_whitespace().collect())
+        .collect();
+    let columns = cells.first().map_or(0, |r| r.len());
+    if cells.iter().any(|r| r.len() != columns) {
+        tracing::warn!(
+            ?rows,
+            "grid_template_areas: rows have different column counts"
+        );
+        return Vec::new();
+    }
+
+    // 名前ごとの外接矩形（0始まりのセル座標）
+    let mut bounds: Vec<(&str, usize, usize, usize, usize)> = Vec::new();
+    for (r, row) in cells.iter().enumerate() {
+        for (c, name) in row.iter().enumerate() {
+            if name.chars().all(|ch| ch == '.') {
+                continue;
+            }
+            match bounds.iter_mut().find(|b| b.0 == *name) {
+                Some(b) => {
+                    b.1 = b.1.min(r);
+                    b.2 = b.2.max(r);
+                    b.3 = b.3.min(c);
+                    b.4 = b.4.max(c);
+                }
+                None => bounds.push((name, r, r, c, c)),
+            }
+        }
+    }
+
+    bounds
+        .into_iter()
+        .filter(|&(name, r0, r1, c0, c1)| {
+            let rectangular = (r0..=r1).all(|r| (c0..=c1).all(|c| cells[r][c] == name));
+            if !rectangular {
+                tracing::warn!(area = name, "grid_template_areas: area is not rectangular");
+            }
+            rectangular
+        })
+        .map(|(name, r0, r1, c0, c1)| taffy::GridTemplateArea {
+            name: name.to_string(),
+            row_start: r0 as u16 + 1,
+            row_end: r1 as u16 + 2,
+            column_start: c0 as u16 + 1,
+            column_end: c1 as u16 + 2,
+        })
+        .collect()
+}
+
+// ===== 書字方向と論理プロパティ =====
+
+/// 書字方向（CSS `writing-mode`）
+///
+/// 付与したエンティティとその子孫（別の`WritingMode`を持つエンティティまで）に継承され、
+/// `BoxStyle`の論理プロパティを物理プロパティへ変換する際の基準となる。
+/// 未指定のツリーは`HorizontalTb`として扱う。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WritingMode {
+    /// 横書き（インライン: 左→右、ブロック: 上→下）
+    #[default]
+    HorizontalTb,
+    /// 縦書き（インライン: 上→下、ブロック: 右→左）
+    VerticalRl,
+    /// 縦書き（インライン: 上→下、ブロック: 左→右）
+    VerticalLr,
+}
+
+impl WritingMode {
+    /// インライン方向が垂直か
+    pub fn is_vertical(self) -> bool {
+        !matches!(self, WritingMode::HorizontalTb)
+    }
+
+    /// 論理サイズを物理サイズへ変換
+    pub fn to_physical_size(self, size: LogicalSize) -> BoxSize {
+        if self.is_vertical() {
+            BoxSize {
+                width: size.block_size,
+                height: size.inline_size,
+            }
+        } else {
+            BoxSize {
+                width: size.inline_size,
+                height: size.block_size,
+            }
+        }
+    }
+
+    /// 論理矩形（block/inline の start/end）を物理矩形へ変換（未指定の辺は`None`）
+    pub fn to_physical_rect<T>(self, rect: LogicalRect<T>) -> Rect<Option<T>> {
+        match self {
+            WritingMode::HorizontalTb => Rect {
+                left: rect.inline_start,
+                right: rect.inline_end,
+                top: rect.block_start,
+                bottom: rect.block_end,
+            },
+            WritingMode::VerticalRl => Rect {
+                left: rect.block_end,
+                right: rect.block_start,
+                top: rect.inline_start,
+                bottom: rect.inline_end,
+            },
+            WritingMode::VerticalLr => Rect {
+                left: rect.block_start,
+                right: rect.block_end,
+                top: rect.inline_start,
+                bottom: rect.inline_end,
+            },
+        }
+    }
+
+    /// 論理Flex方向を物理Flex方向へ変換
+    pub fn to_physical_flex_direction(self, direction: LogicalFlexDirection) -> FlexDirection {
+        use LogicalFlexDirection::*;
+        match (self, direction) {
+            (WritingMode::HorizontalTb, Inline) => FlexDirection::Row,
+            (WritingMode::HorizontalTb, InlineReverse) => FlexDirection::RowReverse,
+            (WritingMode::HorizontalTb, Block) => FlexDirection::Column,
+            (WritingMode::HorizontalTb, BlockReverse) => FlexDirection::ColumnReverse,
+            (_, Inline) => FlexDirection::Column,
+            (_, InlineReverse) => FlexDirection::ColumnReverse,
+            (WritingMode::VerticalRl, Block) => FlexDirection::RowReverse,
+            (WritingMode::VerticalRl, BlockReverse) => FlexDirection::Row,
+            (WritingMode::VerticalLr, Block) => FlexDirection::Row,
+            (WritingMode::VerticalLr, BlockReverse) => FlexDirection::RowReverse,
+        }
+    }
+}
+
+/// 論理サイズ（値オブジェクト）
+///
+/// インライン方向は文字の進む方向（横書きは幅、縦書きは高さ）。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LogicalSize {
+    pub inline_size: Option<Dimension>,
+    pub block_size: Option<Dimension>,
+}
+
+/// 論理方向の4辺（値オブジェクト）
+///
+/// `margin-block-start` などCSS論理プロパティに対応する。
+/// `None`の辺は物理プロパティ（未指定ならtaffyデフォルト）を使う。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogicalRect<T> {
+    pub block_start: Option<T>,
+    pub block_end: Option<T>,
+    pub inline_start: Option<T>,
+    pub inline_end: Option<T>,
+}
+
+impl<T> Default for LogicalRect<T> {
+    fn default() -> Self {
+        Self {
+            block_start: None,
+            block_end: None,
+            inline_start: None,
+            inline_end: None,
+        }
+    }
+}
+
+/// 論理Flex方向（値オブジェクト）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogicalFlexDirection {
+    /// インライン方向（横書きは`Row`、縦書きは`Column`）
+    #[default]
+    Inline,
+    /// インライン方向の逆順
+    InlineReverse,
+    /// ブロック方向（横書きは`Column`、vertical-rlは`RowReverse`）
+    Block,
+    /// ブロック方向の逆順
+    BlockReverse,
+}
+
+/// 物理サイズに論理サイズを重ねる（論理指定のある軸を優先）
+fn overlay_logical_size(
+    physical: Option<BoxSize>,
+    logical: Option<LogicalSize>,
+    writing_mode: WritingMode,
+) -> Option<BoxSize> {
+    let Some(logical) = logical else {
+        return physical;
+    };
+    let logical = writing_mode.to_physical_size(logical);
+    let physical = physical.unwrap_or_default();
+    Some(BoxSize {
+        width: logical.width.or(physical.width),
+        height: logical.height.or(physical.height),
+    })
+}
+
+/// 物理矩形に論理矩形を重ねる（論理指定のある辺を優先）
+///
+/// 物理矩形が未指定の場合、論理指定のない辺は`fallback`（taffyデフォルト）になる。
+fn overlay_logical_rect<T: Copy>(
+    physical: Option<Rect<T>>,
+    logical: Option<LogicalRect<T>>,
+    writing_mode: WritingMode,
+    fallback: Rect<T>,
+) -> Option<Rect<T>> {
+    let Some(logical) = logical else {
+        return physical;
+    };
+    let logical = writing_mode.to_physical_rect(logical);
+    let physical = physical.unwrap_or(fallback);
+    Some(Rect {
+        left: logical.left.unwrap_or(physical.left),
+        right: logical.right.unwrap_or(physical.right),
+        top: logical.top.unwrap_or(physical.top),
+        bottom: logical.bottom.unwrap_or(physical.bottom),
+    })
+}
 
 // ===== BoxStyle統合コンポーネント =====
 
@@ -381,11 +907,20 @@ pub use taffy::{AlignContent, AlignItems, AlignSelf, FlexDirection, JustifyConte
 ///
 /// # 設計意図
 ///
-/// - Box系5種（size, margin, padding, position, inset）をOption型でネスト構造として含める
-/// - Flex系7種（flex_direction, justify_content, align_items, flex_grow, flex_shrink, flex_basis, align_self）
+/// - Box系（size, margin, padding, position, inset, display, aspect_ratio, overflow）を
+///   Option型でネスト構造として含める
+/// - Flex系（flex_direction, justify_content, align_items, flex_grow, flex_shrink, flex_basis,
+///   align_self, flex_wrap, align_content, row_gap, column_gap）
 ///   をフラットなOption型フィールドとして含める（taffyのStyle構造体と同様のフラット設計）
+/// - Grid系8種（grid_template_rows/columns/areas, grid_auto_rows/columns, grid_auto_flow,
+///   grid_row, grid_column）もフラットなOption型フィールドとして含める
+/// - 論理プロパティ（logical_size, logical_margin, logical_padding, logical_inset,
+///   logical_flex_direction）は継承された[`WritingMode`]で物理プロパティへ変換し、
+///   同じ軸・同じ辺の物理プロパティより優先する
 /// - `None`フィールドはtaffyデフォルト値にマッピング
 ///
+/// Gridのトラックリスト（`Vec`）を含むため`Copy`ではない。複製は`clone()`を使う。
+///
 /// # 使用例
 ///
 /// ```rust,ignore
@@ -418,7 +953,7 @@ pub use taffy::{AlignContent, AlignItems, AlignSelf, FlexDirection, JustifyConte
 ///     },
 /// ));
 /// ```
-#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Component, Debug, Clone, PartialEq, Default)]
 pub struct BoxStyle {
     // === Box系プロパティ（ネスト構造） ===
     /// サイズ（width, height）
@@ -435,6 +970,28 @@ pub struct BoxStyle {
     pub position: Option<BoxPosition>,
     /// インセット（絶対配置時の座標）
     pub inset: Option<BoxInset>,
+    /// 表示タイプ（`BoxDisplay::None` でレイアウトから除外）
+    pub display: Option<BoxDisplay>,
+    /// アスペクト比（幅 / 高さ）。一方の軸のみ確定している場合に他方を決定する
+    pub aspect_ratio: Option<f32>,
+    /// はみ出し時の挙動
+    pub overflow: Option<BoxOverflow>,
+
+    // === 論理プロパティ（書字方向で物理プロパティへ変換） ===
+    /// 論理サイズ（inline_size, block_size）
+    pub logical_size: Option<LogicalSize>,
+    /// 論理最小サイズ
+    pub min_logical_size: Option<LogicalSize>,
+    /// 論理最大サイズ
+    pub max_logical_size: Option<LogicalSize>,
+    /// 論理マージン（margin-block-start 等）
+    pub logical_margin: Option<LogicalRect<LengthPercentageAuto>>,
+    /// 論理パディング（padding-inline-start 等）
+    pub logical_padding: Option<LogicalRect<LengthPercentage>>,
+    /// 論理インセット（inset-block-start 等）
+    pub logical_inset: Option<LogicalRect<LengthPercentageAuto>>,
+    /// 論理Flex方向（指定時は`flex_direction`より優先）
+    pub logical_flex_direction: Option<LogicalFlexDirection>,
 
     // === Flex系プロパティ（フラット構造） ===
     /// Flexコンテナーの主軸方向
@@ -453,6 +1010,32 @@ pub struct BoxStyle {
     pub flex_basis: Option<Dimension>,
     /// 自身の交差軸配置（親のalign_itemsを上書き）
     pub align_self: Option<AlignSelf>,
+    /// 折り返し（タグクラウド・アイコン並び等）
+    pub flex_wrap: Option<FlexWrap>,
+    /// 複数行/複数トラック時の交差軸方向の行配置（Flex/Grid共通）
+    pub align_content: Option<AlignContent>,
+    /// 行間の間隔（Flex/Grid共通）
+    pub row_gap: Option<LengthPercentage>,
+    /// 列間の間隔（Flex/Grid共通）
+    pub column_gap: Option<LengthPercentage>,
+
+    // === Grid系プロパティ（フラット構造） ===
+    /// 明示的な行トラック定義
+    pub grid_template_rows: Option<Vec<GridTemplate>>,
+    /// 明示的な列トラック定義
+    pub grid_template_columns: Option<Vec<GridTemplate>>,
+    /// 名前付きエリア（行ごとの文字列、例: `"header header"`）
+    pub grid_template_areas: Option<Vec<String>>,
+    /// 暗黙的な行トラックのサイズ
+    pub grid_auto_rows: Option<Vec<GridTrack>>,
+    /// 暗黙的な列トラックのサイズ
+    pub grid_auto_columns: Option<Vec<GridTrack>>,
+    /// 自動配置方向
+    pub grid_auto_flow: Option<GridAutoFlow>,
+    /// グリッドアイテムの行方向配置
+    pub grid_row: Option<GridPlacement>,
+    /// グリッドアイテムの列方向配置
+    pub grid_column: Option<GridPlacement>,
 }
 
 impl BoxStyle {
@@ -460,15 +1043,63 @@ impl BoxStyle {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-/// BoxStyleからtaffy::Styleへの変換
-impl From<&BoxStyle> for taffy::Style {
-    fn from(style: &BoxStyle) -> Self {
+    /// レイアウト境界か
+    ///
+    /// 絶対配置かつ幅・高さともピクセル指定のボックス（Window・Monitor等）は、
+    /// 内部の変更が自身のサイズ・位置に影響しないため、サブツリー単位で再計算できる。
+    pub fn is_layout_boundary(&self) -> bool {
+        self.position == Some(BoxPosition::Absolute)
+            && self.display != Some(BoxDisplay::None)
+            && self.logical_size.is_none()
+            && matches!(
+                self.size,
+                Some(BoxSize {
+                    width: Some(Dimension::Px(_)),
+                    height: Some(Dimension::Px(_)),
+                })
+            )
+    }
+
+    /// 書字方向を指定してtaffy::Styleへ変換
+    ///
+    /// 論理プロパティは`writing_mode`で物理プロパティへ変換される。
+    pub fn to_taffy_style(&self, writing_mode: WritingMode) -> taffy::Style {
+        let style = self;
         let mut taffy_style = taffy::Style::default();
 
+        // 論理プロパティを物理プロパティへ解決（論理指定を優先）
+        let size = overlay_logical_size(style.size, style.logical_size, writing_mode);
+        let min_size = overlay_logical_size(style.min_size, style.min_logical_size, writing_mode);
+        let max_size = overlay_logical_size(style.max_size, style.max_logical_size, writing_mode);
+        let margin = overlay_logical_rect(
+            style.margin.map(|m| m.0),
+            style.logical_margin,
+            writing_mode,
+            Rect::zero(),
+        )
+        .map(BoxMargin);
+        let padding = overlay_logical_rect(
+            style.padding.map(|p| p.0),
+            style.logical_padding,
+            writing_mode,
+            Rect::zero(),
+        )
+        .map(BoxPadding);
+        let inset = overlay_logical_rect(
+            style.inset.map(|i| i.0),
+            style.logical_inset,
+            writing_mode,
+            Rect::auto(),
+        )
+        .map(BoxInset);
+        let flex_direction = style
+            .logical_flex_direction
+            .map(|d| writing_mode.to_physical_flex_direction(d))
+            .or(style.flex_direction);
+
         // Box系プロパティ変換
-        if let Some(size) = &style.size {
+        if let Some(size) = &size {
             if let Some(w) = size.width {
                 taffy_style.size.width = w.into();
             }
@@ -476,7 +1107,7 @@ impl From<&BoxStyle> for taffy::Style {
                 taffy_style.size.height = h.into();
             }
         }
-        if let Some(min_size) = &style.min_size {
+        if let Some(min_size) = &min_size {
             if let Some(w) = min_size.width {
                 taffy_style.min_size.width = w.into();
             }
@@ -484,7 +1115,7 @@ impl From<&BoxStyle> for taffy::Style {
                 taffy_style.min_size.height = h.into();
             }
         }
-        if let Some(max_size) = &style.max_size {
+        if let Some(max_size) = &max_size {
             if let Some(w) = max_size.width {
                 taffy_style.max_size.width = w.into();
             }
@@ -492,10 +1123,10 @@ impl From<&BoxStyle> for taffy::Style {
                 taffy_style.max_size.height = h.into();
             }
         }
-        if let Some(margin) = &style.margin {
+        if let Some(margin) = &margin {
             taffy_style.margin = margin.0.into();
         }
-        if let Some(padding) = &style.padding {
+        if let Some(padding) = &padding {
             taffy_style.padding = padding.0.into();
         }
         if let Some(position) = &style.position {
@@ -504,19 +1135,26 @@ impl From<&BoxStyle> for taffy::Style {
                 BoxPosition::Absolute => taffy::Position::Absolute,
             };
         }
-        if let Some(inset) = &style.inset {
+        if let Some(inset) = &inset {
             taffy_style.inset = inset.0.into();
         }
+        if let Some(ratio) = style.aspect_ratio {
+            taffy_style.aspect_ratio = Some(ratio);
+        }
+        if let Some(overflow) = style.overflow {
+            taffy_style.overflow = overflow.into();
+        }
 
         // Flex系プロパティ変換
         // コンテナープロパティ設定時にdisplay: Flexを自動設定
-        if style.flex_direction.is_some()
+        if flex_direction.is_some()
             || style.justify_content.is_some()
             || style.align_items.is_some()
+            || style.flex_wrap.is_some()
         {
             taffy_style.display = taffy::Display::Flex;
         }
-        if let Some(dir) = style.flex_direction {
+        if let Some(dir) = flex_direction {
             taffy_style.flex_direction = dir;
         }
         if let Some(jc) = style.justify_content {
@@ -536,7 +1174,67 @@ impl From<&BoxStyle> for taffy::Style {
         if let Some(align_self) = style.align_self {
             taffy_style.align_self = Some(align_self);
         }
+        if let Some(wrap) = style.flex_wrap {
+            taffy_style.flex_wrap = wrap;
+        }
+        if let Some(ac) = style.align_content {
+            taffy_style.align_content = Some(ac);
+        }
+        if let Some(gap) = style.row_gap {
+            taffy_style.gap.height = gap.into();
+        }
+        if let Some(gap) = style.column_gap {
+            taffy_style.gap.width = gap.into();
+        }
+
+        // Grid系プロパティ変換
+        // コンテナープロパティ設定時にdisplay: Gridを自動設定（Flexより優先）
+        if style.grid_template_rows.is_some()
+            || style.grid_template_columns.is_some()
+            || style.grid_template_areas.is_some()
+            || style.grid_auto_rows.is_some()
+            || style.grid_auto_columns.is_some()
+            || style.grid_auto_flow.is_some()
+        {
+            taffy_style.display = taffy::Display::Grid;
+        }
+        if let Some(rows) = &style.grid_template_rows {
+            taffy_style.grid_template_rows = rows.iter().map(Into::into).collect();
+        }
+        if let Some(columns) = &style.grid_template_columns {
+            taffy_style.grid_template_columns = columns.iter().map(Into::into).collect();
+        }
+        if let Some(areas) = &style.grid_template_areas {
+            taffy_style.grid_template_areas = parse_grid_template_areas(areas);
+        }
+        if let Some(rows) = &style.grid_auto_rows {
+            taffy_style.grid_auto_rows = rows.iter().map(|t| (*t).into()).collect();
+        }
+        if let Some(columns) = &style.grid_auto_columns {
+            taffy_style.grid_auto_columns = columns.iter().map(|t| (*t).into()).collect();
+        }
+        if let Some(flow) = style.grid_auto_flow {
+            taffy_style.grid_auto_flow = flow.into();
+        }
+        if let Some(row) = &style.grid_row {
+            taffy_style.grid_row = row.into();
+        }
+        if let Some(column) = &style.grid_column {
+            taffy_style.grid_column = column.into();
+        }
+
+        // 明示的なdisplay指定は自動決定より優先
+        if let Some(display) = style.display {
+            taffy_style.display = display.into();
+        }
 
         taffy_style
     }
 }
+
+/// BoxStyleからtaffy::Styleへの変換（横書き）
+impl From<&BoxStyle> for taffy::Style {
+    fn from(style: &BoxStyle) -> Self {
+        style.to_taffy_style(WritingMode::HorizontalTb)
+    }
+}
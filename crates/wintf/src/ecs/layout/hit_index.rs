@@ -0,0 +1,409 @@
+//! # Hit Test Index - ヒットテスト用空間インデックス
+//!
+//! `hit_test`の線形走査（全子孫を描画順の逆順で判定）を、
+//! 一様グリッドで候補を絞り込んでから判定する方式に置き換えるためのリソース。
+//!
+//! ## 設計
+//! - 各エンティティのヒット領域のスクリーンAABB（`Transform`を含む）を、
+//!   固定サイズのセルに登録する
+//! - セル内の候補は走査順（`stacking_order`の逆順、最前面が先頭）でソート済みに保ち、
+//!   祖先を辿ってルートのサブツリーに属するかを判定する
+//! - `update_hit_test_index_system`がPostLayoutで変更分のみ更新する
+//!   （`GlobalArrangement`/`Transform`の変更 → AABB再計算、階層・`ZIndex`の変更 → 走査順の再計算）
+//!
+//! インデックスは最後の PostLayout 時点の状態を反映する。
+//! 走査順に含まれないルート（インデックス更新前に生成されたエンティティ等）は
+//! `hit_test`が線形走査にフォールバックする。
+
+use std::collections::{HashMap, HashSet};
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemState;
+
+use super::{GlobalArrangement, PhysicalPoint, ZIndex, global_hit_matrix, stacking_order};
+use crate::ecs::transform::Transform;
+
+/// デフォルトのセルサイズ（物理ピクセル）
+pub const DEFAULT_HIT_INDEX_CELL_SIZE: f32 = 256.0;
+
+/// 1エンティティが登録できるセル数の上限（超える場合は全クエリの候補として扱う）
+const MAX_CELLS_PER_ENTRY: i64 = 1024;
+
+/// 浮動小数点誤差を吸収するための AABB の拡張量（物理ピクセル）
+const AABB_MARGIN: f32 = 0.5;
+
+/// 登録先のセル範囲（両端を含む）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellRange {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+}
+
+impl CellRange {
+    fn cells(self) -> impl Iterator<Item = (i32, i32)> {
+        (self.min_y..=self.max_y).flat_map(move |y| (self.min_x..=self.max_x).map(move |x| (x, y)))
+    }
+}
+
+/// エンティティの登録状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Cells(CellRange),
+    /// 巨大・非有限な AABB（`oversized`に登録）
+    Oversized,
+}
+
+/// `entity`が`root`自身またはその子孫か（`ChildOf`をたどる）
+pub fn is_self_or_descendant(world: &World, root: Entity, entity: Entity) -> bool {
+    let mut current = entity;
+    loop {
+        if current == root {
+            return true;
+        }
+        match world.get::<ChildOf>(current) {
+            Some(child_of) => current = child_of.parent(),
+            None => return false,
+        }
+    }
+}
+
+// ============================================================================
+// HitTestIndex
+// ============================================================================
+
+/// ヒットテスト用の一様グリッド空間インデックス
+///
+/// `EcsWorld`ではデフォルトで挿入される。リソースを削除すると`hit_test`は線形走査になる。
+#[derive(Resource, Debug)]
+pub struct HitTestIndex {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<Entity>>,
+    oversized: Vec<Entity>,
+    placements: HashMap<Entity, Placement>,
+    /// 走査順（最前面が0）
+    orders: HashMap<Entity, u32>,
+    /// 走査順の再計算の累計回数
+    order_rebuilds: u64,
+}
+
+impl Default for HitTestIndex {
+    fn default() -> Self {
+        Self::with_cell_size(DEFAULT_HIT_INDEX_CELL_SIZE)
+    }
+}
+
+impl HitTestIndex {
+    /// セルサイズ（物理ピクセル）を指定して作成
+    pub fn with_cell_size(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            placements: HashMap::new(),
+            orders: HashMap::new(),
+            order_rebuilds: 0,
+        }
+    }
+
+    /// セルサイズ（物理ピクセル）
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// 登録済みエンティティ数
+    pub fn len(&self) -> usize {
+        self.placements.len()
+    }
+
+    /// 登録済みエンティティがないか
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty()
+    }
+
+    /// 走査順（全ツリーの描画順と各セルの並べ替え）を再計算した累計回数
+    pub fn order_rebuilds(&self) -> u64 {
+        self.order_rebuilds
+    }
+
+    /// ルート配下で点を含み得るエンティティを前面から順に返す
+    ///
+    /// `root`が走査順に含まれない場合は `None`（呼び出し側で線形走査にフォールバックする）。
+    /// 返す候補はAABBによる絞り込みのみで、実際の判定は `hit_test_entity` で行う。
+    ///
+    /// トップレイヤーを含めても描画順は各サブツリー内で保たれるため、
+    /// 走査順をルートのサブツリーに制限すればルートからの描画順と一致する。
+    pub fn candidates<'a>(
+        &'a self,
+        world: &'a World,
+        root: Entity,
+        point: PhysicalPoint,
+    ) -> Option<impl Iterator<Item = Entity> + 'a> {
+        if !self.orders.contains_key(&root) {
+            return None;
+        }
+        let cell = self
+            .cells
+            .get(&self.cell_of(point.x, point.y))
+            .map_or(&[][..], Vec::as_slice);
+
+        let mut cell = cell.iter().copied().peekable();
+        let mut oversized = self.oversized.iter().copied().peekable();
+        // セル内と巨大エントリの2つのソート済み列を走査順でマージ
+        let merged = std::iter::from_fn(move || match (cell.peek(), oversized.peek()) {
+            (Some(&a), Some(&b)) => {
+                if self.order_key(a) <= self.order_key(b) {
+                    cell.next()
+                } else {
+                    oversized.next()
+                }
+            }
+            (Some(_), None) => cell.next(),
+            (None, _) => oversized.next(),
+        });
+
+        Some(merged.filter(move |&entity| is_self_or_descendant(world, root, entity)))
+    }
+
+    fn cell_of(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn order_key(&self, entity: Entity) -> u32 {
+        self.orders.get(&entity).copied().unwrap_or(u32::MAX)
+    }
+
+    /// 登録を削除
+    fn remove(&mut self, entity: Entity) {
+        match self.placements.remove(&entity) {
+            Some(Placement::Cells(range)) => {
+                for cell in range.cells() {
+                    if let Some(entities) = self.cells.get_mut(&cell) {
+                        entities.retain(|&e| e != entity);
+                        if entities.is_empty() {
+                            self.cells.remove(&cell);
+                        }
+                    }
+                }
+            }
+            Some(Placement::Oversized) => self.oversized.retain(|&e| e != entity),
+            None => {}
+        }
+    }
+
+    /// AABB（left, top, right, bottom）で登録（既存の登録は置き換える）
+    fn insert(&mut self, entity: Entity, aabb: [f32; 4]) {
+        self.remove(entity);
+
+        let placement = self.placement_for(aabb);
+        let order = self.order_key(entity);
+        let orders = &self.orders;
+        let key = |e: &Entity| orders.get(e).copied().unwrap_or(u32::MAX);
+        match placement {
+            Placement::Cells(range) => {
+                for cell in range.cells() {
+                    let entities = self.cells.entry(cell).or_default();
+                    let index = entities.partition_point(|e| key(e) <= order);
+                    entities.insert(index, entity);
+                }
+            }
+            Placement::Oversized => {
+                let index = self.oversized.partition_point(|e| key(e) <= order);
+                self.oversized.insert(index, entity);
+            }
+        }
+        self.placements.insert(entity, placement);
+    }
+
+    fn placement_for(&self, [left, top, right, bottom]: [f32; 4]) -> Placement {
+        if ![left, top, right, bottom].iter().all(|v| v.is_finite()) {
+            return Placement::Oversized;
+        }
+        let (min_x, min_y) = self.cell_of(left - AABB_MARGIN, top - AABB_MARGIN);
+        let (max_x, max_y) = self.cell_of(right + AABB_MARGIN, bottom + AABB_MARGIN);
+        let count = (max_x as i64 - min_x as i64 + 1) * (max_y as i64 - min_y as i64 + 1);
+        if count > MAX_CELLS_PER_ENTRY {
+            return Placement::Oversized;
+        }
+        Placement::Cells(CellRange {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        })
+    }
+
+    /// 走査順を再計算し、各セルを並べ直す
+    fn rebuild_orders(&mut self, world: &World) {
+        self.order_rebuilds += 1;
+        // 登録済みエンティティの最上位祖先から走査する
+        let mut tops = Vec::new();
+        let mut seen = HashSet::new();
+        for &entity in self.placements.keys() {
+            let mut top = entity;
+            while let Some(child_of) = world.get::<ChildOf>(top) {
+                top = child_of.parent();
+            }
+            if seen.insert(top) {
+                tops.push(top);
+            }
+        }
+
+        self.orders.clear();
+        let mut next = 0u32;
+        for top in tops {
+            // 描画順（背面→前面）の逆順が走査順
+            for entity in stacking_order(world, top).into_iter().rev() {
+                self.orders.insert(entity, next);
+                next += 1;
+            }
+        }
+
+        let orders = &self.orders;
+        let key = |e: &Entity| orders.get(e).copied().unwrap_or(u32::MAX);
+        for entities in self.cells.values_mut() {
+            entities.sort_by_key(key);
+        }
+        self.oversized.sort_by_key(key);
+    }
+}
+
+/// ヒット領域（レイアウトボックスを累積変換した四角形）のスクリーンAABB
+///
+/// 変換が潰れている等でヒットし得ない場合は `None`。
+fn hit_aabb(world: &World, entity: Entity) -> Option<[f32; 4]> {
+    let (matrix, size) = global_hit_matrix(world, entity)?;
+    let corners = [
+        (0.0, 0.0),
+        (size.width, 0.0),
+        (0.0, size.height),
+        (size.width, size.height),
+    ];
+    let mut aabb = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+    for (x, y) in corners {
+        let sx = x * matrix.M11 + y * matrix.M21 + matrix.M31;
+        let sy = x * matrix.M12 + y * matrix.M22 + matrix.M32;
+        aabb = [
+            aabb[0].min(sx),
+            aabb[1].min(sy),
+            aabb[2].max(sx),
+            aabb[3].max(sy),
+        ];
+    }
+    Some(aabb)
+}
+
+// ============================================================================
+// update_hit_test_index_system
+// ============================================================================
+
+/// インデックス更新に使う変更検知
+type HitIndexChanges = (
+    Query<'static, 'static, Entity, Changed<GlobalArrangement>>,
+    Query<'static, 'static, Entity, Changed<Transform>>,
+    Query<'static, 'static, Entity, Changed<ChildOf>>,
+    Query<'static, 'static, (), Or<(Changed<Children>, Changed<ZIndex>)>>,
+    RemovedComponents<'static, 'static, GlobalArrangement>,
+    RemovedComponents<'static, 'static, Transform>,
+    RemovedComponents<'static, 'static, ChildOf>,
+    RemovedComponents<'static, 'static, Children>,
+    RemovedComponents<'static, 'static, ZIndex>,
+);
+
+/// `HitTestIndex`を変更分のみ更新する排他システム
+///
+/// - `GlobalArrangement`の変更: 自身のAABBを再計算
+/// - `Transform`の変更・削除、親の付け替え: 祖先の`Transform`は子孫のヒット領域にも
+///   影響するため、サブツリー全体のAABBを再計算
+/// - 階層（`Children`/`ChildOf`）・`ZIndex`の変更、新規登録: 走査順を再計算
+///   （`Transform`のみの変更では走査順は変わらないため再計算しない）
+///
+/// PostLayoutスケジュールで`pixel_snap_system`の後に実行する。
+/// `HitTestIndex`リソースがない場合は何もしない。
+pub fn update_hit_test_index_system(world: &mut World, changes: &mut SystemState<HitIndexChanges>) {
+    if !world.contains_resource::<HitTestIndex>() {
+        return;
+    }
+
+    // 変更検知の結果を先に収集（World の借用を解放するため）
+    let (mut dirty, subtree_roots, removed, mut hierarchy_changed) = {
+        let (
+            changed_globals,
+            changed_transforms,
+            changed_parents,
+            changed_stacking,
+            mut removed_globals,
+            mut removed_transforms,
+            mut removed_parents,
+            mut removed_children,
+            mut removed_z_indices,
+        ) = changes.get_mut(world);
+
+        let dirty: HashSet<Entity> = changed_globals.iter().collect();
+        // 親の付け替えはサブツリーのヒット領域と走査順の両方に影響する
+        let reparented: Vec<Entity> = changed_parents
+            .iter()
+            .chain(removed_parents.read())
+            .collect();
+        let removed: Vec<Entity> = removed_globals.read().collect();
+        let hierarchy_changed = !changed_stacking.is_empty()
+            || !removed.is_empty()
+            || !reparented.is_empty()
+            || removed_children.read().next().is_some()
+            || removed_z_indices.read().next().is_some();
+        let subtree_roots: Vec<Entity> = changed_transforms
+            .iter()
+            .chain(removed_transforms.read())
+            .chain(reparented)
+            .collect();
+        (dirty, subtree_roots, removed, hierarchy_changed)
+    };
+
+    // サブツリーを展開（自身の GlobalArrangement 変更で既に dirty なルートも子孫まで辿る）
+    let mut visited = HashSet::new();
+    let mut stack = subtree_roots;
+    while let Some(entity) = stack.pop() {
+        if !visited.insert(entity) {
+            continue;
+        }
+        dirty.insert(entity);
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter());
+        }
+    }
+
+    if dirty.is_empty() && removed.is_empty() && !hierarchy_changed {
+        return;
+    }
+
+    world.resource_scope(|world, mut index: Mut<HitTestIndex>| {
+        for entity in removed {
+            index.remove(entity);
+        }
+
+        // 新規エンティティは走査順を持たないため再計算が必要
+        hierarchy_changed |= dirty.iter().any(|entity| {
+            !index.orders.contains_key(entity) && world.get::<GlobalArrangement>(*entity).is_some()
+        });
+
+        for &entity in &dirty {
+            match world
+                .get_entity(entity)
+                .ok()
+                .filter(|e| e.contains::<GlobalArrangement>())
+                .and_then(|_| hit_aabb(world, entity))
+            {
+                Some(aabb) => index.insert(entity, aabb),
+                None => index.remove(entity),
+            }
+        }
+
+        if hierarchy_changed {
+            index.rebuild_orders(world);
+        }
+    });
+}
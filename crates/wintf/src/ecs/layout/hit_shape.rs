@@ -0,0 +1,198 @@
+//! # Hit Shape - 形状によるヒット判定
+//!
+//! `HitTestMode` の形状バリアント（楕円・角丸矩形・多角形・カスタム）の判定を
+//! Direct2Dのジオメトリを使わず純粋なRustで行う（ヘッドレステスト可能）。
+//!
+//! 座標はすべてエンティティのローカル座標系（レイアウトボックス左上が原点、
+//! `Transform`の逆変換後）で、判定は `LocalPoint` に対して行う。
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::{LocalPoint, Offset};
+
+// ============================================================================
+// CornerRadii - 角丸半径
+// ============================================================================
+
+/// 角ごとの角丸半径（ローカル座標系）
+///
+/// 隣り合う半径の合計が辺の長さを超える場合は、CSSの`border-radius`と同様に
+/// 全ての半径を同じ比率で縮小して扱う。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// 全ての角に同じ半径を指定
+    pub fn uniform(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// 幅・高さに収まるよう縮小した半径を返す
+    fn fitted(&self, width: f32, height: f32) -> Self {
+        let ratio = |sum: f32, len: f32| {
+            if sum > len && sum > 0.0 {
+                len / sum
+            } else {
+                1.0
+            }
+        };
+        let scale = ratio(self.top_left + self.top_right, width)
+            .min(ratio(self.bottom_left + self.bottom_right, width))
+            .min(ratio(self.top_left + self.bottom_left, height))
+            .min(ratio(self.top_right + self.bottom_right, height));
+        let fit = |r: f32| r.max(0.0) * scale;
+        Self {
+            top_left: fit(self.top_left),
+            top_right: fit(self.top_right),
+            bottom_right: fit(self.bottom_right),
+            bottom_left: fit(self.bottom_left),
+        }
+    }
+}
+
+// ============================================================================
+// FillRule - 多角形の塗りつぶし規則
+// ============================================================================
+
+/// 多角形の内外判定規則（D2D1_FILL_MODE 相当）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// 偶奇規則（自己交差部分は穴になる）
+    #[default]
+    EvenOdd,
+    /// 非ゼロ巻き数規則（自己交差部分も内側）
+    NonZero,
+}
+
+// ============================================================================
+// HitRegion - ユーザー定義のヒット領域
+// ============================================================================
+
+/// ユーザー定義のヒット領域（`Fn(LocalPoint) -> bool`）
+///
+/// 比較は関数の同一性（`Arc::ptr_eq`）で行う。
+#[derive(Clone)]
+pub struct HitRegion(Arc<dyn Fn(LocalPoint) -> bool + Send + Sync>);
+
+impl HitRegion {
+    /// 判定関数から作成
+    pub fn new(f: impl Fn(LocalPoint) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// ローカル座標の点が領域内か
+    pub fn contains(&self, point: LocalPoint) -> bool {
+        (self.0)(point)
+    }
+}
+
+impl PartialEq for HitRegion {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for HitRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HitRegion").finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// 判定関数
+// ============================================================================
+
+/// レイアウトボックスに内接する楕円の内側か（境界を含む）
+pub fn ellipse_contains(point: &LocalPoint) -> bool {
+    let rx = point.size.width / 2.0;
+    let ry = point.size.height / 2.0;
+    if rx <= 0.0 || ry <= 0.0 {
+        return false;
+    }
+    let dx = (point.x - rx) / rx;
+    let dy = (point.y - ry) / ry;
+    dx * dx + dy * dy <= 1.0
+}
+
+/// レイアウトボックスの角丸矩形の内側か（境界を含む）
+pub fn rounded_rect_contains(point: &LocalPoint, radii: &CornerRadii) -> bool {
+    let (width, height) = (point.size.width, point.size.height);
+    let (x, y) = (point.x, point.y);
+    if x < 0.0 || y < 0.0 || x > width || y > height {
+        return false;
+    }
+    let radii = radii.fitted(width, height);
+
+    // 点が属する角の円弧の中心と半径（角の領域外なら矩形内として確定）
+    let corner = if x < radii.top_left && y < radii.top_left {
+        Some((radii.top_left, radii.top_left, radii.top_left))
+    } else if x > width - radii.top_right && y < radii.top_right {
+        Some((width - radii.top_right, radii.top_right, radii.top_right))
+    } else if x > width - radii.bottom_right && y > height - radii.bottom_right {
+        Some((
+            width - radii.bottom_right,
+            height - radii.bottom_right,
+            radii.bottom_right,
+        ))
+    } else if x < radii.bottom_left && y > height - radii.bottom_left {
+        Some((
+            radii.bottom_left,
+            height - radii.bottom_left,
+            radii.bottom_left,
+        ))
+    } else {
+        None
+    };
+
+    match corner {
+        Some((cx, cy, r)) => {
+            let (dx, dy) = (x - cx, y - cy);
+            dx * dx + dy * dy <= r * r
+        }
+        None => true,
+    }
+}
+
+/// 多角形（ローカル座標の頂点列、自動で閉じる）の内側か
+///
+/// 頂点が3未満の場合は常に `false`。
+pub fn polygon_contains(points: &[Offset], fill_rule: FillRule, x: f32, y: f32) -> bool {
+    if points.len() < 3 {
+        return false;
+    }
+
+    // 水平右向きの半直線と各辺の交差を数える
+    let mut crossings = 0u32;
+    let mut winding = 0i32;
+    for (i, a) in points.iter().enumerate() {
+        let b = &points[(i + 1) % points.len()];
+        let upward = a.y <= y && b.y > y;
+        let downward = b.y <= y && a.y > y;
+        if !upward && !downward {
+            continue;
+        }
+        // 辺と半直線の交点のx座標
+        let t = (y - a.y) / (b.y - a.y);
+        let cross_x = a.x + t * (b.x - a.x);
+        if cross_x > x {
+            crossings += 1;
+            winding += if upward { 1 } else { -1 };
+        }
+    }
+
+    match fill_rule {
+        FillRule::EvenOdd => crossings % 2 == 1,
+        FillRule::NonZero => winding != 0,
+    }
+}
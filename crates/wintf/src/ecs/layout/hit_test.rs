@@ -9,13 +9,21 @@
 //!
 //! ### hit_test_entity
 //! 単一エンティティのヒットテストを実行します。
+//! `Transform`（回転・スキュー等）を含む累積変換行列を逆変換し、ローカル座標で判定します。
 //!
 //! ### hit_test
 //! ルート配下を走査してスクリーン座標でヒットテストを実行します。
+//! `HitTestIndex`（空間インデックス）があれば候補を絞り込んでから判定します。
+//!
+//! ### hit_test_all / hit_test_filtered / hit_test_with
+//! 点に重なるすべてのエンティティ（前面→背面）や、条件を満たす最前面のエンティティを返します。
 //!
 //! ### hit_test_in_window
 //! ウィンドウクライアント座標でヒットテストを実行します。
 //!
+//! ### alpha_coverage
+//! αマスクのカバレッジ（α値）をスクリーン座標で取得します（ホバー効果など）。
+//!
 //! # 例
 //!
 //! ```rust,ignore
@@ -31,10 +39,16 @@
 //! ```
 
 use bevy_ecs::prelude::*;
+use windows_numerics::Matrix3x2;
 
-use super::{D2DRectExt, GlobalArrangement};
-use crate::ecs::common::DepthFirstReversePostOrder;
+use super::{
+    ClipToBounds, CornerRadii, DisplayHidden, FillRule, GlobalArrangement, HitRegion, HitTestIndex,
+    Offset, Size, ellipse_contains, is_top_layer, polygon_contains, rounded_rect_contains,
+    stacking_order,
+};
 use crate::ecs::WindowPos;
+use crate::ecs::transform::Transform;
+use crate::ecs::widget::bitmap_source::{AlphaMask, AlphaMaskFormat, AlphaMaskSettings};
 
 // ============================================================================
 // PhysicalPoint - 物理ピクセル座標型
@@ -65,17 +79,31 @@ impl PhysicalPoint {
 /// # 例
 /// ```rust,ignore
 /// use wintf::ecs::layout::{HitTest, HitTestMode};
+/// use wintf::ecs::widget::bitmap_source::AlphaMaskFormat;
 ///
 /// // ヒットテスト対象外
-/// let hit_test = HitTest { mode: HitTestMode::None };
+/// let hit_test = HitTest { mode: HitTestMode::None, ..Default::default() };
 ///
 /// // 矩形領域でヒットテスト（デフォルト）
 /// let hit_test = HitTest::bounds();
 ///
 /// // αマスクによるピクセル単位ヒットテスト
 /// let hit_test = HitTest::alpha_mask();
+///
+/// // 縁のぼけた立ち絵: 閾値を下げ、4x4縮小のカバレッジで保持
+/// let hit_test = HitTest::alpha_mask()
+///     .with_alpha_threshold(32)
+///     .with_alpha_mask_format(AlphaMaskFormat::Coverage { downsample: 4 });
+///
+/// // 丸ボタン
+/// let hit_test = HitTest::ellipse();
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// 形状バリアント（`Ellipse`以降）はローカル座標系で判定し、レイアウトボックスの外はヒットしない。
+///
+/// 多角形の頂点列・ユーザー定義領域を保持するため`Copy`・`Eq`ではない（`HitTest`も同様）。
+/// 複製は`clone()`、比較は`PartialEq`を使う。
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum HitTestMode {
     /// ヒットテスト対象外（マウスイベントを透過）
     None,
@@ -84,6 +112,17 @@ pub enum HitTestMode {
     Bounds,
     /// αマスクによるピクセル単位ヒットテスト
     AlphaMask,
+    /// レイアウトボックスに内接する楕円
+    Ellipse,
+    /// 角ごとの半径を持つ角丸矩形
+    RoundedRect(CornerRadii),
+    /// 多角形（ローカル座標の頂点列）
+    Polygon {
+        points: Vec<Offset>,
+        fill_rule: FillRule,
+    },
+    /// ユーザー定義のヒット領域
+    Custom(HitRegion),
 }
 
 // ============================================================================
@@ -95,6 +134,9 @@ pub enum HitTestMode {
 /// エンティティのヒットテスト動作を設定します。
 /// このコンポーネントが付与されていない場合、デフォルトで `HitTestMode::Bounds` として扱われます。
 ///
+/// `alpha` は `HitTestMode::AlphaMask` のマスク生成設定で、変更するとマスクが再生成されます
+/// （カバレッジ形式で閾値のみの変更は再生成せずに適用）。
+///
 /// # 例
 /// ```rust,ignore
 /// use wintf::ecs::layout::HitTest;
@@ -105,9 +147,11 @@ pub enum HitTestMode {
 /// // 矩形領域でヒットテスト（デフォルト）
 /// commands.spawn((Arrangement::default(), HitTest::bounds()));
 /// ```
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Component, Debug, Clone, PartialEq, Default)]
 pub struct HitTest {
     pub mode: HitTestMode,
+    /// αマスクの閾値・保持形式
+    pub alpha: AlphaMaskSettings,
 }
 
 impl HitTest {
@@ -115,6 +159,7 @@ impl HitTest {
     pub fn none() -> Self {
         Self {
             mode: HitTestMode::None,
+            ..Default::default()
         }
     }
 
@@ -122,6 +167,7 @@ impl HitTest {
     pub fn bounds() -> Self {
         Self {
             mode: HitTestMode::Bounds,
+            ..Default::default()
         }
     }
 
@@ -129,8 +175,168 @@ impl HitTest {
     pub fn alpha_mask() -> Self {
         Self {
             mode: HitTestMode::AlphaMask,
+            ..Default::default()
+        }
+    }
+
+    /// 内接楕円でヒットテスト
+    pub fn ellipse() -> Self {
+        Self {
+            mode: HitTestMode::Ellipse,
+            ..Default::default()
+        }
+    }
+
+    /// 角丸矩形でヒットテスト
+    pub fn rounded_rect(radii: CornerRadii) -> Self {
+        Self {
+            mode: HitTestMode::RoundedRect(radii),
+            ..Default::default()
+        }
+    }
+
+    /// 多角形でヒットテスト
+    pub fn polygon(points: impl Into<Vec<Offset>>, fill_rule: FillRule) -> Self {
+        Self {
+            mode: HitTestMode::Polygon {
+                points: points.into(),
+                fill_rule,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// ユーザー定義の関数でヒットテスト
+    pub fn custom(f: impl Fn(LocalPoint) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            mode: HitTestMode::Custom(HitRegion::new(f)),
+            ..Default::default()
+        }
+    }
+
+    /// αマスクの閾値を設定（α ≧ threshold でヒット）
+    pub fn with_alpha_threshold(mut self, threshold: u8) -> Self {
+        self.alpha.threshold = threshold;
+        self
+    }
+
+    /// αマスクの保持形式を設定
+    pub fn with_alpha_mask_format(mut self, format: AlphaMaskFormat) -> Self {
+        self.alpha.format = format;
+        self
+    }
+}
+
+// ============================================================================
+// LocalPoint - ローカル座標への逆変換
+// ============================================================================
+
+/// スクリーン座標をエンティティのローカル座標系へ逆変換した点
+///
+/// ローカル座標系はレイアウトボックスの左上が原点で、`size`はボックスのローカルサイズ。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalPoint {
+    pub x: f32,
+    pub y: f32,
+    pub size: Size,
+}
+
+impl LocalPoint {
+    /// レイアウトボックス内か（境界を含む）
+    pub fn is_inside(&self) -> bool {
+        self.x >= 0.0 && self.x <= self.size.width && self.y >= 0.0 && self.y <= self.size.height
+    }
+
+    /// ボックスに対する相対位置（0.0〜1.0、ボックス外では範囲外の値）
+    pub fn normalized(&self) -> (f32, f32) {
+        let rel = |v: f32, len: f32| if len > 0.0 { v / len } else { 0.0 };
+        (rel(self.x, self.size.width), rel(self.y, self.size.height))
+    }
+}
+
+/// エンティティのローカル座標 → スクリーン座標の累積変換行列とローカルサイズ
+///
+/// レイアウト（`GlobalArrangement`、平行移動とスケールのみ）に、自身と祖先の
+/// `Transform`を合成する。`Transform`はそのエンティティのローカル座標系で、
+/// `TransformOrigin`をレイアウトボックスに対する相対位置として適用され、子孫にも継承される。
+///
+/// GlobalArrangement がない、またはスケールが0の場合は `None`。
+pub fn global_hit_matrix(world: &World, entity: Entity) -> Option<(Matrix3x2, Size)> {
+    let (mut matrix, size) = layout_matrix(world.get::<GlobalArrangement>(entity)?)?;
+
+    let mut current = Some(entity);
+    while let Some(target) = current {
+        if let Some(transform) = world.get::<Transform>(target) {
+            // スクリーン座標系での変換 = レイアウト逆変換 → ローカルでのTransform → レイアウト変換
+            if let Some((layout, size)) = world
+                .get::<GlobalArrangement>(target)
+                .and_then(layout_matrix)
+            {
+                let inverse = invert_matrix(&layout)?;
+                matrix =
+                    matrix * inverse * transform.matrix_for_size(size.width, size.height) * layout;
+            }
         }
+        current = world.get::<ChildOf>(target).map(ChildOf::parent);
     }
+    Some((matrix, size))
+}
+
+/// スクリーン座標をエンティティのローカル座標へ逆変換
+///
+/// 累積変換行列が逆変換できない（潰れている）場合は `None`。
+pub fn to_local_point(world: &World, entity: Entity, point: PhysicalPoint) -> Option<LocalPoint> {
+    let (matrix, size) = global_hit_matrix(world, entity)?;
+    let inverse = invert_matrix(&matrix)?;
+    let (x, y) = transform_point(&inverse, point.x, point.y);
+    Some(LocalPoint { x, y, size })
+}
+
+/// GlobalArrangement のローカル → スクリーン変換（スケール + bounds左上への平行移動）
+fn layout_matrix(global: &GlobalArrangement) -> Option<(Matrix3x2, Size)> {
+    let (scale_x, scale_y) = global.scale();
+    if scale_x == 0.0 || scale_y == 0.0 {
+        return None;
+    }
+    let bounds = &global.bounds;
+    let matrix = Matrix3x2 {
+        M11: scale_x,
+        M12: 0.0,
+        M21: 0.0,
+        M22: scale_y,
+        M31: bounds.left,
+        M32: bounds.top,
+    };
+    let size = Size {
+        width: (bounds.right - bounds.left) / scale_x,
+        height: (bounds.bottom - bounds.top) / scale_y,
+    };
+    Some((matrix, size))
+}
+
+/// アフィン変換行列の逆行列（特異な場合は `None`）
+fn invert_matrix(m: &Matrix3x2) -> Option<Matrix3x2> {
+    let det = m.M11 * m.M22 - m.M12 * m.M21;
+    if det.abs() <= f32::EPSILON {
+        return None;
+    }
+    let m11 = m.M22 / det;
+    let m12 = -m.M12 / det;
+    let m21 = -m.M21 / det;
+    let m22 = m.M11 / det;
+    Some(Matrix3x2 {
+        M11: m11,
+        M12: m12,
+        M21: m21,
+        M22: m22,
+        M31: -(m.M31 * m11 + m.M32 * m21),
+        M32: -(m.M31 * m12 + m.M32 * m22),
+    })
+}
+
+/// 点を変換（行ベクトル規約: `[x y 1] * M`）
+fn transform_point(m: &Matrix3x2, x: f32, y: f32) -> (f32, f32) {
+    (x * m.M11 + y * m.M21 + m.M31, x * m.M12 + y * m.M22 + m.M32)
 }
 
 // ============================================================================
@@ -152,44 +358,71 @@ impl HitTest {
 ///
 /// # Note
 /// `HitTest` コンポーネントがない場合は `HitTestMode::Bounds` として扱います。
+/// 判定は累積変換行列（`global_hit_matrix`）の逆変換で得たローカル座標で行うため、
+/// 自身や祖先の `Transform`（回転・スキュー・スケール）が反映されます。
+/// `ClipToBounds` を持つ祖先の矩形外の点はヒットしません（祖先のローカル座標で判定）。
+/// `DisplayHidden`（`display: none` のサブツリー）を持つエンティティもヒットしません。
 ///
 /// # AlphaMask判定
 /// `HitTestMode::AlphaMask` の場合:
-/// 1. まず矩形判定（早期リターン）
+/// 1. まずローカル矩形判定（早期リターン）
 /// 2. BitmapSourceResource.alpha_mask を取得
-/// 3. 座標変換（スクリーン → マスク座標）
+/// 3. 座標変換（ローカル → マスク座標）
 /// 4. AlphaMask.is_hit() 呼び出し
 /// 5. αマスク未生成時は矩形判定にフォールバック
+///
+/// # 形状判定
+/// `Ellipse` / `RoundedRect` / `Polygon` / `Custom` は矩形判定の後、
+/// ローカル座標で形状の内外を判定します（`hit_shape` モジュール）。
 pub fn hit_test_entity(world: &World, entity: Entity, point: PhysicalPoint) -> bool {
-    use crate::ecs::widget::bitmap_source::BitmapSourceResource;
-
     // HitTest コンポーネントを取得（なければデフォルト = Bounds）
     let mode = world
         .get::<HitTest>(entity)
-        .map(|h| h.mode)
-        .unwrap_or(HitTestMode::Bounds);
+        .map_or(&HitTestMode::Bounds, |h| &h.mode);
 
     // HitTestMode::None の場合はヒットしない
-    if mode == HitTestMode::None {
+    if *mode == HitTestMode::None {
         return false;
     }
 
-    // GlobalArrangement を取得（なければヒットしない）
-    let Some(global) = world.get::<GlobalArrangement>(entity) else {
+    // display:none のサブツリーはヒットしない
+    if world.get::<DisplayHidden>(entity).is_some() {
+        return false;
+    }
+
+    // ローカル座標へ逆変換（GlobalArrangement がない・変換が潰れている場合はヒットしない）
+    let Some(local) = to_local_point(world, entity, point) else {
         return false;
     };
 
     // まず矩形判定（全モード共通の早期リターン）
-    if !global.bounds.contains(point.x, point.y) {
+    if !local.is_inside() {
         return false;
     }
 
-    // HitTestMode::Bounds の場合は矩形判定のみ
-    if mode == HitTestMode::Bounds {
-        return true;
+    // ClipToBounds を持つ祖先の矩形外はクリップされている
+    if is_clipped_by_ancestor(world, entity, point) {
+        return false;
     }
 
-    // HitTestMode::AlphaMask の場合
+    match mode {
+        HitTestMode::None => false,
+        // 矩形判定のみ
+        HitTestMode::Bounds => true,
+        HitTestMode::AlphaMask => alpha_mask_contains(world, entity, &local),
+        HitTestMode::Ellipse => ellipse_contains(&local),
+        HitTestMode::RoundedRect(radii) => rounded_rect_contains(&local, radii),
+        HitTestMode::Polygon { points, fill_rule } => {
+            polygon_contains(points, *fill_rule, local.x, local.y)
+        }
+        HitTestMode::Custom(region) => region.contains(local),
+    }
+}
+
+/// αマスクによる判定（マスクがない場合は矩形判定にフォールバック）
+fn alpha_mask_contains(world: &World, entity: Entity, local: &LocalPoint) -> bool {
+    use crate::ecs::widget::bitmap_source::BitmapSourceResource;
+
     // BitmapSourceResource を取得
     let Some(resource) = world.get::<BitmapSourceResource>(entity) else {
         // BitmapSourceResource がない場合は矩形判定にフォールバック
@@ -202,25 +435,65 @@ pub fn hit_test_entity(world: &World, entity: Entity, point: PhysicalPoint) -> b
         return true;
     };
 
-    // スクリーン座標 → マスク座標への変換
-    let bounds = &global.bounds;
-    let bounds_width = bounds.right - bounds.left;
-    let bounds_height = bounds.bottom - bounds.top;
-
-    if bounds_width <= 0.0 || bounds_height <= 0.0 {
+    if local.size.width <= 0.0 || local.size.height <= 0.0 {
         return true; // サイズが0以下の場合はフォールバック
     }
 
-    // 相対座標を計算（0.0〜1.0）
-    let rel_x = (point.x - bounds.left) / bounds_width;
-    let rel_y = (point.y - bounds.top) / bounds_height;
+    // αマスクで判定
+    let (mask_x, mask_y) = mask_coordinates(alpha_mask, local);
+    alpha_mask.is_hit(mask_x, mask_y)
+}
 
-    // マスク座標に変換（切り捨て、範囲チェックはis_hit内で行う）
+/// ローカル座標 → マスク座標（切り捨て、範囲チェックはAlphaMask側で行う）
+fn mask_coordinates(alpha_mask: &AlphaMask, local: &LocalPoint) -> (u32, u32) {
+    let (rel_x, rel_y) = local.normalized();
     let mask_x = (rel_x * alpha_mask.width() as f32) as u32;
     let mask_y = (rel_y * alpha_mask.height() as f32) as u32;
+    (mask_x, mask_y)
+}
 
-    // αマスクで判定
-    alpha_mask.is_hit(mask_x, mask_y)
+/// スクリーン座標でのαマスクのカバレッジ（α値）を取得
+///
+/// ホバー時の強調度合いなど、2値のヒット判定より細かい情報が必要な場合に使う。
+/// カバレッジ形式（`AlphaMaskFormat::Coverage`）のマスクでは縮小セルのα平均値、
+/// 2値形式では 0 / 255 を返す。
+///
+/// # Returns
+/// - `Some(alpha)`: レイアウトボックス内でαマスクが生成済み
+/// - `None`: ボックス外、`BitmapSourceResource` がない、またはαマスク未生成
+pub fn alpha_coverage(world: &World, entity: Entity, point: PhysicalPoint) -> Option<u8> {
+    use crate::ecs::widget::bitmap_source::BitmapSourceResource;
+
+    let local = to_local_point(world, entity, point)?;
+    if !local.is_inside() || local.size.width <= 0.0 || local.size.height <= 0.0 {
+        return None;
+    }
+    let alpha_mask = world.get::<BitmapSourceResource>(entity)?.alpha_mask()?;
+    let (mask_x, mask_y) = mask_coordinates(alpha_mask, &local);
+    alpha_mask.coverage(mask_x, mask_y)
+}
+
+/// `ClipToBounds` を持つ祖先のいずれかの矩形外に点があるか
+///
+/// トップレイヤー（`ZIndex::Global`）より上の祖先ではクリップされない。
+fn is_clipped_by_ancestor(world: &World, entity: Entity, point: PhysicalPoint) -> bool {
+    let mut current = entity;
+    while let Some(child_of) = world.get::<ChildOf>(current) {
+        if is_top_layer(world, current) {
+            break;
+        }
+        current = child_of.parent();
+        if world.get::<ClipToBounds>(current).is_none() {
+            continue;
+        }
+        let Some(local) = to_local_point(world, current, point) else {
+            continue;
+        };
+        if !local.is_inside() {
+            return true;
+        }
+    }
+    false
 }
 
 // ============================================================================
@@ -239,17 +512,110 @@ pub fn hit_test_entity(world: &World, entity: Entity, point: PhysicalPoint) -> b
 /// - `None`: ヒットなし
 ///
 /// # Algorithm
-/// 深さ優先・逆順・後順走査で最前面エンティティを優先
+/// 前面（`ZIndex`を考慮した描画順の逆順）から判定し、最初にヒットしたエンティティを返す。
+/// `HitTestIndex` リソースがあり `root` が登録済みの場合は、空間インデックスで
+/// 点を含み得る候補だけを前面から判定する。それ以外は `hit_test_linear` と同じ線形走査。
+/// どちらも結果（最前面のエンティティ）は同一。
 pub fn hit_test(world: &World, root: Entity, screen_point: PhysicalPoint) -> Option<Entity> {
-    let mut traversal = DepthFirstReversePostOrder::new(root);
+    if let Some(mut candidates) = world
+        .get_resource::<HitTestIndex>()
+        .and_then(|index| index.candidates(world, root, screen_point))
+    {
+        return candidates.find(|&entity| hit_test_entity(world, entity, screen_point));
+    }
 
-    while let Some(entity) = traversal.next(world) {
-        if hit_test_entity(world, entity, screen_point) {
-            return Some(entity);
-        }
+    hit_test_linear(world, root, screen_point)
+}
+
+/// ヒット判定の候補を前面から順に列挙（`HitTestIndex`があれば絞り込み済み）
+fn front_to_back<'a>(
+    world: &'a World,
+    root: Entity,
+    screen_point: PhysicalPoint,
+) -> Box<dyn Iterator<Item = Entity> + 'a> {
+    if let Some(candidates) = world
+        .get_resource::<HitTestIndex>()
+        .and_then(|index| index.candidates(world, root, screen_point))
+    {
+        return Box::new(candidates);
     }
+    Box::new(stacking_order(world, root).into_iter().rev())
+}
+
+/// 指定ルートエンティティ配下で、点に重なるすべてのエンティティを前面から順に返す
+///
+/// 判定は `hit_test_entity` と同じ（`HitTestMode`・`Transform`・祖先のクリップを考慮）。
+/// 先頭要素は `hit_test` の結果と一致する。
+///
+/// # Returns
+/// ヒットしたエンティティ（前面→背面）。ヒットなしは空
+pub fn hit_test_all(world: &World, root: Entity, screen_point: PhysicalPoint) -> Vec<Entity> {
+    hit_test_all_filtered(world, root, screen_point, |_| true)
+}
+
+/// `filter` を満たすエンティティに限定した `hit_test_all`
+///
+/// `filter` は判定の前に評価される。除外されたエンティティの子孫は除外されない
+/// （子孫ごと除外するには `is_self_or_descendant` を使う）。
+pub fn hit_test_all_filtered(
+    world: &World,
+    root: Entity,
+    screen_point: PhysicalPoint,
+    mut filter: impl FnMut(Entity) -> bool,
+) -> Vec<Entity> {
+    front_to_back(world, root, screen_point)
+        .filter(|&entity| filter(entity) && hit_test_entity(world, entity, screen_point))
+        .collect()
+}
 
-    None
+/// `filter` を満たすエンティティのうち、ヒットした最前面のものを返す
+///
+/// `filter` で除外されたエンティティは透過する（背面のエンティティが判定される）。
+///
+/// # 例
+/// ```rust,ignore
+/// // ドラッグ中の要素（とその子孫）の下にあるドロップ先
+/// let target = hit_test_filtered(world, root, point, |e| {
+///     !is_self_or_descendant(world, dragged, e)
+/// });
+/// ```
+pub fn hit_test_filtered(
+    world: &World,
+    root: Entity,
+    screen_point: PhysicalPoint,
+    mut filter: impl FnMut(Entity) -> bool,
+) -> Option<Entity> {
+    front_to_back(world, root, screen_point)
+        .find(|&entity| filter(entity) && hit_test_entity(world, entity, screen_point))
+}
+
+/// コンポーネント `C` を持つエンティティのうち、ヒットした最前面のものを返す
+///
+/// # 例
+/// ```rust,ignore
+/// // ポインター下で最も手前のツールチップ対象
+/// let owner = hit_test_with::<Tooltip>(world, root, point);
+/// ```
+pub fn hit_test_with<C: Component>(
+    world: &World,
+    root: Entity,
+    screen_point: PhysicalPoint,
+) -> Option<Entity> {
+    hit_test_filtered(world, root, screen_point, |entity| {
+        world.get::<C>(entity).is_some()
+    })
+}
+
+/// 空間インデックスを使わずにルート配下を全走査するヒットテスト
+///
+/// 描画順（`stacking_order`）の逆順＝前面から判定する。`ZIndex`がなければ
+/// 深さ優先・逆順・後順走査と同じ順序になる。
+/// `hit_test` の基準実装（等価性テスト・ベンチマーク用）。
+pub fn hit_test_linear(world: &World, root: Entity, screen_point: PhysicalPoint) -> Option<Entity> {
+    stacking_order(world, root)
+        .into_iter()
+        .rev()
+        .find(|&entity| hit_test_entity(world, entity, screen_point))
 }
 
 // ============================================================================
@@ -633,6 +999,23 @@ mod tests {
         assert_eq!(hit_test.mode, HitTestMode::AlphaMask);
     }
 
+    #[test]
+    fn test_hit_test_alpha_settings_builder() {
+        assert_eq!(HitTest::alpha_mask().alpha, AlphaMaskSettings::default());
+
+        let hit_test = HitTest::alpha_mask()
+            .with_alpha_threshold(32)
+            .with_alpha_mask_format(AlphaMaskFormat::Coverage { downsample: 4 });
+        assert_eq!(hit_test.mode, HitTestMode::AlphaMask);
+        assert_eq!(hit_test.alpha.threshold, 32);
+        assert_eq!(
+            hit_test.alpha.format,
+            AlphaMaskFormat::Coverage { downsample: 4 }
+        );
+        // 設定が異なれば別のHitTest（Changed<HitTest>で再生成される）
+        assert_ne!(hit_test, HitTest::alpha_mask());
+    }
+
     /// αマスク未設定時は矩形判定にフォールバック
     #[test]
     fn test_hit_test_alpha_mask_fallback_no_bitmap_source() {
@@ -688,4 +1071,42 @@ mod tests {
         let point = PhysicalPoint::new(200.0, 200.0);
         assert!(!hit_test_entity(&world, entity, point));
     }
+
+    /// ClipToBounds を持つ祖先の矩形外にはみ出した部分はヒットしない
+    #[test]
+    fn test_hit_test_clipped_by_ancestor() {
+        let mut world = World::new();
+
+        let clip = world
+            .spawn((
+                make_global_arrangement(0.0, 0.0, 100.0, 100.0),
+                ClipToBounds,
+            ))
+            .id();
+        let child = world
+            .spawn((
+                make_global_arrangement(50.0, 50.0, 150.0, 150.0),
+                ChildOf(clip),
+            ))
+            .id();
+
+        assert!(hit_test_entity(
+            &world,
+            child,
+            PhysicalPoint::new(75.0, 75.0)
+        ));
+        assert!(!hit_test_entity(
+            &world,
+            child,
+            PhysicalPoint::new(125.0, 125.0)
+        ));
+
+        // クリップを外すとはみ出し部分もヒット
+        world.entity_mut(clip).remove::<ClipToBounds>();
+        assert!(hit_test_entity(
+            &world,
+            child,
+            PhysicalPoint::new(125.0, 125.0)
+        ));
+    }
 }
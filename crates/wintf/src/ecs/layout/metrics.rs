@@ -133,3 +133,12 @@ impl Default for Opacity {
         Self(1.0) // 完全不透明がデフォルト
     }
 }
+
+/// 境界クリップ（子孫の描画とヒットテストを自身の矩形で切り抜く）
+///
+/// - 描画: graphicsシステムがVisualのクリップ矩形（ローカル座標、物理ピクセル）に反映
+/// - ヒットテスト: クリップ祖先の`GlobalArrangement.bounds`外の点は子孫にヒットしない
+///
+/// `ScrollViewer`は自動的にこのコンポーネントを付与する。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClipToBounds;
@@ -6,9 +6,15 @@
 //!
 //! - **`taffy`**: taffyエンジン連携 (`TaffyStyle`, `TaffyComputedLayout`)
 //! - **`metrics`**: レイアウトメトリクス (`Size`, `Offset`, `LayoutScale`, `TextLayoutMetrics`)
-//! - **`arrangement`**: 配置情報コンポーネント (`Arrangement`, `GlobalArrangement`, `ArrangementTreeChanged`)
-//! - **`rect`**: 矩形操作ユーティリティ (`Rect`, `D2DRectExt`, `transform_rect_axis_aligned`)
+//! - **`arrangement`**: 配置情報コンポーネント (`Arrangement`, `GlobalArrangement`, `SnappedArrangement`, `ArrangementTreeChanged`)
+//! - **`rect`**: 矩形操作ユーティリティ (`Rect`, `D2DRectExt`, `transform_rect_axis_aligned`, `snap_rect_to_pixels`)
 //! - **`systems`**: 配置伝播システム関数 (`sync_simple_arrangements`, `propagate_global_arrangements`)
+//! - **`text_measure`**: テキストの内在サイズ計測 (`TextMeasure`, `TextMeasurer`, `TextMeasurerResource`)
+//! - **`transition`**: 暗黙的レイアウトトランジション (`LayoutTransition`, `Easing`)
+//! - **`hit_index`**: ヒットテスト用の空間インデックス (`HitTestIndex`, `update_hit_test_index_system`)
+//! - **`z_index`**: 描画・ヒットテストの重なり順序 (`ZIndex`, `stacking_order`)
+//! - **`anchor`**: モニター作業領域へのウィンドウのアンカー (`Anchor`, `AnchorAlign`, `AnchorMonitor`)
+//! - **`debug_dump`**: レイアウトツリーのテキストダンプとスナップショット比較 (`dump_layout_tree`, `assert_layout_snapshot`)
 //!
 //! ## 主要コンポーネント
 //!
@@ -61,22 +67,36 @@
 //! ecs::common::tree_system: sync_simple_transforms, mark_dirty_trees, and propagate_parent_transforms.
 
 // Layout System サブモジュール
+pub mod anchor;
 pub mod arrangement;
+pub mod debug_dump;
 pub mod high_level;
+pub mod hit_index;
+pub mod hit_shape;
 pub mod hit_test;
 pub mod metrics;
 pub mod rect;
 pub mod systems;
 pub mod taffy;
+pub mod text_measure;
+pub mod transition;
+pub mod z_index;
 
 // 公開API
+pub use anchor::*;
 pub use arrangement::*;
+pub use debug_dump::*;
 pub use high_level::*;
+pub use hit_index::*;
+pub use hit_shape::*;
 pub use hit_test::*;
 pub use metrics::*;
 pub use rect::*; // D2DRect, D2DRectExt, transform_rect_axis_aligned
 pub use systems::*;
 pub use taffy::*;
+pub use text_measure::*;
+pub use transition::*;
+pub use z_index::*;
 
 use bevy_ecs::prelude::*;
 
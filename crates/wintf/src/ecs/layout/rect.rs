@@ -206,3 +206,40 @@ pub fn transform_rect_axis_aligned(rect: &D2DRect, matrix: &Matrix3x2) -> D2DRec
         bottom: top_left_y.max(bottom_right_y),
     }
 }
+
+/// ピクセルスナップ前に座標を量子化する刻み（1/256ピクセル）
+///
+/// 同じ辺を異なる経路（`left + size * scale` と `left + offset * scale`）で計算した際の
+/// 浮動小数点誤差を吸収し、隣接する矩形の辺が同じ整数に丸められるようにする。
+const SNAP_QUANTUM: f32 = 256.0;
+
+/// 物理ピクセル座標を最も近い整数ピクセルに丸める
+///
+/// 0.5ちょうどは常に正方向に丸める（負の座標でも平行移動に対して一貫）。
+///
+/// # 使用例
+/// ```
+/// use wintf::ecs::snap_to_pixel;
+///
+/// assert_eq!(snap_to_pixel(12.5), 13.0);
+/// assert_eq!(snap_to_pixel(-12.5), -12.0);
+/// assert_eq!(snap_to_pixel(12.499_999), 13.0);
+/// ```
+#[inline]
+pub fn snap_to_pixel(value: f32) -> f32 {
+    let quantized = (value * SNAP_QUANTUM).round() / SNAP_QUANTUM;
+    (quantized + 0.5).floor()
+}
+
+/// 矩形の各辺を独立に整数ピクセルへ丸める
+///
+/// 幅ではなく辺の絶対座標を丸めるため、辺を共有する兄弟要素は丸め後も辺を共有する
+/// （taffyのレイアウト丸めと同じ方式）。
+pub fn snap_rect_to_pixels(rect: &D2DRect) -> D2DRect {
+    D2D_RECT_F {
+        left: snap_to_pixel(rect.left),
+        top: snap_to_pixel(rect.top),
+        right: snap_to_pixel(rect.right),
+        bottom: snap_to_pixel(rect.bottom),
+    }
+}
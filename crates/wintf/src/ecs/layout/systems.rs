@@ -7,8 +7,12 @@ use bevy_ecs::prelude::*;
 use tracing::{debug, error, info, trace, warn};
 
 use super::metrics::{LayoutScale, Offset, Size};
-use super::taffy::{TaffyComputedLayout, TaffyLayoutResource, TaffyStyle};
-use super::{Arrangement, ArrangementTreeChanged, Dimension, GlobalArrangement, LayoutRoot};
+use super::taffy::{LayoutStats, TaffyComputedLayout, TaffyLayoutResource, TaffyStyle};
+use super::text_measure::{TextMeasure, TextMeasurerResource, measure_text_node};
+use super::{
+    Arrangement, ArrangementTreeChanged, Dimension, GlobalArrangement, LayoutRoot,
+    SnappedArrangement,
+};
 use crate::ecs::graphics::format_entity_name;
 use crate::ecs::window::{DPI, Window, WindowPos};
 use taffy::prelude::*;
@@ -97,9 +101,25 @@ pub fn propagate_global_arrangements(
     );
 }
 
+/// GlobalArrangementの変更をピクセルスナップ済み矩形に反映
+///
+/// `propagate_global_arrangements`の後に実行。丸めは伝播済みの絶対座標に対して行い、
+/// `GlobalArrangement`自体は変更しないため、部分的な再伝播でも結果は一貫する。
+pub fn pixel_snap_system(
+    mut query: Query<
+        (&GlobalArrangement, &mut SnappedArrangement),
+        Or<(Changed<GlobalArrangement>, Added<SnappedArrangement>)>,
+    >,
+) {
+    for (global, mut snapped) in query.iter_mut() {
+        snapped.set_if_neq(SnappedArrangement::from_global(global));
+    }
+}
+
 // ===== Taffyレイアウトシステム =====
 
-use super::BoxStyle;
+use super::{BoxDisplay, BoxStyle, DisplayHidden, WritingMode};
+use crate::ecs::scroll::{ScrollViewer, apply_scroll_viewer_style};
 
 /// BoxStyleからTaffyStyleを構築するシステム（統合後）
 ///
@@ -111,6 +131,10 @@ use super::BoxStyle;
 /// 1. BoxStyleまたはLayoutRootを持ち、TaffyStyleがないエンティティにTaffyStyleを自動挿入
 /// 2. BoxStyleが変更されたエンティティのTaffyStyleを更新
 /// 3. LayoutRootのみでBoxStyleがないエンティティにはBoxStyle::default()相当のスタイルを適用
+/// 4. 自身または継承元の`WritingMode`が変わったエンティティ（`WritingMode`の変更・削除、
+///    親の付け替えがあったサブツリー）の論理プロパティを再解決
+///    （値が変わらないTaffyStyleは更新しない）
+/// 5. `ScrollViewer`とその直接の子にスクロール用の調整を適用（`apply_scroll_viewer_style`）
 pub fn build_taffy_styles_system(
     mut commands: Commands,
     // LayoutRootまたはBoxStyleがあるがTaffyStyleがないエンティティ
@@ -119,13 +143,74 @@ pub fn build_taffy_styles_system(
         (Entity, Option<&BoxStyle>),
         (Or<(With<LayoutRoot>, With<BoxStyle>)>, Without<TaffyStyle>),
     >,
-    // BoxStyleが変更されたエンティティ
-    mut changed: Query<(&BoxStyle, &mut TaffyStyle), Changed<BoxStyle>>,
+    mut styles: Query<(&BoxStyle, &mut TaffyStyle)>,
+    changed_styles: Query<Entity, Changed<BoxStyle>>,
+    // 書字方向の継承解決用
+    writing_modes: Query<&WritingMode>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    changed_writing_modes: Query<Entity, Changed<WritingMode>>,
+    mut removed_writing_modes: RemovedComponents<WritingMode>,
+    changed_hierarchy: Query<Entity, Changed<ChildOf>>,
+    mut removed_hierarchy: RemovedComponents<ChildOf>,
+    // スクロールコンテナー
+    scroll_viewers: Query<&ScrollViewer>,
+    changed_scroll_viewers: Query<Entity, Changed<ScrollViewer>>,
+    mut removed_scroll_viewers: RemovedComponents<ScrollViewer>,
 ) {
+    // 継承する書字方向が変わるサブツリー（自身のWritingModeを持つ子孫より先は影響なし）
+    let mut dirty = std::collections::HashSet::new();
+    let mode_roots: Vec<Entity> = changed_writing_modes
+        .iter()
+        .chain(removed_writing_modes.read())
+        .chain(changed_hierarchy.iter())
+        .chain(removed_hierarchy.read())
+        .collect();
+    for root in mode_roots {
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            // 展開済みのサブツリーは再訪不要
+            if !dirty.insert(entity) {
+                continue;
+            }
+            if let Ok(entity_children) = children.get(entity) {
+                stack.extend(
+                    entity_children
+                        .iter()
+                        .filter(|child| !writing_modes.contains(*child)),
+                );
+            }
+        }
+    }
+    // スクロール調整はビューア自身と直接の子に影響する
+    let viewers: Vec<Entity> = changed_scroll_viewers
+        .iter()
+        .chain(removed_scroll_viewers.read())
+        .collect();
+    for viewer in viewers {
+        dirty.insert(viewer);
+        if let Ok(viewer_children) = children.get(viewer) {
+            dirty.extend(viewer_children.iter());
+        }
+    }
+    dirty.extend(changed_styles.iter());
+
+    let resolve = |entity: Entity, box_style: Option<&BoxStyle>| {
+        // BoxStyleがない場合（LayoutRootのみ）はデフォルトスタイル
+        let mut style = box_style
+            .map(|s| s.to_taffy_style(resolve_writing_mode(entity, &writing_modes, &parents)))
+            .unwrap_or_default();
+        let parent_viewer = parents
+            .get(entity)
+            .ok()
+            .and_then(|c| scroll_viewers.get(c.parent()).ok());
+        apply_scroll_viewer_style(&mut style, scroll_viewers.get(entity).ok(), parent_viewer);
+        style
+    };
+
     // TaffyStyle自動挿入
     for (entity, box_style) in without_style.iter() {
-        // BoxStyleがない場合（LayoutRootのみ）はデフォルトスタイル
-        let taffy_style: taffy::Style = box_style.map(|s| s.into()).unwrap_or_default();
+        let taffy_style = resolve(entity, box_style);
         commands.entity(entity).insert((
             TaffyStyle(taffy_style),
             TaffyComputedLayout::default(),
@@ -134,34 +219,78 @@ pub fn build_taffy_styles_system(
     }
 
     // 変更反映
-    for (box_style, mut taffy_style) in changed.iter_mut() {
-        taffy_style.0 = box_style.into();
+    for entity in dirty {
+        let Ok((box_style, mut taffy_style)) = styles.get_mut(entity) else {
+            continue;
+        };
+        let new_style = resolve(entity, Some(box_style));
+        if taffy_style.0 != new_style {
+            taffy_style.0 = new_style;
+        }
+    }
+}
+
+/// エンティティに適用される書字方向を解決（自身または最も近い祖先の`WritingMode`）
+pub fn resolve_writing_mode(
+    entity: Entity,
+    writing_modes: &Query<&WritingMode>,
+    parents: &Query<&ChildOf>,
+) -> WritingMode {
+    let mut current = entity;
+    loop {
+        if let Ok(mode) = writing_modes.get(current) {
+            return *mode;
+        }
+        match parents.get(current) {
+            Ok(child_of) => current = child_of.parent(),
+            Err(_) => return WritingMode::default(),
+        }
     }
 }
 
 /// TaffyツリーをECS階層と同期
+///
+/// 変更のあったノードの親（親がなければ自身）を`TaffyLayoutResource`にレイアウトダーティとして記録する。
 pub fn sync_taffy_tree_system(
     mut taffy_res: ResMut<TaffyLayoutResource>,
     // 新規エンティティ（TaffyStyleが追加されたがノードがまだ作成されていない）
-    new_entities: Query<(Entity, Option<&ChildOf>), Added<TaffyStyle>>,
+    new_entities: Query<(Entity, Option<&TextMeasure>), Added<TaffyStyle>>,
     // TaffyStyleが変更されたエンティティ
     changed_styles: Query<(Entity, &TaffyStyle), Changed<TaffyStyle>>,
     // 階層が変更されたエンティティ
     changed_hierarchy: Query<(Entity, Option<&ChildOf>), Changed<ChildOf>>,
     // ChildOfが削除されたエンティティ
     mut removed_hierarchy: RemovedComponents<ChildOf>,
+    // 計測対象テキストが変更・削除されたエンティティ
+    changed_text: Query<(Entity, &TextMeasure), Changed<TextMeasure>>,
+    mut removed_text: RemovedComponents<TextMeasure>,
+    parents: Query<&ChildOf>,
 ) {
     // 新規エンティティにtaffyノードを作成
-    for (entity, _) in new_entities.iter() {
+    for (entity, text) in new_entities.iter() {
         if taffy_res.get_node(entity).is_none() {
             let _ = taffy_res.create_node(entity);
+            if let Some(text) = text {
+                let _ = taffy_res.set_text_measure(entity, Some(text.clone()));
+            }
         }
     }
 
+    // 計測対象テキストをノードコンテキストに反映
+    for (entity, text) in changed_text.iter() {
+        let _ = taffy_res.set_text_measure(entity, Some(text.clone()));
+        mark_parent_layout_dirty(&mut taffy_res, &parents, entity);
+    }
+    for entity in removed_text.read() {
+        let _ = taffy_res.set_text_measure(entity, None);
+        mark_parent_layout_dirty(&mut taffy_res, &parents, entity);
+    }
+
     // TaffyStyleの変更をtaffyツリーに反映
     for (entity, style) in changed_styles.iter() {
         if let Some(node_id) = taffy_res.get_node(entity) {
             let _ = taffy_res.taffy_mut().set_style(node_id, style.0.clone());
+            mark_parent_layout_dirty(&mut taffy_res, &parents, entity);
         }
     }
 
@@ -171,6 +300,11 @@ pub fn sync_taffy_tree_system(
             if let Some(parent_ref) = child_of {
                 let parent_entity = parent_ref.parent();
                 if let Some(parent_node) = taffy_res.get_node(parent_entity) {
+                    // 旧親・新親の両方のレイアウトが変化する
+                    if let Some(old_parent) = taffy_res.parent_entity(entity) {
+                        taffy_res.mark_layout_dirty(old_parent);
+                    }
+                    taffy_res.mark_layout_dirty(parent_entity);
                     // 新しい親に追加（taffyが自動的に既存の親から削除する）
                     let _ = taffy_res.taffy_mut().add_child(parent_node, node_id);
                 }
@@ -182,93 +316,211 @@ pub fn sync_taffy_tree_system(
     for entity in removed_hierarchy.read() {
         if let Some(node_id) = taffy_res.get_node(entity) {
             if let Some(parent_node) = taffy_res.taffy().parent(node_id) {
+                if let Some(parent_entity) = taffy_res.get_entity(parent_node) {
+                    taffy_res.mark_layout_dirty(parent_entity);
+                }
                 let _ = taffy_res.taffy_mut().remove_child(parent_node, node_id);
             }
         }
     }
 }
 
+/// エンティティ自身の変更を親（親がなければ自身）のレイアウトダーティとして記録
+fn mark_parent_layout_dirty(
+    taffy_res: &mut TaffyLayoutResource,
+    parents: &Query<&ChildOf>,
+    entity: Entity,
+) {
+    let target = parents.get(entity).map_or(entity, |c| c.parent());
+    taffy_res.mark_layout_dirty(target);
+}
+
+/// レイアウトダーティなエンティティの再計算スコープを解決
+///
+/// 祖先方向に辿って最も近いレイアウト境界（`BoxStyle::is_layout_boundary`）、
+/// なければLayoutRootを返す。`BoxDisplay::None`の祖先より内側の境界は採用しない。
+/// LayoutRootに到達しない（切り離された）エンティティは`None`。
+fn resolve_layout_scope(
+    entity: Entity,
+    parents: &Query<&ChildOf>,
+    box_styles: &Query<&BoxStyle>,
+    roots: &Query<(Entity, Option<&BoxStyle>), With<LayoutRoot>>,
+) -> Option<Entity> {
+    let mut scope = None;
+    let mut current = entity;
+    loop {
+        let style = box_styles.get(current).ok();
+        if style.is_some_and(|s| s.display == Some(BoxDisplay::None)) {
+            scope = None;
+        }
+        match parents.get(current) {
+            Ok(child_of) => {
+                if scope.is_none() && style.is_some_and(BoxStyle::is_layout_boundary) {
+                    scope = Some(current);
+                }
+                current = child_of.parent();
+            }
+            Err(_) => {
+                return roots.contains(current).then(|| scope.unwrap_or(current));
+            }
+        }
+    }
+}
+
+/// LayoutRootのBoxStyleからavailable_spaceを構築
+fn root_available_space(box_style: Option<&BoxStyle>) -> taffy::Size<AvailableSpace> {
+    let to_space = |d: Option<&Dimension>| match d {
+        Some(Dimension::Px(px)) => AvailableSpace::Definite(*px),
+        _ => AvailableSpace::MaxContent,
+    };
+    match box_style.and_then(|style| style.size.as_ref()) {
+        Some(size) => taffy::Size {
+            width: to_space(size.width.as_ref()),
+            height: to_space(size.height.as_ref()),
+        },
+        None => taffy::Size {
+            width: AvailableSpace::MaxContent,
+            height: AvailableSpace::MaxContent,
+        },
+    }
+}
+
 /// Taffyレイアウト計算を実行
+///
+/// `sync_taffy_tree_system`が記録したレイアウトダーティをスコープ（LayoutRootまたは
+/// レイアウト境界）単位にまとめ、影響のあるスコープだけをtaffyで再計算する。
+/// 書き戻しは再計算したサブツリー内で値が変化したノードに限られるため、
+/// 変更のないウィンドウのサブツリーは`update_arrangements_system`でも処理されない。
+///
+/// レイアウト境界のみを再計算した場合、境界自身の位置は前回値を維持し、
+/// 祖先のtaffyノードをダーティにして次回のLayoutRoot再計算で整合させる。
+///
+/// 再計算したスコープ数・ノード数は`LayoutStats`リソースに記録される。
 pub fn compute_taffy_layout_system(
     mut taffy_res: ResMut<TaffyLayoutResource>,
     // LayoutRootマーカーを持つエンティティをレイアウトルートとして扱う
     roots: Query<(Entity, Option<&BoxStyle>), With<LayoutRoot>>,
-    // 変更検知用
-    changed_styles: Query<(), Changed<TaffyStyle>>,
-    changed_box_styles: Query<(), Changed<BoxStyle>>,
-    changed_hierarchy: Query<(), Changed<ChildOf>>,
+    // LayoutRootのBoxStyle変更はavailable_spaceに影響する
+    changed_roots: Query<Entity, (With<LayoutRoot>, Changed<BoxStyle>)>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    box_styles: Query<&BoxStyle>,
+    // テキストノードの計測器（未登録ならテキストはサイズ0）
+    text_measurer: Option<Res<TextMeasurerResource>>,
     // TaffyComputedLayoutを書き込むクエリ
-    mut all_taffy_entities: Query<(Entity, &mut TaffyComputedLayout), With<TaffyStyle>>,
+    mut computed_layouts: Query<&mut TaffyComputedLayout, With<TaffyStyle>>,
+    stats: Option<ResMut<LayoutStats>>,
 ) {
-    // BoxStyleまたはTaffyStyleの変更、階層変更のいずれかで再計算
-    let has_changes = !changed_styles.is_empty()
-        || !changed_box_styles.is_empty()
-        || !changed_hierarchy.is_empty();
-
-    // Changed検知時にレイアウト計算を実行
-    if has_changes {
-        for (root_entity, box_style) in roots.iter() {
-            if let Some(root_node) = taffy_res.get_node(root_entity) {
-                // LayoutRootのBoxStyleからavailable_spaceを構築
-                let available_space = if let Some(style) = box_style {
-                    if let Some(size) = &style.size {
-                        taffy::Size {
-                            width: size.width.as_ref().map_or(AvailableSpace::MaxContent, |d| {
-                                match d {
-                                    Dimension::Px(px) => AvailableSpace::Definite(*px),
-                                    _ => AvailableSpace::MaxContent,
-                                }
-                            }),
-                            height: size
-                                .height
-                                .as_ref()
-                                .map_or(AvailableSpace::MaxContent, |d| match d {
-                                    Dimension::Px(px) => AvailableSpace::Definite(*px),
-                                    _ => AvailableSpace::MaxContent,
-                                }),
-                        }
-                    } else {
-                        taffy::Size {
-                            width: AvailableSpace::MaxContent,
-                            height: AvailableSpace::MaxContent,
-                        }
-                    }
-                } else {
-                    taffy::Size {
-                        width: AvailableSpace::MaxContent,
-                        height: AvailableSpace::MaxContent,
-                    }
-                };
-
-                let result = taffy_res
-                    .taffy_mut()
-                    .compute_layout(root_node, available_space);
-
-                // 計算成功時、全エンティティにTaffyComputedLayoutを書き込む
-                if result.is_ok() {
-                    for (entity, mut computed) in all_taffy_entities.iter_mut() {
-                        if let Some(node_id) = taffy_res.get_node(entity) {
-                            if let Ok(layout) = taffy_res.taffy().layout(node_id) {
-                                let new_layout = TaffyComputedLayout(*layout);
-                                // 値比較で変更検知を抑制
-                                if *computed != new_layout {
-                                    tracing::debug!(
-                                        entity = ?entity,
-                                        old_width = computed.0.size.width,
-                                        old_height = computed.0.size.height,
-                                        new_width = new_layout.0.size.width,
-                                        new_height = new_layout.0.size.height,
-                                        "[compute_taffy_layout] Layout changed"
-                                    );
-                                    *computed = new_layout;
-                                }
-                            }
-                        }
-                    }
+    for root in changed_roots.iter() {
+        taffy_res.mark_layout_dirty(root);
+    }
+    if !taffy_res.has_layout_dirty() {
+        return;
+    }
+    let dirty = taffy_res.take_layout_dirty();
+
+    // ダーティをスコープにまとめ、祖先スコープに含まれるものを除く
+    let mut scopes: Vec<Entity> = dirty
+        .into_iter()
+        .filter_map(|entity| resolve_layout_scope(entity, &parents, &box_styles, &roots))
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+    let nested: Vec<bool> = scopes
+        .iter()
+        .map(|&scope| {
+            let mut current = scope;
+            while let Ok(child_of) = parents.get(current) {
+                current = child_of.parent();
+                if scopes.binary_search(&current).is_ok() {
+                    return true;
                 }
             }
+            false
+        })
+        .collect();
+
+    let measurer = text_measurer.as_deref().map(TextMeasurerResource::measurer);
+    let mut pass_stats = LayoutStats::default();
+
+    for (scope, _) in scopes.iter().zip(nested).filter(|(_, nested)| !nested) {
+        let scope = *scope;
+        let Some(scope_node) = taffy_res.get_node(scope) else {
+            continue;
+        };
+        let is_root = roots.contains(scope);
+        let available_space = if is_root {
+            root_available_space(roots.get(scope).ok().and_then(|(_, style)| style))
+        } else {
+            // レイアウト境界はピクセル指定サイズで確定
+            root_available_space(box_styles.get(scope).ok())
+        };
+
+        let result = taffy_res.taffy_mut().compute_layout_with_measure(
+            scope_node,
+            available_space,
+            |known_dimensions, available_space, _node_id, text, _style| {
+                measure_text_node(known_dimensions, available_space, text, measurer)
+            },
+        );
+        if result.is_err() {
+            continue;
+        }
+        if !is_root {
+            // 境界の位置・祖先のコンテンツサイズは次回のLayoutRoot再計算で確定させる
+            if let Some(parent_node) = taffy_res.taffy().parent(scope_node) {
+                let _ = taffy_res.taffy_mut().mark_dirty(parent_node);
+            }
+        }
+        pass_stats.computed_scopes += 1;
+        debug!(
+            scope = ?scope,
+            is_root,
+            "[compute_taffy_layout] Recomputed layout scope"
+        );
+
+        // 計算結果をスコープのサブツリーに書き込む
+        let mut stack = vec![scope];
+        while let Some(entity) = stack.pop() {
+            if let Ok(entity_children) = children.get(entity) {
+                stack.extend(entity_children.iter());
+            }
+            let Some(node_id) = taffy_res.get_node(entity) else {
+                continue;
+            };
+            let Ok(mut computed) = computed_layouts.get_mut(entity) else {
+                continue;
+            };
+            let Ok(layout) = taffy_res.taffy().layout(node_id) else {
+                continue;
+            };
+            let mut new_layout = TaffyComputedLayout(*layout);
+            if entity == scope && !is_root {
+                // 単独計算ではルート扱いになるため、親に対する位置・順序は前回値を維持
+                new_layout.0.location = computed.0.location;
+                new_layout.0.order = computed.0.order;
+            }
+            pass_stats.visited_nodes += 1;
+            // 値比較で変更検知を抑制
+            if *computed != new_layout {
+                tracing::debug!(
+                    entity = ?entity,
+                    old_width = computed.0.size.width,
+                    old_height = computed.0.size.height,
+                    new_width = new_layout.0.size.width,
+                    new_height = new_layout.0.size.height,
+                    "[compute_taffy_layout] Layout changed"
+                );
+                *computed = new_layout;
+                pass_stats.changed_nodes += 1;
+            }
         }
     }
+
+    if let Some(mut stats) = stats {
+        pass_stats.pass_count = stats.pass_count + 1;
+        *stats = pass_stats;
+    }
 }
 
 /// TaffyComputedLayoutまたはDPIの変更をArrangementに反映
@@ -279,6 +531,10 @@ pub fn compute_taffy_layout_system(
 ///
 /// # Behavior
 /// どちらのトリガーでも全フィールド（offset, size, scale）を再計算
+///
+/// `BoxDisplay::None` のエンティティとその子孫には taffy がサイズ0・位置(0,0)の
+/// レイアウトを返すため、Arrangement もサイズ0となる。描画・ヒットテストからの除外は
+/// 直後の`update_display_hidden_system`が付与する`DisplayHidden`で行う。
 pub fn update_arrangements_system(
     mut commands: Commands,
     mut query: Query<
@@ -369,6 +625,69 @@ pub fn update_arrangements_system(
     }
 }
 
+/// `BoxDisplay::None`による非表示状態を`DisplayHidden`マーカーとして子孫へ伝播
+///
+/// サイズ0になってもVisualやコマンドリストは残るため、非表示のサブツリーに
+/// `DisplayHidden`を付与して描画・ヒットテストから除外する。
+/// 表示状態が変わったエンティティのSurfaceには再描画を要求する。
+///
+/// # Triggers
+/// - `BoxStyle`の変更・削除、親の付け替え（変更のあったサブツリーのみ再評価）
+pub fn update_display_hidden_system(
+    mut commands: Commands,
+    changed: Query<Entity, Or<(Changed<BoxStyle>, Changed<ChildOf>)>>,
+    mut removed_styles: RemovedComponents<BoxStyle>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    box_styles: Query<&BoxStyle>,
+    hidden: Query<(), With<DisplayHidden>>,
+    mut surfaces: Query<&mut crate::ecs::graphics::SurfaceGraphicsDirty>,
+    frame_count: Res<crate::ecs::world::FrameCount>,
+) {
+    let is_display_none = |entity: Entity| {
+        box_styles
+            .get(entity)
+            .is_ok_and(|style| style.display == Some(BoxDisplay::None))
+    };
+
+    // 状態は現在のツリーから求めるため、評価済みのエンティティは再訪不要
+    let mut visited = std::collections::HashSet::new();
+    let roots: Vec<Entity> = changed.iter().chain(removed_styles.read()).collect();
+    for root in roots {
+        // 祖先から継承する非表示状態
+        let mut inherited = false;
+        let mut current = root;
+        while let Ok(child_of) = parents.get(current) {
+            current = child_of.parent();
+            if is_display_none(current) {
+                inherited = true;
+                break;
+            }
+        }
+
+        let mut stack = vec![(root, inherited)];
+        while let Some((entity, parent_hidden)) = stack.pop() {
+            if !visited.insert(entity) {
+                continue;
+            }
+            let is_hidden = parent_hidden || is_display_none(entity);
+            if is_hidden != hidden.contains(entity) {
+                if is_hidden {
+                    commands.entity(entity).insert(DisplayHidden);
+                } else {
+                    commands.entity(entity).remove::<DisplayHidden>();
+                }
+                if let Ok(mut dirty) = surfaces.get_mut(entity) {
+                    dirty.requested_frame = frame_count.0 as u64;
+                }
+            }
+            if let Ok(entity_children) = children.get(entity) {
+                stack.extend(entity_children.iter().map(|child| (child, is_hidden)));
+            }
+        }
+    }
+}
+
 /// 削除されたエンティティのTaffyノードをクリーンアップ
 pub fn cleanup_removed_entities_system(
     mut taffy_res: ResMut<TaffyLayoutResource>,
@@ -683,12 +1002,18 @@ pub fn detect_display_change_system(
     layout_root: Query<Entity, With<LayoutRoot>>,
     mut existing_monitors: Query<(Entity, &mut crate::ecs::Monitor), With<crate::ecs::Monitor>>,
     mut taffy_res: ResMut<TaffyLayoutResource>,
+    mut anchors: Query<&mut super::Anchor>,
 ) {
     // ディスプレイ構成変更フラグをチェック
     if !app.display_configuration_changed() {
         return;
     }
 
+    // プライマリ変更・モニター削除に追従させるため、全Anchorを再解決対象にする
+    for mut anchor in anchors.iter_mut() {
+        anchor.set_changed();
+    }
+
     info!("[detect_display_change_system] Display configuration changed, updating monitors");
 
     // LayoutRootを取得
@@ -715,8 +1040,8 @@ pub fn detect_display_change_system(
     // 新規・更新Monitorの処理
     for new_monitor in new_monitors {
         if let Some((entity, existing_monitor)) = existing_map.remove(&new_monitor.handle) {
-            // 既存Monitorの更新
-            if existing_monitor != new_monitor {
+            // 既存Monitorの更新（解像度・作業領域・DPIの変更）
+            if !existing_monitor.same_geometry(&new_monitor) {
                 debug!(
                     entity = ?entity,
                     "[detect_display_change_system] Updating Monitor entity"
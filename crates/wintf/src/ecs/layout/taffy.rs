@@ -1,8 +1,10 @@
 use bevy_ecs::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use taffy::prelude::*;
 use taffy::TaffyError;
 
+use super::text_measure::TextMeasure;
+
 /// taffyのStyle
 #[derive(Component, Debug, Clone, PartialEq, Default)]
 #[repr(transparent)]
@@ -36,12 +38,14 @@ impl From<Layout> for TaffyComputedLayout {
 /// Taffyレイアウトエンジンとエンティティマッピングを管理するリソース
 #[derive(Resource)]
 pub struct TaffyLayoutResource {
-    /// Taffyレイアウトツリー
-    tree: TaffyTree<()>,
+    /// Taffyレイアウトツリー（ノードコンテキストは計測対象テキスト）
+    tree: TaffyTree<TextMeasure>,
     /// Entity → NodeId マッピング
     entity_to_node: HashMap<Entity, NodeId>,
     /// NodeId → Entity マッピング（逆引き用）
     node_to_entity: HashMap<NodeId, Entity>,
+    /// 子のレイアウトが変化したエンティティ（次回のレイアウト計算で再計算する）
+    layout_dirty: HashSet<Entity>,
 }
 
 // TaffyTreeは内部的に*const ()を持つが、ECSのリソース管理により
@@ -55,6 +59,7 @@ impl Default for TaffyLayoutResource {
             tree: TaffyTree::new(),
             entity_to_node: HashMap::new(),
             node_to_entity: HashMap::new(),
+            layout_dirty: HashSet::new(),
         }
     }
 }
@@ -69,7 +74,12 @@ impl TaffyLayoutResource {
     }
 
     /// エンティティに対応するレイアウトノードを削除する
+    ///
+    /// 親ノードのエンティティはレイアウトダーティとして記録される。
     pub fn remove_node(&mut self, entity: Entity) -> Result<(), TaffyError> {
+        if let Some(parent) = self.parent_entity(entity) {
+            self.layout_dirty.insert(parent);
+        }
         if let Some(node_id) = self.entity_to_node.remove(&entity) {
             self.node_to_entity.remove(&node_id);
             self.tree.remove(node_id)?;
@@ -87,13 +97,51 @@ impl TaffyLayoutResource {
         self.node_to_entity.get(&node_id).copied()
     }
 
+    /// taffyツリー上の親ノードに対応するエンティティを取得
+    pub fn parent_entity(&self, entity: Entity) -> Option<Entity> {
+        let node_id = self.get_node(entity)?;
+        let parent_node = self.tree.parent(node_id)?;
+        self.get_entity(parent_node)
+    }
+
+    /// エンティティの子のレイアウトが変化したことを記録する
+    ///
+    /// 親を持たないエンティティ（LayoutRoot）は自身の変更として記録する。
+    pub fn mark_layout_dirty(&mut self, entity: Entity) {
+        self.layout_dirty.insert(entity);
+    }
+
+    /// レイアウトダーティなエンティティがあるか
+    pub fn has_layout_dirty(&self) -> bool {
+        !self.layout_dirty.is_empty()
+    }
+
+    /// 記録済みのレイアウトダーティなエンティティを取り出す
+    pub fn take_layout_dirty(&mut self) -> HashSet<Entity> {
+        std::mem::take(&mut self.layout_dirty)
+    }
+
+    /// エンティティのノードに計測対象テキストを設定する（`None` で解除）
+    ///
+    /// コンテキストを持つリーフノードはレイアウト計算時にテキストの内在サイズで計測される。
+    pub fn set_text_measure(
+        &mut self,
+        entity: Entity,
+        measure: Option<TextMeasure>,
+    ) -> Result<(), TaffyError> {
+        if let Some(node_id) = self.entity_to_node.get(&entity) {
+            self.tree.set_node_context(*node_id, measure)?;
+        }
+        Ok(())
+    }
+
     /// Taffyツリーへの参照を取得
-    pub fn taffy(&self) -> &TaffyTree<()> {
+    pub fn taffy(&self) -> &TaffyTree<TextMeasure> {
         &self.tree
     }
 
     /// Taffyツリーへの可変参照を取得
-    pub fn taffy_mut(&mut self) -> &mut TaffyTree<()> {
+    pub fn taffy_mut(&mut self) -> &mut TaffyTree<TextMeasure> {
         &mut self.tree
     }
 
@@ -116,3 +164,24 @@ impl TaffyLayoutResource {
         }
     }
 }
+
+/// レイアウト計算の統計
+///
+/// `compute_taffy_layout_system`が再計算を行ったフレームで更新される（直近の1回分）。
+///
+/// # Usage
+/// ```ignore
+/// let stats = world.resource::<LayoutStats>();
+/// eprintln!("scopes: {}, visited: {}", stats.computed_scopes, stats.visited_nodes);
+/// ```
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct LayoutStats {
+    /// taffyで再計算したスコープ（LayoutRootまたはレイアウト境界）の数
+    pub computed_scopes: usize,
+    /// 計算結果を書き戻したノード数（再計算したサブツリーのノード総数）
+    pub visited_nodes: usize,
+    /// `TaffyComputedLayout`が変化したノード数
+    pub changed_nodes: usize,
+    /// 再計算の累計回数
+    pub pass_count: u64,
+}
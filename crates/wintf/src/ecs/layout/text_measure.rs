@@ -0,0 +1,433 @@
+//! テキストの内在サイズ計測（Taffy measure function 連携）
+//!
+//! `Label` / `Typewriter` / `TextBox` の内容を [`TextMeasure`] として
+//! Taffyノードのコンテキストに登録し、サイズが `Auto` の軸をレイアウト計算中に計測する。
+//!
+//! 計測処理は [`TextMeasurer`] トレイトで差し替え可能。既定ではDirectWriteを使用し、
+//! テストでは [`FixedAdvanceTextMeasurer`] で環境に依存しない決定的なメトリクスを使える。
+//!
+//! ```rust,ignore
+//! world.insert_resource(TextMeasurerResource::new(FixedAdvanceTextMeasurer::default()));
+//! ```
+
+use crate::com::dwrite::{dwrite_create_factory, DWriteFactoryExt};
+use crate::ecs::widget::text::{
+    Label, TextBox, TextDirection, Typewriter, TypewriterTalk, TypewriterToken,
+};
+use bevy_ecs::prelude::*;
+use taffy::AvailableSpace;
+use tracing::warn;
+use windows::Win32::Graphics::DirectWrite::*;
+
+/// 計測対象のテキスト
+///
+/// `sync_text_measure_system` が `Label` / `Typewriter` / `TextBox` から自動生成し、
+/// Taffyノードのコンテキストとして保持される。
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TextMeasure {
+    pub text: String,
+    pub font_family: String,
+    pub font_size: f32,
+    pub direction: TextDirection,
+}
+
+/// 論理方向（インライン/ブロック）のテキスト寸法
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextExtent {
+    /// インライン方向（横書きは幅、縦書きは高さ）
+    pub inline: f32,
+    /// ブロック方向（横書きは高さ、縦書きは幅）
+    pub block: f32,
+}
+
+impl TextExtent {
+    /// 物理サイズ（幅・高さ）へ変換
+    pub fn to_physical(self, direction: TextDirection) -> taffy::Size<f32> {
+        if direction.is_vertical() {
+            taffy::Size {
+                width: self.block,
+                height: self.inline,
+            }
+        } else {
+            taffy::Size {
+                width: self.inline,
+                height: self.block,
+            }
+        }
+    }
+}
+
+/// テキスト計測インターフェース
+pub trait TextMeasurer: Send + Sync + 'static {
+    /// インライン方向の最大長 `max_inline` で折り返したときの寸法を返す
+    ///
+    /// - `None`: 折り返しなし（max-content）
+    /// - `Some(0.0)`: 折り返し可能な位置すべてで折り返す（min-content）
+    fn measure(&self, text: &TextMeasure, max_inline: Option<f32>) -> TextExtent;
+}
+
+/// レイアウト計算で使用するテキスト計測器
+///
+/// リソースが存在しない場合、テキストノードはサイズ0として扱われる。
+#[derive(Resource)]
+pub struct TextMeasurerResource(Box<dyn TextMeasurer>);
+
+impl TextMeasurerResource {
+    pub fn new(measurer: impl TextMeasurer) -> Self {
+        Self(Box::new(measurer))
+    }
+
+    pub fn measurer(&self) -> &dyn TextMeasurer {
+        self.0.as_ref()
+    }
+}
+
+/// Taffyのmeasure function本体
+///
+/// 既知の軸はそのまま採用し、未確定の軸だけをテキストの内容から求める。
+/// 縦書きではインライン方向が高さになるため、高さ側の制約で折り返す。
+pub fn measure_text_node(
+    known_dimensions: taffy::Size<Option<f32>>,
+    available_space: taffy::Size<AvailableSpace>,
+    text: Option<&mut TextMeasure>,
+    measurer: Option<&dyn TextMeasurer>,
+) -> taffy::Size<f32> {
+    if let taffy::Size {
+        width: Some(width),
+        height: Some(height),
+    } = known_dimensions
+    {
+        return taffy::Size { width, height };
+    }
+    let (Some(text), Some(measurer)) = (text, measurer) else {
+        return taffy::Size {
+            width: known_dimensions.width.unwrap_or(0.0),
+            height: known_dimensions.height.unwrap_or(0.0),
+        };
+    };
+
+    let (known_inline, available_inline) = if text.direction.is_vertical() {
+        (known_dimensions.height, available_space.height)
+    } else {
+        (known_dimensions.width, available_space.width)
+    };
+    let max_inline = known_inline.or(match available_inline {
+        AvailableSpace::Definite(v) => Some(v),
+        AvailableSpace::MinContent => Some(0.0),
+        AvailableSpace::MaxContent => None,
+    });
+
+    let size = measurer
+        .measure(text, max_inline)
+        .to_physical(text.direction);
+    taffy::Size {
+        width: known_dimensions.width.unwrap_or(size.width),
+        height: known_dimensions.height.unwrap_or(size.height),
+    }
+}
+
+// ============================================================
+// FixedAdvanceTextMeasurer - 固定送り幅（テスト用）
+// ============================================================
+
+/// 固定送り幅のフォントを仮定したテキスト計測器（テスト用）
+///
+/// - 全角文字（U+1100以上）の送り幅は `font_size`、それ以外は `font_size * 0.5`
+/// - 行の高さは `font_size * line_height`
+/// - 空白と全角文字の前後で折り返し、`\n` で改行する（行末の空白は幅に含めない）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedAdvanceTextMeasurer {
+    pub line_height: f32,
+}
+
+impl Default for FixedAdvanceTextMeasurer {
+    fn default() -> Self {
+        Self { line_height: 1.25 }
+    }
+}
+
+impl TextMeasurer for FixedAdvanceTextMeasurer {
+    fn measure(&self, text: &TextMeasure, max_inline: Option<f32>) -> TextExtent {
+        let advance = |c: char| {
+            if c as u32 >= 0x1100 {
+                text.font_size
+            } else {
+                text.font_size * 0.5
+            }
+        };
+
+        let mut inline: f32 = 0.0;
+        let mut lines = 0;
+        for paragraph in text.text.split('\n') {
+            // 折り返し単位: (直前の空白幅, 単位の幅)
+            let mut units: Vec<(f32, f32)> = Vec::new();
+            let (mut space, mut word) = (0.0, 0.0);
+            for c in paragraph.chars() {
+                if c.is_whitespace() {
+                    if word > 0.0 {
+                        units.push((space, word));
+                        (space, word) = (0.0, 0.0);
+                    }
+                    space += advance(c);
+                } else if c as u32 >= 0x1100 {
+                    if word > 0.0 {
+                        units.push((space, word));
+                        space = 0.0;
+                    }
+                    units.push((space, advance(c)));
+                    (space, word) = (0.0, 0.0);
+                } else {
+                    word += advance(c);
+                }
+            }
+            if word > 0.0 {
+                units.push((space, word));
+            }
+
+            let mut line: f32 = 0.0;
+            for (i, (space, width)) in units.into_iter().enumerate() {
+                let fits = max_inline.is_none_or(|max| line + space + width <= max + 1e-3);
+                if i == 0 {
+                    line = width;
+                } else if fits {
+                    line += space + width;
+                } else {
+                    inline = inline.max(line);
+                    lines += 1;
+                    line = width;
+                }
+            }
+            inline = inline.max(line);
+            lines += 1;
+        }
+
+        TextExtent {
+            inline,
+            block: lines as f32 * text.font_size * self.line_height,
+        }
+    }
+}
+
+// ============================================================
+// DirectWriteTextMeasurer - DirectWriteによる計測（既定）
+// ============================================================
+
+/// 折り返しなしの場合に指定するレイアウト最大長
+const UNBOUNDED_LAYOUT_LENGTH: f32 = 1.0e7;
+
+/// DirectWriteによるテキスト計測器
+///
+/// `draw_labels` と同じTextFormat設定（ロケール `ja-JP`、標準ウェイト）で計測する。
+pub struct DirectWriteTextMeasurer {
+    factory: IDWriteFactory2,
+}
+
+// 共有DirectWriteファクトリ（DWRITE_FACTORY_TYPE_SHARED）はスレッドセーフ
+unsafe impl Send for DirectWriteTextMeasurer {}
+unsafe impl Sync for DirectWriteTextMeasurer {}
+
+impl DirectWriteTextMeasurer {
+    pub fn new() -> windows::core::Result<Self> {
+        Ok(Self {
+            factory: dwrite_create_factory(DWRITE_FACTORY_TYPE_SHARED)?,
+        })
+    }
+
+    fn create_layout(
+        &self,
+        text: &TextMeasure,
+        max_inline: Option<f32>,
+    ) -> windows::core::Result<IDWriteTextLayout> {
+        let font_family = windows::core::HSTRING::from(&text.font_family);
+        let locale = windows::core::HSTRING::from("ja-JP");
+        let format = self.factory.create_text_format(
+            &font_family,
+            None::<&IDWriteFontCollection>,
+            DWRITE_FONT_WEIGHT_NORMAL,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            text.font_size,
+            &locale,
+        )?;
+        let vertical = text.direction.is_vertical();
+        unsafe {
+            let (reading, flow) = match text.direction {
+                TextDirection::HorizontalLeftToRight => (
+                    DWRITE_READING_DIRECTION_LEFT_TO_RIGHT,
+                    DWRITE_FLOW_DIRECTION_TOP_TO_BOTTOM,
+                ),
+                TextDirection::HorizontalRightToLeft => (
+                    DWRITE_READING_DIRECTION_RIGHT_TO_LEFT,
+                    DWRITE_FLOW_DIRECTION_TOP_TO_BOTTOM,
+                ),
+                TextDirection::VerticalRightToLeft => (
+                    DWRITE_READING_DIRECTION_TOP_TO_BOTTOM,
+                    DWRITE_FLOW_DIRECTION_RIGHT_TO_LEFT,
+                ),
+                TextDirection::VerticalLeftToRight => (
+                    DWRITE_READING_DIRECTION_TOP_TO_BOTTOM,
+                    DWRITE_FLOW_DIRECTION_LEFT_TO_RIGHT,
+                ),
+            };
+            format.SetReadingDirection(reading)?;
+            format.SetFlowDirection(flow)?;
+        }
+
+        let inline = max_inline.unwrap_or(UNBOUNDED_LAYOUT_LENGTH);
+        let (max_width, max_height) = if vertical {
+            (UNBOUNDED_LAYOUT_LENGTH, inline)
+        } else {
+            (inline, UNBOUNDED_LAYOUT_LENGTH)
+        };
+        let text_hstring = windows::core::HSTRING::from(&text.text);
+        let layout =
+            self.factory
+                .create_text_layout(&text_hstring, &format, max_width, max_height)?;
+        if max_inline.is_none() {
+            unsafe { layout.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP)? };
+        }
+        Ok(layout)
+    }
+}
+
+impl TextMeasurer for DirectWriteTextMeasurer {
+    fn measure(&self, text: &TextMeasure, max_inline: Option<f32>) -> TextExtent {
+        let result = self.create_layout(text, max_inline).and_then(|layout| {
+            unsafe {
+                // min-content: 単語内で折り返さない最小のインライン長で再レイアウト
+                if max_inline == Some(0.0) {
+                    let min = layout.DetermineMinWidth()?;
+                    if text.direction.is_vertical() {
+                        layout.SetMaxHeight(min)?;
+                    } else {
+                        layout.SetMaxWidth(min)?;
+                    }
+                }
+                let mut metrics = DWRITE_TEXT_METRICS::default();
+                layout.GetMetrics(&mut metrics)?;
+                Ok(metrics)
+            }
+        });
+        match result {
+            Ok(metrics) if text.direction.is_vertical() => TextExtent {
+                inline: metrics.height,
+                block: metrics.width,
+            },
+            Ok(metrics) => TextExtent {
+                inline: metrics.width,
+                block: metrics.height,
+            },
+            Err(err) => {
+                warn!(error = ?err, font = %text.font_family, "Failed to measure text");
+                TextExtent::default()
+            }
+        }
+    }
+}
+
+// ============================================================
+// Systems
+// ============================================================
+
+/// `Label` / `Typewriter` / `TextBox` の内容から [`TextMeasure`] を同期
+///
+/// Typewriterはトーク全文（テキストトークンの連結）、TextBoxは変換中文字列を含む表示内容を
+/// 計測対象とする。
+/// 値が変わらない場合は `TextMeasure` を更新しない（再レイアウトを抑制）。
+pub fn sync_text_measure_system(
+    mut commands: Commands,
+    mut labels: Query<
+        (Entity, &Label, Option<&mut TextMeasure>),
+        (Changed<Label>, Without<Typewriter>, Without<TextBox>),
+    >,
+    mut typewriters: Query<
+        (
+            Entity,
+            &Typewriter,
+            Option<&TypewriterTalk>,
+            Option<&mut TextMeasure>,
+        ),
+        (
+            Or<(Changed<Typewriter>, Changed<TypewriterTalk>)>,
+            Without<Label>,
+            Without<TextBox>,
+        ),
+    >,
+    mut text_boxes: Query<
+        (Entity, &TextBox, Option<&mut TextMeasure>),
+        (Changed<TextBox>, Without<Label>, Without<Typewriter>),
+    >,
+    mut removed_labels: RemovedComponents<Label>,
+    mut removed_typewriters: RemovedComponents<Typewriter>,
+    mut removed_text_boxes: RemovedComponents<TextBox>,
+) {
+    for (entity, label, current) in labels.iter_mut() {
+        let measure = TextMeasure {
+            text: label.text.clone(),
+            font_family: label.font_family.clone(),
+            font_size: label.font_size,
+            direction: label.direction,
+        };
+        match current {
+            Some(mut current) => {
+                current.set_if_neq(measure);
+            }
+            None => {
+                commands.entity(entity).insert(measure);
+            }
+        }
+    }
+
+    for (entity, typewriter, talk, current) in typewriters.iter_mut() {
+        let text: String = talk
+            .map(|talk| talk.tokens())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|t| match t {
+                TypewriterToken::Text(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        let measure = TextMeasure {
+            text,
+            font_family: typewriter.font_family.clone(),
+            font_size: typewriter.font_size,
+            direction: typewriter.direction,
+        };
+        match current {
+            Some(mut current) => {
+                current.set_if_neq(measure);
+            }
+            None => {
+                commands.entity(entity).insert(measure);
+            }
+        }
+    }
+
+    for (entity, text_box, current) in text_boxes.iter_mut() {
+        let measure = TextMeasure {
+            text: text_box.display_text().0,
+            font_family: text_box.font_family.clone(),
+            font_size: text_box.font_size,
+            direction: text_box.direction,
+        };
+        match current {
+            Some(mut current) => {
+                current.set_if_neq(measure);
+            }
+            None => {
+                commands.entity(entity).insert(measure);
+            }
+        }
+    }
+
+    for entity in removed_labels
+        .read()
+        .chain(removed_typewriters.read())
+        .chain(removed_text_boxes.read())
+    {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<TextMeasure>();
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! 暗黙的レイアウトトランジション
+//!
+//! `LayoutTransition`を持つエンティティは、レイアウト結果（`Arrangement`）が変化したとき
+//! 直前の表示位置・サイズから新しい値へ時間をかけて補間される。
+//!
+//! # Window との関係
+//! Windowの位置は`WindowPos`が唯一のsource of truthのため、Windowではサイズのみを補間する。
+//! 補間中のサイズは`window_pos_sync_system`経由で`SetWindowPos`されるが、その echo
+//! （`WM_WINDOWPOSCHANGED`）では`BoxStyle.size`を書き戻さない。
+//! 書き戻すとレイアウトの目標値が中間値に置き換わり、トランジションと競合するため。
+
+use super::{Arrangement, Offset, Size};
+use crate::ecs::graphics::FrameTime;
+use crate::ecs::scroll::ScrollViewer;
+use crate::ecs::window::Window;
+use bevy_ecs::hierarchy::ChildOf;
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::DeferredWorld;
+use std::time::Duration;
+
+/// イージング関数
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    /// 等速
+    Linear,
+    /// 加速（3次）
+    EaseIn,
+    /// 減速（3次）
+    #[default]
+    EaseOut,
+    /// 加速してから減速（3次）
+    EaseInOut,
+}
+
+impl Easing {
+    /// 進捗`t`（0.0..=1.0）を補間係数に変換
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// レイアウトトランジション設定（オプトイン）
+///
+/// 挿入時に`LayoutTransitionState`が自動挿入される。
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[component(on_add = on_layout_transition_add)]
+pub struct LayoutTransition {
+    /// 補間時間
+    pub duration: Duration,
+    /// イージング
+    pub easing: Easing,
+}
+
+impl Default for LayoutTransition {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(200),
+            easing: Easing::default(),
+        }
+    }
+}
+
+impl LayoutTransition {
+    /// 補間時間とイージングを指定して作成
+    pub fn new(duration: Duration, easing: Easing) -> Self {
+        Self { duration, easing }
+    }
+}
+
+fn on_layout_transition_add(mut world: DeferredWorld, context: HookContext) {
+    if world.get::<LayoutTransitionState>(context.entity).is_none() {
+        world
+            .commands()
+            .entity(context.entity)
+            .insert(LayoutTransitionState::default());
+    }
+}
+
+/// 進行中の補間
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrangementAnimation {
+    /// 開始時の表示値
+    pub from: Arrangement,
+    /// 目標値（レイアウト結果）
+    pub to: Arrangement,
+    /// 開始時刻（`FrameTime`の経過秒）
+    pub start_secs: f64,
+}
+
+/// レイアウトトランジションの状態
+///
+/// `displayed`は直近に表示した`Arrangement`。レイアウトが`Arrangement`を書き換えると
+/// `displayed`から新しい値への補間を開始する。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct LayoutTransitionState {
+    /// 直近に表示した値（未観測なら`None`で、最初の値は補間せず採用）
+    pub displayed: Option<Arrangement>,
+    /// 進行中の補間
+    pub animation: Option<ArrangementAnimation>,
+}
+
+impl LayoutTransitionState {
+    /// 補間中か
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// レイアウトが書き込んだ新しい値を目標として補間を開始し、表示すべき値を返す
+    ///
+    /// `adopt_offset`が`true`の場合、オフセットは補間せず即座に採用する
+    /// （WindowやScrollViewerの子など、オフセットを別の仕組みが管理する場合）。
+    pub fn retarget(&mut self, target: Arrangement, now: f64, adopt_offset: bool) -> Arrangement {
+        if let Some(animation) = &mut self.animation {
+            let mut to = animation.to;
+            if adopt_offset {
+                to.offset = target.offset;
+            }
+            to.scale = target.scale;
+            if to == target {
+                // 目標サイズが同じ（オフセット・スケールのみ外部で更新）なら補間を継続
+                animation.to = target;
+                animation.from.scale = target.scale;
+                let mut displayed = self.displayed.unwrap_or(target);
+                if adopt_offset {
+                    animation.from.offset = target.offset;
+                    displayed.offset = target.offset;
+                }
+                displayed.scale = target.scale;
+                self.displayed = Some(displayed);
+                return displayed;
+            }
+        }
+        let Some(mut from) = self.displayed else {
+            self.displayed = Some(target);
+            self.animation = None;
+            return target;
+        };
+        if adopt_offset {
+            from.offset = target.offset;
+        }
+        // スケールは補間しない（DPI変更等）
+        from.scale = target.scale;
+        if from == target {
+            self.displayed = Some(target);
+            self.animation = None;
+            return target;
+        }
+        self.animation = Some(ArrangementAnimation {
+            from,
+            to: target,
+            start_secs: now,
+        });
+        self.displayed = Some(from);
+        from
+    }
+
+    /// 時刻`now`の値を計算して`displayed`を更新する。補間が終了したら`animation`を解除
+    pub fn sample(&mut self, transition: &LayoutTransition, now: f64) -> Option<Arrangement> {
+        let animation = self.animation?;
+        let duration = transition.duration.as_secs_f64();
+        let t = if duration <= 0.0 {
+            1.0
+        } else {
+            ((now - animation.start_secs) / duration).clamp(0.0, 1.0) as f32
+        };
+        let value = if t >= 1.0 {
+            self.animation = None;
+            animation.to
+        } else {
+            lerp_arrangement(&animation.from, &animation.to, transition.easing.apply(t))
+        };
+        self.displayed = Some(value);
+        Some(value)
+    }
+}
+
+/// Arrangementのオフセット・サイズを線形補間（スケールは`to`を採用）
+pub fn lerp_arrangement(from: &Arrangement, to: &Arrangement, k: f32) -> Arrangement {
+    let lerp = |a: f32, b: f32| a + (b - a) * k;
+    Arrangement {
+        offset: Offset {
+            x: lerp(from.offset.x, to.offset.x),
+            y: lerp(from.offset.y, to.offset.y),
+        },
+        scale: to.scale,
+        size: Size {
+            width: lerp(from.size.width, to.size.width),
+            height: lerp(from.size.height, to.size.height),
+        },
+    }
+}
+
+/// レイアウトトランジションシステム
+///
+/// 1. このシステム以外が`Arrangement`を変更したエンティティは、表示中の値から補間を開始する
+/// 2. 補間中のエンティティの`Arrangement`を`FrameTime`に従って更新する
+///
+/// 自身の書き込みは次回の`Changed<Arrangement>`に現れないため、補間値と目標値は区別される。
+/// `FrameTime`がない場合は補間せず即座に目標値を採用する。
+///
+/// Layoutスケジュールで`update_arrangements_system`・スクロール配置の後に実行。
+pub fn layout_transition_system(
+    frame_time: Option<Res<FrameTime>>,
+    mut query: Query<(
+        &LayoutTransition,
+        &mut LayoutTransitionState,
+        &mut Arrangement,
+        Has<Window>,
+        Option<&ChildOf>,
+    )>,
+    scroll_viewers: Query<(), With<ScrollViewer>>,
+) {
+    let now = frame_time.as_deref().map(FrameTime::elapsed_secs);
+
+    for (transition, mut state, mut arrangement, is_window, child_of) in query.iter_mut() {
+        let mut next = None;
+        if arrangement.is_changed() && state.displayed != Some(*arrangement) {
+            let target = *arrangement;
+            next = Some(match now {
+                Some(now) => {
+                    let adopt_offset =
+                        is_window || child_of.is_some_and(|c| scroll_viewers.contains(c.parent()));
+                    state.retarget(target, now, adopt_offset)
+                }
+                None => {
+                    state.displayed = Some(target);
+                    state.animation = None;
+                    target
+                }
+            });
+        }
+        if let Some(now) = now {
+            if let Some(value) = state.sample(transition, now) {
+                next = Some(value);
+            }
+        }
+        if let Some(value) = next {
+            arrangement.set_if_neq(value);
+        }
+    }
+}
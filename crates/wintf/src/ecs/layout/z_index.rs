@@ -0,0 +1,169 @@
+//! # ZIndex - 重なり順序
+//!
+//! 描画（DirectCompositionのVisual順序）とヒットテストの前後関係は、既定では
+//! `Children`の順序（後ろの子ほど前面）で決まる。`ZIndex`を付与すると
+//! 親の付け替えなしに前後関係を変更できる。
+//!
+//! - `ZIndex::Local`: 兄弟間の順序。値が大きいほど前面で、同値は`Children`の順序
+//! - `ZIndex::Global`: トップレイヤー。通常のコンテンツ（同じウィンドウ内）より常に前面に置かれ、
+//!   トップレイヤー内では値の大きいほど前面。祖先の`ClipToBounds`によるクリップを受けない
+//!
+//! トップレイヤーの要素も論理的な親子関係は変わらないため、ポインターイベントは
+//! 通常どおり`ChildOf`の祖先へバブリングする。
+//!
+//! ```rust,ignore
+//! // ツールチップをスクロール領域のクリップの外に、他の要素より前面に表示
+//! commands.spawn((tooltip_widget, ZIndex::Global(100), ChildOf(button)));
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// 重なり順序
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZIndex {
+    /// 兄弟間の順序（大きいほど前面）
+    Local(i32),
+    /// トップレイヤーでの順序（大きいほど前面）
+    Global(i32),
+}
+
+impl Default for ZIndex {
+    fn default() -> Self {
+        Self::Local(0)
+    }
+}
+
+impl ZIndex {
+    /// トップレイヤーか
+    pub fn is_global(&self) -> bool {
+        matches!(self, Self::Global(_))
+    }
+}
+
+/// エンティティがトップレイヤー（`ZIndex::Global`）か
+pub fn is_top_layer(world: &World, entity: Entity) -> bool {
+    world.get::<ZIndex>(entity).is_some_and(ZIndex::is_global)
+}
+
+/// 通常フローの子を背面→前面の順に並べる（トップレイヤーの子は除く）
+///
+/// `Local`の値で安定ソートし、同値（`ZIndex`なしは0）は`Children`の順序を保つ。
+pub fn sorted_children(
+    children: &[Entity],
+    z_index_of: impl Fn(Entity) -> Option<ZIndex>,
+) -> Vec<Entity> {
+    let mut sorted: Vec<(i32, Entity)> = children
+        .iter()
+        .filter_map(|&child| match z_index_of(child) {
+            Some(ZIndex::Global(_)) => None,
+            Some(ZIndex::Local(z)) => Some((z, child)),
+            None => Some((0, child)),
+        })
+        .collect();
+    sorted.sort_by_key(|&(z, _)| z);
+    sorted.into_iter().map(|(_, child)| child).collect()
+}
+
+/// `root`配下の描画順（背面→前面）を計算
+///
+/// 通常フローは前順走査（親 → 背面の子 → 前面の子）で、トップレイヤーの
+/// サブツリーはその後ろ（前面）に`Global`の値順（同値は発見順）で並ぶ。
+/// トップレイヤー内のトップレイヤーも同じ層に平坦化される。`root`自身の`ZIndex`は無視する。
+///
+/// `children_of`・`z_index_of`を差し替えることで、`World`とシステムのクエリの両方から使える。
+pub fn stacking_order_with<'a>(
+    root: Entity,
+    children_of: impl Fn(Entity) -> Option<&'a [Entity]>,
+    z_index_of: impl Fn(Entity) -> Option<ZIndex>,
+) -> Vec<Entity> {
+    let mut order = Vec::new();
+    let mut globals: Vec<(i32, Entity)> = Vec::new();
+    push_subtree(root, &children_of, &z_index_of, &mut order, &mut globals);
+
+    // トップレイヤー（走査中に見つかったものも含む）
+    let mut layers = Vec::new();
+    let mut next = 0;
+    while next < globals.len() {
+        let (z, entity) = globals[next];
+        let mut subtree = Vec::new();
+        push_subtree(
+            entity,
+            &children_of,
+            &z_index_of,
+            &mut subtree,
+            &mut globals,
+        );
+        layers.push((z, next, subtree));
+        next += 1;
+    }
+    layers.sort_by_key(|&(z, seq, _)| (z, seq));
+    order.extend(layers.into_iter().flat_map(|(_, _, subtree)| subtree));
+    order
+}
+
+/// 通常フローのサブツリーを前順で追加し、トップレイヤーの子は`globals`へ退避
+fn push_subtree<'a>(
+    root: Entity,
+    children_of: &impl Fn(Entity) -> Option<&'a [Entity]>,
+    z_index_of: &impl Fn(Entity) -> Option<ZIndex>,
+    order: &mut Vec<Entity>,
+    globals: &mut Vec<(i32, Entity)>,
+) {
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        order.push(entity);
+        let Some(children) = children_of(entity) else {
+            continue;
+        };
+        for &child in children {
+            if let Some(ZIndex::Global(z)) = z_index_of(child) {
+                globals.push((z, child));
+            }
+        }
+        // 背面の子から先に取り出されるよう逆順に積む
+        stack.extend(sorted_children(children, z_index_of).into_iter().rev());
+    }
+}
+
+/// `World`から`root`配下の描画順（背面→前面）を計算
+///
+/// ヒットテストはこの逆順（前面→背面）で判定する。
+pub fn stacking_order(world: &World, root: Entity) -> Vec<Entity> {
+    stacking_order_with(
+        root,
+        |entity| world.get::<Children>(entity).map(|c| &**c),
+        |entity| world.get::<ZIndex>(entity).copied(),
+    )
+}
+
+/// 兄弟Visualへの挿入位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackPosition {
+    /// 指定した兄弟の背面
+    Behind(Entity),
+    /// 指定した兄弟の前面
+    Above(Entity),
+    /// 兄弟がいない（最前面に追加）
+    Top,
+}
+
+/// 描画順に並んだ兄弟（背面→前面）の中で、`target`を挿入すべき位置
+///
+/// `is_attached`は既に親Visualに追加済みの兄弟か。前面側で最も近い追加済みの兄弟の背面、
+/// なければ背面側で最も近い兄弟の前面に挿入する。
+pub fn stack_position(
+    siblings: &[Entity],
+    target: Entity,
+    is_attached: impl Fn(Entity) -> bool,
+) -> StackPosition {
+    let Some(index) = siblings.iter().position(|&e| e == target) else {
+        return StackPosition::Top;
+    };
+    if let Some(&front) = siblings[index + 1..].iter().find(|&&e| is_attached(e)) {
+        return StackPosition::Behind(front);
+    }
+    if let Some(&back) = siblings[..index].iter().rev().find(|&&e| is_attached(e)) {
+        return StackPosition::Above(back);
+    }
+    StackPosition::Top
+}
@@ -2,10 +2,12 @@ mod app;
 pub mod common;
 pub mod drag;
 mod graphics;
+pub mod keyboard;
 pub mod layout;
 pub mod monitor;
 mod nchittest_cache;
 pub mod pointer;
+pub mod scroll;
 pub mod transform;
 pub mod widget;
 pub mod window;
@@ -30,6 +32,12 @@ pub use drag::{
 pub use graphics::FrameTime;
 pub use graphics::calculate_surface_size_from_global_arrangement;
 pub use graphics::*;
+pub use keyboard::{
+    Composition, CompositionEvent, FocusChangedEvent, FocusNavigation, FocusScope, Focusable,
+    Focused, ImeCaretRect, KeyEvent, KeyState, KeyboardInput, KeyboardState, Modifiers,
+    OnComposition, OnGotFocus, OnKeyDown, OnKeyUp, OnLostFocus, OnTextInput, TabIndex,
+    TextInputEvent, clear_focus, dispatch_keyboard_events, focused_entity, move_focus, set_focus,
+};
 pub use layout::*;
 pub use monitor::*;
 pub use pointer::{
@@ -39,6 +47,9 @@ pub use pointer::{
     debug_pointer_leave, debug_pointer_state_changes, dispatch_pointer_events,
     process_pointer_buffers,
 };
+pub use scroll::{
+    ScrollChangedEvent, ScrollState, ScrollViewer, ScrollVirtualization, VirtualizedVisual,
+};
 // 後方互換性エイリアス
 #[allow(deprecated)]
 pub use pointer::{
@@ -51,8 +62,8 @@ pub use widget::{
     WintfTaskPool, draw_bitmap_sources,
 };
 pub use widget::{
-    Typewriter, TypewriterEvent, TypewriterEventKind, TypewriterState, TypewriterTalk,
-    TypewriterTimeline, TypewriterToken, draw_typewriters, update_typewriters,
+    TextBox, TextEditor, Typewriter, TypewriterEvent, TypewriterEventKind, TypewriterState,
+    TypewriterTalk, TypewriterTimeline, TypewriterToken, draw_typewriters, update_typewriters,
 };
 pub use window::{
     DPI, DpiChangeContext, SetWindowPosCommand, Window, WindowHandle, WindowPos, WindowStyle,
@@ -149,6 +149,22 @@ impl Monitor {
     pub fn top_left(&self) -> (f32, f32) {
         (self.bounds.left as f32, self.bounds.top as f32)
     }
+
+    /// 物理座標(x, y)がモニター矩形内にあるか
+    pub fn contains_point(&self, x: f32, y: f32) -> bool {
+        let b = &self.bounds;
+        x >= b.left as f32 && x < b.right as f32 && y >= b.top as f32 && y < b.bottom as f32
+    }
+
+    /// 矩形・作業領域・DPI・プライマリフラグが一致するか
+    ///
+    /// `PartialEq`はハンドルのみを比較するため、同じモニターの構成変更検知にはこちらを使う。
+    pub fn same_geometry(&self, other: &Monitor) -> bool {
+        self.bounds == other.bounds
+            && self.work_area == other.work_area
+            && self.dpi == other.dpi
+            && self.is_primary == other.is_primary
+    }
 }
 
 /// モニター関連エラー
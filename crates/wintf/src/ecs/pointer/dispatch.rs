@@ -110,6 +110,10 @@ pub struct OnPointerMoved(pub PointerEventHandler);
 ///
 /// バブリング経路を構築する汎用ヘルパー関数。
 /// 公開APIとして、他のイベントシステム（ドラッグ等）でも利用可能。
+///
+/// 経路は論理的な親子関係（`ChildOf`）で決まり、`ZIndex`には影響されない。
+/// `ZIndex::Global`でトップレイヤーに表示された要素も、`ZIndex`を考慮した
+/// `hit_test`で前面として選ばれた後、論理的な祖先へバブリングする。
 pub fn build_bubble_path(world: &World, start: Entity) -> Vec<Entity> {
     let mut path = vec![start];
     let mut current = start;
@@ -137,25 +141,29 @@ pub fn build_bubble_path(world: &World, start: Entity) -> Vec<Entity> {
 /// - `path`: バブリングパス（sender → root）
 /// - `event`: イベントデータ
 /// - `get_handler`: ハンドラコンポーネントからハンドラ関数を取得する関数
+///
+/// # Returns
+/// 伝播が停止した場合（ハンドラが `true` を返した、または経路上のエンティティが削除された）`true`。
+/// 呼び出し側は `false` のときのみ既定動作（Tab によるフォーカス移動等）を行う。
 pub fn dispatch_event_for_handler<T: Clone, H: Component + Copy>(
     world: &mut World,
     sender: Entity,
     path: &[Entity],
     event: &T,
     get_handler: fn(&H) -> EventHandler<T>,
-) {
+) -> bool {
     // Tunnel フェーズ: root → sender
     for &entity in path.iter().rev() {
         // エンティティ存在チェック
         if world.get_entity(entity).is_err() {
-            return; // 静かに終了
+            return true; // 静かに終了
         }
 
         // ハンドラ取得
         if let Some(handler_comp) = world.get::<H>(entity).copied() {
             let handler = get_handler(&handler_comp);
             if handler(world, sender, entity, &Phase::Tunnel(event.clone())) {
-                return; // handled
+                return true; // handled
             }
         }
     }
@@ -164,17 +172,19 @@ pub fn dispatch_event_for_handler<T: Clone, H: Component + Copy>(
     for &entity in path.iter() {
         // エンティティ存在チェック
         if world.get_entity(entity).is_err() {
-            return; // 静かに終了
+            return true; // 静かに終了
         }
 
         // ハンドラ取得
         if let Some(handler_comp) = world.get::<H>(entity).copied() {
             let handler = get_handler(&handler_comp);
             if handler(world, sender, entity, &Phase::Bubble(event.clone())) {
-                return; // handled
+                return true; // handled
             }
         }
     }
+
+    false
 }
 
 /// ポインターイベントディスパッチシステム
@@ -0,0 +1,192 @@
+//! スクロールコンテナーモジュール
+//!
+//! `ScrollViewer`を付与したエンティティは子をコンテンツサイズでレイアウトし、
+//! 自身の境界でクリップ（`ClipToBounds`）、スクロールオフセット分だけ子の配置をずらす。
+//! ホイール・ドラッグ（慣性付き）でスクロールし、オフセット変化時に`ScrollChangedEvent`を送出する。
+//! `ScrollVirtualization`を付与すると実体化範囲外の子のVisualを解放する。
+//!
+//! オフセット計算と実体化範囲の判定は`ScrollState`の純粋なメソッドとして提供し、
+//! GPUなしで検証できる。
+
+mod state;
+mod systems;
+
+pub use state::{ScrollState, MIN_KINETIC_VELOCITY};
+pub use systems::{
+    apply_scroll_offset_system, drag_scroll_system, kinetic_scroll_system,
+    update_scroll_extents_system, virtualize_scroll_children_system, wheel_scroll_system,
+};
+
+use crate::ecs::drag::DragConfig;
+use crate::ecs::graphics::Visual;
+use crate::ecs::layout::{ClipToBounds, Offset};
+use crate::ecs::pointer::WheelDelta;
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::DeferredWorld;
+
+/// ホイール1ノッチの回転量（WHEEL_DELTA）
+const WHEEL_DELTA: f32 = 120.0;
+
+/// スクロールコンテナーコンポーネント
+///
+/// # ライフタイムイベント
+/// - `on_add`: `ScrollState`と`ClipToBounds`を自動挿入。
+///   `drag_scroll`が有効なら`DragConfig`（ウィンドウ移動なし）も挿入する。
+///
+/// # レイアウト
+/// スクロール軸の`overflow`は`Scroll`になり、直接の子は縮小されない（`flex_shrink = 0`）。
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[component(on_add = on_scroll_viewer_add)]
+pub struct ScrollViewer {
+    /// 水平スクロール有効
+    pub horizontal: bool,
+    /// 垂直スクロール有効
+    pub vertical: bool,
+    /// ホイール1ノッチあたりのスクロール量（論理ピクセル）
+    pub wheel_step: f32,
+    /// ドラッグでスクロールするか
+    pub drag_scroll: bool,
+    /// 慣性スクロールの減衰係数（1/秒）。速度は`exp(-friction * dt)`倍に減衰する
+    pub friction: f32,
+}
+
+impl Default for ScrollViewer {
+    fn default() -> Self {
+        Self {
+            horizontal: false,
+            vertical: true,
+            wheel_step: 48.0,
+            drag_scroll: true,
+            friction: 4.0,
+        }
+    }
+}
+
+impl ScrollViewer {
+    /// 垂直スクロールのみ
+    pub fn vertical() -> Self {
+        Self::default()
+    }
+
+    /// 水平スクロールのみ
+    pub fn horizontal() -> Self {
+        Self {
+            horizontal: true,
+            vertical: false,
+            ..Default::default()
+        }
+    }
+
+    /// 両方向
+    pub fn both() -> Self {
+        Self {
+            horizontal: true,
+            vertical: true,
+            ..Default::default()
+        }
+    }
+
+    /// ホイール回転量をスクロール量（論理ピクセル）に変換
+    ///
+    /// - 垂直ホイール上（正）はオフセットを減らし、水平ホイール右（正）は増やす
+    /// - Shift押下時、または水平スクロールのみのコンテナーでは垂直ホイールを水平に振り替える
+    /// - 無効な軸の成分は0
+    pub fn wheel_scroll_delta(&self, wheel: WheelDelta, shift: bool) -> Offset {
+        let mut dx = wheel.horizontal as f32 / WHEEL_DELTA * self.wheel_step;
+        let mut dy = -(wheel.vertical as f32) / WHEEL_DELTA * self.wheel_step;
+        if shift || !self.vertical {
+            dx += dy;
+            dy = 0.0;
+        }
+        self.filter_axes(Offset { x: dx, y: dy })
+    }
+
+    /// 無効な軸の成分を0にする
+    pub fn filter_axes(&self, delta: Offset) -> Offset {
+        Offset {
+            x: if self.horizontal { delta.x } else { 0.0 },
+            y: if self.vertical { delta.y } else { 0.0 },
+        }
+    }
+}
+
+/// ScrollViewerコンポーネントが追加されたときに呼ばれるフック
+fn on_scroll_viewer_add(mut world: DeferredWorld, context: HookContext) {
+    let entity = context.entity;
+    let Some(viewer) = world.get::<ScrollViewer>(entity).copied() else {
+        return;
+    };
+    let needs_state = world.get::<ScrollState>(entity).is_none();
+    let needs_clip = world.get::<ClipToBounds>(entity).is_none();
+    let needs_drag = viewer.drag_scroll && world.get::<DragConfig>(entity).is_none();
+
+    let mut cmds = world.commands();
+    let mut entity_cmds = cmds.entity(entity);
+    if needs_state {
+        entity_cmds.insert(ScrollState::default());
+    }
+    if needs_clip {
+        entity_cmds.insert(ClipToBounds);
+    }
+    if needs_drag {
+        entity_cmds.insert(DragConfig {
+            move_window: false,
+            ..Default::default()
+        });
+    }
+}
+
+/// スクロール仮想化設定
+///
+/// `ScrollViewer`と併用する。表示領域を`overscan`（論理ピクセル）だけ広げた実体化範囲と
+/// 交差しない子は、サブツリーの`Visual`を`VirtualizedVisual`に退避してGPUリソースを解放する。
+/// 範囲に戻ると`Visual`を復元する。
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ScrollVirtualization {
+    /// 表示領域の前後に余分に実体化する量（論理ピクセル）
+    pub overscan: f32,
+}
+
+impl Default for ScrollVirtualization {
+    fn default() -> Self {
+        Self { overscan: 100.0 }
+    }
+}
+
+/// 仮想化により退避された`Visual`
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct VirtualizedVisual(pub Visual);
+
+/// スクロールオフセット変更イベント
+#[derive(Message, Clone, Debug)]
+pub struct ScrollChangedEvent {
+    /// ScrollViewerエンティティ
+    pub target: Entity,
+    /// 新しいオフセット
+    pub offset: Offset,
+    /// 前回通知からの変化量
+    pub delta: Offset,
+}
+
+/// ScrollViewerのレイアウト調整をTaffyスタイルに適用
+///
+/// - `viewer`: 自身のScrollViewer（スクロール軸の`overflow`を`Scroll`にする）
+/// - `parent_viewer`: 親のScrollViewer（コンテンツサイズを保つため`flex_shrink`を0にする）
+pub fn apply_scroll_viewer_style(
+    style: &mut taffy::Style,
+    viewer: Option<&ScrollViewer>,
+    parent_viewer: Option<&ScrollViewer>,
+) {
+    if let Some(viewer) = viewer {
+        if viewer.horizontal {
+            style.overflow.x = taffy::Overflow::Scroll;
+        }
+        if viewer.vertical {
+            style.overflow.y = taffy::Overflow::Scroll;
+        }
+    }
+    if parent_viewer.is_some() {
+        style.flex_shrink = 0.0;
+    }
+}
@@ -0,0 +1,139 @@
+//! スクロール状態
+//!
+//! オフセットのクランプ、慣性スクロールの減衰、実体化範囲の判定を提供する。
+//! いずれもECS・GPUに依存しない純粋な計算。
+
+use crate::ecs::layout::{Offset, Size};
+use crate::ecs::pointer::PhysicalPoint;
+use bevy_ecs::prelude::*;
+use std::time::Instant;
+
+/// 慣性スクロールを停止する速度の下限（論理ピクセル/秒）
+pub const MIN_KINETIC_VELOCITY: f32 = 10.0;
+
+/// ドラッグスクロール中の直前位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ScrollDragAnchor {
+    pub position: PhysicalPoint,
+    pub timestamp: Instant,
+}
+
+/// スクロール状態コンポーネント
+///
+/// `ScrollViewer`のon_addフックで自動挿入される。
+/// `viewport`と`extent`はレイアウト後に更新され、`offset`は常に`0..=max_offset()`に保たれる。
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollState {
+    /// スクロールオフセット（論理ピクセル）
+    pub offset: Offset,
+    /// 表示領域サイズ（ScrollViewer自身のサイズ）
+    pub viewport: Size,
+    /// コンテンツサイズ
+    pub extent: Size,
+    /// 慣性スクロール速度（論理ピクセル/秒）
+    pub velocity: Offset,
+    pub(crate) drag: Option<ScrollDragAnchor>,
+    pub(crate) last_kinetic_secs: Option<f64>,
+    pub(crate) reported_offset: Offset,
+}
+
+impl ScrollState {
+    /// 最大オフセット（コンテンツが表示領域より小さい軸は0）
+    pub fn max_offset(&self) -> Offset {
+        Offset {
+            x: (self.extent.width - self.viewport.width).max(0.0),
+            y: (self.extent.height - self.viewport.height).max(0.0),
+        }
+    }
+
+    /// 指定オフセットへスクロール（範囲内にクランプ）。変化があれば`true`
+    pub fn scroll_to(&mut self, target: Offset) -> bool {
+        let max = self.max_offset();
+        let clamped = Offset {
+            x: target.x.clamp(0.0, max.x),
+            y: target.y.clamp(0.0, max.y),
+        };
+        if clamped == self.offset {
+            return false;
+        }
+        self.offset = clamped;
+        true
+    }
+
+    /// 相対スクロール（範囲内にクランプ）。変化があれば`true`
+    pub fn scroll_by(&mut self, delta: Offset) -> bool {
+        self.scroll_to(Offset {
+            x: self.offset.x + delta.x,
+            y: self.offset.y + delta.y,
+        })
+    }
+
+    /// 表示領域とコンテンツサイズを更新し、オフセットを再クランプ
+    pub fn set_bounds(&mut self, viewport: Size, extent: Size) {
+        self.viewport = viewport;
+        self.extent = Size {
+            width: extent.width.max(viewport.width),
+            height: extent.height.max(viewport.height),
+        };
+        self.scroll_to(self.offset);
+    }
+
+    /// 慣性スクロール中か
+    pub fn is_kinetic(&self) -> bool {
+        self.velocity.x != 0.0 || self.velocity.y != 0.0
+    }
+
+    /// 慣性スクロールを停止
+    pub fn stop(&mut self) {
+        self.velocity = Offset::default();
+        self.last_kinetic_secs = None;
+    }
+
+    /// 慣性スクロールを`dt`秒進める。オフセットが変化すれば`true`
+    ///
+    /// 速度は`exp(-friction * dt)`倍に減衰し、端に達した軸と
+    /// `MIN_KINETIC_VELOCITY`未満の軸は停止する。
+    pub fn step_kinetic(&mut self, dt: f32, friction: f32) -> bool {
+        if !self.is_kinetic() || dt <= 0.0 {
+            return false;
+        }
+        let changed = self.scroll_by(Offset {
+            x: self.velocity.x * dt,
+            y: self.velocity.y * dt,
+        });
+
+        let decay = (-friction * dt).exp();
+        let max = self.max_offset();
+        let settle = |velocity: f32, offset: f32, max: f32| {
+            let velocity = velocity * decay;
+            let at_edge = (velocity < 0.0 && offset <= 0.0) || (velocity > 0.0 && offset >= max);
+            if at_edge || velocity.abs() < MIN_KINETIC_VELOCITY {
+                0.0
+            } else {
+                velocity
+            }
+        };
+        self.velocity = Offset {
+            x: settle(self.velocity.x, self.offset.x, max.x),
+            y: settle(self.velocity.y, self.offset.y, max.y),
+        };
+        if !self.is_kinetic() {
+            self.last_kinetic_secs = None;
+        }
+        changed
+    }
+
+    /// 子の矩形（コンテンツ座標）が実体化範囲と交差するか
+    ///
+    /// 実体化範囲は表示領域を全方向に`overscan`だけ広げた矩形。
+    pub fn is_realized(&self, location: Offset, size: Size, overscan: f32) -> bool {
+        let start_x = self.offset.x - overscan;
+        let start_y = self.offset.y - overscan;
+        let end_x = self.offset.x + self.viewport.width + overscan;
+        let end_y = self.offset.y + self.viewport.height + overscan;
+        location.x < end_x
+            && location.x + size.width > start_x
+            && location.y < end_y
+            && location.y + size.height > start_y
+    }
+}
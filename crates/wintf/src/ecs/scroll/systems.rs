@@ -0,0 +1,307 @@
+//! スクロールシステム
+//!
+//! 入力（ホイール・ドラッグ・慣性）によるオフセット更新と、
+//! レイアウト後の表示領域更新・子の配置補正・仮想化を提供する。
+
+use super::state::ScrollDragAnchor;
+use super::{
+    ScrollChangedEvent, ScrollState, ScrollViewer, ScrollVirtualization, VirtualizedVisual,
+};
+use crate::ecs::drag::{DragEndEvent, DragEvent, DragStartEvent};
+use crate::ecs::graphics::{
+    FrameTime, SurfaceGraphics, SurfaceGraphicsDirty, Visual, VisualGraphics,
+};
+use crate::ecs::layout::{Arrangement, GlobalArrangement, Offset, Size, TaffyComputedLayout};
+use crate::ecs::pointer::{PointerState, WheelDelta};
+use bevy_ecs::hierarchy::{ChildOf, Children};
+use bevy_ecs::message::{MessageReader, MessageWriter};
+use bevy_ecs::prelude::*;
+use std::time::Duration;
+
+/// ドラッグ終了直前にこの時間以上静止していた場合は慣性スクロールしない
+const KINETIC_RELEASE_WINDOW: Duration = Duration::from_millis(100);
+
+/// ホイールスクロールシステム
+///
+/// ホイール入力を受けたエンティティから祖先方向に最も近い`ScrollViewer`をスクロールする。
+/// 端に達していてスクロールできない場合は外側の`ScrollViewer`へ伝播する。
+///
+/// Inputスケジュールで実行。
+pub fn wheel_scroll_system(
+    pointers: Query<(Entity, &PointerState)>,
+    parents: Query<&ChildOf>,
+    mut viewers: Query<(&ScrollViewer, &mut ScrollState)>,
+) {
+    for (entity, pointer) in pointers.iter() {
+        if pointer.wheel == WheelDelta::default() {
+            continue;
+        }
+        let mut current = Some(entity);
+        while let Some(target) = current {
+            if let Ok((viewer, mut state)) = viewers.get_mut(target) {
+                let delta = viewer.wheel_scroll_delta(pointer.wheel, pointer.shift_down);
+                let mut next = *state;
+                if next.scroll_by(delta) {
+                    next.stop();
+                    *state = next;
+                    break;
+                }
+            }
+            current = parents.get(target).ok().map(|c| c.parent());
+        }
+    }
+}
+
+/// ドラッグスクロールシステム
+///
+/// `ScrollViewer`をターゲットとするドラッグイベントでコンテンツを指に追従させ、
+/// ドラッグ終了時の速度を慣性スクロールに引き継ぐ。
+/// ドラッグ位置（物理ピクセル）は`GlobalArrangement`の累積スケールで論理ピクセルに変換する。
+///
+/// Inputスケジュールで`dispatch_drag_events`の後に実行。
+pub fn drag_scroll_system(
+    mut drag_starts: MessageReader<DragStartEvent>,
+    mut drags: MessageReader<DragEvent>,
+    mut drag_ends: MessageReader<DragEndEvent>,
+    mut viewers: Query<(&ScrollViewer, &mut ScrollState, Option<&GlobalArrangement>)>,
+) {
+    for event in drag_starts.read() {
+        let Ok((viewer, mut state, _)) = viewers.get_mut(event.target) else {
+            continue;
+        };
+        if !viewer.drag_scroll {
+            continue;
+        }
+        state.stop();
+        state.drag = Some(ScrollDragAnchor {
+            position: event.position,
+            timestamp: event.timestamp,
+        });
+    }
+
+    for event in drags.read() {
+        let Ok((viewer, mut state, global)) = viewers.get_mut(event.target) else {
+            continue;
+        };
+        let Some(anchor) = state.drag else {
+            continue;
+        };
+        let (scale_x, scale_y) = global
+            .map(|g| (g.scale_x(), g.scale_y()))
+            .filter(|(x, y)| *x > 0.0 && *y > 0.0)
+            .unwrap_or((1.0, 1.0));
+        // 指の移動と逆方向にオフセットが進む
+        let delta = viewer.filter_axes(Offset {
+            x: -((event.position.x - anchor.position.x) as f32) / scale_x,
+            y: -((event.position.y - anchor.position.y) as f32) / scale_y,
+        });
+        state.scroll_by(delta);
+
+        let dt = event
+            .timestamp
+            .saturating_duration_since(anchor.timestamp)
+            .as_secs_f32();
+        if dt > 0.0 {
+            state.velocity = Offset {
+                x: delta.x / dt,
+                y: delta.y / dt,
+            };
+        }
+        state.drag = Some(ScrollDragAnchor {
+            position: event.position,
+            timestamp: event.timestamp,
+        });
+    }
+
+    for event in drag_ends.read() {
+        let Ok((_, mut state, _)) = viewers.get_mut(event.target) else {
+            continue;
+        };
+        let Some(anchor) = state.drag.take() else {
+            continue;
+        };
+        let idle = event.timestamp.saturating_duration_since(anchor.timestamp);
+        if event.cancelled || idle >= KINETIC_RELEASE_WINDOW {
+            state.stop();
+        } else {
+            state.last_kinetic_secs = None;
+        }
+    }
+}
+
+/// 慣性スクロールシステム
+///
+/// ドラッグ中でない`ScrollState`の速度を`FrameTime`の経過時間で積分・減衰させる。
+///
+/// Updateスケジュールで実行。
+pub fn kinetic_scroll_system(
+    frame_time: Option<Res<FrameTime>>,
+    mut viewers: Query<(&ScrollViewer, &mut ScrollState)>,
+) {
+    let Some(frame_time) = frame_time else {
+        return;
+    };
+    let now = frame_time.elapsed_secs();
+    for (viewer, mut state) in viewers.iter_mut() {
+        if state.drag.is_some() || !state.is_kinetic() {
+            continue;
+        }
+        let dt = state
+            .last_kinetic_secs
+            .map_or(0.0, |last| (now - last).max(0.0) as f32);
+        state.last_kinetic_secs = Some(now);
+        state.step_kinetic(dt, viewer.friction);
+    }
+}
+
+/// スクロール表示領域更新システム
+///
+/// ScrollViewerのレイアウトサイズを表示領域、taffyのコンテンツサイズをスクロール範囲として
+/// `ScrollState`に反映し、オフセットを再クランプする。
+///
+/// Layoutスケジュールで`update_arrangements_system`の後に実行。
+pub fn update_scroll_extents_system(
+    mut viewers: Query<
+        (&TaffyComputedLayout, &mut ScrollState),
+        (
+            With<ScrollViewer>,
+            Or<(Changed<TaffyComputedLayout>, Added<ScrollState>)>,
+        ),
+    >,
+) {
+    for (computed_layout, mut state) in viewers.iter_mut() {
+        let layout = &computed_layout.0;
+        let mut next = *state;
+        next.set_bounds(
+            Size {
+                width: layout.size.width,
+                height: layout.size.height,
+            },
+            Size {
+                width: layout.content_size.width,
+                height: layout.content_size.height,
+            },
+        );
+        state.set_if_neq(next);
+    }
+}
+
+/// スクロールオフセット適用システム
+///
+/// ScrollViewerの直接の子の`Arrangement.offset`をtaffyの配置位置からスクロールオフセット分ずらす。
+/// 前回通知時からオフセットが変化していれば`ScrollChangedEvent`を送出する。
+///
+/// Layoutスケジュールで`update_scroll_extents_system`の後に実行。
+pub fn apply_scroll_offset_system(
+    mut viewers: Query<(Entity, &mut ScrollState, Option<&Children>), With<ScrollViewer>>,
+    mut items: Query<(&TaffyComputedLayout, &mut Arrangement)>,
+    mut scroll_changed: MessageWriter<ScrollChangedEvent>,
+) {
+    for (entity, mut state, children) in viewers.iter_mut() {
+        let offset = state.offset;
+        for child in children.into_iter().flat_map(|c| c.iter()) {
+            let Ok((computed_layout, mut arrangement)) = items.get_mut(child) else {
+                continue;
+            };
+            let scrolled = Offset {
+                x: computed_layout.0.location.x - offset.x,
+                y: computed_layout.0.location.y - offset.y,
+            };
+            if arrangement.offset != scrolled {
+                arrangement.offset = scrolled;
+            }
+        }
+
+        if state.reported_offset != offset {
+            let delta = Offset {
+                x: offset.x - state.reported_offset.x,
+                y: offset.y - state.reported_offset.y,
+            };
+            state.reported_offset = offset;
+            scroll_changed.write(ScrollChangedEvent {
+                target: entity,
+                offset,
+                delta,
+            });
+        }
+    }
+}
+
+/// スクロール仮想化システム
+///
+/// `ScrollVirtualization`を持つScrollViewerの直接の子について、実体化範囲外なら
+/// サブツリーの`Visual`を`VirtualizedVisual`に退避（VisualGraphics等も削除してGPUリソースを解放）し、
+/// 範囲内に戻れば`Visual`を復元する（on_addフックでグラフィックスが再作成される）。
+/// 判定は子自身が`Visual`または`VirtualizedVisual`を持つ場合に行う。
+///
+/// Layoutスケジュールで`apply_scroll_offset_system`の後に実行。
+pub fn virtualize_scroll_children_system(
+    mut commands: Commands,
+    viewers: Query<(&ScrollState, &ScrollVirtualization, &Children), With<ScrollViewer>>,
+    layouts: Query<&TaffyComputedLayout>,
+    visuals: Query<&Visual>,
+    stashed: Query<&VirtualizedVisual>,
+    hierarchy: Query<&Children>,
+) {
+    for (state, virtualization, children) in viewers.iter() {
+        for child in children.iter() {
+            let Ok(computed_layout) = layouts.get(child) else {
+                continue;
+            };
+            let layout = &computed_layout.0;
+            let realized = state.is_realized(
+                Offset {
+                    x: layout.location.x,
+                    y: layout.location.y,
+                },
+                Size {
+                    width: layout.size.width,
+                    height: layout.size.height,
+                },
+                virtualization.overscan,
+            );
+
+            let needs_update = if realized {
+                stashed.contains(child)
+            } else {
+                visuals.contains(child)
+            };
+            if !needs_update {
+                continue;
+            }
+
+            let mut subtree = Vec::new();
+            collect_subtree(child, &hierarchy, &mut subtree);
+            for entity in subtree {
+                if realized {
+                    if let Ok(stashed_visual) = stashed.get(entity) {
+                        commands
+                            .entity(entity)
+                            .remove::<VirtualizedVisual>()
+                            .insert(stashed_visual.0.clone());
+                    }
+                } else if let Ok(visual) = visuals.get(entity) {
+                    commands
+                        .entity(entity)
+                        .remove::<(
+                            Visual,
+                            VisualGraphics,
+                            SurfaceGraphics,
+                            SurfaceGraphicsDirty,
+                        )>()
+                        .insert(VirtualizedVisual(visual.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// エンティティとその子孫を収集
+fn collect_subtree(entity: Entity, hierarchy: &Query<&Children>, out: &mut Vec<Entity>) {
+    out.push(entity);
+    if let Ok(children) = hierarchy.get(entity) {
+        for child in children.iter() {
+            collect_subtree(child, hierarchy, out);
+        }
+    }
+}
@@ -64,7 +64,16 @@ pub struct Rotate(pub f32);
 
 impl From<Rotate> for Matrix3x2 {
     fn from(r: Rotate) -> Self {
-        Matrix3x2::rotation(r.0.to_radians())
+        // Matrix3x2::rotationは度数法（D2D1MakeRotateMatrix）のため、ここで直接組み立てる
+        let (sin, cos) = r.0.to_radians().sin_cos();
+        Matrix3x2 {
+            M11: cos,
+            M12: sin,
+            M21: -sin,
+            M22: cos,
+            M31: 0.0,
+            M32: 0.0,
+        }
     }
 }
 
@@ -154,6 +163,30 @@ impl From<Transform> for Matrix3x2 {
     }
 }
 
+impl Transform {
+    /// `TransformOrigin`を幅`width`・高さ`height`のボックスに対する相対位置として解釈した変換行列
+    ///
+    /// `From<Transform>`は`origin`をそのまま座標として扱うが、こちらは
+    /// `(origin.x * width, origin.y * height)`を基準点とする（ヒットテストで使用）。
+    pub fn matrix_for_size(&self, width: f32, height: f32) -> Matrix3x2 {
+        let origin = Translate::new(self.origin.x * width, self.origin.y * height);
+        let origin_offset = Matrix3x2::translation(-origin.x, -origin.y);
+        let origin_restore: Matrix3x2 = origin.into();
+
+        let scale_matrix: Matrix3x2 = self.scale.into();
+        let rotate_matrix: Matrix3x2 = self.rotate.into();
+        let skew_matrix: Matrix3x2 = self.skew.into();
+        let translate_matrix: Matrix3x2 = self.translate.into();
+
+        origin_offset
+            * scale_matrix
+            * rotate_matrix
+            * skew_matrix
+            * origin_restore
+            * translate_matrix
+    }
+}
+
 /// グローバル変換行列コンポーネント
 #[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
 #[repr(transparent)]
@@ -1,26 +1,62 @@
 //! αマスクデータ構造
 //!
-//! ビットパック形式（1ビット/ピクセル）でαマスクを保持し、
-//! 高速なヒット判定を提供する。
+//! αマスクを次のいずれかの形式で保持し、高速なヒット判定を提供する。
+//! - 2値（1ビット/ピクセル）: 生成時の閾値で2値化。省メモリで境界が正確
+//! - カバレッジ（8ビット/セル）: 縮小したα平均値を保持。ホバー効果などで
+//!   αの強さを参照でき、閾値の変更で再生成が不要
 
-/// 固定閾値（α ≧ 128 でヒット対象）
-const ALPHA_THRESHOLD: u8 = 128;
+/// 既定の閾値（α ≧ 128 でヒット対象）
+pub const DEFAULT_ALPHA_THRESHOLD: u8 = 128;
+
+/// αマスクの保持形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaMaskFormat {
+    /// 1ビット/ピクセル（閾値で2値化）
+    #[default]
+    Binary,
+    /// 8ビット/セルのカバレッジ
+    ///
+    /// `downsample`×`downsample`ピクセルを1セルとしてα値を平均する（0は1として扱う）。
+    /// 値を大きくするほど省メモリになり、境界の精度は下がる。
+    Coverage { downsample: u32 },
+}
+
+/// αマスクの生成設定（`HitTest::alpha`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphaMaskSettings {
+    /// ヒット判定の閾値（α ≧ threshold でヒット）
+    pub threshold: u8,
+    /// 保持形式
+    pub format: AlphaMaskFormat,
+}
+
+impl Default for AlphaMaskSettings {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_ALPHA_THRESHOLD,
+            format: AlphaMaskFormat::Binary,
+        }
+    }
+}
 
 /// αマスクデータ構造
 ///
-/// ビットパック形式でマスクデータを保持:
+/// 2値形式のビットパック:
 /// - 1ビット/ピクセル（8ピクセル/バイト）
 /// - MSBファースト（ビット7 = 最左ピクセル）
 /// - 行ごとに8ピクセル単位でアラインメント
+///
+/// カバレッジ形式は1バイト/セルの行優先配列。座標は形式によらず元画像のピクセル単位で指定する。
 #[derive(Debug, Clone)]
 pub struct AlphaMask {
     data: Vec<u8>,
     width: u32,
     height: u32,
+    settings: AlphaMaskSettings,
 }
 
 impl AlphaMask {
-    /// PBGRA32ピクセルデータからαマスクを生成
+    /// PBGRA32ピクセルデータから既定の設定（2値、閾値128）でαマスクを生成
     ///
     /// # Arguments
     /// - `pixels`: PBGRA32形式のピクセルデータ（B, G, R, A の順）
@@ -31,50 +67,115 @@ impl AlphaMask {
     /// # Returns
     /// 生成されたαマスク
     pub fn from_pbgra32(pixels: &[u8], width: u32, height: u32, stride: u32) -> Self {
-        // 行あたりのバイト数（8ピクセル単位でアラインメント）
-        let row_bytes = ((width + 7) / 8) as usize;
-        let mut data = vec![0u8; row_bytes * height as usize];
-
-        for y in 0..height {
-            let row_start = (y * stride) as usize;
-            for x in 0..width {
-                // PBGRA32: 4バイト/ピクセル、α値は4バイト目（オフセット+3）
-                let pixel_offset = row_start + (x as usize * 4);
-                let alpha = pixels.get(pixel_offset + 3).copied().unwrap_or(0);
-
-                // 閾値128で2値化
-                if alpha >= ALPHA_THRESHOLD {
-                    // MSBファースト: ビット7 = 最左ピクセル
-                    let byte_index = (y as usize * row_bytes) + (x as usize / 8);
-                    let bit_index = 7 - (x % 8);
-                    data[byte_index] |= 1 << bit_index;
+        Self::from_pbgra32_with(pixels, width, height, stride, AlphaMaskSettings::default())
+    }
+
+    /// PBGRA32ピクセルデータから指定の設定でαマスクを生成
+    pub fn from_pbgra32_with(
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        stride: u32,
+        settings: AlphaMaskSettings,
+    ) -> Self {
+        // PBGRA32: 4バイト/ピクセル、α値は4バイト目（オフセット+3）
+        let alpha_at = |x: u32, y: u32| {
+            let pixel_offset = (y * stride) as usize + (x as usize * 4);
+            pixels.get(pixel_offset + 3).copied().unwrap_or(0)
+        };
+
+        let data = match settings.format {
+            AlphaMaskFormat::Binary => {
+                // 行あたりのバイト数（8ピクセル単位でアラインメント）
+                let row_bytes = width.div_ceil(8) as usize;
+                let mut data = vec![0u8; row_bytes * height as usize];
+                for y in 0..height {
+                    for x in 0..width {
+                        if alpha_at(x, y) >= settings.threshold {
+                            // MSBファースト: ビット7 = 最左ピクセル
+                            let byte_index = (y as usize * row_bytes) + (x as usize / 8);
+                            let bit_index = 7 - (x % 8);
+                            data[byte_index] |= 1 << bit_index;
+                        }
+                    }
                 }
+                data
             }
-        }
+            AlphaMaskFormat::Coverage { downsample } => {
+                let factor = downsample.max(1);
+                let columns = width.div_ceil(factor);
+                let rows = height.div_ceil(factor);
+                let mut data = Vec::with_capacity((columns * rows) as usize);
+                for row in 0..rows {
+                    let y_range = row * factor..((row + 1) * factor).min(height);
+                    for column in 0..columns {
+                        let x_range = column * factor..((column + 1) * factor).min(width);
+                        // 画像端の欠けたセルは存在するピクセルのみで平均
+                        let mut sum = 0u64;
+                        let mut count = 0u64;
+                        for y in y_range.clone() {
+                            for x in x_range.clone() {
+                                sum += alpha_at(x, y) as u64;
+                                count += 1;
+                            }
+                        }
+                        data.push(((sum + count / 2) / count) as u8);
+                    }
+                }
+                data
+            }
+        };
 
         Self {
             data,
             width,
             height,
+            settings,
         }
     }
 
     /// 指定座標がヒット対象かを判定
     ///
     /// # Returns
-    /// - `true`: α ≧ 128 のピクセル（ヒット対象）
-    /// - `false`: α < 128 または範囲外
+    /// - `true`: α ≧ 閾値 のピクセル（ヒット対象）
+    /// - `false`: α < 閾値 または範囲外
     pub fn is_hit(&self, x: u32, y: u32) -> bool {
         // 範囲外チェック
         if x >= self.width || y >= self.height {
             return false;
         }
 
-        let row_bytes = ((self.width + 7) / 8) as usize;
-        let byte_index = (y as usize * row_bytes) + (x as usize / 8);
-        let bit_index = 7 - (x % 8);
+        match self.settings.format {
+            AlphaMaskFormat::Binary => {
+                let row_bytes = self.width.div_ceil(8) as usize;
+                let byte_index = (y as usize * row_bytes) + (x as usize / 8);
+                let bit_index = 7 - (x % 8);
 
-        (self.data[byte_index] >> bit_index) & 1 == 1
+                (self.data[byte_index] >> bit_index) & 1 == 1
+            }
+            AlphaMaskFormat::Coverage { .. } => self
+                .coverage(x, y)
+                .is_some_and(|alpha| alpha >= self.settings.threshold),
+        }
+    }
+
+    /// 指定座標のカバレッジ（α値）を取得
+    ///
+    /// 2値形式ではヒット対象なら255、それ以外は0を返す。範囲外は`None`。
+    pub fn coverage(&self, x: u32, y: u32) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        match self.settings.format {
+            AlphaMaskFormat::Binary => Some(if self.is_hit(x, y) { 255 } else { 0 }),
+            AlphaMaskFormat::Coverage { downsample } => {
+                let factor = downsample.max(1);
+                let columns = self.width.div_ceil(factor);
+                let index = (y / factor) * columns + (x / factor);
+                Some(self.data[index as usize])
+            }
+        }
     }
 
     /// マスク幅を取得
@@ -86,6 +187,35 @@ impl AlphaMask {
     pub fn height(&self) -> u32 {
         self.height
     }
+
+    /// 生成時の設定を取得
+    pub fn settings(&self) -> AlphaMaskSettings {
+        self.settings
+    }
+
+    /// マスクデータのバイト数
+    pub fn byte_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// 再生成せずに設定を適用する
+    ///
+    /// カバレッジ形式で閾値だけが異なる場合に適用できる。
+    ///
+    /// # Returns
+    /// 適用後に設定が一致していれば`true`（再生成が必要なら`false`）
+    pub fn apply_settings(&mut self, settings: AlphaMaskSettings) -> bool {
+        if self.settings.format != settings.format {
+            return false;
+        }
+        match settings.format {
+            AlphaMaskFormat::Binary => self.settings == settings,
+            AlphaMaskFormat::Coverage { .. } => {
+                self.settings.threshold = settings.threshold;
+                true
+            }
+        }
+    }
 }
 
 // Send + Sync は自動導出（Vec<u8> と u32 のみ）
@@ -203,4 +333,113 @@ mod tests {
         assert_eq!(mask.width(), 100);
         assert_eq!(mask.height(), 50);
     }
+
+    /// 1行のPBGRA32ピクセルデータ（α値のみ指定）
+    fn row_of(alphas: &[u8]) -> Vec<u8> {
+        alphas.iter().flat_map(|&a| [0, 0, 0, a]).collect()
+    }
+
+    fn coverage_settings(threshold: u8, downsample: u32) -> AlphaMaskSettings {
+        AlphaMaskSettings {
+            threshold,
+            format: AlphaMaskFormat::Coverage { downsample },
+        }
+    }
+
+    /// 閾値を指定した2値化
+    #[test]
+    fn test_custom_threshold() {
+        let pixels = row_of(&[31, 32, 200]);
+        let settings = AlphaMaskSettings {
+            threshold: 32,
+            ..Default::default()
+        };
+        let mask = AlphaMask::from_pbgra32_with(&pixels, 3, 1, 12, settings);
+
+        assert!(!mask.is_hit(0, 0));
+        assert!(mask.is_hit(1, 0));
+        assert!(mask.is_hit(2, 0));
+        assert_eq!(mask.settings(), settings);
+    }
+
+    /// 2値形式のカバレッジは 0 / 255
+    #[test]
+    fn test_binary_coverage() {
+        let pixels = row_of(&[100, 200]);
+        let mask = AlphaMask::from_pbgra32(&pixels, 2, 1, 8);
+
+        assert_eq!(mask.coverage(0, 0), Some(0));
+        assert_eq!(mask.coverage(1, 0), Some(255));
+        assert_eq!(mask.coverage(2, 0), None);
+    }
+
+    /// 等倍のカバレッジは元のα値を保持する
+    #[test]
+    fn test_full_resolution_coverage() {
+        let pixels = row_of(&[0, 64, 127, 255]);
+        let mask = AlphaMask::from_pbgra32_with(&pixels, 4, 1, 16, coverage_settings(64, 1));
+
+        assert_eq!(mask.coverage(0, 0), Some(0));
+        assert_eq!(mask.coverage(1, 0), Some(64));
+        assert_eq!(mask.coverage(2, 0), Some(127));
+        assert_eq!(mask.coverage(3, 0), Some(255));
+        assert!(!mask.is_hit(0, 0));
+        assert!(mask.is_hit(1, 0));
+        assert_eq!(mask.byte_len(), 4);
+    }
+
+    /// 縮小したカバレッジはセル内のα平均値（端の欠けたセルは存在するピクセルのみ）
+    #[test]
+    fn test_downsampled_coverage() {
+        // 3x2: 2x2セルに縮小すると [0,100,200,255 の平均, 端の 40,60 の平均]
+        let pixels = [row_of(&[0, 100, 40]), row_of(&[200, 255, 60])].concat();
+        let mask = AlphaMask::from_pbgra32_with(&pixels, 3, 2, 12, coverage_settings(128, 2));
+
+        assert_eq!(mask.width(), 3);
+        assert_eq!(mask.height(), 2);
+        assert_eq!(mask.byte_len(), 2);
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            assert_eq!(mask.coverage(x, y), Some(139));
+            assert!(mask.is_hit(x, y));
+        }
+        assert_eq!(mask.coverage(2, 0), Some(50));
+        assert!(!mask.is_hit(2, 1));
+        assert_eq!(mask.coverage(3, 0), None);
+    }
+
+    /// 縮小率0は等倍として扱う
+    #[test]
+    fn test_zero_downsample_is_full_resolution() {
+        let pixels = row_of(&[10, 20]);
+        let mask = AlphaMask::from_pbgra32_with(&pixels, 2, 1, 8, coverage_settings(15, 0));
+
+        assert_eq!(mask.coverage(0, 0), Some(10));
+        assert_eq!(mask.coverage(1, 0), Some(20));
+        assert!(!mask.is_hit(0, 0));
+        assert!(mask.is_hit(1, 0));
+    }
+
+    /// カバレッジ形式は閾値の変更を再生成なしで適用できる
+    #[test]
+    fn test_apply_settings() {
+        let pixels = row_of(&[50, 150]);
+        let mut coverage =
+            AlphaMask::from_pbgra32_with(&pixels, 2, 1, 8, coverage_settings(128, 1));
+        assert!(!coverage.is_hit(0, 0));
+
+        assert!(coverage.apply_settings(coverage_settings(40, 1)));
+        assert!(coverage.is_hit(0, 0));
+        assert_eq!(coverage.settings(), coverage_settings(40, 1));
+        // 縮小率の変更は再生成が必要
+        assert!(!coverage.apply_settings(coverage_settings(40, 2)));
+
+        // 2値形式は閾値の変更でも再生成が必要
+        let mut binary = AlphaMask::from_pbgra32(&pixels, 2, 1, 8);
+        assert!(binary.apply_settings(AlphaMaskSettings::default()));
+        assert!(!binary.apply_settings(AlphaMaskSettings {
+            threshold: 40,
+            ..Default::default()
+        }));
+        assert!(!binary.is_hit(0, 0));
+    }
 }
@@ -10,7 +10,7 @@ pub mod systems;
 mod task_pool;
 mod wic_core;
 
-pub use alpha_mask::AlphaMask;
+pub use alpha_mask::{AlphaMask, AlphaMaskFormat, AlphaMaskSettings, DEFAULT_ALPHA_THRESHOLD};
 pub use bitmap_source::BitmapSource;
 pub use resource::{BitmapSourceGraphics, BitmapSourceResource};
 pub use systems::{draw_bitmap_sources, generate_alpha_mask_system};
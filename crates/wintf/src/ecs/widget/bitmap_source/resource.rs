@@ -40,6 +40,11 @@ impl BitmapSourceResource {
         self.alpha_mask.as_ref()
     }
 
+    /// αマスクへの可変参照を取得
+    pub fn alpha_mask_mut(&mut self) -> Option<&mut AlphaMask> {
+        self.alpha_mask.as_mut()
+    }
+
     /// αマスクを設定（非同期生成完了時に呼び出し）
     pub fn set_alpha_mask(&mut self, mask: AlphaMask) {
         self.alpha_mask = Some(mask);
@@ -315,6 +315,11 @@ impl Command for SetAlphaMaskCommand {
     fn apply(self, world: &mut World) {
         // エンティティが存在し、BitmapSourceResourceを持っているか確認
         if let Ok(mut entity_ref) = world.get_entity_mut(self.entity) {
+            // 生成中に設定が変わった場合は破棄（新しい設定のタスクが後から届く）
+            let current = entity_ref.get::<HitTest>().map(|h| h.alpha);
+            if current != Some(self.mask.settings()) {
+                return;
+            }
             if let Some(mut resource) = entity_ref.get_mut::<BitmapSourceResource>() {
                 resource.set_alpha_mask(self.mask);
             }
@@ -324,33 +329,53 @@ impl Command for SetAlphaMaskCommand {
 
 /// αマスク生成システム
 ///
-/// BitmapSourceResourceが追加された時、HitTestMode::AlphaMaskの場合のみ
-/// 非同期でαマスクを生成する。
+/// BitmapSourceResourceが追加された時、またはHitTestが変更された時、
+/// HitTestMode::AlphaMaskの場合のみ非同期でαマスクを生成する。
 ///
 /// # Trigger
-/// - `Added<BitmapSourceResource>` + `With<HitTest>`
+/// - `Added<BitmapSourceResource>` または `Changed<HitTest>`
 /// - `HitTestMode::AlphaMask` の場合のみ実行
+/// - 生成済みのマスクが `HitTest::alpha` と一致する場合はスキップ
+///   （カバレッジ形式で閾値のみ異なる場合はその場で適用）
 ///
 /// # Flow
 /// 1. WIC BitmapSource からピクセルデータを取得（同期）
-/// 2. 非同期タスクでAlphaMask::from_pbgra32() でマスク生成
+/// 2. 非同期タスクでAlphaMask::from_pbgra32_with() でマスク生成
 /// 3. Command 経由で BitmapSourceResource.alpha_mask に設定
+///
+/// 再生成中は古いマスクで判定を続ける。
 pub fn generate_alpha_mask_system(
-    query: Query<(Entity, &BitmapSourceResource, &HitTest), Added<BitmapSourceResource>>,
+    mut query: Query<
+        (Entity, &mut BitmapSourceResource, &HitTest),
+        Or<(Added<BitmapSourceResource>, Changed<HitTest>)>,
+    >,
     task_pool: Option<Res<WintfTaskPool>>,
 ) {
     let Some(task_pool) = task_pool else {
         return;
     };
 
-    for (entity, resource, hit_test) in query.iter() {
+    for (entity, mut resource, hit_test) in query.iter_mut() {
         // HitTestMode::AlphaMask 以外はスキップ
         if hit_test.mode != HitTestMode::AlphaMask {
             continue;
         }
 
-        // 既にαマスクが生成済みの場合はスキップ
-        if resource.alpha_mask().is_some() {
+        // 既に同じ設定のαマスクが生成済みの場合はスキップ
+        let settings = hit_test.alpha;
+        if resource
+            .alpha_mask()
+            .is_some_and(|mask| mask.settings() == settings)
+        {
+            continue;
+        }
+
+        // 再生成なしで適用できる場合（描画には影響しないため変更検知しない）
+        if resource
+            .bypass_change_detection()
+            .alpha_mask_mut()
+            .is_some_and(|mask| mask.apply_settings(settings))
+        {
             continue;
         }
 
@@ -379,7 +404,7 @@ pub fn generate_alpha_mask_system(
         // 非同期でαマスクを生成（ピクセルデータはSend可能）
         task_pool.spawn(move |tx| async move {
             // αマスクを生成
-            let mask = AlphaMask::from_pbgra32(&buffer, width, height, stride);
+            let mask = AlphaMask::from_pbgra32_with(&buffer, width, height, stride, settings);
 
             // Commandを送信してBitmapSourceResourceにαマスクを設定
             let cmd: super::task_pool::BoxedCommand = Box::new(move |world: &mut World| {
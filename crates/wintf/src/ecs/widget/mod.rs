@@ -11,6 +11,6 @@ pub use bitmap_source::{
 pub use brushes::{Brush, BrushInherit, Brushes, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
 
 pub use text::{
-    draw_typewriters, update_typewriters, Typewriter, TypewriterEvent, TypewriterEventKind,
-    TypewriterState, TypewriterTalk, TypewriterTimeline, TypewriterToken,
+    draw_typewriters, update_typewriters, TextBox, TextEditor, Typewriter, TypewriterEvent,
+    TypewriterEventKind, TypewriterState, TypewriterTalk, TypewriterTimeline, TypewriterToken,
 };
@@ -17,6 +17,16 @@ pub enum TextDirection {
     VerticalLeftToRight,   // writing-mode: vertical-lr
 }
 
+impl TextDirection {
+    /// 縦書きか（インライン方向が垂直）
+    pub fn is_vertical(self) -> bool {
+        matches!(
+            self,
+            TextDirection::VerticalRightToLeft | TextDirection::VerticalLeftToRight
+        )
+    }
+}
+
 /// Labelコンポーネント: テキスト表示ウィジット
 ///
 /// 色は`Brushes`コンポーネントで指定します。
@@ -37,6 +47,9 @@ pub enum TextDirection {
 /// - `font_family`: フォントファミリー名 (例: "メイリオ", "Arial")
 /// - `font_size`: フォントサイズ (pt単位, 範囲: 8.0～72.0)
 /// - `direction`: テキストの方向
+///
+/// `BoxStyle` のサイズが未指定（`Auto`）の軸は、レイアウト計算時にテキストの内在サイズで
+/// 決まります（`TextMeasurerResource` による計測）。
 #[derive(Component)]
 // NOTE: on_add フックは既存の描画フロー（draw_recursive方式）と競合する
 // Phase 4（自己描画方式への移行）完了後に正常表示される
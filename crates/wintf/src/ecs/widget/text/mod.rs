@@ -1,11 +1,16 @@
 pub mod draw_labels;
 pub mod label;
+pub mod text_box;
 pub mod typewriter;
 pub mod typewriter_ir;
 pub mod typewriter_systems;
 
 pub use draw_labels::draw_labels;
 pub use label::{Label, TextDirection, TextLayoutResource};
+pub use text_box::{
+    Clipboard, ClipboardResource, EditCommand, MemoryClipboard, Movement, Selection,
+    SystemClipboard, TextBox, TextBoxLayout, TextEditor, draw_text_boxes,
+};
 pub use typewriter::{Typewriter, TypewriterLayoutCache, TypewriterState, TypewriterTalk};
 pub use typewriter_ir::{
     TimelineItem, TypewriterEvent, TypewriterEventKind, TypewriterTimeline, TypewriterToken,
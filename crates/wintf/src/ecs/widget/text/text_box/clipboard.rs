@@ -0,0 +1,105 @@
+//! Win32クリップボード（CF_UNICODETEXT）
+
+use super::command::Clipboard;
+use bevy_ecs::prelude::*;
+use tracing::warn;
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HGLOBAL};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard,
+    SetClipboardData,
+};
+use windows::Win32::System::Memory::{GMEM_MOVEABLE, GlobalAlloc, GlobalLock, GlobalUnlock};
+use windows::Win32::System::Ole::CF_UNICODETEXT;
+
+/// TextBoxが使用するクリップボード
+///
+/// リソースが存在しない場合はWin32クリップボードを使用する。
+/// テストでは`MemoryClipboard`を挿入してシステムのクリップボードから切り離す。
+#[derive(Resource)]
+pub struct ClipboardResource(Box<dyn Clipboard>);
+
+impl ClipboardResource {
+    pub fn new(clipboard: impl Clipboard + 'static) -> Self {
+        Self(Box::new(clipboard))
+    }
+
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.0.as_mut()
+    }
+}
+
+/// Win32クリップボード
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClipboard;
+
+/// 開いている間だけ保持するクリップボード
+struct OpenedClipboard;
+
+impl OpenedClipboard {
+    fn open() -> Option<Self> {
+        match unsafe { OpenClipboard(None) } {
+            Ok(()) => Some(Self),
+            Err(err) => {
+                warn!(error = ?err, "[SystemClipboard] OpenClipboard failed");
+                None
+            }
+        }
+    }
+}
+
+impl Drop for OpenedClipboard {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseClipboard();
+        }
+    }
+}
+
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        let format = CF_UNICODETEXT.0 as u32;
+        unsafe { IsClipboardFormatAvailable(format) }.ok()?;
+        let _clipboard = OpenedClipboard::open()?;
+        let handle = unsafe { GetClipboardData(format) }.ok()?;
+        let memory = HGLOBAL(handle.0);
+        let data = unsafe { GlobalLock(memory) } as *const u16;
+        if data.is_null() {
+            return None;
+        }
+        // NUL終端のUTF-16
+        let text = unsafe {
+            let len = (0..).take_while(|&i| *data.add(i) != 0).count();
+            String::from_utf16_lossy(std::slice::from_raw_parts(data, len))
+        };
+        unsafe {
+            let _ = GlobalUnlock(memory);
+        }
+        Some(text)
+    }
+
+    fn set_text(&mut self, text: &str) {
+        let Some(_clipboard) = OpenedClipboard::open() else {
+            return;
+        };
+        let units: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe {
+            let _ = EmptyClipboard();
+            let Ok(memory) = GlobalAlloc(GMEM_MOVEABLE, units.len() * size_of::<u16>()) else {
+                warn!("[SystemClipboard] GlobalAlloc failed");
+                return;
+            };
+            let data = GlobalLock(memory) as *mut u16;
+            if data.is_null() {
+                let _ = GlobalFree(Some(memory));
+                return;
+            }
+            std::ptr::copy_nonoverlapping(units.as_ptr(), data, units.len());
+            let _ = GlobalUnlock(memory);
+            // 成功すると所有権はシステムへ移る
+            if let Err(err) = SetClipboardData(CF_UNICODETEXT.0 as u32, Some(HANDLE(memory.0))) {
+                warn!(error = ?err, "[SystemClipboard] SetClipboardData failed");
+                let _ = GlobalFree(Some(memory));
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! 編集コマンド: キー入力から編集操作への対応付け
+//!
+//! 矢印キーは書字方向に応じて論理方向へ読み替える。縦書きでは↑↓が文字方向、←→が行方向。
+
+use super::editor::{Movement, TextEditor};
+use crate::ecs::keyboard::KeyEvent;
+use crate::ecs::widget::text::TextDirection;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    VIRTUAL_KEY, VK_A, VK_BACK, VK_C, VK_DELETE, VK_DOWN, VK_END, VK_HOME, VK_INSERT, VK_LEFT,
+    VK_RETURN, VK_RIGHT, VK_UP, VK_V, VK_X, VK_Y, VK_Z,
+};
+
+/// 編集コマンド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCommand {
+    /// キャレット移動（`extend`なら選択を伸ばす）
+    Move {
+        movement: Movement,
+        extend: bool,
+    },
+    /// 選択範囲または`Movement`までを削除
+    Delete(Movement),
+    InsertNewline,
+    SelectAll,
+    Copy,
+    Cut,
+    Paste,
+    Undo,
+    Redo,
+}
+
+impl EditCommand {
+    /// 編集を伴うコマンドか（読み取り専用では無効）
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Self::Delete(_)
+                | Self::InsertNewline
+                | Self::Cut
+                | Self::Paste
+                | Self::Undo
+                | Self::Redo
+        )
+    }
+
+    /// キー押下を編集コマンドに変換
+    ///
+    /// 対応しないキー（単一行での↑↓・Enter、Tab等）は`None`で、
+    /// 親への伝播やフォーカス移動に委ねる。
+    pub fn from_key_event(
+        event: &KeyEvent,
+        direction: TextDirection,
+        multiline: bool,
+    ) -> Option<Self> {
+        if !event.is_pressed() {
+            return None;
+        }
+        let modifiers = event.modifiers;
+        if modifiers.alt || modifiers.win {
+            return None;
+        }
+        let (ctrl, shift) = (modifiers.ctrl, modifiers.shift);
+        let key = |k: VIRTUAL_KEY| event.is_key(k);
+
+        if let Some(arrow) = Arrow::from_key_event(event) {
+            let movement = match (arrow.logical(direction), ctrl) {
+                (Logical::Backward, false) => Movement::PrevGrapheme,
+                (Logical::Forward, false) => Movement::NextGrapheme,
+                (Logical::Backward, true) => Movement::PrevWord,
+                (Logical::Forward, true) => Movement::NextWord,
+                (Logical::PrevLine, false) if multiline => Movement::PrevLine,
+                (Logical::NextLine, false) if multiline => Movement::NextLine,
+                _ => return None,
+            };
+            return Some(Self::Move {
+                movement,
+                extend: shift,
+            });
+        }
+
+        let command = if key(VK_HOME) || key(VK_END) {
+            let movement = match (key(VK_HOME), ctrl) {
+                (true, false) => Movement::LineStart,
+                (false, false) => Movement::LineEnd,
+                (true, true) => Movement::DocumentStart,
+                (false, true) => Movement::DocumentEnd,
+            };
+            Self::Move {
+                movement,
+                extend: shift,
+            }
+        } else if key(VK_BACK) {
+            Self::Delete(if ctrl {
+                Movement::PrevWord
+            } else {
+                Movement::PrevGrapheme
+            })
+        } else if key(VK_DELETE) && shift && !ctrl {
+            Self::Cut
+        } else if key(VK_DELETE) {
+            Self::Delete(if ctrl {
+                Movement::NextWord
+            } else {
+                Movement::NextGrapheme
+            })
+        } else if key(VK_INSERT) && ctrl && !shift {
+            Self::Copy
+        } else if key(VK_INSERT) && shift && !ctrl {
+            Self::Paste
+        } else if key(VK_RETURN) && multiline && !ctrl {
+            Self::InsertNewline
+        } else if ctrl && key(VK_A) && !shift {
+            Self::SelectAll
+        } else if ctrl && key(VK_C) && !shift {
+            Self::Copy
+        } else if ctrl && key(VK_X) && !shift {
+            Self::Cut
+        } else if ctrl && key(VK_V) && !shift {
+            Self::Paste
+        } else if ctrl && key(VK_Z) {
+            if shift { Self::Redo } else { Self::Undo }
+        } else if ctrl && key(VK_Y) && !shift {
+            Self::Redo
+        } else {
+            return None;
+        };
+        Some(command)
+    }
+
+    /// 編集モデルに適用
+    ///
+    /// 処理した（キー入力を消費した）場合`true`。選択なしのコピー等、
+    /// 何も変わらなくても対応するコマンドであれば`true`を返す。
+    pub fn execute(self, editor: &mut TextEditor, clipboard: &mut dyn Clipboard) -> bool {
+        match self {
+            Self::Move { movement, extend } => editor.move_caret(movement, extend),
+            Self::Delete(movement) => {
+                editor.delete(movement);
+            }
+            Self::InsertNewline => {
+                editor.insert_newline();
+            }
+            Self::SelectAll => editor.select_all(),
+            Self::Copy => {
+                if let Some(text) = editor.copy() {
+                    clipboard.set_text(&text);
+                }
+            }
+            Self::Cut => {
+                if let Some(text) = editor.cut() {
+                    clipboard.set_text(&text);
+                }
+            }
+            Self::Paste => {
+                if let Some(text) = clipboard.get_text() {
+                    editor.paste(&text);
+                }
+            }
+            Self::Undo => {
+                editor.undo();
+            }
+            Self::Redo => {
+                editor.redo();
+            }
+        }
+        true
+    }
+}
+
+/// 物理的な矢印キー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Arrow {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// 書字方向で読み替えた論理方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Logical {
+    Backward,
+    Forward,
+    PrevLine,
+    NextLine,
+}
+
+impl Arrow {
+    fn from_key_event(event: &KeyEvent) -> Option<Self> {
+        [
+            (VK_UP, Self::Up),
+            (VK_DOWN, Self::Down),
+            (VK_LEFT, Self::Left),
+            (VK_RIGHT, Self::Right),
+        ]
+        .into_iter()
+        .find_map(|(key, arrow)| event.is_key(key).then_some(arrow))
+    }
+
+    fn logical(self, direction: TextDirection) -> Logical {
+        use Arrow::*;
+        use Logical::*;
+        match (direction, self) {
+            (TextDirection::HorizontalLeftToRight, Left) => Backward,
+            (TextDirection::HorizontalLeftToRight, Right) => Forward,
+            (TextDirection::HorizontalRightToLeft, Left) => Forward,
+            (TextDirection::HorizontalRightToLeft, Right) => Backward,
+            (_, Up) if !direction.is_vertical() => PrevLine,
+            (_, Down) if !direction.is_vertical() => NextLine,
+            // 縦書き: 文字は上から下、行は右から左（vertical-rl）または左から右（vertical-lr）
+            (_, Up) => Backward,
+            (_, Down) => Forward,
+            (TextDirection::VerticalRightToLeft, Right) => PrevLine,
+            (TextDirection::VerticalRightToLeft, Left) => NextLine,
+            (_, Left) => PrevLine,
+            (_, Right) => NextLine,
+        }
+    }
+}
+
+// ============================================================================
+// クリップボード
+// ============================================================================
+
+/// クリップボードへのアクセス
+///
+/// 既定はWin32クリップボード（`SystemClipboard`）。`ClipboardResource`で差し替えられる。
+pub trait Clipboard: Send + Sync {
+    /// 文字列を取得（文字列がなければ`None`）
+    fn get_text(&mut self) -> Option<String>;
+    /// 文字列を設定
+    fn set_text(&mut self, text: &str);
+}
+
+/// プロセス内のみのクリップボード（テスト用）
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    pub text: Option<String>,
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_string());
+    }
+}
@@ -0,0 +1,415 @@
+use super::segment::utf16_offset;
+use super::{TextBox, TextBoxLayout};
+use crate::com::d2d::{D2D1CommandListExt, D2D1DeviceContextExt};
+use crate::com::dwrite::{DWriteFactoryExt, DWriteTextLayoutExt};
+use crate::ecs::TextLayoutMetrics;
+use crate::ecs::graphics::{GraphicsCommandList, GraphicsCore};
+use crate::ecs::keyboard::{Focused, ImeCaretRect};
+use crate::ecs::layout::GlobalArrangement;
+use crate::ecs::widget::brushes::{Brushes, DEFAULT_FOREGROUND};
+use crate::ecs::widget::text::TextDirection;
+use bevy_ecs::prelude::*;
+use std::ops::Range;
+use tracing::{debug, warn};
+use windows::Win32::Graphics::Direct2D::Common::{D2D_RECT_F, D2D1_COLOR_F};
+use windows::Win32::Graphics::Direct2D::{D2D1_DRAW_TEXT_OPTIONS_NONE, ID2D1DeviceContext};
+use windows::Win32::Graphics::DirectWrite::*;
+use windows_numerics::Vector2;
+
+/// 透明色定数（内部使用）
+const TRANSPARENT_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 0.0,
+    g: 0.0,
+    b: 0.0,
+    a: 0.0,
+};
+
+/// 選択範囲の背景色（フォーカス中）
+const SELECTION_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 0.0,
+    g: 0.47,
+    b: 0.84,
+    a: 0.4,
+};
+
+/// 選択範囲の背景色（フォーカスなし）
+const INACTIVE_SELECTION_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 0.5,
+    g: 0.5,
+    b: 0.5,
+    a: 0.3,
+};
+
+/// キャレット・変換中文字列の下線の太さ（変換対象の文節は2倍）
+const CARET_WIDTH: f32 = 1.0;
+
+/// TextBoxコンポーネントから GraphicsCommandList を生成
+///
+/// `Label`と同様にテキストレイアウトを生成し、選択範囲・テキスト・変換中文字列の下線・
+/// キャレット（フォーカス中のみ）をCommandListに記録する。
+/// 変換中文字列はキャレット位置に挿入して表示する。
+///
+/// あわせて、フォーカス中のTextBoxの`ImeCaretRect`をキャレット位置（スクリーン座標）に更新する。
+#[allow(clippy::type_complexity)]
+pub fn draw_text_boxes(
+    mut commands: Commands,
+    query: Query<(
+        Entity,
+        Ref<TextBox>,
+        Ref<Brushes>,
+        Has<Focused>,
+        Option<&TextBoxLayout>,
+        Has<GraphicsCommandList>,
+        Option<&GlobalArrangement>,
+        Option<&ImeCaretRect>,
+    )>,
+    graphics_core: Option<Res<GraphicsCore>>,
+) {
+    let Some(graphics_core) = graphics_core else {
+        warn!("GraphicsCore not available, skipping draw_text_boxes");
+        return;
+    };
+
+    let Some(dwrite_factory) = graphics_core.dwrite_factory() else {
+        warn!("DirectWrite factory not available");
+        return;
+    };
+
+    let Some(dc) = graphics_core.device_context() else {
+        warn!("DeviceContext not available");
+        return;
+    };
+
+    for (entity, text_box, brushes, focused, cached, has_command_list, arrangement, caret_rect) in
+        query.iter()
+    {
+        let dirty = text_box.is_changed()
+            || brushes.is_changed()
+            || !has_command_list
+            || cached.is_none_or(|cached| cached.focused != focused);
+
+        let (text, composition_start) = text_box.display_text();
+        let caret = match text_box.composition() {
+            Some(composition) => composition_start + composition.caret,
+            None => text_box.editor().caret(),
+        };
+
+        // キャレット位置をIMEへ（レイアウトの再生成を伴わない移動にも追従する）
+        if !dirty {
+            if let Some(cached) = cached.filter(|_| focused) {
+                update_ime_caret_rect(
+                    &mut commands,
+                    entity,
+                    cached,
+                    &text,
+                    caret,
+                    text_box.direction,
+                    arrangement,
+                    caret_rect,
+                );
+            }
+            continue;
+        }
+
+        #[cfg(debug_assertions)]
+        debug!(
+            entity = ?entity,
+            text = %text,
+            caret,
+            focused,
+            "Drawing text box"
+        );
+
+        let color = brushes
+            .foreground
+            .as_color()
+            .unwrap_or_else(|| DEFAULT_FOREGROUND.as_color().unwrap());
+
+        let Some(text_layout) = create_layout(dwrite_factory, &text_box, &text, entity) else {
+            continue;
+        };
+
+        let mut metrics = DWRITE_TEXT_METRICS::default();
+        unsafe {
+            let _ = text_layout.GetMetrics(&mut metrics);
+        }
+        // RTL・縦書きの負の領域を正の領域に持ってくる（draw_labelsと同じ）
+        let origin = Vector2 {
+            X: -metrics.left,
+            Y: -metrics.top,
+        };
+
+        let command_list = match unsafe { dc.CreateCommandList() } {
+            Ok(cl) => cl,
+            Err(err) => {
+                warn!(entity = ?entity, error = ?err, "Failed to create CommandList");
+                continue;
+            }
+        };
+        unsafe {
+            dc.SetTarget(&command_list);
+            dc.BeginDraw();
+        }
+        dc.clear(Some(&TRANSPARENT_COLOR));
+
+        let drawn = draw_contents(
+            dc,
+            &text_layout,
+            origin,
+            &text_box,
+            &text,
+            composition_start,
+            caret,
+            color,
+            focused,
+        );
+
+        if let Err(err) = unsafe { dc.EndDraw(None, None) } {
+            warn!(entity = ?entity, error = ?err, "EndDraw failed");
+            continue;
+        }
+        if let Err(err) = drawn {
+            warn!(entity = ?entity, error = ?err, "Failed to draw text box");
+            continue;
+        }
+        if let Err(err) = command_list.close() {
+            warn!(entity = ?entity, error = ?err, "Failed to close CommandList");
+            continue;
+        }
+
+        let layout = TextBoxLayout {
+            layout: text_layout,
+            origin,
+            composing: text_box.composition().is_some(),
+            focused,
+        };
+        if focused {
+            update_ime_caret_rect(
+                &mut commands,
+                entity,
+                &layout,
+                &text,
+                caret,
+                text_box.direction,
+                arrangement,
+                caret_rect,
+            );
+        }
+        commands.entity(entity).insert((
+            GraphicsCommandList::new(command_list),
+            layout,
+            TextLayoutMetrics {
+                width: metrics.width,
+                height: metrics.height,
+            },
+        ));
+    }
+}
+
+/// TextFormat・TextLayoutを作成（Labelと同じ書字方向設定、折り返しなし）
+fn create_layout(
+    dwrite_factory: &IDWriteFactory2,
+    text_box: &TextBox,
+    text: &str,
+    entity: Entity,
+) -> Option<IDWriteTextLayout> {
+    let font_family_hstring = windows::core::HSTRING::from(&text_box.font_family);
+    let locale_hstring = windows::core::HSTRING::from("ja-JP");
+    let text_format = dwrite_factory
+        .create_text_format(
+            &font_family_hstring,
+            None::<&IDWriteFontCollection>,
+            DWRITE_FONT_WEIGHT_NORMAL,
+            DWRITE_FONT_STYLE_NORMAL,
+            DWRITE_FONT_STRETCH_NORMAL,
+            text_box.font_size,
+            &locale_hstring,
+        )
+        .inspect_err(|err| warn!(entity = ?entity, error = ?err, "Failed to create TextFormat"))
+        .ok()?;
+
+    let (reading, flow) = match text_box.direction {
+        TextDirection::HorizontalLeftToRight => (
+            DWRITE_READING_DIRECTION_LEFT_TO_RIGHT,
+            DWRITE_FLOW_DIRECTION_TOP_TO_BOTTOM,
+        ),
+        TextDirection::HorizontalRightToLeft => (
+            DWRITE_READING_DIRECTION_RIGHT_TO_LEFT,
+            DWRITE_FLOW_DIRECTION_TOP_TO_BOTTOM,
+        ),
+        TextDirection::VerticalRightToLeft => (
+            DWRITE_READING_DIRECTION_TOP_TO_BOTTOM,
+            DWRITE_FLOW_DIRECTION_RIGHT_TO_LEFT,
+        ),
+        TextDirection::VerticalLeftToRight => (
+            DWRITE_READING_DIRECTION_TOP_TO_BOTTOM,
+            DWRITE_FLOW_DIRECTION_LEFT_TO_RIGHT,
+        ),
+    };
+    unsafe {
+        let _ = text_format.SetReadingDirection(reading);
+        let _ = text_format.SetFlowDirection(flow);
+    }
+
+    let text_hstring = windows::core::HSTRING::from(text);
+    let text_layout = dwrite_factory
+        .create_text_layout(&text_hstring, &text_format, 0.0, 0.0)
+        .inspect_err(|err| warn!(entity = ?entity, error = ?err, "Failed to create TextLayout"))
+        .ok()?;
+    unsafe {
+        let _ = text_layout.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP);
+    }
+    Some(text_layout)
+}
+
+/// 選択範囲・テキスト・変換中文字列の下線・キャレットを描画
+#[allow(clippy::too_many_arguments)]
+fn draw_contents(
+    dc: &ID2D1DeviceContext,
+    text_layout: &IDWriteTextLayout,
+    origin: Vector2,
+    text_box: &TextBox,
+    text: &str,
+    composition_start: usize,
+    caret: usize,
+    color: D2D1_COLOR_F,
+    focused: bool,
+) -> windows::core::Result<()> {
+    let brush = dc.create_solid_color_brush(&color, None)?;
+    let vertical = text_box.direction.is_vertical();
+
+    // 選択範囲（変換中は選択範囲を削除済み）
+    let selection = text_box.editor().selection().range();
+    if !selection.is_empty() {
+        let selection_color = if focused {
+            SELECTION_COLOR
+        } else {
+            INACTIVE_SELECTION_COLOR
+        };
+        let selection_brush = dc.create_solid_color_brush(&selection_color, None)?;
+        for rect in range_rects(text_layout, origin, text, selection)? {
+            dc.fill_rectangle(&rect, &selection_brush);
+        }
+    }
+
+    dc.draw_text_layout(origin, text_layout, &brush, D2D1_DRAW_TEXT_OPTIONS_NONE);
+
+    // 変換中文字列の下線（横書きは下、縦書きは右）
+    if let Some(composition) = text_box.composition() {
+        for clause in &composition.clauses {
+            let range =
+                composition_start + clause.range.start..composition_start + clause.range.end;
+            let thickness = if clause.attribute.is_target() {
+                CARET_WIDTH * 2.0
+            } else {
+                CARET_WIDTH
+            };
+            for rect in range_rects(text_layout, origin, text, range)? {
+                // 文節の境界が分かるよう、インライン方向を少し縮める
+                let underline = if vertical {
+                    D2D_RECT_F {
+                        left: rect.right - thickness,
+                        top: rect.top + 1.0,
+                        right: rect.right,
+                        bottom: rect.bottom - 1.0,
+                    }
+                } else {
+                    D2D_RECT_F {
+                        left: rect.left + 1.0,
+                        top: rect.bottom - thickness,
+                        right: rect.right - 1.0,
+                        bottom: rect.bottom,
+                    }
+                };
+                dc.fill_rectangle(&underline, &brush);
+            }
+        }
+    }
+
+    if focused {
+        let rect = caret_rect(text_layout, origin, text, caret, vertical)?;
+        dc.fill_rectangle(&rect, &brush);
+    }
+    Ok(())
+}
+
+/// テキスト範囲の矩形（行ごと、ローカル座標）
+fn range_rects(
+    text_layout: &IDWriteTextLayout,
+    origin: Vector2,
+    text: &str,
+    range: Range<usize>,
+) -> windows::core::Result<Vec<D2D_RECT_F>> {
+    let start = utf16_offset(text, range.start);
+    let end = utf16_offset(text, range.end);
+    Ok(text_layout
+        .hit_test_text_range(start, end - start, origin.X, origin.Y)?
+        .into_iter()
+        .map(|m| D2D_RECT_F {
+            left: m.left,
+            top: m.top,
+            right: m.left + m.width,
+            bottom: m.top + m.height,
+        })
+        .collect())
+}
+
+/// キャレットの矩形（ローカル座標）。横書きは縦線、縦書きは横線
+fn caret_rect(
+    text_layout: &IDWriteTextLayout,
+    origin: Vector2,
+    text: &str,
+    caret: usize,
+    vertical: bool,
+) -> windows::core::Result<D2D_RECT_F> {
+    let hit = text_layout.hit_test_text_position(utf16_offset(text, caret), false)?;
+    let x = origin.X + hit.point_x;
+    let y = origin.Y + hit.point_y;
+    Ok(if vertical {
+        let left = origin.X + hit.metrics.left;
+        D2D_RECT_F {
+            left,
+            top: y,
+            right: left + hit.metrics.width,
+            bottom: y + CARET_WIDTH,
+        }
+    } else {
+        let top = origin.Y + hit.metrics.top;
+        D2D_RECT_F {
+            left: x,
+            top,
+            right: x + CARET_WIDTH,
+            bottom: top + hit.metrics.height,
+        }
+    })
+}
+
+/// キャレット矩形をスクリーン座標に変換して`ImeCaretRect`を更新（変化時のみ）
+#[allow(clippy::too_many_arguments)]
+fn update_ime_caret_rect(
+    commands: &mut Commands,
+    entity: Entity,
+    layout: &TextBoxLayout,
+    text: &str,
+    caret: usize,
+    direction: TextDirection,
+    arrangement: Option<&GlobalArrangement>,
+    current: Option<&ImeCaretRect>,
+) {
+    let Some(arrangement) = arrangement else {
+        return;
+    };
+    let Ok(rect) = caret_rect(
+        &layout.layout,
+        layout.origin,
+        text,
+        caret,
+        direction.is_vertical(),
+    ) else {
+        return;
+    };
+    let screen = ImeCaretRect(arrangement.to_screen_rect(rect));
+    if current != Some(&screen) {
+        commands.entity(entity).insert(screen);
+    }
+}
@@ -0,0 +1,444 @@
+//! 編集モデル: バッファ・選択範囲・キャレット移動・取り消し履歴
+//!
+//! DirectWriteに依存しない純粋なRust実装。オフセットはUTF-8のバイトオフセットで、
+//! 常に書記素クラスタ境界を指す。行は改行（LF）で区切られた論理行。
+
+use super::segment::{
+    grapheme_column, line_end, line_start, next_grapheme_boundary, next_word_boundary,
+    offset_at_column, prev_grapheme_boundary, prev_word_boundary, snap_to_grapheme, word_range_at,
+};
+use std::ops::Range;
+
+/// 取り消し履歴の既定の上限
+pub const DEFAULT_UNDO_LIMIT: usize = 100;
+
+/// 選択範囲
+///
+/// `anchor`は選択を始めた位置、`caret`はキャレット位置。同じなら選択なし。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Selection {
+    pub anchor: usize,
+    pub caret: usize,
+}
+
+impl Selection {
+    pub fn new(anchor: usize, caret: usize) -> Self {
+        Self { anchor, caret }
+    }
+
+    /// 選択なし（キャレットのみ）
+    pub fn collapsed(offset: usize) -> Self {
+        Self::new(offset, offset)
+    }
+
+    /// 先頭から末尾への範囲
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.caret)..self.anchor.max(self.caret)
+    }
+
+    /// 選択なしか
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.caret
+    }
+}
+
+/// キャレット移動の単位（論理順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    PrevGrapheme,
+    NextGrapheme,
+    PrevWord,
+    NextWord,
+    LineStart,
+    LineEnd,
+    PrevLine,
+    NextLine,
+    DocumentStart,
+    DocumentEnd,
+}
+
+/// 編集の種類（連続入力をまとめて取り消すため）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Backspace,
+    Delete,
+    Other,
+}
+
+/// 取り消し履歴の1項目: `at`から`removed`を`inserted`に置き換えた
+#[derive(Debug, Clone)]
+struct Edit {
+    at: usize,
+    removed: String,
+    inserted: String,
+    before: Selection,
+    after: Selection,
+    kind: EditKind,
+}
+
+impl Edit {
+    /// 直後の編集`next`をまとめられるならまとめる
+    fn merge(&mut self, next: &Edit) -> bool {
+        let merged = match (self.kind, next.kind) {
+            (EditKind::Typing, EditKind::Typing) => {
+                // 空白の後に単語が始まったら区切る
+                let word_starts = self.inserted.ends_with(char::is_whitespace)
+                    && !next.inserted.starts_with(char::is_whitespace);
+                next.removed.is_empty() && next.at == self.at + self.inserted.len() && !word_starts
+            }
+            (EditKind::Backspace, EditKind::Backspace) => next.at + next.removed.len() == self.at,
+            (EditKind::Delete, EditKind::Delete) => next.at == self.at,
+            _ => false,
+        };
+        if !merged {
+            return false;
+        }
+        match self.kind {
+            EditKind::Typing => self.inserted.push_str(&next.inserted),
+            EditKind::Backspace => {
+                self.removed.insert_str(0, &next.removed);
+                self.at = next.at;
+            }
+            _ => self.removed.push_str(&next.removed),
+        }
+        self.after = next.after;
+        true
+    }
+}
+
+/// テキスト編集モデル
+///
+/// ```ignore
+/// let mut editor = TextEditor::new("Hello");
+/// editor.move_caret(Movement::PrevWord, true);
+/// editor.insert("World");
+/// assert_eq!(editor.text(), "World");
+/// editor.undo();
+/// assert_eq!(editor.text(), "Hello");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextEditor {
+    text: String,
+    selection: Selection,
+    multiline: bool,
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+    undo_limit: usize,
+    /// 次の編集を直前の履歴項目にまとめてよいか（キャレット移動・取り消しで解除）
+    coalesce: bool,
+    /// 行移動で維持する桁（書記素クラスタ数）
+    preferred_column: Option<usize>,
+}
+
+impl Default for TextEditor {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl TextEditor {
+    /// 単一行の編集モデルを作成（キャレットは末尾）
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::with_mode(text.into(), false)
+    }
+
+    /// 複数行の編集モデルを作成（キャレットは末尾）
+    pub fn multiline(text: impl Into<String>) -> Self {
+        Self::with_mode(text.into(), true)
+    }
+
+    fn with_mode(text: String, multiline: bool) -> Self {
+        let text = normalize(&text, multiline);
+        Self {
+            selection: Selection::collapsed(text.len()),
+            text,
+            multiline,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            undo_limit: DEFAULT_UNDO_LIMIT,
+            coalesce: false,
+            preferred_column: None,
+        }
+    }
+
+    /// 取り消し履歴の上限を設定
+    pub fn with_undo_limit(mut self, limit: usize) -> Self {
+        self.undo_limit = limit;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    pub fn caret(&self) -> usize {
+        self.selection.caret
+    }
+
+    /// 選択中の文字列
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection.range()]
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// 文字列を置き換える（取り消し履歴は消去、キャレットは末尾）
+    pub fn set_text(&mut self, text: &str) {
+        self.text = normalize(text, self.multiline);
+        self.selection = Selection::collapsed(self.text.len());
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.coalesce = false;
+        self.preferred_column = None;
+    }
+
+    // ========================================================================
+    // 選択・キャレット移動
+    // ========================================================================
+
+    /// 選択範囲を設定（書記素クラスタ境界に丸める）
+    pub fn set_selection(&mut self, selection: Selection) {
+        self.selection = Selection::new(
+            snap_to_grapheme(&self.text, selection.anchor),
+            snap_to_grapheme(&self.text, selection.caret),
+        );
+        self.coalesce = false;
+        self.preferred_column = None;
+    }
+
+    /// キャレットを`offset`へ（`extend`なら選択を伸ばす）
+    pub fn set_caret(&mut self, offset: usize, extend: bool) {
+        let anchor = if extend {
+            self.selection.anchor
+        } else {
+            offset
+        };
+        self.set_selection(Selection::new(anchor, offset));
+    }
+
+    /// すべて選択
+    pub fn select_all(&mut self) {
+        self.set_selection(Selection::new(0, self.text.len()));
+    }
+
+    /// `offset`の単語を選択
+    pub fn select_word_at(&mut self, offset: usize) {
+        let range = word_range_at(&self.text, offset);
+        self.set_selection(Selection::new(range.start, range.end));
+    }
+
+    /// キャレットを移動（`extend`なら選択を伸ばす）
+    ///
+    /// 選択がある状態で伸ばさずに1文字移動すると、選択の端へ移動する。
+    pub fn move_caret(&mut self, movement: Movement, extend: bool) {
+        let range = self.selection.range();
+        let target = match movement {
+            Movement::PrevGrapheme if !extend && !range.is_empty() => range.start,
+            Movement::NextGrapheme if !extend && !range.is_empty() => range.end,
+            _ => self.target(movement),
+        };
+        let column = self
+            .preferred_column
+            .unwrap_or_else(|| grapheme_column(&self.text, self.caret()));
+        self.set_caret(target, extend);
+        // 行移動が続く間は、短い行を通過しても元の桁を保つ
+        if matches!(movement, Movement::PrevLine | Movement::NextLine) {
+            self.preferred_column = Some(column);
+        }
+    }
+
+    /// キャレットから`movement`だけ移動した位置
+    fn target(&self, movement: Movement) -> usize {
+        let text = self.text.as_str();
+        let caret = self.caret();
+        let column = || {
+            self.preferred_column
+                .unwrap_or_else(|| grapheme_column(text, caret))
+        };
+        match movement {
+            Movement::PrevGrapheme => prev_grapheme_boundary(text, caret),
+            Movement::NextGrapheme => next_grapheme_boundary(text, caret),
+            Movement::PrevWord => prev_word_boundary(text, caret),
+            Movement::NextWord => next_word_boundary(text, caret),
+            Movement::LineStart => line_start(text, caret),
+            Movement::LineEnd => line_end(text, caret),
+            Movement::PrevLine => match line_start(text, caret) {
+                0 => 0,
+                start => offset_at_column(text, line_start(text, start - 1), column()),
+            },
+            Movement::NextLine => {
+                let end = line_end(text, caret);
+                if end == text.len() {
+                    end
+                } else {
+                    offset_at_column(text, end + 1, column())
+                }
+            }
+            Movement::DocumentStart => 0,
+            Movement::DocumentEnd => text.len(),
+        }
+    }
+
+    // ========================================================================
+    // 編集
+    // ========================================================================
+
+    /// 選択範囲を`text`で置き換える（文字入力）
+    ///
+    /// 改行は複数行ならLFに、単一行なら空白に変換する。連続した入力は1回で取り消せる。
+    pub fn insert(&mut self, text: &str) -> bool {
+        self.replace_selection(text, EditKind::Typing)
+    }
+
+    /// 貼り付け（`insert`と同じだが、直前の入力とまとめない）
+    pub fn paste(&mut self, text: &str) -> bool {
+        self.replace_selection(text, EditKind::Other)
+    }
+
+    /// 改行を挿入（単一行では何もしない）
+    pub fn insert_newline(&mut self) -> bool {
+        self.multiline && self.replace_selection("\n", EditKind::Other)
+    }
+
+    /// 選択範囲、選択がなければキャレットから`movement`までを削除
+    ///
+    /// Backspaceは`Movement::PrevGrapheme`、Deleteは`Movement::NextGrapheme`。
+    pub fn delete(&mut self, movement: Movement) -> bool {
+        if !self.selection.is_empty() {
+            return self.delete_selection();
+        }
+        let caret = self.caret();
+        let target = self.target(movement);
+        let kind = if target < caret {
+            EditKind::Backspace
+        } else {
+            EditKind::Delete
+        };
+        self.replace(target.min(caret)..target.max(caret), "", kind)
+    }
+
+    /// 選択範囲を削除
+    pub fn delete_selection(&mut self) -> bool {
+        self.replace(self.selection.range(), "", EditKind::Other)
+    }
+
+    /// 選択中の文字列（選択がなければ`None`）
+    pub fn copy(&self) -> Option<String> {
+        (!self.selection.is_empty()).then(|| self.selected_text().to_string())
+    }
+
+    /// 選択中の文字列を削除して返す
+    pub fn cut(&mut self) -> Option<String> {
+        let text = self.copy()?;
+        self.delete_selection();
+        Some(text)
+    }
+
+    fn replace_selection(&mut self, text: &str, kind: EditKind) -> bool {
+        let text = normalize(text, self.multiline);
+        self.replace(self.selection.range(), &text, kind)
+    }
+
+    /// `range`を`inserted`で置き換えて履歴に積む
+    fn replace(&mut self, range: Range<usize>, inserted: &str, kind: EditKind) -> bool {
+        if range.is_empty() && inserted.is_empty() {
+            return false;
+        }
+        let removed = self.text[range.clone()].to_string();
+        self.text.replace_range(range.clone(), inserted);
+        let edit = Edit {
+            at: range.start,
+            removed,
+            inserted: inserted.to_string(),
+            before: self.selection,
+            after: Selection::collapsed(range.start + inserted.len()),
+            kind,
+        };
+        self.selection = edit.after;
+        self.preferred_column = None;
+        self.redo_stack.clear();
+
+        let merged = self.coalesce
+            && self
+                .undo_stack
+                .last_mut()
+                .is_some_and(|last| last.merge(&edit));
+        if !merged {
+            self.undo_stack.push(edit);
+            if self.undo_stack.len() > self.undo_limit {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.coalesce = kind != EditKind::Other;
+        true
+    }
+
+    // ========================================================================
+    // 取り消し・やり直し
+    // ========================================================================
+
+    /// 直前の編集を取り消す
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.text
+            .replace_range(edit.at..edit.at + edit.inserted.len(), &edit.removed);
+        self.selection = edit.before;
+        self.redo_stack.push(edit);
+        self.coalesce = false;
+        self.preferred_column = None;
+        true
+    }
+
+    /// 取り消した編集をやり直す
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.text
+            .replace_range(edit.at..edit.at + edit.removed.len(), &edit.inserted);
+        self.selection = edit.after;
+        self.undo_stack.push(edit);
+        self.coalesce = false;
+        self.preferred_column = None;
+        true
+    }
+}
+
+/// 入力文字列の正規化
+///
+/// CRLF・CRはLFに（単一行では空白に）。タブ以外の制御文字は取り除く。
+fn normalize(text: &str, multiline: bool) -> String {
+    let newline = if multiline { '\n' } else { ' ' };
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                result.push(newline);
+            }
+            '\n' => result.push(newline),
+            '\t' => result.push(c),
+            _ if c.is_control() => {}
+            _ => result.push(c),
+        }
+    }
+    result
+}
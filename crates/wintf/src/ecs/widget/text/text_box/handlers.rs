@@ -0,0 +1,218 @@
+//! TextBoxの入力イベントハンドラ
+//!
+//! `on_text_box_add`が自動で登録する。いずれもBubbleフェーズで処理し、
+//! 処理したイベントは伝播を止める。
+
+use super::clipboard::{ClipboardResource, SystemClipboard};
+use super::command::{Clipboard, EditCommand};
+use super::segment::byte_offset;
+use super::{TextBox, TextBoxLayout};
+use crate::com::dwrite::DWriteTextLayoutExt;
+use crate::ecs::drag::{DragEndEvent, DragEvent};
+use crate::ecs::keyboard::{CompositionEvent, KeyEvent, TextInputEvent, focus_root};
+use crate::ecs::layout::to_local_point;
+use crate::ecs::pointer::{DoubleClick, Phase, PhysicalPoint, PointerState};
+use crate::ecs::window::WindowPos;
+use bevy_ecs::prelude::*;
+
+/// クリップボードを使う処理を実行（`ClipboardResource`がなければWin32クリップボード）
+fn with_clipboard<R>(world: &mut World, f: impl FnOnce(&mut World, &mut dyn Clipboard) -> R) -> R {
+    if world.contains_resource::<ClipboardResource>() {
+        world.resource_scope(|world, mut resource: Mut<ClipboardResource>| {
+            f(world, resource.clipboard())
+        })
+    } else {
+        f(world, &mut SystemClipboard)
+    }
+}
+
+/// キー押下: 編集コマンドを実行
+pub fn text_box_key_down(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<KeyEvent>,
+) -> bool {
+    let Phase::Bubble(event) = ev else {
+        return false;
+    };
+    let Some(text_box) = world.get::<TextBox>(entity) else {
+        return false;
+    };
+    // 変換中のキー入力はIMEが処理する
+    if text_box.composition.is_some() {
+        return false;
+    }
+    let Some(command) =
+        EditCommand::from_key_event(event, text_box.direction, text_box.editor.is_multiline())
+    else {
+        return false;
+    };
+    if text_box.read_only && command.is_mutating() {
+        return true;
+    }
+    with_clipboard(world, |world, clipboard| {
+        world
+            .get_mut::<TextBox>(entity)
+            .is_some_and(|mut text_box| command.execute(&mut text_box.editor, clipboard))
+    })
+}
+
+/// 文字入力: 選択範囲を置き換える
+pub fn text_box_text_input(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<TextInputEvent>,
+) -> bool {
+    let Phase::Bubble(event) = ev else {
+        return false;
+    };
+    let Some(mut text_box) = world.get_mut::<TextBox>(entity) else {
+        return false;
+    };
+    if !text_box.read_only {
+        text_box.editor.insert(&event.text);
+    }
+    true
+}
+
+/// IME変換: 変換中文字列をキャレット位置に表示
+///
+/// 変換開始時に選択範囲を削除し、確定文字列は`OnTextInput`で挿入される。
+pub fn text_box_composition(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<CompositionEvent>,
+) -> bool {
+    let Phase::Bubble(event) = ev else {
+        return false;
+    };
+    let Some(mut text_box) = world.get_mut::<TextBox>(entity) else {
+        return false;
+    };
+    if text_box.read_only {
+        return true;
+    }
+    match event {
+        CompositionEvent::Started => {
+            text_box.editor.delete_selection();
+        }
+        CompositionEvent::Updated(composition) => {
+            text_box.composition = (!composition.is_empty()).then(|| composition.clone());
+        }
+        CompositionEvent::Ended => {
+            text_box.composition = None;
+        }
+    }
+    true
+}
+
+/// ポインター押下: キャレット移動・単語選択・ドラッグ選択の開始
+pub fn text_box_pointer_pressed(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<PointerState>,
+) -> bool {
+    let Phase::Bubble(state) = ev else {
+        return false;
+    };
+    if !state.left_down {
+        return false;
+    }
+    let screen_point = client_to_screen(world, entity, state.client_point);
+    let Some(offset) = hit_test_offset(world, entity, screen_point) else {
+        return false;
+    };
+    let Some(mut text_box) = world.get_mut::<TextBox>(entity) else {
+        return false;
+    };
+    if state.double_click == DoubleClick::Left {
+        text_box.editor.select_word_at(offset);
+        text_box.selecting = false;
+    } else {
+        text_box.editor.set_caret(offset, state.shift_down);
+        text_box.selecting = true;
+    }
+    true
+}
+
+/// ドラッグ: ドラッグ選択中は選択範囲を伸ばす
+///
+/// ボタンの押下・解放はドラッグ状態が追跡するため、TextBoxの外に出ても追従する。
+pub fn text_box_drag(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<DragEvent>,
+) -> bool {
+    let Phase::Bubble(event) = ev else {
+        return false;
+    };
+    if !world.get::<TextBox>(entity).is_some_and(|t| t.selecting) {
+        return false;
+    }
+    let offset = hit_test_offset(world, entity, event.position);
+    if let (Some(offset), Some(mut text_box)) = (offset, world.get_mut::<TextBox>(entity)) {
+        text_box.editor.set_caret(offset, true);
+    }
+    true
+}
+
+/// ドラッグ終了: ドラッグ選択を終える
+pub fn text_box_drag_end(
+    world: &mut World,
+    _sender: Entity,
+    entity: Entity,
+    ev: &Phase<DragEndEvent>,
+) -> bool {
+    let Phase::Bubble(_) = ev else {
+        return false;
+    };
+    if !world.get::<TextBox>(entity).is_some_and(|t| t.selecting) {
+        return false;
+    }
+    if let Some(mut text_box) = world.get_mut::<TextBox>(entity) {
+        text_box.selecting = false;
+    }
+    true
+}
+
+/// クライアント座標 → スクリーン座標（物理ピクセル）
+fn client_to_screen(world: &World, entity: Entity, client_point: PhysicalPoint) -> PhysicalPoint {
+    let window = focus_root(world, entity);
+    let position = world
+        .get::<WindowPos>(window)
+        .and_then(|pos| pos.position)
+        .unwrap_or_default();
+    PhysicalPoint::new(client_point.x + position.x, client_point.y + position.y)
+}
+
+/// スクリーン座標（物理ピクセル） → `TextBox::text`のバイトオフセット
+///
+/// ローカル座標（DIP、`Transform`・スケールを考慮）に逆変換してからヒットテストする。
+/// 変換中（描画結果のオフセットが編集モデルと一致しない）やレイアウト未生成なら`None`。
+fn hit_test_offset(world: &World, entity: Entity, screen_point: PhysicalPoint) -> Option<usize> {
+    let text_box = world.get::<TextBox>(entity)?;
+    let layout = world
+        .get::<TextBoxLayout>(entity)
+        .filter(|l| !l.composing)?;
+    let point =
+        crate::ecs::layout::PhysicalPoint::new(screen_point.x as f32, screen_point.y as f32);
+    let local = to_local_point(world, entity, point)?;
+
+    // ローカル座標 → レイアウト座標
+    let hit = layout
+        .layout
+        .hit_test_point(local.x - layout.origin.X, local.y - layout.origin.Y)
+        .ok()?;
+    let position = hit.metrics.textPosition
+        + if hit.is_trailing_hit {
+            hit.metrics.length
+        } else {
+            0
+        };
+    Some(byte_offset(text_box.text(), position))
+}
@@ -0,0 +1,210 @@
+//! TextBox: 編集可能なテキスト入力ウィジット
+//!
+//! 編集モデル（[`TextEditor`]）はDirectWriteに依存しない純粋なRust実装で、
+//! ECS側はキー・文字・IME・ポインター入力を編集モデルへ橋渡しし、描画するだけの薄い層。
+//!
+//! - `segment`: 書記素クラスタ・単語・行の区切り
+//! - `editor`: バッファ・選択範囲・キャレット移動・取り消し履歴
+//! - `command`: キー入力 → 編集コマンド（書字方向による矢印キーの読み替え）
+//! - `clipboard`: Win32クリップボード
+//! - `handlers`: 入力イベントハンドラ
+//! - `draw`: 選択範囲・テキスト・変換中文字列・キャレットの描画
+
+mod clipboard;
+mod command;
+mod draw;
+mod editor;
+mod handlers;
+pub mod segment;
+
+pub use clipboard::{ClipboardResource, SystemClipboard};
+pub use command::{Clipboard, EditCommand, MemoryClipboard};
+pub use draw::draw_text_boxes;
+pub use editor::{DEFAULT_UNDO_LIMIT, Movement, Selection, TextEditor};
+pub use handlers::{
+    text_box_composition, text_box_drag, text_box_drag_end, text_box_key_down,
+    text_box_pointer_pressed, text_box_text_input,
+};
+
+use super::TextDirection;
+use crate::ecs::Visual;
+use crate::ecs::drag::{DragConfig, OnDrag, OnDragEnd};
+use crate::ecs::graphics::GraphicsCommandList;
+use crate::ecs::keyboard::{Composition, Focusable, OnComposition, OnKeyDown, OnTextInput};
+use crate::ecs::pointer::OnPointerPressed;
+use bevy_ecs::change_detection::DetectChangesMut;
+use bevy_ecs::component::Component;
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::world::DeferredWorld;
+use windows::Win32::Graphics::DirectWrite::IDWriteTextLayout;
+use windows_numerics::Vector2;
+
+/// TextBoxコンポーネント: 編集可能なテキスト入力ウィジット
+///
+/// 追加すると`Focusable`と入力ハンドラ（`OnKeyDown`・`OnTextInput`・`OnComposition`・
+/// `OnPointerPressed`・`OnDrag`・`OnDragEnd`）、ドラッグ選択用の`DragConfig`（ウィンドウ移動なし）
+/// が自動で付与される（既にあれば上書きしない）。
+/// 色は`Brushes`コンポーネントで指定します。
+/// ```ignore
+/// world.spawn((
+///     TextBox::multiline("縦書きの\n入力欄")
+///         .with_direction(TextDirection::VerticalRightToLeft),
+///     BoxStyle { width: Some(Dimension::Px(200.0)), ..Default::default() },
+/// ));
+/// ```
+///
+/// # 操作
+/// - 矢印キー: 文字・行移動（Ctrl: 単語）、Home/End（Ctrl: 文書の先頭/末尾）、Shiftで選択
+/// - Backspace/Delete（Ctrl: 単語）、Enter（複数行のみ）
+/// - Ctrl+A/C/X/V、Ctrl+Z（取り消し）、Ctrl+Y・Ctrl+Shift+Z（やり直し）
+/// - クリックでキャレット移動（Shift: 選択）、ドラッグで選択、ダブルクリックで単語選択
+///
+/// 単一行では↑↓・Enterを処理しないため、親への伝播・フォーカス移動に使われる。
+#[derive(Component)]
+#[component(storage = "SparseSet", on_add = on_text_box_add, on_remove = on_text_box_remove)]
+pub struct TextBox {
+    pub font_family: String,
+    pub font_size: f32,
+    pub direction: TextDirection,
+    /// 読み取り専用（選択・コピーのみ可能）
+    pub read_only: bool,
+    editor: TextEditor,
+    /// IMEの変換中文字列（キャレット位置に表示、確定まで`editor`には入らない）
+    composition: Option<Composition>,
+    /// ドラッグ選択中
+    selecting: bool,
+}
+
+impl Default for TextBox {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl TextBox {
+    /// 単一行のTextBoxを作成
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::with_editor(TextEditor::new(text))
+    }
+
+    /// 複数行のTextBoxを作成
+    pub fn multiline(text: impl Into<String>) -> Self {
+        Self::with_editor(TextEditor::multiline(text))
+    }
+
+    fn with_editor(editor: TextEditor) -> Self {
+        Self {
+            font_family: "メイリオ".to_string(),
+            font_size: 16.0,
+            direction: TextDirection::default(),
+            read_only: false,
+            editor,
+            composition: None,
+            selecting: false,
+        }
+    }
+
+    /// フォントを設定
+    pub fn with_font(mut self, font_family: impl Into<String>, font_size: f32) -> Self {
+        self.font_family = font_family.into();
+        self.font_size = font_size;
+        self
+    }
+
+    /// テキストの方向を設定
+    pub fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// 読み取り専用にする
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        self.editor.text()
+    }
+
+    /// テキストを置き換える（取り消し履歴は消去される）
+    pub fn set_text(&mut self, text: &str) {
+        self.editor.set_text(text);
+    }
+
+    pub fn editor(&self) -> &TextEditor {
+        &self.editor
+    }
+
+    pub fn editor_mut(&mut self) -> &mut TextEditor {
+        &mut self.editor
+    }
+
+    /// IMEの変換中文字列
+    pub fn composition(&self) -> Option<&Composition> {
+        self.composition.as_ref()
+    }
+
+    /// 表示する文字列（キャレット位置に変換中文字列を挿入）と、変換中文字列の開始位置
+    pub fn display_text(&self) -> (String, usize) {
+        let caret = self.editor.caret();
+        let mut text = self.editor.text().to_string();
+        if let Some(composition) = &self.composition {
+            text.insert_str(caret, &composition.text);
+        }
+        (text, caret)
+    }
+}
+
+/// TextBox追加時のフック: Visual・Focusable・入力ハンドラを自動挿入
+fn on_text_box_add(mut world: DeferredWorld, hook: HookContext) {
+    let mut commands = world.commands();
+    let mut entity = commands.entity(hook.entity);
+    entity.insert_if_new((
+        Visual::default(),
+        Focusable,
+        OnKeyDown(text_box_key_down),
+        OnTextInput(text_box_text_input),
+        OnComposition(text_box_composition),
+        OnPointerPressed(text_box_pointer_pressed),
+        OnDrag(text_box_drag),
+        OnDragEnd(text_box_drag_end),
+        DragConfig {
+            move_window: false,
+            ..Default::default()
+        },
+    ));
+}
+
+/// TextBox削除時のフック
+/// GraphicsCommandListをクリアしてChanged検出に対応
+fn on_text_box_remove(mut world: DeferredWorld, hook: HookContext) {
+    if let Some(mut cmd_list) = world.get_mut::<GraphicsCommandList>(hook.entity) {
+        cmd_list.set_if_neq(GraphicsCommandList::empty());
+    }
+}
+
+/// TextBoxの描画結果（ヒットテスト用）
+///
+/// `draw_text_boxes`が生成する。座標はエンティティのローカル座標（DIP）。
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct TextBoxLayout {
+    layout: IDWriteTextLayout,
+    /// レイアウト原点（描画位置）
+    origin: Vector2,
+    /// 変換中文字列を含むか（含む場合、オフセットは`TextBox::text`と一致しない）
+    composing: bool,
+    /// 描画時のフォーカス状態
+    focused: bool,
+}
+
+impl TextBoxLayout {
+    pub fn layout(&self) -> &IDWriteTextLayout {
+        &self.layout
+    }
+
+    pub fn origin(&self) -> Vector2 {
+        self.origin
+    }
+}
@@ -0,0 +1,331 @@
+//! 文字列の区切り（書記素クラスタ・単語・行）とUTF-16オフセット変換
+//!
+//! 書記素クラスタと単語の区切りは `unicode-segmentation`（UAX #29）に従う。
+//! UAX #29 は漢字・ひらがなを1文字ずつ区切るため、単語は同じ文字種の並びにまとめる。
+//! オフセットはすべて UTF-8 のバイトオフセット。
+
+use std::ops::Range;
+
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+
+/// `offset`（書記素クラスタの先頭）から始まるクラスタの終端
+pub fn next_grapheme_boundary(text: &str, offset: usize) -> usize {
+    if offset >= text.len() {
+        return text.len();
+    }
+    // 文字列全体を1チャンクで渡すので GraphemeIncomplete は起きない
+    GraphemeCursor::new(offset, text.len(), true)
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+/// `offset`より前の最も近い書記素クラスタ境界（先頭なら0）
+///
+/// `offset`は文字境界でなくてもよい。
+pub fn prev_grapheme_boundary(text: &str, offset: usize) -> usize {
+    let mut char_start = offset.min(text.len());
+    while !text.is_char_boundary(char_start) {
+        char_start -= 1;
+    }
+    if char_start < offset && is_grapheme_boundary(text, char_start) {
+        return char_start;
+    }
+    GraphemeCursor::new(char_start, text.len(), true)
+        .prev_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// `offset`が書記素クラスタ境界か
+pub fn is_grapheme_boundary(text: &str, offset: usize) -> bool {
+    text.is_char_boundary(offset)
+        && GraphemeCursor::new(offset, text.len(), true)
+            .is_boundary(text, 0)
+            .unwrap_or(false)
+}
+
+/// `offset`を含む書記素クラスタの先頭に丸める
+pub fn snap_to_grapheme(text: &str, offset: usize) -> usize {
+    let offset = offset.min(text.len());
+    if is_grapheme_boundary(text, offset) {
+        offset
+    } else {
+        prev_grapheme_boundary(text, offset)
+    }
+}
+
+/// 書記素クラスタの範囲を先頭から列挙
+pub fn graphemes(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    text.grapheme_indices(true)
+        .map(|(start, cluster)| (start, start + cluster.len()))
+}
+
+// ============================================================================
+// 単語
+// ============================================================================
+
+/// 単語区切り用の文字種
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Punctuation,
+    /// 英数字・アンダースコア
+    Word,
+    Hiragana,
+    Katakana,
+    Han,
+}
+
+fn char_class(c: char) -> CharClass {
+    match c as u32 {
+        0x3041..=0x309F => CharClass::Hiragana,
+        // 長音記号「ー」はカタカナと同じ単語に含める
+        0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => CharClass::Katakana,
+        0x3005..=0x3007
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xF900..=0xFAFF
+        | 0x20000..=0x3FFFF => CharClass::Han,
+        _ if c.is_whitespace() => CharClass::Space,
+        _ if c.is_alphanumeric() || c == '_' => CharClass::Word,
+        _ => CharClass::Punctuation,
+    }
+}
+
+fn is_line_break(segment: &str) -> bool {
+    segment.starts_with(['\r', '\n'])
+}
+
+/// 単語区切りの区間
+struct WordSegment {
+    range: Range<usize>,
+    /// 先頭の文字の文字種（改行は`None`）
+    class: Option<CharClass>,
+}
+
+/// UAX #29 の単語区間を、隣り合う同じ文字種ごとにまとめて列挙する。改行は単独の区間になる。
+fn word_segments(text: &str) -> Vec<WordSegment> {
+    let mut segments: Vec<WordSegment> = Vec::new();
+    for (start, word) in text.split_word_bound_indices() {
+        let class = if is_line_break(word) {
+            None
+        } else {
+            word.chars().next().map(char_class)
+        };
+        let end = start + word.len();
+        match segments.last_mut() {
+            Some(last) if class.is_some() && last.class == class => last.range.end = end,
+            _ => segments.push(WordSegment {
+                range: start..end,
+                class,
+            }),
+        }
+    }
+    segments
+}
+
+/// `offset`を含む区間の添字
+fn segment_index(segments: &[WordSegment], offset: usize) -> usize {
+    segments.partition_point(|segment| segment.range.end <= offset)
+}
+
+/// 次の単語の先頭（Ctrl+→）
+///
+/// 現在の単語と、それに続く空白を読み飛ばす。行末では改行の直後で止まる。
+pub fn next_word_boundary(text: &str, offset: usize) -> usize {
+    if offset >= text.len() {
+        return text.len();
+    }
+    let segments = word_segments(text);
+    let index = segment_index(&segments, offset);
+    let segment = &segments[index];
+    let mut current = offset;
+    let rest = match segment.class {
+        None => return segment.range.end,
+        Some(CharClass::Space) => &segments[index..],
+        Some(_) => {
+            current = segment.range.end;
+            &segments[index + 1..]
+        }
+    };
+    for segment in rest {
+        if segment.class != Some(CharClass::Space) {
+            break;
+        }
+        current = segment.range.end;
+    }
+    current
+}
+
+/// 前の単語の先頭（Ctrl+←）
+///
+/// 直前の空白を読み飛ばし、その前の単語の先頭へ。行頭では改行の直前で止まる。
+pub fn prev_word_boundary(text: &str, offset: usize) -> usize {
+    let offset = offset.min(text.len());
+    if offset == 0 {
+        return 0;
+    }
+    let segments = word_segments(text);
+    let mut index = segment_index(&segments, offset - 1);
+    if segments[index].class == Some(CharClass::Space) {
+        if index == 0 || segments[index - 1].class.is_none() {
+            return segments[index].range.start;
+        }
+        index -= 1;
+    }
+    segments[index].range.start
+}
+
+/// `offset`の単語の範囲（ダブルクリック選択）
+///
+/// `offset`の直後の書記素クラスタ（末尾では直前）を含む単語。改行は含まない。
+pub fn word_range_at(text: &str, offset: usize) -> Range<usize> {
+    let offset = snap_to_grapheme(text, offset);
+    let start = if offset >= text.len() || is_line_break(&text[offset..]) {
+        if offset == 0 || text[..offset].ends_with(['\r', '\n']) {
+            return offset..offset;
+        }
+        prev_grapheme_boundary(text, offset)
+    } else {
+        offset
+    };
+    let segments = word_segments(text);
+    let segment = &segments[segment_index(&segments, start)];
+    if segment.class.is_none() {
+        return offset..offset;
+    }
+    segment.range.clone()
+}
+
+// ============================================================================
+// 行
+// ============================================================================
+
+/// `offset`を含む論理行の先頭
+pub fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// `offset`を含む論理行の末尾（改行の直前）
+pub fn line_end(text: &str, offset: usize) -> usize {
+    text[offset..].find('\n').map_or(text.len(), |i| offset + i)
+}
+
+/// 行頭から`offset`までの書記素クラスタ数
+pub fn grapheme_column(text: &str, offset: usize) -> usize {
+    let start = line_start(text, offset);
+    graphemes(&text[start..offset]).count()
+}
+
+/// `line_start`から始まる行で、書記素クラスタ数`column`の位置（行末を超えない）
+pub fn offset_at_column(text: &str, line_start: usize, column: usize) -> usize {
+    let end = line_end(text, line_start);
+    let mut offset = line_start;
+    for _ in 0..column {
+        if offset >= end {
+            break;
+        }
+        offset = next_grapheme_boundary(text, offset);
+    }
+    offset.min(end)
+}
+
+// ============================================================================
+// UTF-16
+// ============================================================================
+
+/// バイトオフセット → UTF-16オフセット（DirectWrite・IMMとの変換用）
+pub fn utf16_offset(text: &str, offset: usize) -> u32 {
+    text[..offset.min(text.len())]
+        .chars()
+        .map(|c| c.len_utf16() as u32)
+        .sum()
+}
+
+/// UTF-16オフセット → バイトオフセット（サロゲートペアの途中は文字の先頭）
+pub fn byte_offset(text: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    for (i, c) in text.char_indices() {
+        units += c.len_utf16() as u32;
+        if units > utf16 {
+            return i;
+        }
+    }
+    text.len()
+}
+
+// ============================================================================
+// テスト
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clusters(text: &str) -> Vec<&str> {
+        graphemes(text).map(|(s, e)| &text[s..e]).collect()
+    }
+
+    #[test]
+    fn test_graphemes() {
+        assert_eq!(clusters("abc"), ["a", "b", "c"]);
+        assert_eq!(clusters("a\r\nb"), ["a", "\r\n", "b"]);
+        // 結合文字・結合用濁点
+        assert_eq!(clusters("e\u{301}か\u{3099}"), ["e\u{301}", "か\u{3099}"]);
+        // 異体字セレクタ（葛󠄀）
+        assert_eq!(clusters("葛\u{E0100}x"), ["葛\u{E0100}", "x"]);
+        // ZWJ連結・肌色修飾子
+        assert_eq!(
+            clusters("👨\u{200D}👩\u{200D}👧👍\u{1F3FD}"),
+            ["👨\u{200D}👩\u{200D}👧", "👍\u{1F3FD}"]
+        );
+        // 国旗は地域指示子2つずつ
+        assert_eq!(clusters("🇯🇵🇺🇸"), ["🇯🇵", "🇺🇸"]);
+        // 改行の後の結合文字は結合しない
+        assert_eq!(clusters("\n\u{301}"), ["\n", "\u{301}"]);
+    }
+
+    #[test]
+    fn test_graphemes_follow_uax29() {
+        // 母音記号（SpacingMark）は子音と結合する（नि）
+        assert_eq!(
+            clusters("\u{928}\u{93F}\u{915}"),
+            ["\u{928}\u{93F}", "\u{915}"]
+        );
+        // ハングル字母 L V T は1音節
+        assert_eq!(
+            clusters("\u{1100}\u{1161}\u{11A8}\u{1100}"),
+            ["\u{1100}\u{1161}\u{11A8}", "\u{1100}"]
+        );
+        // 絵文字以外の後のZWJは次の文字と連結しない
+        assert_eq!(clusters("a\u{200D}b"), ["a\u{200D}", "b"]);
+        assert!(!is_grapheme_boundary("\u{928}\u{93F}", 3));
+        assert_eq!(prev_grapheme_boundary("\u{928}\u{93F}", 6), 0);
+    }
+
+    #[test]
+    fn test_prev_boundary_and_snap() {
+        let text = "a👍\u{1F3FD}b";
+        let b = text.len() - 1;
+        assert_eq!(prev_grapheme_boundary(text, b), 1);
+        assert_eq!(prev_grapheme_boundary(text, 1), 0);
+        assert_eq!(prev_grapheme_boundary(text, 0), 0);
+        assert!(!is_grapheme_boundary(text, 5));
+        assert_eq!(snap_to_grapheme(text, 5), 1);
+        assert_eq!(snap_to_grapheme(text, 100), text.len());
+    }
+
+    #[test]
+    fn test_utf16_conversion() {
+        let text = "a𠮷b";
+        assert_eq!(utf16_offset(text, 1), 1);
+        assert_eq!(utf16_offset(text, 5), 3);
+        assert_eq!(byte_offset(text, 3), 5);
+        // サロゲートペアの途中は文字の先頭
+        assert_eq!(byte_offset(text, 2), 1);
+        assert_eq!(byte_offset(text, 99), text.len());
+    }
+}
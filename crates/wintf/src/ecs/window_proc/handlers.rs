@@ -257,7 +257,14 @@ pub(super) fn WM_WINDOWPOSCHANGED(
                                 let size_changed =
                                     current_size.map(|cs| cs != new_size).unwrap_or(false);
 
-                                if size_changed {
+                                // レイアウトトランジション中の echo は補間途中のサイズなので書き戻さない
+                                // （書き戻すと目標サイズが中間値に置き換わり、補間と競合する）
+                                let transitioning = is_echo
+                                    && entity_ref
+                                        .get::<crate::ecs::layout::LayoutTransitionState>()
+                                        .is_some_and(|state| state.is_animating());
+
+                                if size_changed && !transitioning {
                                     if let Some(mut box_style) =
                                         entity_ref.get_mut::<crate::ecs::layout::BoxStyle>()
                                     {
@@ -328,6 +335,23 @@ pub(super) fn WM_DISPLAYCHANGE(
     None // DefWindowProcWに委譲
 }
 
+/// WM_SETTINGCHANGE: システム設定変更通知
+///
+/// タスクバーの移動・サイズ変更（`SPI_SETWORKAREA`）は作業領域の変更なので、
+/// ディスプレイ構成変更として扱いMonitor・Anchorを更新させる
+#[inline]
+pub(super) fn WM_SETTINGCHANGE(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> HandlerResult {
+    if wparam.0 != SPI_SETWORKAREA.0 as usize {
+        return None;
+    }
+    WM_DISPLAYCHANGE(hwnd, message, wparam, lparam)
+}
+
 /// WM_DPICHANGED: DPI変更通知（モニター間移動など）
 ///
 /// Per-Monitor DPI Aware (v2)では、アプリケーションが明示的にSetWindowPosを呼ぶ必要がある
@@ -1449,10 +1473,10 @@ pub(super) fn WM_MOUSEHWHEEL(
 /// WM_KEYDOWN: キー押下
 #[inline]
 pub(super) fn WM_KEYDOWN(
-    _hwnd: HWND,
-    _message: u32,
+    hwnd: HWND,
+    message: u32,
     wparam: WPARAM,
-    _lparam: LPARAM,
+    lparam: LPARAM,
 ) -> HandlerResult {
     use windows::Win32::UI::Input::KeyboardAndMouse::VK_ESCAPE;
 
@@ -1496,9 +1520,144 @@ pub(super) fn WM_KEYDOWN(
         tracing::debug!("[WM_KEYDOWN] ESC key pressed, drag cancelled");
     }
 
+    record_key_message(hwnd, message, wparam, lparam);
+    None // DefWindowProcWに委譲
+}
+
+/// WM_KEYUP: キー解放
+#[inline]
+pub(super) fn WM_KEYUP(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> HandlerResult {
+    record_key_message(hwnd, message, wparam, lparam);
+    None // DefWindowProcWに委譲
+}
+
+/// WM_SYSKEYDOWN: システムキー押下（Alt併用・F10）
+///
+/// Alt+F4やメニュー操作のためDefWindowProcWにも委譲する。
+#[inline]
+pub(super) fn WM_SYSKEYDOWN(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> HandlerResult {
+    record_key_message(hwnd, message, wparam, lparam);
+    None // DefWindowProcWに委譲
+}
+
+/// WM_SYSKEYUP: システムキー解放
+#[inline]
+pub(super) fn WM_SYSKEYUP(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> HandlerResult {
+    record_key_message(hwnd, message, wparam, lparam);
+    None // DefWindowProcWに委譲
+}
+
+/// WM_KILLFOCUS: キーボードフォーカス喪失
+///
+/// 離されたキーのWM_KEYUPは届かないため、押下中のキーを解放させる。
+#[inline]
+pub(super) fn WM_KILLFOCUS(
+    hwnd: HWND,
+    _message: u32,
+    _wparam: WPARAM,
+    _lparam: LPARAM,
+) -> HandlerResult {
+    if let Some(entity) = super::get_entity_from_hwnd(hwnd) {
+        crate::ecs::keyboard::record_focus_lost(entity);
+    }
     None // DefWindowProcWに委譲
 }
 
+/// WM_CHAR: 文字入力（UTF-16コードユニット、サロゲートペアは2回に分かれて届く）
+#[inline]
+pub(super) fn WM_CHAR(hwnd: HWND, _message: u32, wparam: WPARAM, _lparam: LPARAM) -> HandlerResult {
+    if let Some(entity) = super::get_entity_from_hwnd(hwnd) {
+        crate::ecs::keyboard::record_text_input(
+            entity,
+            crate::ecs::keyboard::TextInputMessage::Char(wparam.0 as u16),
+        );
+    }
+    None // DefWindowProcWに委譲
+}
+
+/// WM_IME_STARTCOMPOSITION: IME変換開始
+///
+/// 変換中文字列はウィジェットが描画するため、既定の変換ウィンドウは表示しない。
+#[inline]
+pub(super) fn WM_IME_STARTCOMPOSITION(
+    hwnd: HWND,
+    _message: u32,
+    _wparam: WPARAM,
+    _lparam: LPARAM,
+) -> HandlerResult {
+    if let Some(entity) = super::get_entity_from_hwnd(hwnd) {
+        crate::ecs::keyboard::record_text_input(
+            entity,
+            crate::ecs::keyboard::TextInputMessage::StartComposition,
+        );
+    }
+    Some(LRESULT(0))
+}
+
+/// WM_IME_COMPOSITION: 変換中文字列・確定文字列の更新
+///
+/// 確定文字列はここで読み出すため、DefWindowProcWには渡さない（WM_IME_CHAR → WM_CHARの重複を防ぐ）。
+#[inline]
+pub(super) fn WM_IME_COMPOSITION(
+    hwnd: HWND,
+    _message: u32,
+    _wparam: WPARAM,
+    lparam: LPARAM,
+) -> HandlerResult {
+    if let Some(entity) = super::get_entity_from_hwnd(hwnd) {
+        let message = crate::ecs::keyboard::read_composition(hwnd, lparam);
+        trace!(entity = ?entity, message = ?message, "[WM_IME_COMPOSITION]");
+        crate::ecs::keyboard::record_text_input(entity, message);
+    }
+    Some(LRESULT(0))
+}
+
+/// WM_IME_ENDCOMPOSITION: IME変換終了
+#[inline]
+pub(super) fn WM_IME_ENDCOMPOSITION(
+    hwnd: HWND,
+    _message: u32,
+    _wparam: WPARAM,
+    _lparam: LPARAM,
+) -> HandlerResult {
+    if let Some(entity) = super::get_entity_from_hwnd(hwnd) {
+        crate::ecs::keyboard::record_text_input(
+            entity,
+            crate::ecs::keyboard::TextInputMessage::EndComposition,
+        );
+    }
+    Some(LRESULT(0))
+}
+
+/// キーボードメッセージを`KeyEvent`に変換してバッファに記録
+fn record_key_message(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) {
+    let Some(entity) = super::get_entity_from_hwnd(hwnd) else {
+        return;
+    };
+    let modifiers = crate::ecs::keyboard::Modifiers::current();
+    if let Some(event) =
+        crate::ecs::keyboard::decode_key_message(message, wparam.0, lparam.0, modifiers)
+    {
+        trace!(
+            entity = ?entity,
+            key = event.virtual_key,
+            state = ?event.state,
+            "[record_key_message] Key event recorded"
+        );
+        crate::ecs::keyboard::record_key_event(entity, event);
+    }
+}
+
 /// WM_CANCELMODE: システムキャンセル
 #[inline]
 pub(super) fn WM_CANCELMODE(
@@ -47,6 +47,7 @@ pub(crate) extern "system" fn ecs_wndproc(
         WM_CLOSE => handlers::WM_CLOSE(hwnd, message, wparam, lparam),
         WM_WINDOWPOSCHANGED => handlers::WM_WINDOWPOSCHANGED(hwnd, message, wparam, lparam),
         WM_DISPLAYCHANGE => handlers::WM_DISPLAYCHANGE(hwnd, message, wparam, lparam),
+        WM_SETTINGCHANGE => handlers::WM_SETTINGCHANGE(hwnd, message, wparam, lparam),
         WM_DPICHANGED => handlers::WM_DPICHANGED(hwnd, message, wparam, lparam),
         // マウスメッセージ
         WM_NCHITTEST => handlers::WM_NCHITTEST(hwnd, message, wparam, lparam),
@@ -67,6 +68,15 @@ pub(crate) extern "system" fn ecs_wndproc(
         WM_MOUSEWHEEL => handlers::WM_MOUSEWHEEL(hwnd, message, wparam, lparam),
         WM_MOUSEHWHEEL => handlers::WM_MOUSEHWHEEL(hwnd, message, wparam, lparam),
         WM_KEYDOWN => handlers::WM_KEYDOWN(hwnd, message, wparam, lparam),
+        WM_KEYUP => handlers::WM_KEYUP(hwnd, message, wparam, lparam),
+        WM_SYSKEYDOWN => handlers::WM_SYSKEYDOWN(hwnd, message, wparam, lparam),
+        WM_SYSKEYUP => handlers::WM_SYSKEYUP(hwnd, message, wparam, lparam),
+        WM_KILLFOCUS => handlers::WM_KILLFOCUS(hwnd, message, wparam, lparam),
+        // テキスト入力・IME
+        WM_CHAR => handlers::WM_CHAR(hwnd, message, wparam, lparam),
+        WM_IME_STARTCOMPOSITION => handlers::WM_IME_STARTCOMPOSITION(hwnd, message, wparam, lparam),
+        WM_IME_COMPOSITION => handlers::WM_IME_COMPOSITION(hwnd, message, wparam, lparam),
+        WM_IME_ENDCOMPOSITION => handlers::WM_IME_ENDCOMPOSITION(hwnd, message, wparam, lparam),
         WM_CANCELMODE => handlers::WM_CANCELMODE(hwnd, message, wparam, lparam),
         WM_ACTIVATE => handlers::WM_ACTIVATE(hwnd, message, wparam, lparam),
         _ => None,
@@ -195,6 +195,12 @@ impl EcsWorld {
         world.insert_resource(crate::ecs::app::App::new());
         world.insert_resource(FrameCount::default());
         world.insert_resource(crate::ecs::layout::taffy::TaffyLayoutResource::default());
+        world.insert_resource(crate::ecs::layout::taffy::LayoutStats::default());
+        world.insert_resource(crate::ecs::layout::HitTestIndex::default());
+        // テキスト計測器（DirectWrite）。テストではFixedAdvanceTextMeasurerで差し替え可能
+        if let Ok(measurer) = crate::ecs::layout::DirectWriteTextMeasurer::new() {
+            world.insert_resource(crate::ecs::layout::TextMeasurerResource::new(measurer));
+        }
         // Surface生成統計リソース（Req 5.3）
         world.insert_resource(crate::ecs::graphics::SurfaceCreationStats::default());
 
@@ -223,6 +229,15 @@ impl EcsWorld {
         world.init_resource::<Messages<crate::ecs::drag::DragEvent>>();
         world.init_resource::<Messages<crate::ecs::drag::DragEndEvent>>();
 
+        // スクロールイベント用Messages
+        world.init_resource::<Messages<crate::ecs::scroll::ScrollChangedEvent>>();
+
+        // キーボード入力キュー・状態とフォーカス変更Messages
+        world.init_resource::<crate::ecs::keyboard::KeyboardInput>();
+        world.init_resource::<crate::ecs::keyboard::KeyboardState>();
+        world.init_resource::<Messages<crate::ecs::keyboard::FocusChangedEvent>>();
+        world.init_resource::<Messages<crate::ecs::keyboard::CandidateWindowRequest>>();
+
         // スケジュールの登録
         {
             world.init_resource::<Schedules>();
@@ -260,12 +275,39 @@ impl EcsWorld {
                 crate::ecs::widget::bitmap_source::systems::drain_task_pool_commands,
             );
 
+            // Inputスケジュール: クリックによるフォーカス移動
+            // （dispatch_pointer_eventsがボタン状態をクリアする前に実行）
+            schedules.add_systems(
+                Input,
+                crate::ecs::keyboard::focus_on_click_system
+                    .after(crate::ecs::widget::bitmap_source::systems::drain_task_pool_commands),
+            );
+
             // Inputスケジュール: ポインターイベントディスパッチ
             // transfer_buffers_to_world()でWorldに直接データ投入済み
             schedules.add_systems(
                 Input,
                 crate::ecs::pointer::dispatch_pointer_events
-                    .after(crate::ecs::widget::bitmap_source::systems::drain_task_pool_commands),
+                    .after(crate::ecs::keyboard::focus_on_click_system),
+            );
+
+            // Inputスケジュール: キーボードイベントディスパッチ
+            // （Focusableを失ったエンティティのフォーカスを外してから配信）
+            schedules.add_systems(
+                Input,
+                (
+                    crate::ecs::keyboard::validate_focus_system,
+                    crate::ecs::keyboard::dispatch_keyboard_events,
+                )
+                    .chain()
+                    .after(crate::ecs::pointer::dispatch_pointer_events),
+            );
+
+            // Inputスケジュール: IME候補ウィンドウの配置
+            schedules.add_systems(
+                Input,
+                crate::ecs::keyboard::apply_candidate_window_system
+                    .after(crate::ecs::keyboard::dispatch_keyboard_events),
             );
 
             // 注: process_pointer_buffersは廃止
@@ -285,6 +327,17 @@ impl EcsWorld {
                 crate::ecs::drag::cleanup_drag_state.after(crate::ecs::drag::dispatch_drag_events),
             );
 
+            // Inputスケジュール: ホイール・ドラッグによるスクロール
+            schedules.add_systems(
+                Input,
+                (
+                    crate::ecs::scroll::wheel_scroll_system
+                        .after(crate::ecs::pointer::dispatch_pointer_events),
+                    crate::ecs::scroll::drag_scroll_system
+                        .after(crate::ecs::drag::dispatch_drag_events),
+                ),
+            );
+
             // Inputスケジュール: ポインターデバッグ監視（デバッグビルドのみ）
             #[cfg(debug_assertions)]
             schedules.add_systems(
@@ -322,6 +375,9 @@ impl EcsWorld {
                     .chain(),
             );
 
+            // Updateスケジュール: 慣性スクロール
+            schedules.add_systems(Update, crate::ecs::scroll::kinetic_scroll_system);
+
             // PreLayoutスケジュール: GraphicsCore初期化とVisual作成
             // Phase 6: VisualはPreLayoutで早期作成、SurfaceはDrawで遅延作成
             schedules.add_systems(
@@ -332,9 +388,12 @@ impl EcsWorld {
                     // Changed<VisualGraphics> + !is_valid() で初期化と再初期化を統一処理
                     crate::ecs::graphics::visual_resource_management_system
                         .after(crate::ecs::graphics::init_graphics_core),
+                    // ZIndex変更時はVisualを外して再挿入させる
+                    crate::ecs::graphics::visual_restack_system
+                        .after(crate::ecs::graphics::visual_resource_management_system),
                     // Visual階層同期（parent_visual==Noneで未同期を検出）
                     crate::ecs::graphics::visual_hierarchy_sync_system
-                        .after(crate::ecs::graphics::visual_resource_management_system),
+                        .after(crate::ecs::graphics::visual_restack_system),
                 ),
             );
 
@@ -342,6 +401,7 @@ impl EcsWorld {
             schedules.add_systems(
                 Layout,
                 (
+                    crate::ecs::layout::sync_text_measure_system,
                     crate::ecs::layout::build_taffy_styles_system,
                     crate::ecs::layout::sync_taffy_tree_system
                         .after(crate::ecs::layout::build_taffy_styles_system),
@@ -349,23 +409,50 @@ impl EcsWorld {
                         .after(crate::ecs::layout::sync_taffy_tree_system),
                     crate::ecs::layout::update_arrangements_system
                         .after(crate::ecs::layout::compute_taffy_layout_system),
+                    crate::ecs::layout::update_display_hidden_system,
                     crate::ecs::layout::cleanup_removed_entities_system,
                 )
                     .chain(),
             );
 
+            // Layoutスケジュール: スクロール（taffyの配置結果をオフセット分ずらす）
+            schedules.add_systems(
+                Layout,
+                (
+                    crate::ecs::scroll::update_scroll_extents_system,
+                    crate::ecs::scroll::apply_scroll_offset_system,
+                    crate::ecs::scroll::virtualize_scroll_children_system,
+                )
+                    .chain()
+                    .after(crate::ecs::layout::update_arrangements_system),
+            );
+
+            // Layoutスケジュール: レイアウトトランジション（レイアウト・スクロール確定後に補間）
+            schedules.add_systems(
+                Layout,
+                crate::ecs::layout::layout_transition_system
+                    .after(crate::ecs::scroll::virtualize_scroll_children_system),
+            );
+
             // PostLayoutスケジュール: 論理計算系（Arrangement伝播まで）
             // Note: Arrangementは各コンポーネントのon_addフックで自動挿入されるため、
             //       init_window_arrangementシステムは廃止されました。
             schedules.add_systems(
                 PostLayout,
                 (
+                    // Anchorの解決結果をWindowPosへ（同フレームでArrangementへ反映させる）
+                    crate::ecs::layout::resolve_window_anchors_system,
                     crate::ecs::layout::sync_window_arrangement_from_window_pos,
                     crate::ecs::layout::sync_simple_arrangements,
                     crate::ecs::layout::mark_dirty_arrangement_trees
                         .after(crate::ecs::layout::sync_simple_arrangements),
                     crate::ecs::layout::propagate_global_arrangements
                         .after(crate::ecs::layout::mark_dirty_arrangement_trees),
+                    crate::ecs::layout::pixel_snap_system
+                        .after(crate::ecs::layout::propagate_global_arrangements),
+                    // ヒットテスト用空間インデックスの差分更新
+                    crate::ecs::layout::update_hit_test_index_system
+                        .after(crate::ecs::layout::propagate_global_arrangements),
                     crate::ecs::layout::window_pos_sync_system
                         .after(crate::ecs::layout::propagate_global_arrangements),
                 )
@@ -397,6 +484,8 @@ impl EcsWorld {
                         .after(crate::ecs::graphics::resolve_inherited_brushes),
                     crate::ecs::widget::text::draw_labels
                         .after(crate::ecs::graphics::resolve_inherited_brushes),
+                    crate::ecs::widget::text::draw_text_boxes
+                        .after(crate::ecs::graphics::resolve_inherited_brushes),
                     // Typewriter: Arrangement変更で無効化 → LayoutCache初期化 → 描画の順
                     crate::ecs::widget::text::invalidate_typewriter_layout_on_arrangement_change
                         .after(crate::ecs::graphics::resolve_inherited_brushes),
@@ -445,6 +534,12 @@ impl EcsWorld {
                 crate::ecs::pointer::clear_transient_pointer_state,
             );
 
+            // FrameFinalizeスケジュール: 一時的キーボード状態クリア
+            schedules.add_systems(
+                FrameFinalize,
+                crate::ecs::keyboard::clear_transient_keyboard_state,
+            );
+
             // FrameFinalizeスケジュール: Messagesの更新
             schedules.add_systems(
                 FrameFinalize,
@@ -464,6 +559,21 @@ impl EcsWorld {
                             .resource_mut::<Messages<crate::ecs::drag::DragEndEvent>>()
                             .update()
                     },
+                    |world: &mut World| {
+                        world
+                            .resource_mut::<Messages<crate::ecs::scroll::ScrollChangedEvent>>()
+                            .update()
+                    },
+                    |world: &mut World| {
+                        world
+                            .resource_mut::<Messages<crate::ecs::keyboard::FocusChangedEvent>>()
+                            .update()
+                    },
+                    |world: &mut World| {
+                        world
+                            .resource_mut::<Messages<crate::ecs::keyboard::CandidateWindowRequest>>()
+                            .update()
+                    },
                 ),
             );
         }
@@ -598,6 +708,7 @@ impl EcsWorld {
         // WndProcスレッドのthread_localバッファからWorldに直接データを投入
         // これにより、マルチスレッドで実行されるシステムでもデータにアクセス可能になる
         crate::ecs::pointer::transfer_buffers_to_world(&mut self.world);
+        crate::ecs::keyboard::transfer_keyboard_buffers_to_world(&mut self.world);
 
         // 各Scheduleを順番に実行
         let _ = self.world.try_run_schedule(Input);
@@ -0,0 +1,258 @@
+//! αマスク設定（閾値・保持形式）のテスト
+//!
+//! 1. `HitTest::alpha`の閾値によるヒット判定
+//! 2. 設定変更時のαマスク再生成（`generate_alpha_mask_system`）
+//! 3. `alpha_coverage`によるカバレッジ取得
+use bevy_ecs::prelude::*;
+use std::time::{Duration, Instant};
+use windows::Win32::Graphics::Imaging::{GUID_WICPixelFormat32bppPBGRA, IWICBitmapSource};
+use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx, CoUninitialize};
+use windows_core::Interface;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::layout::{
+    GlobalArrangement, HitTest, PhysicalPoint, alpha_coverage, hit_test_entity,
+};
+use wintf::ecs::widget::bitmap_source::{
+    AlphaMask, AlphaMaskFormat, AlphaMaskSettings, BitmapSourceResource, WicCore, WintfTaskPool,
+    generate_alpha_mask_system,
+};
+
+fn with_com_initialized<F: FnOnce() -> R, R>(f: F) -> R {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+    let result = f();
+    unsafe {
+        CoUninitialize();
+    }
+    result
+}
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+/// 1行のソフトエッジ画像（左から α = 16, 64, 128, 255）
+const ALPHAS: [u8; 4] = [16, 64, 128, 255];
+
+fn soft_edge_pixels() -> Vec<u8> {
+    ALPHAS.iter().flat_map(|&a| [0, 0, 0, a]).collect()
+}
+
+fn soft_edge_resource(wic: &WicCore) -> BitmapSourceResource {
+    let pixels = soft_edge_pixels();
+    let bitmap = unsafe {
+        wic.factory()
+            .CreateBitmapFromMemory(4, 1, &GUID_WICPixelFormat32bppPBGRA, 16, &pixels)
+            .expect("CreateBitmapFromMemory failed")
+    };
+    BitmapSourceResource::new(bitmap.cast::<IWICBitmapSource>().unwrap())
+}
+
+/// 40x10 に配置（各ピクセルが幅10）
+fn spawn_soft_edge(world: &mut World, wic: &WicCore, hit_test: HitTest) -> Entity {
+    world
+        .spawn((
+            global(0.0, 0.0, 40.0, 10.0),
+            hit_test,
+            soft_edge_resource(wic),
+        ))
+        .id()
+}
+
+/// 各ピクセル中央のヒット結果
+fn hit_columns(world: &World, entity: Entity) -> Vec<bool> {
+    (0..4)
+        .map(|i| {
+            hit_test_entity(
+                world,
+                entity,
+                PhysicalPoint::new(i as f32 * 10.0 + 5.0, 5.0),
+            )
+        })
+        .collect()
+}
+
+fn mask_settings(world: &World, entity: Entity) -> Option<AlphaMaskSettings> {
+    world
+        .get::<BitmapSourceResource>(entity)?
+        .alpha_mask()
+        .map(AlphaMask::settings)
+}
+
+/// 非同期生成されたマスクが指定の設定になるまで待つ
+fn wait_for_mask(world: &mut World, entity: Entity, settings: AlphaMaskSettings) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while mask_settings(world, entity) != Some(settings) {
+        assert!(Instant::now() < deadline, "alpha mask was not generated");
+        let commands = world.resource::<WintfTaskPool>().drain_commands();
+        for cmd in commands {
+            cmd(world);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn mask_world() -> (World, Schedule) {
+    let mut world = World::new();
+    world.insert_resource(WintfTaskPool::new());
+    let mut schedule = Schedule::default();
+    schedule.add_systems(generate_alpha_mask_system);
+    (world, schedule)
+}
+
+#[test]
+fn test_default_threshold_is_128() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let (mut world, mut schedule) = mask_world();
+        let entity = spawn_soft_edge(&mut world, &wic, HitTest::alpha_mask());
+
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, AlphaMaskSettings::default());
+        assert_eq!(hit_columns(&world, entity), [false, false, true, true]);
+    });
+}
+
+#[test]
+fn test_threshold_change_regenerates_binary_mask() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let (mut world, mut schedule) = mask_world();
+        let soft = HitTest::alpha_mask().with_alpha_threshold(48);
+        let entity = spawn_soft_edge(&mut world, &wic, soft.clone());
+
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, soft.alpha);
+        assert_eq!(hit_columns(&world, entity), [false, true, true, true]);
+
+        // 閾値を上げると再生成される
+        let strict = HitTest::alpha_mask().with_alpha_threshold(200);
+        world.entity_mut(entity).insert(strict.clone());
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, strict.alpha);
+        assert_eq!(hit_columns(&world, entity), [false, false, false, true]);
+    });
+}
+
+#[test]
+fn test_stale_mask_is_discarded() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let (mut world, mut schedule) = mask_world();
+        let entity = spawn_soft_edge(&mut world, &wic, HitTest::alpha_mask());
+        schedule.run(&mut world);
+
+        // 生成完了前に設定を変更
+        let latest = HitTest::alpha_mask().with_alpha_threshold(1);
+        world.entity_mut(entity).insert(latest.clone());
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, latest.alpha);
+
+        // 古い設定のマスクが後から届いても上書きされない
+        std::thread::sleep(Duration::from_millis(50));
+        let commands = world.resource::<WintfTaskPool>().drain_commands();
+        for cmd in commands {
+            cmd(&mut world);
+        }
+        assert_eq!(mask_settings(&world, entity), Some(latest.alpha));
+        assert_eq!(hit_columns(&world, entity), [true, true, true, true]);
+    });
+}
+
+#[test]
+fn test_coverage_threshold_change_applies_without_regeneration() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let (mut world, mut schedule) = mask_world();
+        let coverage = AlphaMaskFormat::Coverage { downsample: 1 };
+        let hit_test = HitTest::alpha_mask()
+            .with_alpha_mask_format(coverage)
+            .with_alpha_threshold(100);
+        let entity = spawn_soft_edge(&mut world, &wic, hit_test.clone());
+
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, hit_test.alpha);
+        assert_eq!(hit_columns(&world, entity), [false, false, true, true]);
+
+        // 閾値のみの変更は同じフレームで適用される（非同期生成を待たない）
+        let relaxed = hit_test.with_alpha_threshold(10);
+        world.entity_mut(entity).insert(relaxed.clone());
+        schedule.run(&mut world);
+        assert_eq!(mask_settings(&world, entity), Some(relaxed.alpha));
+        assert_eq!(hit_columns(&world, entity), [true, true, true, true]);
+    });
+}
+
+#[test]
+fn test_non_alpha_mask_mode_does_not_generate() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let (mut world, mut schedule) = mask_world();
+        let entity = spawn_soft_edge(&mut world, &wic, HitTest::bounds());
+        schedule.run(&mut world);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(world.resource::<WintfTaskPool>().is_empty());
+        assert_eq!(mask_settings(&world, entity), None);
+
+        // 後からαマスクモードへ切り替えると生成される
+        world.entity_mut(entity).insert(HitTest::alpha_mask());
+        schedule.run(&mut world);
+        wait_for_mask(&mut world, entity, AlphaMaskSettings::default());
+        assert_eq!(hit_columns(&world, entity), [false, false, true, true]);
+    });
+}
+
+#[test]
+fn test_alpha_coverage() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let mut world = World::new();
+        let settings = AlphaMaskSettings {
+            threshold: 128,
+            format: AlphaMaskFormat::Coverage { downsample: 2 },
+        };
+        let mut resource = soft_edge_resource(&wic);
+        resource.set_alpha_mask(AlphaMask::from_pbgra32_with(
+            &soft_edge_pixels(),
+            4,
+            1,
+            16,
+            settings,
+        ));
+        let entity = world
+            .spawn((
+                global(0.0, 0.0, 40.0, 10.0),
+                HitTest::alpha_mask(),
+                resource,
+            ))
+            .id();
+
+        // 2ピクセルずつ平均: (16 + 64) / 2 = 40, (128 + 255) / 2 ≒ 192
+        let at = |x: f32| alpha_coverage(&world, entity, PhysicalPoint::new(x, 5.0));
+        assert_eq!(at(5.0), Some(40));
+        assert_eq!(at(15.0), Some(40));
+        assert_eq!(at(25.0), Some(192));
+        assert_eq!(at(35.0), Some(192));
+        // ボックス外
+        assert_eq!(at(50.0), None);
+        // 縮小セル単位でヒット判定
+        assert_eq!(hit_columns(&world, entity), [false, false, true, true]);
+
+        // αマスクがなければ None
+        let plain = world.spawn(global(0.0, 0.0, 40.0, 10.0)).id();
+        assert_eq!(
+            alpha_coverage(&world, plain, PhysicalPoint::new(5.0, 5.0)),
+            None
+        );
+    });
+}
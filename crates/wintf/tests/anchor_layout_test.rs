@@ -0,0 +1,309 @@
+//! モニター作業領域へのアンカー（Anchor）のテスト
+//!
+//! 合成したモニター構成（タスクバー位置・DPI・マルチモニター）で
+//! 1. `Anchor::resolve`による位置計算
+//! 2. `resolve_window_anchors_system`によるWindowPosへの反映とモニター変更への追従
+//! を検証する。
+use bevy_ecs::prelude::*;
+use windows::Win32::Foundation::{POINT, RECT};
+use wintf::ecs::layout::{resolve_window_anchors_system, sync_window_arrangement_from_window_pos};
+use wintf::ecs::window::{Window, WindowPos};
+use wintf::ecs::{
+    Anchor, AnchorAlign, AnchorMonitor, Arrangement, LayoutScale, Monitor, Offset, Rect, Size,
+    select_anchor_monitor,
+};
+
+fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+    RECT {
+        left,
+        top,
+        right,
+        bottom,
+    }
+}
+
+fn monitor(handle: isize, bounds: RECT, work_area: RECT, dpi: u32, is_primary: bool) -> Monitor {
+    Monitor {
+        handle,
+        bounds,
+        work_area,
+        dpi,
+        is_primary,
+    }
+}
+
+/// 1920x1080、下端に高さ40のタスクバー
+fn primary_with_bottom_taskbar() -> Monitor {
+    monitor(1, rect(0, 0, 1920, 1080), rect(0, 0, 1920, 1040), 96, true)
+}
+
+/// プライマリの左に置いた 1280x1024 のセカンダリ（150%）
+fn secondary_on_left() -> Monitor {
+    monitor(
+        2,
+        rect(-1280, 0, 0, 1024),
+        rect(-1280, 0, 0, 1024),
+        144,
+        false,
+    )
+}
+
+fn margin(left: f32, top: f32, right: f32, bottom: f32) -> Rect<f32> {
+    Rect {
+        left,
+        right,
+        top,
+        bottom,
+    }
+}
+
+// ===== Anchor::resolve =====
+
+#[test]
+fn test_bottom_right_sits_above_taskbar() {
+    let monitor = primary_with_bottom_taskbar();
+    let anchor = Anchor::bottom_right(20.0, 10.0);
+    let position = anchor.resolve(&monitor.work_area, monitor.dpi, (200.0, 300.0));
+    assert_eq!(position, POINT { x: 1700, y: 730 });
+}
+
+#[test]
+fn test_start_alignment_respects_left_taskbar() {
+    // 左端に幅48のタスクバー
+    let work_area = rect(48, 0, 1920, 1080);
+    let anchor = Anchor::new(AnchorAlign::Start, AnchorAlign::Start)
+        .with_margin(margin(20.0, 20.0, 0.0, 0.0));
+    assert_eq!(
+        anchor.resolve(&work_area, 96, (200.0, 300.0)),
+        POINT { x: 68, y: 20 }
+    );
+}
+
+#[test]
+fn test_center_alignment() {
+    let monitor = primary_with_bottom_taskbar();
+    let position = Anchor::center().resolve(&monitor.work_area, monitor.dpi, (400.0, 240.0));
+    assert_eq!(position, POINT { x: 760, y: 400 });
+
+    // 左右の余白の差分だけずれる
+    let shifted = Anchor::center()
+        .with_margin(margin(100.0, 0.0, 0.0, 0.0))
+        .resolve(&monitor.work_area, monitor.dpi, (400.0, 240.0));
+    assert_eq!(shifted, POINT { x: 810, y: 400 });
+}
+
+#[test]
+fn test_margin_is_scaled_by_monitor_dpi() {
+    let monitor = secondary_on_left();
+    let anchor = Anchor::bottom_right(20.0, 20.0);
+    // 150%: 余白20DIP = 30px、ウィンドウは物理サイズ 300x450
+    let position = anchor.resolve(&monitor.work_area, monitor.dpi, (300.0, 450.0));
+    assert_eq!(position, POINT { x: -330, y: 544 });
+}
+
+// ===== select_anchor_monitor =====
+
+#[test]
+fn test_select_monitor_by_target() {
+    let mut world = World::new();
+    let secondary = world.spawn(secondary_on_left()).id();
+    let primary = world.spawn(primary_with_bottom_taskbar()).id();
+    let mut query = world.query::<(Entity, &Monitor)>();
+    let monitors: Vec<_> = query.iter(&world).collect();
+
+    let select = |target, center| {
+        select_anchor_monitor(target, center, monitors.iter().copied())
+            .unwrap()
+            .handle
+    };
+    assert_eq!(select(AnchorMonitor::Primary, (0.0, 0.0)), 1);
+    assert_eq!(select(AnchorMonitor::Entity(secondary), (0.0, 0.0)), 2);
+    assert_eq!(select(AnchorMonitor::Entity(primary), (0.0, 0.0)), 1);
+    assert_eq!(select(AnchorMonitor::Containing, (-640.0, 500.0)), 2);
+    assert_eq!(select(AnchorMonitor::Containing, (960.0, 500.0)), 1);
+    // どのモニターにも含まれない場合はプライマリ
+    assert_eq!(select(AnchorMonitor::Containing, (5000.0, 5000.0)), 1);
+}
+
+// ===== resolve_window_anchors_system =====
+
+fn schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(
+        (
+            resolve_window_anchors_system,
+            sync_window_arrangement_from_window_pos,
+        )
+            .chain(),
+    );
+    schedule
+}
+
+/// DIPサイズ・DPIスケールを持つWindowを生成
+fn spawn_window(world: &mut World, anchor: Anchor, width: f32, height: f32, scale: f32) -> Entity {
+    world
+        .spawn((
+            Window::default(),
+            WindowPos::default(),
+            Arrangement {
+                offset: Offset::default(),
+                scale: LayoutScale { x: scale, y: scale },
+                size: Size { width, height },
+            },
+            anchor,
+        ))
+        .id()
+}
+
+fn position(world: &World, entity: Entity) -> POINT {
+    world.get::<WindowPos>(entity).unwrap().position.unwrap()
+}
+
+#[test]
+fn test_system_writes_window_pos_and_offset() {
+    let mut world = World::new();
+    world.spawn(primary_with_bottom_taskbar());
+    let window = spawn_window(
+        &mut world,
+        Anchor::bottom_right(20.0, 10.0),
+        200.0,
+        300.0,
+        1.0,
+    );
+
+    let mut schedule = schedule();
+    schedule.run(&mut world);
+
+    assert_eq!(position(&world, window), POINT { x: 1700, y: 730 });
+    // 同じフレームでArrangement.offsetまで反映される
+    let arrangement = world.get::<Arrangement>(window).unwrap();
+    assert_eq!(
+        arrangement.offset,
+        Offset {
+            x: 1700.0,
+            y: 730.0
+        }
+    );
+
+    // 変化がなければWindowPosに触れない
+    let last_changed = |world: &World| {
+        world
+            .entity(window)
+            .get_ref::<WindowPos>()
+            .unwrap()
+            .last_changed()
+    };
+    let before = last_changed(&world);
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    assert_eq!(last_changed(&world), before);
+}
+
+#[test]
+fn test_follows_work_area_change() {
+    let mut world = World::new();
+    let monitor_entity = world.spawn(primary_with_bottom_taskbar()).id();
+    let window = spawn_window(
+        &mut world,
+        Anchor::bottom_right(20.0, 10.0),
+        200.0,
+        300.0,
+        1.0,
+    );
+    let mut schedule = schedule();
+    schedule.run(&mut world);
+
+    // タスクバーを右端（幅60）へ移動
+    world.get_mut::<Monitor>(monitor_entity).unwrap().work_area = rect(0, 0, 1860, 1080);
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: 1640, y: 770 });
+}
+
+#[test]
+fn test_follows_window_resize() {
+    let mut world = World::new();
+    world.spawn(primary_with_bottom_taskbar());
+    let window = spawn_window(
+        &mut world,
+        Anchor::bottom_right(20.0, 10.0),
+        200.0,
+        300.0,
+        1.0,
+    );
+    let mut schedule = schedule();
+    schedule.run(&mut world);
+
+    // 右下を固定したまま左上へ伸びる
+    world.get_mut::<Arrangement>(window).unwrap().size = Size {
+        width: 400.0,
+        height: 500.0,
+    };
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: 1500, y: 530 });
+}
+
+#[test]
+fn test_targeted_monitor_removal_falls_back_to_primary() {
+    let mut world = World::new();
+    world.spawn(primary_with_bottom_taskbar());
+    let secondary = world.spawn(secondary_on_left()).id();
+    let anchor = Anchor::bottom_right(20.0, 20.0).with_monitor(AnchorMonitor::Entity(secondary));
+    let window = spawn_window(&mut world, anchor, 200.0, 300.0, 1.5);
+    let mut schedule = schedule();
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: -330, y: 544 });
+
+    // セカンダリを外すとプライマリ（96DPI）の作業領域で再解決
+    world.despawn(secondary);
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: 1600, y: 570 });
+}
+
+#[test]
+fn test_new_primary_monitor_reanchors() {
+    let mut world = World::new();
+    let old_primary = world.spawn(primary_with_bottom_taskbar()).id();
+    let window = spawn_window(
+        &mut world,
+        Anchor::bottom_right(0.0, 0.0),
+        100.0,
+        100.0,
+        1.0,
+    );
+    let mut schedule = schedule();
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: 1820, y: 940 });
+
+    // プライマリを入れ替え
+    world.get_mut::<Monitor>(old_primary).unwrap().is_primary = false;
+    let mut secondary = secondary_on_left();
+    secondary.dpi = 96;
+    secondary.is_primary = true;
+    world.spawn(secondary);
+    schedule.run(&mut world);
+    assert_eq!(position(&world, window), POINT { x: -100, y: 924 });
+}
+
+#[test]
+fn test_unsized_window_is_not_anchored() {
+    let mut world = World::new();
+    world.spawn(primary_with_bottom_taskbar());
+    let window = spawn_window(&mut world, Anchor::center(), 0.0, 0.0, 1.0);
+    schedule().run(&mut world);
+    // サイズ確定前は初期値（CW_USEDEFAULT）のまま
+    assert_eq!(
+        world.get::<WindowPos>(window).unwrap().position,
+        WindowPos::default().position
+    );
+}
+
+#[test]
+fn test_monitor_same_geometry() {
+    let a = primary_with_bottom_taskbar();
+    let mut b = a.clone();
+    assert!(a.same_geometry(&b));
+    b.work_area = rect(0, 0, 1920, 1000);
+    // PartialEqはハンドルのみ比較するため、作業領域の変更は same_geometry で検知する
+    assert!(a == b);
+    assert!(!a.same_geometry(&b));
+}
@@ -409,3 +409,35 @@ fn test_global_arrangement_mul_arrangement() {
     assert_eq!(result.bounds.right, 45.0);
     assert_eq!(result.bounds.bottom, 47.0);
 }
+
+#[test]
+fn test_global_arrangement_to_screen_rect_applies_scale() {
+    // DPIスケール2.0: ローカル(DIP)の矩形は2倍されてboundsの左上へ平行移動される
+    let global = GlobalArrangement {
+        transform: Matrix3x2 {
+            M11: 2.0,
+            M12: 0.0,
+            M21: 0.0,
+            M22: 2.0,
+            M31: 100.0,
+            M32: 50.0,
+        },
+        bounds: D2D_RECT_F {
+            left: 100.0,
+            top: 50.0,
+            right: 300.0,
+            bottom: 150.0,
+        },
+    };
+
+    let screen = global.to_screen_rect(D2D_RECT_F {
+        left: 10.0,
+        top: 5.0,
+        right: 12.0,
+        bottom: 25.0,
+    });
+    assert_eq!(screen.left, 120.0);
+    assert_eq!(screen.top, 60.0);
+    assert_eq!(screen.right, 124.0);
+    assert_eq!(screen.bottom, 100.0);
+}
@@ -40,6 +40,16 @@ fn test_box_style_default_all_none() {
     assert!(style.flex_shrink.is_none());
     assert!(style.flex_basis.is_none());
     assert!(style.align_self.is_none());
+
+    // Grid系プロパティがNone
+    assert!(style.grid_template_rows.is_none());
+    assert!(style.grid_template_columns.is_none());
+    assert!(style.grid_template_areas.is_none());
+    assert!(style.grid_auto_rows.is_none());
+    assert!(style.grid_auto_columns.is_none());
+    assert!(style.grid_auto_flow.is_none());
+    assert!(style.grid_row.is_none());
+    assert!(style.grid_column.is_none());
 }
 
 /// テスト: BoxStyleが必要なトレイト（Clone, Debug, PartialEq）を実装していること
@@ -0,0 +1,327 @@
+//! フォーカスナビゲーションのテスト
+//!
+//! 1. Tab順序（`TabIndex`・ドキュメント順・循環）
+//! 2. フォーカススコープ（ポップアップ内での循環）
+//! 3. 矢印キーによる方向移動（合成レイアウト）
+//! 4. キーイベントの既定動作としてのフォーカス移動
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    VK_A, VK_DOWN, VK_LEFT, VK_RIGHT, VK_TAB, VK_UP,
+};
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::keyboard::{
+    FocusChangedEvent, FocusNavigation, FocusScope, Focusable, KeyEvent, KeyboardInput,
+    KeyboardState, Modifiers, OnKeyDown, TabIndex, dispatch_keyboard_events, find_in_direction,
+    focus_scope, focused_entity, move_focus, next_tab_stop, set_focus, tab_order,
+};
+use wintf::ecs::layout::GlobalArrangement;
+use wintf::ecs::pointer::Phase;
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+fn shift() -> Modifiers {
+    Modifiers {
+        shift: true,
+        ..Default::default()
+    }
+}
+
+/// root ─┬─ a
+///       ├─ b（TabIndex 2）
+///       ├─ c（TabIndex -1）
+///       ├─ group（Focusableでない）── e
+///       └─ d（TabIndex 1）
+struct Form {
+    root: Entity,
+    a: Entity,
+    b: Entity,
+    c: Entity,
+    d: Entity,
+    e: Entity,
+}
+
+fn spawn_form(world: &mut World) -> Form {
+    let root = world.spawn_empty().id();
+    let a = world.spawn((Focusable, ChildOf(root))).id();
+    let b = world.spawn((Focusable, TabIndex(2), ChildOf(root))).id();
+    let c = world.spawn((Focusable, TabIndex(-1), ChildOf(root))).id();
+    let group = world.spawn(ChildOf(root)).id();
+    let e = world.spawn((Focusable, ChildOf(group))).id();
+    let d = world.spawn((Focusable, TabIndex(1), ChildOf(root))).id();
+    Form {
+        root,
+        a,
+        b,
+        c,
+        d,
+        e,
+    }
+}
+
+#[test]
+fn test_tab_order_uses_tab_index_then_document_order() {
+    let mut world = World::new();
+    let f = spawn_form(&mut world);
+
+    // 正のTabIndex昇順 → ドキュメント順、負のTabIndexは除外
+    assert_eq!(tab_order(&world, f.root), vec![f.d, f.b, f.a, f.e]);
+}
+
+#[test]
+fn test_next_tab_stop_wraps_around() {
+    let mut world = World::new();
+    let f = spawn_form(&mut world);
+
+    assert_eq!(next_tab_stop(&world, f.d, false), Some(f.b));
+    assert_eq!(next_tab_stop(&world, f.e, false), Some(f.d));
+    assert_eq!(next_tab_stop(&world, f.d, true), Some(f.e));
+    assert_eq!(next_tab_stop(&world, f.a, true), Some(f.b));
+
+    // Tabストップ以外からは先頭・末尾へ
+    assert_eq!(next_tab_stop(&world, f.root, false), Some(f.d));
+    assert_eq!(next_tab_stop(&world, f.root, true), Some(f.e));
+    assert_eq!(next_tab_stop(&world, f.c, false), Some(f.d));
+
+    // Tabストップがなければ None
+    let empty = world.spawn_empty().id();
+    world.spawn(ChildOf(empty));
+    assert_eq!(next_tab_stop(&world, empty, false), None);
+}
+
+#[test]
+fn test_focus_scope_contains_navigation() {
+    let mut world = World::new();
+    let f = spawn_form(&mut world);
+    let popup = world.spawn((FocusScope, ChildOf(f.root))).id();
+    let p1 = world.spawn((Focusable, ChildOf(popup))).id();
+    let p2 = world.spawn((Focusable, ChildOf(popup))).id();
+
+    assert_eq!(focus_scope(&world, p1), popup);
+    assert_eq!(focus_scope(&world, f.e), f.root);
+
+    // 外側の順序にポップアップの中身は含まれない
+    assert_eq!(tab_order(&world, f.root), vec![f.d, f.b, f.a, f.e]);
+    assert_eq!(tab_order(&world, popup), vec![p1, p2]);
+
+    // ポップアップ内で循環
+    assert_eq!(next_tab_stop(&world, p2, false), Some(p1));
+    assert_eq!(next_tab_stop(&world, p1, true), Some(p2));
+    assert_eq!(next_tab_stop(&world, popup, false), Some(p1));
+}
+
+/// 3x3 グリッド（各 40x20、間隔 10）
+fn spawn_grid(world: &mut World) -> (Entity, [[Entity; 3]; 3]) {
+    let root = world.spawn(global(0.0, 0.0, 200.0, 200.0)).id();
+    let cells = std::array::from_fn(|row| {
+        std::array::from_fn(|col| {
+            let left = col as f32 * 50.0;
+            let top = row as f32 * 30.0;
+            world
+                .spawn((
+                    Focusable,
+                    global(left, top, left + 40.0, top + 20.0),
+                    ChildOf(root),
+                ))
+                .id()
+        })
+    });
+    (root, cells)
+}
+
+#[test]
+fn test_directional_navigation_in_grid() {
+    let mut world = World::new();
+    let (root, g) = spawn_grid(&mut world);
+    let center = g[1][1];
+
+    assert_eq!(
+        find_in_direction(&world, center, FocusNavigation::Right),
+        Some(g[1][2])
+    );
+    assert_eq!(
+        find_in_direction(&world, center, FocusNavigation::Left),
+        Some(g[1][0])
+    );
+    assert_eq!(
+        find_in_direction(&world, center, FocusNavigation::Up),
+        Some(g[0][1])
+    );
+    assert_eq!(
+        find_in_direction(&world, center, FocusNavigation::Down),
+        Some(g[2][1])
+    );
+
+    // 端からはそれ以上進まない
+    assert_eq!(
+        find_in_direction(&world, g[1][2], FocusNavigation::Right),
+        None
+    );
+    assert_eq!(
+        find_in_direction(&world, g[0][0], FocusNavigation::Up),
+        None
+    );
+
+    // Tab移動は方向移動ではない・スコープのルートからは移動しない
+    assert_eq!(
+        find_in_direction(&world, center, FocusNavigation::Next),
+        None
+    );
+    assert_eq!(find_in_direction(&world, root, FocusNavigation::Down), None);
+}
+
+#[test]
+fn test_directional_navigation_prefers_aligned_targets() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 400.0, 400.0)).id();
+    let from = world
+        .spawn((Focusable, global(0.0, 0.0, 40.0, 20.0), ChildOf(root)))
+        .id();
+    // 斜め下の近いもの
+    world.spawn((Focusable, global(60.0, 30.0, 100.0, 50.0), ChildOf(root)));
+    // 真下だが少し遠いもの
+    let below = world
+        .spawn((Focusable, global(0.0, 60.0, 40.0, 80.0), ChildOf(root)))
+        .id();
+    // 真下の幅広いもの（さらに遠い）
+    world.spawn((Focusable, global(0.0, 100.0, 400.0, 120.0), ChildOf(root)));
+    // Tab対象外は選ばれない
+    world.spawn((
+        Focusable,
+        TabIndex(-1),
+        global(0.0, 25.0, 40.0, 45.0),
+        ChildOf(root),
+    ));
+
+    assert_eq!(
+        find_in_direction(&world, from, FocusNavigation::Down),
+        Some(below)
+    );
+}
+
+#[test]
+fn test_directional_navigation_tie_breaks_by_center_offset() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 400.0, 400.0)).id();
+    let from = world
+        .spawn((Focusable, global(100.0, 0.0, 200.0, 20.0), ChildOf(root)))
+        .id();
+    // どちらも from と横方向に重なる（直交方向の隙間 0）
+    world.spawn((Focusable, global(0.0, 40.0, 120.0, 60.0), ChildOf(root)));
+    let centered = world
+        .spawn((Focusable, global(110.0, 40.0, 210.0, 60.0), ChildOf(root)))
+        .id();
+
+    assert_eq!(
+        find_in_direction(&world, from, FocusNavigation::Down),
+        Some(centered)
+    );
+}
+
+#[test]
+fn test_move_focus() {
+    let mut world = World::new();
+    let f = spawn_form(&mut world);
+
+    assert!(move_focus(&mut world, f.root, FocusNavigation::Next));
+    assert_eq!(focused_entity(&world, f.root), Some(f.d));
+    assert!(move_focus(&mut world, f.d, FocusNavigation::Previous));
+    assert_eq!(focused_entity(&world, f.root), Some(f.e));
+    // 配置がなければ方向移動しない
+    assert!(!move_focus(&mut world, f.e, FocusNavigation::Down));
+    assert_eq!(focused_entity(&world, f.root), Some(f.e));
+}
+
+#[test]
+fn test_from_key_event() {
+    let nav = |event: KeyEvent| FocusNavigation::from_key_event(&event);
+    assert_eq!(nav(KeyEvent::pressed(VK_TAB)), Some(FocusNavigation::Next));
+    assert_eq!(
+        nav(KeyEvent::pressed(VK_TAB).with_modifiers(shift())),
+        Some(FocusNavigation::Previous)
+    );
+    assert_eq!(nav(KeyEvent::pressed(VK_LEFT)), Some(FocusNavigation::Left));
+    assert_eq!(nav(KeyEvent::pressed(VK_UP)), Some(FocusNavigation::Up));
+    // 解放・修飾キー併用・その他のキーは対象外
+    assert_eq!(nav(KeyEvent::released(VK_TAB)), None);
+    assert_eq!(
+        nav(KeyEvent::pressed(VK_RIGHT).with_modifiers(shift())),
+        None
+    );
+    assert_eq!(
+        nav(KeyEvent::pressed(VK_TAB).with_modifiers(Modifiers {
+            ctrl: true,
+            ..Default::default()
+        })),
+        None
+    );
+    assert_eq!(nav(KeyEvent::pressed(VK_A)), None);
+}
+
+fn init_keyboard(world: &mut World) {
+    world.init_resource::<KeyboardInput>();
+    world.init_resource::<KeyboardState>();
+    world.init_resource::<Messages<FocusChangedEvent>>();
+}
+
+fn send(world: &mut World, window: Entity, event: KeyEvent) {
+    world.resource_mut::<KeyboardInput>().push(window, event);
+    dispatch_keyboard_events(world);
+}
+
+#[test]
+fn test_unhandled_keys_move_focus() {
+    let mut world = World::new();
+    init_keyboard(&mut world);
+    let (root, g) = spawn_grid(&mut world);
+
+    // フォーカスなし: Tab で先頭へ
+    send(&mut world, root, KeyEvent::pressed(VK_TAB));
+    assert_eq!(focused_entity(&world, root), Some(g[0][0]));
+    send(&mut world, root, KeyEvent::pressed(VK_TAB));
+    assert_eq!(focused_entity(&world, root), Some(g[0][1]));
+    send(
+        &mut world,
+        root,
+        KeyEvent::pressed(VK_TAB).with_modifiers(shift()),
+    );
+    assert_eq!(focused_entity(&world, root), Some(g[0][0]));
+
+    send(&mut world, root, KeyEvent::pressed(VK_DOWN));
+    assert_eq!(focused_entity(&world, root), Some(g[1][0]));
+    send(&mut world, root, KeyEvent::pressed(VK_RIGHT));
+    assert_eq!(focused_entity(&world, root), Some(g[1][1]));
+}
+
+fn handle_arrows(_: &mut World, _: Entity, _: Entity, ev: &Phase<KeyEvent>) -> bool {
+    let event = ev.value();
+    ev.is_bubble() && (event.is_key(VK_LEFT) || event.is_key(VK_RIGHT))
+}
+
+#[test]
+fn test_handled_keys_do_not_move_focus() {
+    let mut world = World::new();
+    init_keyboard(&mut world);
+    let (root, g) = spawn_grid(&mut world);
+    world.entity_mut(g[1][1]).insert(OnKeyDown(handle_arrows));
+    set_focus(&mut world, g[1][1]);
+
+    // 左右はハンドラが処理するため移動しない
+    send(&mut world, root, KeyEvent::pressed(VK_RIGHT));
+    assert_eq!(focused_entity(&world, root), Some(g[1][1]));
+
+    // 上下は未処理のため移動する
+    send(&mut world, root, KeyEvent::pressed(VK_UP));
+    assert_eq!(focused_entity(&world, root), Some(g[0][1]));
+}
@@ -0,0 +1,309 @@
+//! 形状ベースの HitTestMode（楕円・角丸矩形・多角形・カスタム）のテスト
+//!
+//! 1. hit_shape の純粋な判定関数
+//! 2. hit_test_entity / hit_test 経由の判定（Direct2D不要）
+use bevy_ecs::prelude::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::layout::{
+    CornerRadii, FillRule, GlobalArrangement, HitRegion, HitTest, HitTestMode, LocalPoint, Offset,
+    PhysicalPoint, Size, ellipse_contains, hit_test, hit_test_entity, polygon_contains,
+    rounded_rect_contains,
+};
+use wintf::ecs::transform::{Rotate, Transform};
+
+fn local(x: f32, y: f32, width: f32, height: f32) -> LocalPoint {
+    LocalPoint {
+        x,
+        y,
+        size: Size { width, height },
+    }
+}
+
+fn points(coords: &[(f32, f32)]) -> Vec<Offset> {
+    coords.iter().map(|&(x, y)| Offset { x, y }).collect()
+}
+
+/// 五芒星（自己交差する多角形、中央の五角形が偶奇規則では穴になる）
+fn pentagram() -> Vec<Offset> {
+    points(&[
+        (50.0, 0.0),
+        (79.4, 90.5),
+        (2.4, 34.5),
+        (97.6, 34.5),
+        (20.6, 90.5),
+    ])
+}
+
+// ===== 楕円 =====
+
+#[test]
+fn test_ellipse_contains() {
+    // 200x100 の内接楕円
+    assert!(ellipse_contains(&local(100.0, 50.0, 200.0, 100.0)));
+    assert!(ellipse_contains(&local(0.0, 50.0, 200.0, 100.0)));
+    assert!(ellipse_contains(&local(100.0, 100.0, 200.0, 100.0)));
+    // 角は楕円外
+    assert!(!ellipse_contains(&local(10.0, 10.0, 200.0, 100.0)));
+    assert!(!ellipse_contains(&local(195.0, 95.0, 200.0, 100.0)));
+    // サイズ0は常に外
+    assert!(!ellipse_contains(&local(0.0, 0.0, 0.0, 0.0)));
+}
+
+// ===== 角丸矩形 =====
+
+#[test]
+fn test_rounded_rect_uniform_radius() {
+    let radii = CornerRadii::uniform(20.0);
+    // 辺の中央・中心は内側
+    assert!(rounded_rect_contains(
+        &local(50.0, 0.0, 100.0, 60.0),
+        &radii
+    ));
+    assert!(rounded_rect_contains(
+        &local(50.0, 30.0, 100.0, 60.0),
+        &radii
+    ));
+    // 角の欠けた部分は外側
+    assert!(!rounded_rect_contains(
+        &local(1.0, 1.0, 100.0, 60.0),
+        &radii
+    ));
+    assert!(!rounded_rect_contains(
+        &local(99.0, 59.0, 100.0, 60.0),
+        &radii
+    ));
+    // 角の円弧の内側
+    assert!(rounded_rect_contains(&local(6.0, 6.0, 100.0, 60.0), &radii));
+}
+
+#[test]
+fn test_rounded_rect_per_corner_radii() {
+    // 吹き出し: 左下だけ角張っている
+    let radii = CornerRadii {
+        top_left: 30.0,
+        top_right: 30.0,
+        bottom_right: 30.0,
+        bottom_left: 0.0,
+    };
+    assert!(rounded_rect_contains(
+        &local(0.0, 100.0, 100.0, 100.0),
+        &radii
+    ));
+    assert!(!rounded_rect_contains(
+        &local(100.0, 100.0, 100.0, 100.0),
+        &radii
+    ));
+    assert!(!rounded_rect_contains(
+        &local(2.0, 2.0, 100.0, 100.0),
+        &radii
+    ));
+    assert!(!rounded_rect_contains(
+        &local(98.0, 2.0, 100.0, 100.0),
+        &radii
+    ));
+}
+
+#[test]
+fn test_rounded_rect_oversized_radii_become_capsule() {
+    // 高さ40に半径100 → 半径20のカプセル形状に縮小される
+    let radii = CornerRadii::uniform(100.0);
+    assert!(rounded_rect_contains(
+        &local(20.0, 20.0, 200.0, 40.0),
+        &radii
+    ));
+    assert!(rounded_rect_contains(
+        &local(100.0, 1.0, 200.0, 40.0),
+        &radii
+    ));
+    assert!(!rounded_rect_contains(
+        &local(3.0, 3.0, 200.0, 40.0),
+        &radii
+    ));
+    assert!(rounded_rect_contains(
+        &local(0.0, 20.0, 200.0, 40.0),
+        &radii
+    ));
+}
+
+// ===== 多角形 =====
+
+#[test]
+fn test_polygon_triangle() {
+    // 吹き出しの尻尾（三角形）
+    let triangle = points(&[(0.0, 0.0), (40.0, 0.0), (0.0, 40.0)]);
+    for rule in [FillRule::EvenOdd, FillRule::NonZero] {
+        assert!(polygon_contains(&triangle, rule, 5.0, 5.0), "{rule:?}");
+        assert!(!polygon_contains(&triangle, rule, 30.0, 30.0), "{rule:?}");
+    }
+}
+
+#[test]
+fn test_polygon_fill_rules_on_self_intersecting_star() {
+    let star = pentagram();
+    // 中央の五角形: 偶奇規則では穴、非ゼロ規則では内側
+    assert!(!polygon_contains(&star, FillRule::EvenOdd, 50.0, 50.0));
+    assert!(polygon_contains(&star, FillRule::NonZero, 50.0, 50.0));
+    // 星の先端はどちらの規則でも内側
+    assert!(polygon_contains(&star, FillRule::EvenOdd, 50.0, 10.0));
+    assert!(polygon_contains(&star, FillRule::NonZero, 50.0, 10.0));
+    // 先端の間は外側
+    assert!(!polygon_contains(&star, FillRule::NonZero, 85.0, 70.0));
+}
+
+#[test]
+fn test_polygon_orientation_does_not_matter() {
+    let square = points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+    let reversed: Vec<_> = square.iter().rev().copied().collect();
+    for polygon in [&square, &reversed] {
+        assert!(polygon_contains(polygon, FillRule::NonZero, 5.0, 5.0));
+        assert!(polygon_contains(polygon, FillRule::EvenOdd, 5.0, 5.0));
+    }
+}
+
+#[test]
+fn test_polygon_with_too_few_points() {
+    let line = points(&[(0.0, 0.0), (10.0, 10.0)]);
+    assert!(!polygon_contains(&line, FillRule::NonZero, 5.0, 5.0));
+}
+
+// ===== HitRegion =====
+
+#[test]
+fn test_hit_region_equality_is_identity() {
+    let region = HitRegion::new(|p| p.x < 10.0);
+    assert_eq!(region, region.clone());
+    assert_ne!(region, HitRegion::new(|p| p.x < 10.0));
+    assert!(region.contains(local(5.0, 0.0, 20.0, 20.0)));
+}
+
+// ===== hit_test_entity 経由 =====
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+fn hits(world: &World, entity: Entity, x: f32, y: f32) -> bool {
+    hit_test_entity(world, entity, PhysicalPoint::new(x, y))
+}
+
+#[test]
+fn test_round_button_ignores_corners() {
+    let mut world = World::new();
+    let button = world
+        .spawn((global(100.0, 100.0, 150.0, 150.0), HitTest::ellipse()))
+        .id();
+    assert!(hits(&world, button, 125.0, 125.0));
+    assert!(!hits(&world, button, 102.0, 102.0));
+
+    // 角をクリックすると背面の要素に届く
+    let back = world.spawn(global(0.0, 0.0, 300.0, 300.0)).id();
+    let root = world.spawn_empty().id();
+    world.entity_mut(root).add_children(&[back, button]);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(102.0, 102.0)),
+        Some(back)
+    );
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(125.0, 125.0)),
+        Some(button)
+    );
+}
+
+#[test]
+fn test_polygon_uses_local_coordinates() {
+    let mut world = World::new();
+    let triangle = points(&[(0.0, 0.0), (40.0, 0.0), (0.0, 40.0)]);
+    let entity = world
+        .spawn((
+            global(200.0, 100.0, 240.0, 140.0),
+            HitTest::polygon(triangle, FillRule::NonZero),
+        ))
+        .id();
+    assert!(hits(&world, entity, 205.0, 105.0));
+    assert!(!hits(&world, entity, 235.0, 135.0));
+}
+
+#[test]
+fn test_shapes_follow_transform() {
+    let mut world = World::new();
+    // 左上だけ角張った角丸矩形を180度回転 → 角張った角は右下に来る
+    let radii = CornerRadii {
+        top_left: 0.0,
+        top_right: 20.0,
+        bottom_right: 20.0,
+        bottom_left: 20.0,
+    };
+    let entity = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 100.0),
+            HitTest::rounded_rect(radii),
+            Transform {
+                rotate: Rotate(180.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+    assert!(hits(&world, entity, 99.0, 99.0));
+    assert!(!hits(&world, entity, 1.0, 1.0));
+}
+
+#[test]
+fn test_custom_region_receives_local_point() {
+    let mut world = World::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    // 右半分だけヒット
+    let entity = world
+        .spawn((
+            global(50.0, 50.0, 150.0, 100.0),
+            HitTest::custom(move |p| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                assert_eq!(
+                    p.size,
+                    Size {
+                        width: 100.0,
+                        height: 50.0
+                    }
+                );
+                p.x >= p.size.width / 2.0
+            }),
+        ))
+        .id();
+
+    assert!(hits(&world, entity, 120.0, 60.0));
+    assert!(!hits(&world, entity, 60.0, 60.0));
+    // レイアウトボックス外では関数を呼ばない
+    assert!(!hits(&world, entity, 200.0, 60.0));
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_hit_test_mode_constructors() {
+    assert_eq!(HitTest::ellipse().mode, HitTestMode::Ellipse);
+    assert_eq!(
+        HitTest::rounded_rect(CornerRadii::uniform(4.0)).mode,
+        HitTestMode::RoundedRect(CornerRadii::uniform(4.0))
+    );
+    let polygon = HitTest::polygon(
+        points(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]),
+        FillRule::EvenOdd,
+    );
+    assert!(matches!(
+        polygon.mode,
+        HitTestMode::Polygon {
+            ref points,
+            fill_rule: FillRule::EvenOdd
+        } if points.len() == 3
+    ));
+}
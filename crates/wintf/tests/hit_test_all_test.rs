@@ -0,0 +1,197 @@
+//! 重なったエンティティをすべて返すヒットテスト（hit_test_all / hit_test_filtered）のテスト
+//!
+//! 1. 前面→背面の順序と判定モード（`HitTest`・クリップ・`ZIndex`）の反映
+//! 2. フィルター（述語・コンポーネント）
+//! 3. 空間インデックスの有無による結果の一致
+use bevy_ecs::prelude::*;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::layout::{
+    ClipToBounds, GlobalArrangement, HitTest, HitTestIndex, PhysicalPoint, ZIndex, hit_test,
+    hit_test_all, hit_test_all_filtered, hit_test_filtered, hit_test_with, is_self_or_descendant,
+    update_hit_test_index_system,
+};
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+#[derive(Component)]
+struct Tooltip;
+
+/// root(0..200) ─┬─ panel(0..100) ── label(20..80)
+///               ├─ card(50..150)  ── icon(60..90, 楕円)
+///               └─ ghost(0..200, HitTest::none)
+struct Scene {
+    root: Entity,
+    panel: Entity,
+    label: Entity,
+    card: Entity,
+    icon: Entity,
+}
+
+fn spawn_scene(world: &mut World) -> Scene {
+    let root = world.spawn(global(0.0, 0.0, 200.0, 200.0)).id();
+    let panel = world
+        .spawn((global(0.0, 0.0, 100.0, 100.0), Tooltip, ChildOf(root)))
+        .id();
+    let label = world
+        .spawn((global(20.0, 20.0, 80.0, 80.0), ChildOf(panel)))
+        .id();
+    let card = world
+        .spawn((global(50.0, 50.0, 150.0, 150.0), ChildOf(root)))
+        .id();
+    let icon = world
+        .spawn((
+            global(60.0, 60.0, 90.0, 90.0),
+            HitTest::ellipse(),
+            ChildOf(card),
+        ))
+        .id();
+    world.spawn((
+        global(0.0, 0.0, 200.0, 200.0),
+        HitTest::none(),
+        ChildOf(root),
+    ));
+    Scene {
+        root,
+        panel,
+        label,
+        card,
+        icon,
+    }
+}
+
+#[test]
+fn test_hit_test_all_is_front_to_back() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+
+    let point = PhysicalPoint::new(75.0, 75.0);
+    assert_eq!(
+        hit_test_all(&world, s.root, point),
+        vec![s.icon, s.card, s.label, s.panel, s.root]
+    );
+    assert_eq!(hit_test(&world, s.root, point), Some(s.icon));
+    // 楕円の外（アイコンの角）はアイコンを含まない
+    assert_eq!(
+        hit_test_all(&world, s.root, PhysicalPoint::new(61.0, 61.0)),
+        vec![s.card, s.label, s.panel, s.root]
+    );
+    assert!(hit_test_all(&world, s.root, PhysicalPoint::new(500.0, 500.0)).is_empty());
+    // サブツリーをルートにした場合
+    assert_eq!(hit_test_all(&world, s.card, point), vec![s.icon, s.card]);
+}
+
+#[test]
+fn test_hit_test_all_respects_clip_and_z_index() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    world.entity_mut(s.card).insert(ClipToBounds);
+    // アイコンをカードの外にはみ出させる
+    *world.get_mut::<GlobalArrangement>(s.icon).unwrap() = global(120.0, 120.0, 180.0, 180.0);
+    world.entity_mut(s.icon).insert(HitTest::bounds());
+
+    let outside = PhysicalPoint::new(170.0, 170.0);
+    assert_eq!(hit_test_all(&world, s.root, outside), vec![s.root]);
+
+    // パネルを前面へ
+    world.entity_mut(s.panel).insert(ZIndex::Local(1));
+    assert_eq!(
+        hit_test_all(&world, s.root, PhysicalPoint::new(75.0, 75.0)),
+        vec![s.label, s.panel, s.card, s.root]
+    );
+}
+
+#[test]
+fn test_filtered_excludes_dragged_subtree() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    let point = PhysicalPoint::new(75.0, 75.0);
+
+    // カードをドラッグ中: カードとその子孫の下にあるもの
+    let dragged = s.card;
+    let below = hit_test_filtered(&world, s.root, point, |e| {
+        !is_self_or_descendant(&world, dragged, e)
+    });
+    assert_eq!(below, Some(s.label));
+    assert_eq!(
+        hit_test_all_filtered(&world, s.root, point, |e| {
+            !is_self_or_descendant(&world, dragged, e)
+        }),
+        vec![s.label, s.panel, s.root]
+    );
+
+    // 自身のみ除外した場合、子孫は残る
+    assert_eq!(
+        hit_test_all_filtered(&world, s.root, point, |e| e != dragged),
+        vec![s.icon, s.label, s.panel, s.root]
+    );
+}
+
+#[test]
+fn test_hit_test_with_component() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+
+    // 最前面はアイコンだが、Tooltipを持つ最前面はパネル
+    assert_eq!(
+        hit_test_with::<Tooltip>(&world, s.root, PhysicalPoint::new(75.0, 75.0)),
+        Some(s.panel)
+    );
+    // パネル外
+    assert_eq!(
+        hit_test_with::<Tooltip>(&world, s.root, PhysicalPoint::new(140.0, 140.0)),
+        None
+    );
+}
+
+#[test]
+fn test_indexed_results_match_linear_walk() {
+    let mut linear = World::new();
+    let s = spawn_scene(&mut linear);
+
+    let mut indexed = World::new();
+    indexed.insert_resource(HitTestIndex::with_cell_size(32.0));
+    let t = spawn_scene(&mut indexed);
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_hit_test_index_system);
+    schedule.run(&mut indexed);
+
+    let to_scene = |e: Entity| {
+        [
+            (t.root, s.root),
+            (t.panel, s.panel),
+            (t.label, s.label),
+            (t.card, s.card),
+            (t.icon, s.icon),
+        ]
+        .into_iter()
+        .find_map(|(from, to)| (from == e).then_some(to))
+        .unwrap()
+    };
+
+    let mut y = -5.0;
+    while y < 210.0 {
+        let mut x = -5.0;
+        while x < 210.0 {
+            let point = PhysicalPoint::new(x, y);
+            let expected = hit_test_all(&linear, s.root, point);
+            let actual: Vec<Entity> = hit_test_all(&indexed, t.root, point)
+                .into_iter()
+                .map(to_scene)
+                .collect();
+            assert_eq!(actual, expected, "point=({x}, {y})");
+            x += 7.0;
+        }
+        y += 7.0;
+    }
+}
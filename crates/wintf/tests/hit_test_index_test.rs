@@ -0,0 +1,406 @@
+//! ヒットテスト用空間インデックス（HitTestIndex）のテスト
+//!
+//! 1. インデックス経由の`hit_test`と線形走査`hit_test_linear`の等価性
+//! 2. `GlobalArrangement`・`Transform`・階層の変更に対する差分更新
+//! 3. ベンチマーク（`--ignored`で実行、計測値は`tracing`で報告）
+use bevy_ecs::prelude::*;
+use std::time::Instant;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::layout::{
+    ClipToBounds, GlobalArrangement, HitTest, HitTestIndex, PhysicalPoint, ZIndex, hit_test,
+    hit_test_linear, update_hit_test_index_system,
+};
+use wintf::ecs::transform::{Rotate, Transform};
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+/// 再現性のある疑似乱数（線形合同法）
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_f32(&mut self) -> f32 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+struct Scene {
+    root: Entity,
+    groups: Vec<Entity>,
+    sprites: Vec<Entity>,
+}
+
+/// ルート → グループ → スプライトの3階層シーンを生成
+///
+/// 重なり・`HitTest::none()`・`ClipToBounds`・回転を含む。
+fn spawn_scene(world: &mut World, groups: usize, sprites_per_group: usize, seed: u64) -> Scene {
+    let mut rng = Lcg(seed);
+    let root = world.spawn(global(0.0, 0.0, 2000.0, 2000.0)).id();
+    let mut group_entities = Vec::new();
+    let mut sprites = Vec::new();
+    for g in 0..groups {
+        let left = rng.range(0.0, 1500.0);
+        let top = rng.range(0.0, 1500.0);
+        let group = world
+            .spawn((global(left, top, left + 400.0, top + 400.0), ChildOf(root)))
+            .id();
+        if g % 3 == 0 {
+            world.entity_mut(group).insert(ClipToBounds);
+        }
+        for i in 0..sprites_per_group {
+            let x = rng.range(left - 50.0, left + 400.0);
+            let y = rng.range(top - 50.0, top + 400.0);
+            let size = rng.range(4.0, 64.0);
+            let mut sprite = world.spawn((global(x, y, x + size, y + size), ChildOf(group)));
+            match i % 5 {
+                0 => {
+                    sprite.insert(HitTest::none());
+                }
+                1 => {
+                    sprite.insert(Transform {
+                        rotate: Rotate(rng.range(0.0, 90.0)),
+                        ..Default::default()
+                    });
+                }
+                2 => {
+                    sprite.insert(HitTest::ellipse());
+                }
+                _ => {}
+            }
+            sprites.push(sprite.id());
+        }
+        group_entities.push(group);
+    }
+    Scene {
+        root,
+        groups: group_entities,
+        sprites,
+    }
+}
+
+fn index_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_hit_test_index_system);
+    schedule
+}
+
+/// インデックスあり・なしの結果が一致することを格子点で検証
+fn assert_equivalent(world: &World, root: Entity, step: f32) {
+    let mut y = -20.0;
+    while y < 2020.0 {
+        let mut x = -20.0;
+        while x < 2020.0 {
+            let point = PhysicalPoint::new(x, y);
+            assert_eq!(
+                hit_test(world, root, point),
+                hit_test_linear(world, root, point),
+                "root={root:?} point=({x}, {y})"
+            );
+            x += step;
+        }
+        y += step;
+    }
+}
+
+fn indexed_world() -> World {
+    let mut world = World::new();
+    world.insert_resource(HitTestIndex::default());
+    world
+}
+
+#[test]
+fn test_indexed_matches_linear_walk() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 12, 40, 1);
+    index_schedule().run(&mut world);
+
+    assert_eq!(world.resource::<HitTestIndex>().len(), 1 + 12 + 12 * 40);
+    assert_equivalent(&world, scene.root, 13.0);
+    // サブツリーをルートにした場合も同じ
+    assert_equivalent(&world, scene.groups[0], 13.0);
+    assert_equivalent(&world, scene.groups[5], 13.0);
+}
+
+#[test]
+fn test_small_cells_match_linear_walk() {
+    // スプライトが多数のセルにまたがる場合
+    let mut world = World::new();
+    world.insert_resource(HitTestIndex::with_cell_size(8.0));
+    let scene = spawn_scene(&mut world, 6, 30, 7);
+    index_schedule().run(&mut world);
+    assert_equivalent(&world, scene.root, 11.0);
+}
+
+#[test]
+fn test_candidates_are_narrowed() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 12, 40, 3);
+    index_schedule().run(&mut world);
+
+    let index = world.resource::<HitTestIndex>();
+    let candidates = index
+        .candidates(&world, scene.root, PhysicalPoint::new(100.0, 100.0))
+        .unwrap()
+        .count();
+    assert!(candidates < 1 + 12 + 12 * 40 / 4, "{candidates}");
+}
+
+#[test]
+fn test_front_to_back_order_is_preserved() {
+    let mut world = indexed_world();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let back = world
+        .spawn((global(10.0, 10.0, 90.0, 90.0), ChildOf(root)))
+        .id();
+    let front = world
+        .spawn((global(20.0, 20.0, 80.0, 80.0), ChildOf(root)))
+        .id();
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(50.0, 50.0)),
+        Some(front)
+    );
+
+    // 兄弟の順序を入れ替えると前後関係も入れ替わる
+    world.entity_mut(root).detach_child(back);
+    world.entity_mut(root).insert_children(1, &[back]);
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(50.0, 50.0)),
+        Some(back)
+    );
+    assert_equivalent(&world, root, 5.0);
+}
+
+#[test]
+fn test_follows_global_arrangement_changes() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 4, 20, 11);
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+
+    // スプライトを遠くへ移動
+    let sprite = scene.sprites[3];
+    *world.get_mut::<GlobalArrangement>(sprite).unwrap() = global(1900.0, 1900.0, 1990.0, 1990.0);
+    schedule.run(&mut world);
+    assert_equivalent(&world, scene.root, 9.0);
+    assert_eq!(
+        hit_test(&world, scene.root, PhysicalPoint::new(1950.0, 1950.0)),
+        Some(sprite)
+    );
+}
+
+#[test]
+fn test_follows_parent_transform_changes() {
+    let mut world = indexed_world();
+    let root = world.spawn(global(0.0, 0.0, 1000.0, 1000.0)).id();
+    let parent = world
+        .spawn((global(0.0, 0.0, 200.0, 200.0), ChildOf(root)))
+        .id();
+    let child = world
+        .spawn((global(0.0, 0.0, 50.0, 50.0), ChildOf(parent)))
+        .id();
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(25.0, 25.0)),
+        Some(child)
+    );
+
+    // 親を180度回転 → 子は右下へ（子のGlobalArrangementは変わらない）
+    world.entity_mut(parent).insert(Transform {
+        rotate: Rotate(180.0),
+        ..Default::default()
+    });
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(175.0, 175.0)),
+        Some(child)
+    );
+    assert_equivalent(&world, root, 10.0);
+
+    // Transform を外すと元に戻る
+    world.entity_mut(parent).remove::<Transform>();
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(25.0, 25.0)),
+        Some(child)
+    );
+    assert_equivalent(&world, root, 10.0);
+}
+
+#[test]
+fn test_transform_change_does_not_rebuild_orders() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 4, 20, 17);
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+    let rebuilds = world.resource::<HitTestIndex>().order_rebuilds();
+
+    // アニメーション中のスプライト: Transform のみの変更ではAABBだけを更新する
+    let sprite = scene.sprites[1];
+    for frame in 0..5 {
+        world.get_mut::<Transform>(sprite).unwrap().rotate = Rotate(frame as f32 * 15.0);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<HitTestIndex>().order_rebuilds(),
+            rebuilds,
+            "frame {frame}"
+        );
+    }
+    assert_equivalent(&world, scene.root, 9.0);
+
+    // ZIndex の変更は走査順を再計算する
+    world.entity_mut(scene.sprites[2]).insert(ZIndex::Local(1));
+    schedule.run(&mut world);
+    assert_eq!(
+        world.resource::<HitTestIndex>().order_rebuilds(),
+        rebuilds + 1
+    );
+    assert_equivalent(&world, scene.root, 9.0);
+}
+
+#[test]
+fn test_follows_resize_and_rotate_in_same_frame() {
+    let mut world = indexed_world();
+    let root = world.spawn(global(0.0, 0.0, 1000.0, 1000.0)).id();
+    let parent = world
+        .spawn((global(0.0, 0.0, 200.0, 200.0), ChildOf(root)))
+        .id();
+    let child = world
+        .spawn((global(0.0, 0.0, 50.0, 50.0), ChildOf(parent)))
+        .id();
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+
+    // 同じフレームで親のサイズ変更（GlobalArrangement）と回転（Transform）
+    world.entity_mut(parent).insert((
+        global(0.0, 0.0, 300.0, 300.0),
+        Transform {
+            rotate: Rotate(180.0),
+            ..Default::default()
+        },
+    ));
+    schedule.run(&mut world);
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(275.0, 275.0)),
+        Some(child)
+    );
+    assert_equivalent(&world, root, 10.0);
+}
+
+#[test]
+fn test_follows_reparent_and_despawn() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 4, 20, 5);
+    let mut schedule = index_schedule();
+    schedule.run(&mut world);
+
+    // グループ間の付け替え
+    world
+        .entity_mut(scene.sprites[25])
+        .insert(ChildOf(scene.groups[0]));
+    world.despawn(scene.sprites[2]);
+    world.despawn(scene.groups[3]);
+    schedule.run(&mut world);
+
+    assert_equivalent(&world, scene.root, 9.0);
+    assert_equivalent(&world, scene.groups[0], 9.0);
+}
+
+#[test]
+fn test_unindexed_root_falls_back_to_linear_walk() {
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, 2, 10, 9);
+    index_schedule().run(&mut world);
+
+    // インデックス更新前に追加したサブツリー
+    let late_root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let late_child = world
+        .spawn((global(10.0, 10.0, 20.0, 20.0), ChildOf(late_root)))
+        .id();
+    assert_eq!(
+        hit_test(&world, late_root, PhysicalPoint::new(15.0, 15.0)),
+        Some(late_child)
+    );
+    assert_equivalent(&world, scene.root, 20.0);
+}
+
+#[test]
+#[ignore = "ベンチマーク（--ignored で実行、計測値は tracing の info で報告）"]
+fn bench_hit_test_many_sprites() {
+    const GROUPS: usize = 50;
+    const SPRITES: usize = 100;
+    const QUERIES: usize = 2000;
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+    let mut world = indexed_world();
+    let scene = spawn_scene(&mut world, GROUPS, SPRITES, 42);
+    let entities = 1 + GROUPS + GROUPS * SPRITES;
+
+    let started = Instant::now();
+    index_schedule().run(&mut world);
+    let build = started.elapsed();
+
+    let mut rng = Lcg(123);
+    let points: Vec<_> = (0..QUERIES)
+        .map(|_| PhysicalPoint::new(rng.range(0.0, 2000.0), rng.range(0.0, 2000.0)))
+        .collect();
+
+    let started = Instant::now();
+    let indexed: Vec<_> = points
+        .iter()
+        .map(|&p| hit_test(&world, scene.root, p))
+        .collect();
+    let indexed_time = started.elapsed();
+
+    let started = Instant::now();
+    let linear: Vec<_> = points
+        .iter()
+        .map(|&p| hit_test_linear(&world, scene.root, p))
+        .collect();
+    let linear_time = started.elapsed();
+
+    let index = world.resource::<HitTestIndex>();
+    let candidates: usize = points
+        .iter()
+        .map(|&p| index.candidates(&world, scene.root, p).unwrap().count())
+        .sum();
+
+    tracing::info!(
+        entities,
+        queries = QUERIES,
+        ?build,
+        indexed_per_query = ?indexed_time / QUERIES as u32,
+        linear_per_query = ?linear_time / QUERIES as u32,
+        candidates_per_query = candidates / QUERIES,
+        "hit test benchmark"
+    );
+
+    assert_eq!(indexed, linear);
+    // 経過時間ではなく候補数で比較する（線形走査は全エンティティを判定する）
+    assert!(
+        candidates * 4 < entities * QUERIES,
+        "candidates/query={} entities={entities}",
+        candidates / QUERIES
+    );
+}
@@ -0,0 +1,393 @@
+//! インクリメンタルレイアウトのテスト
+//!
+//! 1. ウィンドウ（レイアウト境界）内の変更はそのサブツリーだけを再計算すること
+//! 2. 境界自身のサイズ変更・境界をまたぐ移動はLayoutRootから再計算すること
+//! 3. インクリメンタル計算の結果が全体計算と一致すること
+//! 4. 多数のウィンドウがあっても再計算ノード数が変更のあったウィンドウ分に収まること
+//!
+//! 計測用ベンチマーク（`#[ignore]`、計測値は`tracing`で報告）:
+//! `cargo test -p wintf --test incremental_layout_test -- --ignored`
+use bevy_ecs::name::Name;
+use bevy_ecs::prelude::*;
+use std::time::Instant;
+use wintf::ecs::ChildOf;
+use wintf::ecs::layout::*;
+use wintf::ecs::world::EcsWorld;
+
+fn px_size(width: f32, height: f32) -> Option<BoxSize> {
+    Some(BoxSize {
+        width: Some(Dimension::Px(width)),
+        height: Some(Dimension::Px(height)),
+    })
+}
+
+fn item_style(height: f32) -> BoxStyle {
+    BoxStyle {
+        size: Some(BoxSize {
+            width: None,
+            height: Some(Dimension::Px(height)),
+        }),
+        ..Default::default()
+    }
+}
+
+struct Desktop {
+    root: Entity,
+    windows: Vec<Entity>,
+    /// ウィンドウごとの子（縦並び）
+    items: Vec<Vec<Entity>>,
+}
+
+/// LayoutRoot(1920x1080) に絶対配置・固定サイズの「ウィンドウ」を並べ、
+/// 各ウィンドウに高さ20の子を縦に並べる
+fn spawn_desktop(ecs_world: &mut EcsWorld, window_count: usize, item_count: usize) -> Desktop {
+    let world = ecs_world.world_mut();
+    let root = world
+        .spawn((
+            Name::new("Root"),
+            LayoutRoot,
+            BoxStyle {
+                size: px_size(1920.0, 1080.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    let mut windows = Vec::new();
+    let mut items = Vec::new();
+    for i in 0..window_count {
+        let window = world
+            .spawn((
+                Name::new(format!("Window{i}")),
+                BoxStyle {
+                    size: px_size(300.0, 400.0),
+                    position: Some(BoxPosition::Absolute),
+                    inset: Some(BoxInset(Rect {
+                        left: LengthPercentageAuto::Px(10.0 * i as f32),
+                        top: LengthPercentageAuto::Px(5.0 * i as f32),
+                        right: LengthPercentageAuto::Auto,
+                        bottom: LengthPercentageAuto::Auto,
+                    })),
+                    flex_direction: Some(FlexDirection::Column),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(root),
+            ))
+            .id();
+        let window_items = (0..item_count)
+            .map(|j| {
+                world
+                    .spawn((
+                        Name::new(format!("Item{i}-{j}")),
+                        item_style(20.0),
+                        Arrangement::default(),
+                        ChildOf(window),
+                    ))
+                    .id()
+            })
+            .collect();
+        windows.push(window);
+        items.push(window_items);
+    }
+    Desktop {
+        root,
+        windows,
+        items,
+    }
+}
+
+fn stats(ecs_world: &EcsWorld) -> LayoutStats {
+    ecs_world.world().resource::<LayoutStats>().clone()
+}
+
+fn layout_changed_since(ecs_world: &EcsWorld, entity: Entity, since: u32) -> bool {
+    let entity_ref = ecs_world.world().entity(entity);
+    let layout = entity_ref.get_ref::<TaffyComputedLayout>().unwrap();
+    layout.last_changed().get() >= since
+}
+
+fn set_item_height(ecs_world: &mut EcsWorld, entity: Entity, height: f32) {
+    *ecs_world.world_mut().get_mut::<BoxStyle>(entity).unwrap() = item_style(height);
+}
+
+#[test]
+fn test_change_inside_window_recomputes_only_that_window() {
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, 3, 4);
+    ecs_world.try_tick_world();
+
+    let since = layout_change_tick(ecs_world.world());
+    set_item_height(&mut ecs_world, desktop.items[1][0], 50.0);
+    ecs_world.try_tick_world();
+
+    // Window1 と子4つだけを再計算
+    let stats = stats(&ecs_world);
+    assert_eq!(stats.computed_scopes, 1);
+    assert_eq!(stats.visited_nodes, 5);
+    assert_eq!(stats.changed_nodes, 4);
+
+    let world = ecs_world.world();
+    let resized = world.get::<Arrangement>(desktop.items[1][0]).unwrap();
+    assert_eq!(resized.size.height, 50.0);
+    let next = world.get::<Arrangement>(desktop.items[1][1]).unwrap();
+    assert_eq!(next.offset.y, 50.0);
+
+    // 他のウィンドウのサブツリーは触れない
+    for &entity in [desktop.windows[0], desktop.windows[2]]
+        .iter()
+        .chain(&desktop.items[0])
+        .chain(&desktop.items[2])
+    {
+        assert!(!layout_changed_since(&ecs_world, entity, since));
+    }
+    // ウィンドウ自身の位置・サイズは変わらない
+    assert!(!layout_changed_since(&ecs_world, desktop.windows[1], since));
+    assert!(!layout_changed_since(&ecs_world, desktop.root, since));
+}
+
+#[test]
+fn test_no_change_skips_layout() {
+    let mut ecs_world = EcsWorld::new();
+    spawn_desktop(&mut ecs_world, 2, 2);
+    ecs_world.try_tick_world();
+    let pass_count = stats(&ecs_world).pass_count;
+
+    ecs_world.try_tick_world();
+    assert_eq!(stats(&ecs_world).pass_count, pass_count);
+}
+
+#[test]
+fn test_changes_in_two_windows_compute_two_scopes() {
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, 3, 4);
+    ecs_world.try_tick_world();
+
+    set_item_height(&mut ecs_world, desktop.items[0][3], 30.0);
+    set_item_height(&mut ecs_world, desktop.items[2][1], 30.0);
+    ecs_world.try_tick_world();
+
+    let stats = stats(&ecs_world);
+    assert_eq!(stats.computed_scopes, 2);
+    assert_eq!(stats.visited_nodes, 10);
+}
+
+#[test]
+fn test_window_resize_recomputes_from_root() {
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, 3, 4);
+    ecs_world.try_tick_world();
+
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(desktop.windows[1])
+        .unwrap()
+        .size = px_size(500.0, 400.0);
+    ecs_world.try_tick_world();
+
+    // 境界自身のサイズ変更は親（LayoutRoot）のスコープで再計算
+    let stats = stats(&ecs_world);
+    assert_eq!(stats.computed_scopes, 1);
+    assert!(stats.visited_nodes >= 1 + 3 * 5, "{stats:?}");
+
+    let world = ecs_world.world();
+    assert_eq!(
+        world
+            .get::<Arrangement>(desktop.windows[1])
+            .unwrap()
+            .size
+            .width,
+        500.0
+    );
+    assert_eq!(
+        world
+            .get::<Arrangement>(desktop.items[1][0])
+            .unwrap()
+            .size
+            .width,
+        500.0
+    );
+    assert_eq!(
+        world
+            .get::<Arrangement>(desktop.items[0][0])
+            .unwrap()
+            .size
+            .width,
+        300.0
+    );
+}
+
+#[test]
+fn test_reparent_across_windows_marks_both() {
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, 3, 4);
+    ecs_world.try_tick_world();
+
+    let moved = desktop.items[0][0];
+    ecs_world
+        .world_mut()
+        .entity_mut(moved)
+        .insert(ChildOf(desktop.windows[2]));
+    ecs_world.try_tick_world();
+
+    // 旧ウィンドウ（残り3）と新ウィンドウ（5）
+    let stats = stats(&ecs_world);
+    assert_eq!(stats.computed_scopes, 2);
+    assert_eq!(stats.visited_nodes, (1 + 3) + (1 + 5));
+
+    let world = ecs_world.world();
+    assert_eq!(
+        world
+            .get::<Arrangement>(desktop.items[0][1])
+            .unwrap()
+            .offset
+            .y,
+        0.0
+    );
+    assert_eq!(world.get::<Arrangement>(moved).unwrap().offset.y, 80.0);
+}
+
+#[test]
+fn test_despawned_widget_relayouts_its_window() {
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, 2, 3);
+    ecs_world.try_tick_world();
+
+    ecs_world.world_mut().despawn(desktop.items[1][0]);
+    ecs_world.try_tick_world();
+    ecs_world.try_tick_world();
+
+    let world = ecs_world.world();
+    assert_eq!(
+        world
+            .get::<Arrangement>(desktop.items[1][1])
+            .unwrap()
+            .offset
+            .y,
+        0.0
+    );
+    assert_eq!(stats(&ecs_world).computed_scopes, 1);
+}
+
+#[test]
+fn test_incremental_result_matches_full_layout() {
+    let mut incremental = EcsWorld::new();
+    let desktop = spawn_desktop(&mut incremental, 3, 4);
+    incremental.try_tick_world();
+    set_item_height(&mut incremental, desktop.items[1][2], 45.0);
+    incremental.try_tick_world();
+    set_item_height(&mut incremental, desktop.items[2][0], 10.0);
+    incremental.try_tick_world();
+
+    // 同じ最終状態を一度に計算
+    let mut full = EcsWorld::new();
+    let fresh = spawn_desktop(&mut full, 3, 4);
+    set_item_height(&mut full, fresh.items[1][2], 45.0);
+    set_item_height(&mut full, fresh.items[2][0], 10.0);
+    full.try_tick_world();
+
+    let options = LayoutDumpOptions::default();
+    assert_layout_snapshot(
+        &dump_layout_tree(incremental.world(), desktop.root, options),
+        &dump_layout_tree(full.world(), fresh.root, options),
+    );
+}
+
+#[test]
+fn test_many_windows_visit_only_changed_window() {
+    const WINDOWS: usize = 50;
+    const ITEMS: usize = 40;
+    const FRAMES: usize = 10;
+
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, WINDOWS, ITEMS);
+    ecs_world.try_tick_world();
+
+    // 1つのウィンドウ内だけを毎フレーム変更: ウィンドウとアイテムのみ走査
+    for frame in 0..FRAMES {
+        let height = 21.0 + frame as f32;
+        set_item_height(&mut ecs_world, desktop.items[WINDOWS / 2][0], height);
+        ecs_world.try_tick_world();
+        let stats = stats(&ecs_world);
+        assert_eq!(stats.computed_scopes, 1, "frame {}", frame);
+        assert!(
+            stats.visited_nodes <= ITEMS + 1,
+            "frame {}: {:?}",
+            frame,
+            stats
+        );
+    }
+
+    // ルートを毎フレーム変更: 全ウィンドウのアイテムまで走査
+    for frame in 0..FRAMES {
+        let width = 1921.0 + frame as f32;
+        ecs_world
+            .world_mut()
+            .get_mut::<BoxStyle>(desktop.root)
+            .unwrap()
+            .size = px_size(width, 1080.0);
+        ecs_world.try_tick_world();
+        let stats = stats(&ecs_world);
+        assert!(
+            stats.visited_nodes > WINDOWS * ITEMS,
+            "frame {}: {:?}",
+            frame,
+            stats
+        );
+    }
+}
+
+#[test]
+#[ignore = "ベンチマーク（--ignored で実行、計測値は tracing の info で報告）"]
+fn bench_incremental_layout_many_windows() {
+    const WINDOWS: usize = 50;
+    const ITEMS: usize = 40;
+    const FRAMES: usize = 200;
+    let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+    let mut ecs_world = EcsWorld::new();
+    let desktop = spawn_desktop(&mut ecs_world, WINDOWS, ITEMS);
+    ecs_world.try_tick_world();
+
+    // 1つのウィンドウ内だけを毎フレーム変更
+    let started = Instant::now();
+    let mut visited = 0;
+    for frame in 0..FRAMES {
+        let height = 21.0 + (frame % 10) as f32;
+        set_item_height(&mut ecs_world, desktop.items[WINDOWS / 2][0], height);
+        ecs_world.try_tick_world();
+        visited += stats(&ecs_world).visited_nodes;
+    }
+    let incremental = started.elapsed();
+
+    // ルートを毎フレーム変更（全体再計算）
+    let started = Instant::now();
+    let mut full_visited = 0;
+    for frame in 0..FRAMES {
+        let width = 1921.0 + (frame % 10) as f32;
+        ecs_world
+            .world_mut()
+            .get_mut::<BoxStyle>(desktop.root)
+            .unwrap()
+            .size = px_size(width, 1080.0);
+        ecs_world.try_tick_world();
+        full_visited += stats(&ecs_world).visited_nodes;
+    }
+    let full = started.elapsed();
+
+    tracing::info!(
+        windows = WINDOWS,
+        items = ITEMS,
+        frames = FRAMES,
+        incremental_per_frame = ?incremental / FRAMES as u32,
+        incremental_nodes_per_frame = visited / FRAMES,
+        full_per_frame = ?full / FRAMES as u32,
+        full_nodes_per_frame = full_visited / FRAMES,
+        "incremental layout benchmark"
+    );
+    // 経過時間ではなく走査ノード数で比較する
+    assert!(
+        visited * 10 < full_visited,
+        "incremental={visited} full={full_visited} nodes"
+    );
+}
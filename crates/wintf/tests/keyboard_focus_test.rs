@@ -0,0 +1,332 @@
+//! キーボード入力とフォーカス管理のテスト
+//!
+//! 1. フォーカス中のエンティティへのキーイベント配信（Tunnel/Bubble・伝播停止）
+//! 2. `set_focus`/`clear_focus`と`OnGotFocus`/`OnLostFocus`・`FocusChangedEvent`
+//! 3. クリックによるフォーカス移動・`Focusable`喪失時の解除
+//! 4. `KeyboardState`の更新
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::{VK_A, VK_ESCAPE, VK_SHIFT};
+use wintf::ecs::keyboard::{
+    FocusChangedEvent, Focusable, Focused, KeyEvent, KeyboardInput, KeyboardState, OnGotFocus,
+    OnKeyDown, OnKeyUp, OnLostFocus, clear_focus, dispatch_keyboard_events, focus_on_click_system,
+    focused_entity, set_focus, validate_focus_system,
+};
+use wintf::ecs::pointer::{Phase, PointerState};
+
+/// ハンドラ呼び出し記録
+#[derive(Resource, Default)]
+struct Log(Vec<(&'static str, Entity, bool)>);
+
+fn log(world: &mut World, name: &'static str, entity: Entity, tunnel: bool) {
+    world.resource_mut::<Log>().0.push((name, entity, tunnel));
+}
+
+fn on_key_down(world: &mut World, _: Entity, entity: Entity, ev: &Phase<KeyEvent>) -> bool {
+    log(world, "down", entity, ev.is_tunnel());
+    false
+}
+
+fn on_key_up(world: &mut World, _: Entity, entity: Entity, ev: &Phase<KeyEvent>) -> bool {
+    log(world, "up", entity, ev.is_tunnel());
+    false
+}
+
+/// ESC を Tunnel で横取りする
+fn swallow_escape(world: &mut World, _: Entity, entity: Entity, ev: &Phase<KeyEvent>) -> bool {
+    log(world, "down", entity, ev.is_tunnel());
+    ev.is_tunnel() && ev.value().is_key(VK_ESCAPE)
+}
+
+fn on_got_focus(
+    world: &mut World,
+    _: Entity,
+    entity: Entity,
+    ev: &Phase<FocusChangedEvent>,
+) -> bool {
+    if ev.is_bubble() {
+        log(world, "got", entity, false);
+    }
+    false
+}
+
+fn on_lost_focus(
+    world: &mut World,
+    _: Entity,
+    entity: Entity,
+    ev: &Phase<FocusChangedEvent>,
+) -> bool {
+    if ev.is_bubble() {
+        log(world, "lost", entity, false);
+    }
+    false
+}
+
+/// root ─┬─ form ─┬─ name（Focusable）
+///       │        └─ email（Focusable）
+///       └─ label
+struct Scene {
+    root: Entity,
+    form: Entity,
+    name: Entity,
+    email: Entity,
+    label: Entity,
+}
+
+fn spawn_scene(world: &mut World) -> Scene {
+    world.init_resource::<Log>();
+    world.init_resource::<KeyboardInput>();
+    world.init_resource::<KeyboardState>();
+    world.init_resource::<Messages<FocusChangedEvent>>();
+
+    let root = world.spawn(OnKeyDown(on_key_down)).id();
+    let form = world
+        .spawn((
+            OnKeyDown(on_key_down),
+            OnGotFocus(on_got_focus),
+            ChildOf(root),
+        ))
+        .id();
+    let name = world
+        .spawn((
+            Focusable,
+            OnKeyDown(on_key_down),
+            OnKeyUp(on_key_up),
+            OnGotFocus(on_got_focus),
+            OnLostFocus(on_lost_focus),
+            ChildOf(form),
+        ))
+        .id();
+    let email = world
+        .spawn((
+            Focusable,
+            OnGotFocus(on_got_focus),
+            OnLostFocus(on_lost_focus),
+            ChildOf(form),
+        ))
+        .id();
+    let label = world.spawn(ChildOf(root)).id();
+    Scene {
+        root,
+        form,
+        name,
+        email,
+        label,
+    }
+}
+
+fn send(world: &mut World, window: Entity, event: KeyEvent) {
+    world.resource_mut::<KeyboardInput>().push(window, event);
+    dispatch_keyboard_events(world);
+}
+
+fn take_log(world: &mut World) -> Vec<(&'static str, Entity, bool)> {
+    std::mem::take(&mut world.resource_mut::<Log>().0)
+}
+
+#[test]
+fn test_key_event_routes_to_focused_entity() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    assert!(set_focus(&mut world, s.name));
+    take_log(&mut world);
+
+    send(&mut world, s.root, KeyEvent::pressed(VK_A));
+    assert_eq!(
+        take_log(&mut world),
+        vec![
+            ("down", s.root, true),
+            ("down", s.form, true),
+            ("down", s.name, true),
+            ("down", s.name, false),
+            ("down", s.form, false),
+            ("down", s.root, false),
+        ]
+    );
+
+    send(&mut world, s.root, KeyEvent::released(VK_A));
+    assert_eq!(
+        take_log(&mut world),
+        vec![("up", s.name, true), ("up", s.name, false)]
+    );
+}
+
+#[test]
+fn test_key_event_without_focus_goes_to_window() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+
+    send(&mut world, s.root, KeyEvent::pressed(VK_A));
+    assert_eq!(
+        take_log(&mut world),
+        vec![("down", s.root, true), ("down", s.root, false)]
+    );
+    assert!(world.resource::<KeyboardInput>().is_empty());
+}
+
+#[test]
+fn test_tunnel_handler_stops_propagation() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    world.entity_mut(s.form).insert(OnKeyDown(swallow_escape));
+    set_focus(&mut world, s.name);
+    take_log(&mut world);
+
+    send(&mut world, s.root, KeyEvent::pressed(VK_ESCAPE));
+    assert_eq!(
+        take_log(&mut world),
+        vec![("down", s.root, true), ("down", s.form, true)]
+    );
+
+    // ESC以外は通過する
+    send(&mut world, s.root, KeyEvent::pressed(VK_A));
+    assert_eq!(take_log(&mut world).len(), 6);
+}
+
+#[test]
+fn test_set_focus_moves_focus_and_notifies() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+
+    // Focusableでなければ受け取れない
+    assert!(!set_focus(&mut world, s.label));
+    assert_eq!(focused_entity(&world, s.root), None);
+
+    assert!(set_focus(&mut world, s.name));
+    assert_eq!(
+        take_log(&mut world),
+        vec![("got", s.name, false), ("got", s.form, false)]
+    );
+
+    assert!(set_focus(&mut world, s.email));
+    assert_eq!(
+        take_log(&mut world),
+        vec![
+            ("lost", s.name, false),
+            ("got", s.email, false),
+            ("got", s.form, false),
+        ]
+    );
+    assert!(world.get::<Focused>(s.name).is_none());
+    assert!(world.get::<Focused>(s.email).is_some());
+    assert_eq!(focused_entity(&world, s.label), Some(s.email));
+
+    // 既にフォーカス中なら通知しない
+    assert!(set_focus(&mut world, s.email));
+    assert!(take_log(&mut world).is_empty());
+
+    assert!(clear_focus(&mut world, s.email));
+    assert!(!clear_focus(&mut world, s.email));
+    assert_eq!(take_log(&mut world), vec![("lost", s.email, false)]);
+    assert_eq!(focused_entity(&world, s.root), None);
+
+    let events: Vec<FocusChangedEvent> = world
+        .resource_mut::<Messages<FocusChangedEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            FocusChangedEvent {
+                root: s.root,
+                previous: None,
+                current: Some(s.name),
+            },
+            FocusChangedEvent {
+                root: s.root,
+                previous: Some(s.name),
+                current: Some(s.email),
+            },
+            FocusChangedEvent {
+                root: s.root,
+                previous: Some(s.email),
+                current: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_focus_is_tracked_per_root() {
+    let mut world = World::new();
+    let a = spawn_scene(&mut world);
+    let b = spawn_scene(&mut world);
+
+    set_focus(&mut world, a.name);
+    set_focus(&mut world, b.email);
+    assert_eq!(focused_entity(&world, a.root), Some(a.name));
+    assert_eq!(focused_entity(&world, b.root), Some(b.email));
+    take_log(&mut world);
+
+    // 各ウィンドウのキー入力はそれぞれのフォーカスへ
+    send(&mut world, b.root, KeyEvent::pressed(VK_A));
+    let log = take_log(&mut world);
+    assert!(log.iter().all(|&(_, e, _)| e == b.root || e == b.form));
+    assert!(!log.is_empty());
+}
+
+#[test]
+fn test_click_focuses_nearest_focusable() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    let icon = world.spawn(ChildOf(s.email)).id();
+
+    // 押下中のポインターがあればフォーカス移動
+    world.entity_mut(icon).insert(PointerState {
+        left_down: true,
+        ..Default::default()
+    });
+    focus_on_click_system(&mut world);
+    assert_eq!(focused_entity(&world, s.root), Some(s.email));
+
+    // ホバーのみでは移動しない
+    world.entity_mut(icon).remove::<PointerState>();
+    world.entity_mut(s.name).insert(PointerState::default());
+    focus_on_click_system(&mut world);
+    assert_eq!(focused_entity(&world, s.root), Some(s.email));
+
+    // Focusableの祖先がなければフォーカスは変わらない
+    world.entity_mut(s.label).insert(PointerState {
+        right_down: true,
+        ..Default::default()
+    });
+    focus_on_click_system(&mut world);
+    assert_eq!(focused_entity(&world, s.root), Some(s.email));
+}
+
+#[test]
+fn test_focus_cleared_when_focusable_removed() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+    set_focus(&mut world, s.name);
+    take_log(&mut world);
+
+    world.entity_mut(s.name).remove::<Focusable>();
+    validate_focus_system(&mut world);
+    assert!(world.get::<Focused>(s.name).is_none());
+    assert_eq!(take_log(&mut world), vec![("lost", s.name, false)]);
+}
+
+#[test]
+fn test_keyboard_state_follows_dispatch() {
+    let mut world = World::new();
+    let s = spawn_scene(&mut world);
+
+    send(&mut world, s.root, KeyEvent::pressed(VK_SHIFT));
+    send(&mut world, s.root, KeyEvent::pressed(VK_A));
+    {
+        let state = world.resource::<KeyboardState>();
+        assert!(state.is_pressed(VK_SHIFT));
+        assert!(state.just_pressed(VK_A));
+    }
+
+    // ウィンドウのフォーカス喪失で全キー解放
+    world
+        .resource_mut::<KeyboardInput>()
+        .push_focus_lost(s.root);
+    dispatch_keyboard_events(&mut world);
+    let state = world.resource::<KeyboardState>();
+    assert!(!state.is_pressed(VK_SHIFT));
+    assert!(!state.is_pressed(VK_A));
+    assert!(state.just_released(VK_A));
+}
@@ -0,0 +1,132 @@
+//! レイアウトツリーのテキストダンプとスナップショット比較のテスト
+use bevy_ecs::name::Name;
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::ChildOf;
+
+fn height_item(name: &str, height: f32, parent: Entity) -> impl Bundle {
+    (
+        Name::new(name.to_string()),
+        BoxStyle {
+            size: Some(BoxSize {
+                width: None,
+                height: Some(Dimension::Px(height)),
+            }),
+            ..Default::default()
+        },
+        Arrangement::default(),
+        ChildOf(parent),
+    )
+}
+
+/// Root(800x600, 縦並び) → Header(高さ50) / Body(高さ100)
+fn spawn_tree(ecs_world: &mut EcsWorld) -> (Entity, Entity, Entity) {
+    let world = ecs_world.world_mut();
+    let root = world
+        .spawn((
+            Name::new("Root"),
+            LayoutRoot,
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Px(800.0)),
+                    height: Some(Dimension::Px(600.0)),
+                }),
+                flex_direction: Some(FlexDirection::Column),
+                ..Default::default()
+            },
+        ))
+        .id();
+    let header = world.spawn(height_item("Header", 50.0, root)).id();
+    let body = world.spawn(height_item("Body", 100.0, root)).id();
+    (root, header, body)
+}
+
+#[test]
+fn test_compact_dump_snapshot() {
+    let mut ecs_world = EcsWorld::new();
+    let (root, _, _) = spawn_tree(&mut ecs_world);
+    ecs_world.try_tick_world();
+
+    let dump = dump_layout_tree(ecs_world.world(), root, LayoutDumpOptions::compact());
+    assert_layout_snapshot(
+        &dump,
+        r#"
+        Root [LayoutRoot] local=(0, 0) 800x600
+          Header local=(0, 0) 800x50
+          Body local=(0, 50) 800x100
+        "#,
+    );
+
+    // 全LayoutRootのダンプにも含まれる
+    let all = dump_layout_roots(ecs_world.world(), LayoutDumpOptions::compact());
+    assert!(all.contains(&dump));
+}
+
+#[test]
+fn test_full_dump_includes_global_bounds_and_style() {
+    let mut ecs_world = EcsWorld::new();
+    let (root, _, _) = spawn_tree(&mut ecs_world);
+    ecs_world.try_tick_world();
+
+    let dump = dump_layout_tree(ecs_world.world(), root, LayoutDumpOptions::default());
+    assert_layout_snapshot(
+        &dump,
+        r#"
+        Root [LayoutRoot] local=(0, 0) 800x600 global=[0, 0, 800, 600] style={flex_direction=Column size=(800px, 600px)}
+          Header local=(0, 0) 800x50 global=[0, 0, 800, 50] style={size=(-, 50px)}
+          Body local=(0, 50) 800x100 global=[0, 50, 800, 150] style={size=(-, 100px)}
+        "#,
+    );
+}
+
+#[test]
+fn test_dirty_flags_since_tick() {
+    let mut ecs_world = EcsWorld::new();
+    let (root, header, _) = spawn_tree(&mut ecs_world);
+    ecs_world.try_tick_world();
+
+    let since = layout_change_tick(ecs_world.world());
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(header)
+        .unwrap()
+        .size = Some(BoxSize {
+        width: None,
+        height: Some(Dimension::Px(80.0)),
+    });
+    ecs_world.try_tick_world();
+
+    let dump = dump_layout_tree(
+        ecs_world.world(),
+        root,
+        LayoutDumpOptions::compact().dirty_since(since),
+    );
+    let lines: Vec<&str> = dump.lines().collect();
+    assert!(!lines[0].contains("BoxStyle"), "{dump}");
+    assert!(
+        lines[1].starts_with("  Header local=(0, 0) 800x80 dirty={BoxStyle, TaffyStyle"),
+        "{dump}"
+    );
+    assert!(lines[1].contains("Arrangement"), "{dump}");
+    // Body は位置だけが変わる
+    assert!(
+        lines[2].starts_with("  Body local=(0, 80) 800x100 dirty={"),
+        "{dump}"
+    );
+    assert!(!lines[2].contains("BoxStyle"), "{dump}");
+}
+
+#[test]
+fn test_snapshot_ignores_indentation_and_blank_lines() {
+    assert_layout_snapshot("A\n  B\n", "\n    A\n      B   \n\n");
+}
+
+#[test]
+#[should_panic(expected = "-   A local=(0, 0) 10x10\n+   A local=(0, 0) 10x20")]
+fn test_snapshot_mismatch_shows_line_diff() {
+    assert_layout_snapshot(
+        "Root\n  A local=(0, 0) 10x20\n",
+        "Root\n  A local=(0, 0) 10x10\n",
+    );
+}
@@ -0,0 +1,205 @@
+//! 暗黙的レイアウトトランジション（LayoutTransition）のテスト
+//!
+//! 1. イージング・補間状態の計算（純粋な計算）
+//! 2. layout_transition_system による Arrangement の補間
+use bevy_ecs::prelude::*;
+use std::time::Duration;
+use wintf::ecs::FrameTime;
+use wintf::ecs::layout::*;
+
+fn arrangement(x: f32, y: f32, width: f32, height: f32) -> Arrangement {
+    Arrangement {
+        offset: Offset { x, y },
+        scale: LayoutScale::default(),
+        size: Size { width, height },
+    }
+}
+
+fn linear(millis: u64) -> LayoutTransition {
+    LayoutTransition::new(Duration::from_millis(millis), Easing::Linear)
+}
+
+// ===== Easing =====
+
+#[test]
+fn test_easing_endpoints_and_monotonic() {
+    for easing in [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{easing:?}");
+        // 範囲外はクランプ
+        assert_eq!(easing.apply(-1.0), 0.0, "{easing:?}");
+        assert!((easing.apply(2.0) - 1.0).abs() < 1e-6, "{easing:?}");
+
+        let mut previous = 0.0;
+        for i in 1..=10 {
+            let value = easing.apply(i as f32 / 10.0);
+            assert!(value >= previous, "{easing:?}");
+            previous = value;
+        }
+    }
+    assert!(Easing::EaseIn.apply(0.5) < 0.5);
+    assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-6);
+}
+
+// ===== LayoutTransitionState =====
+
+#[test]
+fn test_first_arrangement_is_adopted_without_animation() {
+    let mut state = LayoutTransitionState::default();
+    let target = arrangement(0.0, 0.0, 100.0, 50.0);
+    assert_eq!(state.retarget(target, 0.0, false), target);
+    assert!(!state.is_animating());
+    assert_eq!(state.displayed, Some(target));
+}
+
+#[test]
+fn test_transition_interpolates_offset_and_size() {
+    let transition = linear(200);
+    let mut state = LayoutTransitionState::default();
+    let from = arrangement(0.0, 0.0, 100.0, 50.0);
+    let to = arrangement(20.0, 10.0, 300.0, 150.0);
+    state.retarget(from, 0.0, false);
+
+    // 開始時は直前の表示値のまま
+    assert_eq!(state.retarget(to, 1.0, false), from);
+    assert!(state.is_animating());
+
+    let half = state.sample(&transition, 1.1).unwrap();
+    assert_eq!(half, arrangement(10.0, 5.0, 200.0, 100.0));
+
+    // 終了時刻で目標値に到達し、補間を解除
+    assert_eq!(state.sample(&transition, 1.2).unwrap(), to);
+    assert!(!state.is_animating());
+    assert_eq!(state.sample(&transition, 1.3), None);
+}
+
+#[test]
+fn test_adopt_offset_animates_size_only() {
+    let transition = linear(100);
+    let mut state = LayoutTransitionState::default();
+    state.retarget(arrangement(0.0, 0.0, 100.0, 100.0), 0.0, true);
+
+    let start = state.retarget(arrangement(50.0, 60.0, 200.0, 100.0), 0.0, true);
+    assert_eq!(start.offset, Offset { x: 50.0, y: 60.0 });
+    assert_eq!(start.size.width, 100.0);
+
+    let half = state.sample(&transition, 0.05).unwrap();
+    assert_eq!(half.offset, Offset { x: 50.0, y: 60.0 });
+    assert_eq!(half.size.width, 150.0);
+}
+
+#[test]
+fn test_offset_only_update_keeps_running_animation() {
+    let transition = linear(100);
+    let mut state = LayoutTransitionState::default();
+    state.retarget(arrangement(0.0, 0.0, 100.0, 100.0), 0.0, true);
+    state.retarget(arrangement(0.0, 0.0, 200.0, 100.0), 0.0, true);
+    state.sample(&transition, 0.05);
+
+    // ウィンドウ移動（オフセットのみ外部更新）は補間をやり直さない
+    let moved = state.retarget(arrangement(30.0, 40.0, 200.0, 100.0), 0.05, true);
+    assert_eq!(moved.offset, Offset { x: 30.0, y: 40.0 });
+    assert_eq!(moved.size.width, 150.0);
+    assert_eq!(state.animation.unwrap().start_secs, 0.0);
+
+    assert_eq!(
+        state.sample(&transition, 0.1).unwrap(),
+        arrangement(30.0, 40.0, 200.0, 100.0)
+    );
+}
+
+#[test]
+fn test_retarget_mid_animation_starts_from_displayed_value() {
+    let transition = linear(100);
+    let mut state = LayoutTransitionState::default();
+    state.retarget(arrangement(0.0, 0.0, 100.0, 100.0), 0.0, false);
+    state.retarget(arrangement(0.0, 0.0, 200.0, 100.0), 0.0, false);
+    state.sample(&transition, 0.05);
+
+    let start = state.retarget(arrangement(0.0, 0.0, 50.0, 100.0), 0.05, false);
+    assert_eq!(start.size.width, 150.0);
+    let animation = state.animation.unwrap();
+    assert_eq!(animation.from.size.width, 150.0);
+    assert_eq!(animation.to.size.width, 50.0);
+    assert_eq!(animation.start_secs, 0.05);
+}
+
+#[test]
+fn test_zero_duration_jumps_to_target() {
+    let transition = linear(0);
+    let mut state = LayoutTransitionState::default();
+    state.retarget(arrangement(0.0, 0.0, 100.0, 100.0), 0.0, false);
+    state.retarget(arrangement(0.0, 0.0, 200.0, 100.0), 0.0, false);
+    assert_eq!(
+        state.sample(&transition, 0.0).unwrap(),
+        arrangement(0.0, 0.0, 200.0, 100.0)
+    );
+    assert!(!state.is_animating());
+}
+
+// ===== layout_transition_system =====
+
+fn spawn_transitioned(world: &mut World, transition: LayoutTransition) -> Entity {
+    world
+        .spawn((arrangement(0.0, 0.0, 100.0, 100.0), transition))
+        .id()
+}
+
+#[test]
+fn test_state_is_inserted_by_hook() {
+    let mut world = World::new();
+    let entity = spawn_transitioned(&mut world, LayoutTransition::default());
+    assert!(world.get::<LayoutTransitionState>(entity).is_some());
+}
+
+#[test]
+fn test_system_without_frame_time_adopts_immediately() {
+    let mut world = World::new();
+    let entity = spawn_transitioned(&mut world, linear(1000));
+    let mut schedule = Schedule::default();
+    schedule.add_systems(layout_transition_system);
+    schedule.run(&mut world);
+
+    *world.get_mut::<Arrangement>(entity).unwrap() = arrangement(0.0, 0.0, 300.0, 100.0);
+    schedule.run(&mut world);
+
+    assert_eq!(world.get::<Arrangement>(entity).unwrap().size.width, 300.0);
+    assert!(
+        !world
+            .get::<LayoutTransitionState>(entity)
+            .unwrap()
+            .is_animating()
+    );
+}
+
+#[test]
+fn test_system_holds_previous_arrangement_at_start() {
+    let mut world = World::new();
+    world.insert_resource(FrameTime::new());
+    let entity = spawn_transitioned(&mut world, linear(60_000));
+    let mut schedule = Schedule::default();
+    schedule.add_systems(layout_transition_system);
+    schedule.run(&mut world);
+
+    // レイアウトが新しい値を書き込む
+    *world.get_mut::<Arrangement>(entity).unwrap() = arrangement(0.0, 0.0, 300.0, 100.0);
+    schedule.run(&mut world);
+
+    // 60秒かけて補間するので、直後はほぼ元の値
+    let width = world.get::<Arrangement>(entity).unwrap().size.width;
+    assert!((100.0..101.0).contains(&width), "{width}");
+    let state = world.get::<LayoutTransitionState>(entity).unwrap();
+    assert!(state.is_animating());
+    assert_eq!(state.animation.unwrap().to.size.width, 300.0);
+
+    // 自身の書き込みは新しい目標として扱わない
+    schedule.run(&mut world);
+    let state = world.get::<LayoutTransitionState>(entity).unwrap();
+    assert_eq!(state.animation.unwrap().to.size.width, 300.0);
+}
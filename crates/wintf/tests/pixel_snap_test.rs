@@ -0,0 +1,185 @@
+//! ピクセルスナップ（SnappedArrangement）のテスト
+//!
+//! 複数のDPIスケール・入れ子のLayoutScaleで、スナップ済み矩形の辺が整数ピクセルになり、
+//! 隣接する兄弟要素が辺を共有することを検証する。
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::{
+    mark_dirty_arrangement_trees, pixel_snap_system, propagate_global_arrangements,
+    sync_simple_arrangements,
+};
+use wintf::ecs::{
+    Arrangement, ChildOf, GlobalArrangement, LayoutScale, Offset, Size, SnappedArrangement,
+    snap_rect_to_pixels, snap_to_pixel,
+};
+
+const DPI_SCALES: [f32; 6] = [1.0, 1.25, 1.5, 1.75, 2.0, 2.25];
+
+fn arrangement(x: f32, y: f32, width: f32, height: f32, scale: f32) -> Arrangement {
+    Arrangement {
+        offset: Offset { x, y },
+        scale: LayoutScale { x: scale, y: scale },
+        size: Size { width, height },
+    }
+}
+
+fn schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(
+        (
+            sync_simple_arrangements,
+            mark_dirty_arrangement_trees,
+            propagate_global_arrangements,
+            pixel_snap_system,
+        )
+            .chain(),
+    );
+    schedule
+}
+
+struct Row {
+    container: Entity,
+    cells: Vec<Entity>,
+}
+
+/// ルート（DPIスケール）→ コンテナー（`container_scale`）→ 幅 100/7 のセル7つを横並び
+fn spawn_row(world: &mut World, dpi: f32, container_scale: f32) -> Row {
+    let root = world.spawn(arrangement(0.0, 0.0, 800.0, 600.0, dpi)).id();
+    let container = world
+        .spawn((
+            arrangement(10.4, 5.2, 100.0, 20.0, container_scale),
+            ChildOf(root),
+        ))
+        .id();
+    let width = 100.0 / 7.0;
+    let cells = (0..7)
+        .map(|i| {
+            world
+                .spawn((
+                    arrangement(width * i as f32, 0.0, width, 20.0, 1.0),
+                    ChildOf(container),
+                ))
+                .id()
+        })
+        .collect();
+    Row { container, cells }
+}
+
+fn snapped(world: &World, entity: Entity) -> SnappedArrangement {
+    *world.get::<SnappedArrangement>(entity).unwrap()
+}
+
+fn assert_integral(rect: &SnappedArrangement, context: &str) {
+    let b = rect.bounds;
+    for edge in [b.left, b.top, b.right, b.bottom] {
+        assert_eq!(edge, edge.round(), "{context}: {b:?}");
+    }
+}
+
+fn assert_row_shares_edges(world: &World, row: &Row, context: &str) {
+    let container = snapped(world, row.container);
+    assert_integral(&container, context);
+
+    let cells: Vec<_> = row.cells.iter().map(|&e| snapped(world, e)).collect();
+    for (i, cell) in cells.iter().enumerate() {
+        assert_integral(cell, context);
+        // 丸め誤差は0.5ピクセル以内
+        let exact = world.get::<GlobalArrangement>(row.cells[i]).unwrap().bounds;
+        assert!((cell.bounds.left - exact.left).abs() <= 0.5, "{context}");
+        assert!((cell.bounds.right - exact.right).abs() <= 0.5, "{context}");
+    }
+    for pair in cells.windows(2) {
+        assert_eq!(pair[0].bounds.right, pair[1].bounds.left, "{context}");
+    }
+    // 両端はコンテナーの辺と一致し、幅の合計もコンテナー幅と一致する
+    assert_eq!(cells[0].bounds.left, container.bounds.left, "{context}");
+    assert_eq!(cells[6].bounds.right, container.bounds.right, "{context}");
+    let total: f32 = cells.iter().map(SnappedArrangement::width).sum();
+    assert_eq!(total, container.width(), "{context}");
+}
+
+#[test]
+fn test_snap_to_pixel_rounds_half_up() {
+    assert_eq!(snap_to_pixel(12.4), 12.0);
+    assert_eq!(snap_to_pixel(12.5), 13.0);
+    assert_eq!(snap_to_pixel(-12.5), -12.0);
+    assert_eq!(snap_to_pixel(-12.6), -13.0);
+    // 浮動小数点誤差は吸収される
+    assert_eq!(snap_to_pixel(12.499_999), snap_to_pixel(12.500_001));
+}
+
+#[test]
+fn test_snap_rect_rounds_each_edge() {
+    let rect = snap_rect_to_pixels(&wintf::ecs::D2DRect {
+        left: 112.5,
+        top: 10.2,
+        right: 612.5,
+        bottom: 20.7,
+    });
+    assert_eq!(rect.left, 113.0);
+    assert_eq!(rect.top, 10.0);
+    assert_eq!(rect.right, 613.0);
+    assert_eq!(rect.bottom, 21.0);
+}
+
+#[test]
+fn test_siblings_share_edges_across_dpi_scales() {
+    for dpi in DPI_SCALES {
+        let mut world = World::new();
+        let row = spawn_row(&mut world, dpi, 1.0);
+        schedule().run(&mut world);
+        assert_row_shares_edges(&world, &row, &format!("dpi={dpi}"));
+    }
+}
+
+#[test]
+fn test_siblings_share_edges_with_nested_scale() {
+    for dpi in DPI_SCALES {
+        for nested in [0.75, 1.5, 1.333] {
+            let mut world = World::new();
+            let row = spawn_row(&mut world, dpi, nested);
+            schedule().run(&mut world);
+            assert_row_shares_edges(&world, &row, &format!("dpi={dpi} nested={nested}"));
+        }
+    }
+}
+
+#[test]
+fn test_partial_update_matches_full_propagation() {
+    for dpi in DPI_SCALES {
+        let mut world = World::new();
+        let row = spawn_row(&mut world, dpi, 1.0);
+        let mut pipeline = schedule();
+        pipeline.run(&mut world);
+
+        // コンテナーだけを動かして再伝播
+        world.get_mut::<Arrangement>(row.container).unwrap().offset = Offset { x: 33.3, y: 7.7 };
+        pipeline.run(&mut world);
+
+        let mut fresh = World::new();
+        let fresh_row = spawn_row(&mut fresh, dpi, 1.0);
+        fresh
+            .get_mut::<Arrangement>(fresh_row.container)
+            .unwrap()
+            .offset = Offset { x: 33.3, y: 7.7 };
+        schedule().run(&mut fresh);
+
+        for (&a, &b) in row.cells.iter().zip(&fresh_row.cells) {
+            assert_eq!(snapped(&world, a), snapped(&fresh, b), "dpi={dpi}");
+        }
+        assert_row_shares_edges(&world, &row, &format!("dpi={dpi}"));
+    }
+}
+
+#[test]
+fn test_snapped_offset_from_parent_is_integral() {
+    let mut world = World::new();
+    let row = spawn_row(&mut world, 1.25, 1.0);
+    schedule().run(&mut world);
+
+    let container = snapped(&world, row.container);
+    for &cell in &row.cells {
+        let offset = snapped(&world, cell).offset_from(&container);
+        assert_eq!(offset.x, offset.x.round());
+        assert_eq!(offset.y, 0.0);
+    }
+}
@@ -0,0 +1,317 @@
+//! スクロールコンテナー（ScrollViewer）のテスト
+//!
+//! 1. ScrollState のオフセット計算・慣性・実体化範囲（純粋な計算）
+//! 2. ScrollViewer の子がコンテンツサイズでレイアウトされ、オフセット分ずれること
+//! 3. 仮想化で範囲外の子の Visual が退避・復元されること
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::scroll::*;
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::{ChildOf, DragConfig, Visual, WheelDelta};
+
+fn offset(x: f32, y: f32) -> Offset {
+    Offset { x, y }
+}
+
+fn size(width: f32, height: f32) -> Size {
+    Size { width, height }
+}
+
+fn state(viewport: Size, extent: Size) -> ScrollState {
+    let mut state = ScrollState::default();
+    state.set_bounds(viewport, extent);
+    state
+}
+
+// ===== ScrollState =====
+
+#[test]
+fn test_scroll_offset_is_clamped() {
+    let mut state = state(size(200.0, 100.0), size(200.0, 250.0));
+    assert_eq!(state.max_offset(), offset(0.0, 150.0));
+
+    assert!(state.scroll_by(offset(30.0, 80.0)));
+    assert_eq!(state.offset, offset(0.0, 80.0));
+
+    assert!(state.scroll_to(offset(0.0, 500.0)));
+    assert_eq!(state.offset, offset(0.0, 150.0));
+    // 端ではそれ以上動かない
+    assert!(!state.scroll_by(offset(0.0, 10.0)));
+
+    // コンテンツが縮むとオフセットも再クランプ
+    state.set_bounds(size(200.0, 100.0), size(200.0, 120.0));
+    assert_eq!(state.offset, offset(0.0, 20.0));
+
+    // 表示領域より小さいコンテンツはスクロールしない
+    state.set_bounds(size(200.0, 100.0), size(200.0, 40.0));
+    assert_eq!(state.max_offset(), offset(0.0, 0.0));
+    assert_eq!(state.offset, offset(0.0, 0.0));
+}
+
+#[test]
+fn test_kinetic_scroll_decays_and_stops() {
+    let mut state = state(size(200.0, 100.0), size(200.0, 10000.0));
+    state.velocity = offset(0.0, 1000.0);
+
+    assert!(state.step_kinetic(0.1, 4.0));
+    assert!((state.offset.y - 100.0).abs() < 1e-3);
+    let expected = 1000.0 * (-0.4f32).exp();
+    assert!((state.velocity.y - expected).abs() < 1e-2);
+
+    // 十分に時間が経つと停止する
+    for _ in 0..100 {
+        state.step_kinetic(0.1, 4.0);
+    }
+    assert!(!state.is_kinetic());
+    let rest = state.offset;
+    assert!(!state.step_kinetic(0.1, 4.0));
+    assert_eq!(state.offset, rest);
+}
+
+#[test]
+fn test_kinetic_scroll_stops_at_edge() {
+    let mut state = state(size(200.0, 100.0), size(200.0, 150.0));
+    state.velocity = offset(0.0, 2000.0);
+
+    assert!(state.step_kinetic(0.1, 4.0));
+    assert_eq!(state.offset, offset(0.0, 50.0));
+    assert!(!state.is_kinetic());
+}
+
+#[test]
+fn test_realization_window() {
+    let mut state = state(size(200.0, 100.0), size(200.0, 1000.0));
+    state.scroll_to(offset(0.0, 300.0));
+    let item = |y: f32| (offset(0.0, y), size(200.0, 50.0));
+
+    // 表示領域 300..400
+    let (loc, sz) = item(320.0);
+    assert!(state.is_realized(loc, sz, 0.0));
+    let (loc, sz) = item(250.0);
+    assert!(!state.is_realized(loc, sz, 0.0));
+    let (loc, sz) = item(400.0);
+    assert!(!state.is_realized(loc, sz, 0.0));
+
+    // overscan 分だけ前後に広がる
+    let (loc, sz) = item(250.0);
+    assert!(state.is_realized(loc, sz, 10.0));
+    let (loc, sz) = item(450.0);
+    assert!(state.is_realized(loc, sz, 60.0));
+    assert!(!state.is_realized(loc, sz, 50.0));
+}
+
+// ===== ScrollViewer =====
+
+#[test]
+fn test_wheel_scroll_delta() {
+    let wheel = |vertical, horizontal| WheelDelta {
+        vertical,
+        horizontal,
+    };
+
+    let vertical = ScrollViewer::vertical();
+    // 上回転（正）はオフセットを減らす
+    assert_eq!(
+        vertical.wheel_scroll_delta(wheel(120, 0), false),
+        offset(0.0, -48.0)
+    );
+    assert_eq!(
+        vertical.wheel_scroll_delta(wheel(-240, 120), false),
+        offset(0.0, 96.0)
+    );
+
+    // 水平のみのコンテナーでは垂直ホイールを水平に振り替える
+    let horizontal = ScrollViewer::horizontal();
+    assert_eq!(
+        horizontal.wheel_scroll_delta(wheel(-120, 0), false),
+        offset(48.0, 0.0)
+    );
+
+    // Shift+ホイールは水平スクロール
+    let both = ScrollViewer::both();
+    assert_eq!(
+        both.wheel_scroll_delta(wheel(-120, 0), true),
+        offset(48.0, 0.0)
+    );
+    assert_eq!(
+        both.wheel_scroll_delta(wheel(-120, 0), false),
+        offset(0.0, 48.0)
+    );
+}
+
+#[test]
+fn test_scroll_viewer_style_adjustment() {
+    let mut viewer_style = taffy::Style::default();
+    apply_scroll_viewer_style(&mut viewer_style, Some(&ScrollViewer::vertical()), None);
+    assert_eq!(viewer_style.overflow.x, taffy::Overflow::Visible);
+    assert_eq!(viewer_style.overflow.y, taffy::Overflow::Scroll);
+
+    let mut child_style = taffy::Style::default();
+    apply_scroll_viewer_style(&mut child_style, None, Some(&ScrollViewer::vertical()));
+    assert_eq!(child_style.flex_shrink, 0.0);
+}
+
+// ===== レイアウト =====
+
+/// 200x100 の縦スクロールコンテナーに高さ50の子を5つ
+fn spawn_list(ecs_world: &mut EcsWorld) -> (Entity, Vec<Entity>) {
+    let world = ecs_world.world_mut();
+    let root = world
+        .spawn((
+            LayoutRoot,
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Px(800.0)),
+                    height: Some(Dimension::Px(600.0)),
+                }),
+                align_items: Some(AlignItems::FlexStart),
+                ..Default::default()
+            },
+        ))
+        .id();
+    let viewer = world
+        .spawn((
+            ScrollViewer::vertical(),
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Px(200.0)),
+                    height: Some(Dimension::Px(100.0)),
+                }),
+                flex_direction: Some(FlexDirection::Column),
+                ..Default::default()
+            },
+            Arrangement::default(),
+            ChildOf(root),
+        ))
+        .id();
+    let items = (0..5)
+        .map(|_| {
+            world
+                .spawn((
+                    BoxStyle {
+                        size: Some(BoxSize {
+                            width: None,
+                            height: Some(Dimension::Px(50.0)),
+                        }),
+                        ..Default::default()
+                    },
+                    Arrangement::default(),
+                    ChildOf(viewer),
+                ))
+                .id()
+        })
+        .collect();
+    (viewer, items)
+}
+
+fn offset_of(ecs_world: &EcsWorld, entity: Entity) -> Offset {
+    ecs_world.world().get::<Arrangement>(entity).unwrap().offset
+}
+
+#[test]
+fn test_scroll_viewer_lays_out_content_size() {
+    let mut ecs_world = EcsWorld::new();
+    let (viewer, items) = spawn_list(&mut ecs_world);
+
+    ecs_world.try_tick_world();
+
+    let world = ecs_world.world();
+    assert!(world.get::<ClipToBounds>(viewer).is_some());
+    assert!(!world.get::<DragConfig>(viewer).unwrap().move_window);
+
+    // 子は縮小されずコンテンツサイズ（250）でスクロール範囲になる
+    let state = world.get::<ScrollState>(viewer).unwrap();
+    assert_eq!(state.viewport, size(200.0, 100.0));
+    assert_eq!(state.extent, size(200.0, 250.0));
+    let last = world.get::<Arrangement>(items[4]).unwrap();
+    assert_eq!(last.size, size(200.0, 50.0));
+    assert_eq!(last.offset, offset(0.0, 200.0));
+}
+
+#[test]
+fn test_scroll_offset_moves_children_and_emits_event() {
+    let mut ecs_world = EcsWorld::new();
+    let (viewer, items) = spawn_list(&mut ecs_world);
+    ecs_world.try_tick_world();
+
+    ecs_world
+        .world_mut()
+        .get_mut::<ScrollState>(viewer)
+        .unwrap()
+        .scroll_to(offset(0.0, 120.0));
+    ecs_world.try_tick_world();
+
+    assert_eq!(offset_of(&ecs_world, items[0]), offset(0.0, -120.0));
+    assert_eq!(offset_of(&ecs_world, items[3]), offset(0.0, 30.0));
+
+    let messages = ecs_world.world().resource::<Messages<ScrollChangedEvent>>();
+    let events: Vec<_> = messages.get_cursor().read(messages).cloned().collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].target, viewer);
+    assert_eq!(events[0].offset, offset(0.0, 120.0));
+    assert_eq!(events[0].delta, offset(0.0, 120.0));
+
+    // 範囲外は最大オフセットにクランプ
+    ecs_world
+        .world_mut()
+        .get_mut::<ScrollState>(viewer)
+        .unwrap()
+        .scroll_to(offset(0.0, 1000.0));
+    ecs_world.try_tick_world();
+    assert_eq!(offset_of(&ecs_world, items[4]), offset(0.0, 50.0));
+}
+
+// ===== 仮想化 =====
+
+fn item_layout(y: f32) -> TaffyComputedLayout {
+    let mut layout = taffy::Layout::new();
+    layout.location = taffy::Point { x: 0.0, y };
+    layout.size = taffy::Size {
+        width: 200.0,
+        height: 50.0,
+    };
+    layout.into()
+}
+
+#[test]
+fn test_virtualization_stashes_and_restores_visuals() {
+    let mut world = World::new();
+    let viewer = world
+        .spawn((
+            ScrollViewer::vertical(),
+            state(size(200.0, 100.0), size(200.0, 1000.0)),
+            ScrollVirtualization { overscan: 0.0 },
+        ))
+        .id();
+    let near = world
+        .spawn((Visual::default(), item_layout(50.0), ChildOf(viewer)))
+        .id();
+    let far = world
+        .spawn((Visual::default(), item_layout(500.0), ChildOf(viewer)))
+        .id();
+    let far_grandchild = world.spawn((Visual::default(), ChildOf(far))).id();
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(virtualize_scroll_children_system);
+    schedule.run(&mut world);
+
+    assert!(world.get::<Visual>(near).is_some());
+    assert!(world.get::<Visual>(far).is_none());
+    assert!(world.get::<VirtualizedVisual>(far).is_some());
+    // サブツリーごと退避
+    assert!(world.get::<Visual>(far_grandchild).is_none());
+    assert!(world.get::<VirtualizedVisual>(far_grandchild).is_some());
+
+    // スクロールして範囲内に入ると復元、範囲外に出た子は退避
+    world
+        .get_mut::<ScrollState>(viewer)
+        .unwrap()
+        .scroll_to(offset(0.0, 480.0));
+    schedule.run(&mut world);
+
+    assert!(world.get::<Visual>(far).is_some());
+    assert!(world.get::<VirtualizedVisual>(far).is_none());
+    assert!(world.get::<Visual>(far_grandchild).is_some());
+    assert!(world.get::<Visual>(near).is_none());
+}
@@ -0,0 +1,322 @@
+//! Flex wrap / gap / align_content / aspect_ratio / overflow / display:none のテスト
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::widget::text::Label;
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::ChildOf;
+
+fn px_size(width: f32, height: f32) -> Option<BoxSize> {
+    Some(BoxSize {
+        width: Some(Dimension::Px(width)),
+        height: Some(Dimension::Px(height)),
+    })
+}
+
+fn arrangement(ecs_world: &EcsWorld, entity: Entity) -> (f32, f32, f32, f32) {
+    let arr = ecs_world
+        .world()
+        .get::<Arrangement>(entity)
+        .expect("Arrangement");
+    (arr.offset.x, arr.offset.y, arr.size.width, arr.size.height)
+}
+
+/// 800x600 のルート直下に折り返しFlexコンテナーと 250x100 のアイテム5個を配置
+fn spawn_wrapping_tags(ecs_world: &mut EcsWorld) -> Vec<Entity> {
+    let world = ecs_world.world_mut();
+    let root = world
+        .spawn((
+            LayoutRoot,
+            BoxStyle {
+                size: px_size(800.0, 600.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+    let container = world
+        .spawn((
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Percent(100.0)),
+                    height: Some(Dimension::Percent(100.0)),
+                }),
+                flex_wrap: Some(FlexWrap::Wrap),
+                align_content: Some(AlignContent::FlexStart),
+                column_gap: Some(LengthPercentage::Px(10.0)),
+                row_gap: Some(LengthPercentage::Px(20.0)),
+                ..Default::default()
+            },
+            Arrangement::default(),
+            ChildOf(root),
+        ))
+        .id();
+    (0..5)
+        .map(|_| {
+            world
+                .spawn((
+                    BoxStyle {
+                        size: px_size(250.0, 100.0),
+                        flex_shrink: Some(0.0),
+                        ..Default::default()
+                    },
+                    Arrangement::default(),
+                    ChildOf(container),
+                ))
+                .id()
+        })
+        .collect()
+}
+
+// ===== 変換テスト =====
+
+#[test]
+fn test_new_properties_to_taffy() {
+    let style = BoxStyle {
+        flex_wrap: Some(FlexWrap::Wrap),
+        align_content: Some(AlignContent::SpaceBetween),
+        row_gap: Some(LengthPercentage::Px(4.0)),
+        column_gap: Some(LengthPercentage::Px(8.0)),
+        aspect_ratio: Some(1.5),
+        overflow: Some(BoxOverflow {
+            x: Overflow::Hidden,
+            y: Overflow::Scroll,
+        }),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+
+    // flex_wrapはコンテナープロパティなのでdisplay: Flexが設定される
+    assert_eq!(taffy_style.display, taffy::Display::Flex);
+    assert_eq!(taffy_style.flex_wrap, taffy::FlexWrap::Wrap);
+    assert_eq!(
+        taffy_style.align_content,
+        Some(taffy::AlignContent::SpaceBetween)
+    );
+    assert_eq!(
+        taffy_style.gap,
+        taffy::Size {
+            width: taffy::LengthPercentage::length(8.0),
+            height: taffy::LengthPercentage::length(4.0),
+        }
+    );
+    assert_eq!(taffy_style.aspect_ratio, Some(1.5));
+    assert_eq!(
+        taffy_style.overflow,
+        taffy::Point {
+            x: taffy::Overflow::Hidden,
+            y: taffy::Overflow::Scroll,
+        }
+    );
+}
+
+#[test]
+fn test_display_overrides_auto_detection() {
+    let style = BoxStyle {
+        flex_direction: Some(FlexDirection::Column),
+        display: Some(BoxDisplay::None),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+    assert_eq!(taffy_style.display, taffy::Display::None);
+
+    let style = BoxStyle {
+        display: Some(BoxDisplay::Block),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+    assert_eq!(taffy_style.display, taffy::Display::Block);
+}
+
+#[test]
+fn test_box_overflow_all() {
+    assert_eq!(
+        BoxOverflow::all(Overflow::Clip),
+        BoxOverflow {
+            x: Overflow::Clip,
+            y: Overflow::Clip,
+        }
+    );
+}
+
+// ===== レイアウトテスト =====
+
+#[test]
+fn test_flex_wrap_with_gap_layout() {
+    let mut ecs_world = EcsWorld::new();
+    let items = spawn_wrapping_tags(&mut ecs_world);
+
+    ecs_world.try_tick_world();
+
+    // 250 + 10 + 250 + 10 + 250 = 770 <= 800 → 1行目に3個、2行目は 100 + 20 = 120 から
+    let offsets: Vec<(f32, f32)> = items
+        .iter()
+        .map(|e| {
+            let (x, y, _, _) = arrangement(&ecs_world, *e);
+            (x, y)
+        })
+        .collect();
+    assert_eq!(
+        offsets,
+        vec![
+            (0.0, 0.0),
+            (260.0, 0.0),
+            (520.0, 0.0),
+            (0.0, 120.0),
+            (260.0, 120.0),
+        ]
+    );
+}
+
+#[test]
+fn test_display_none_removes_from_layout_and_restores() {
+    let mut ecs_world = EcsWorld::new();
+    let items = spawn_wrapping_tags(&mut ecs_world);
+    ecs_world.try_tick_world();
+
+    // 2番目のアイテムを非表示にすると後続が詰められる
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(items[1])
+        .unwrap()
+        .display = Some(BoxDisplay::None);
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, items[1]), (0.0, 0.0, 0.0, 0.0));
+    assert_eq!(
+        arrangement(&ecs_world, items[2]),
+        (260.0, 0.0, 250.0, 100.0)
+    );
+    assert_eq!(
+        arrangement(&ecs_world, items[3]),
+        (520.0, 0.0, 250.0, 100.0)
+    );
+    assert_eq!(
+        arrangement(&ecs_world, items[4]),
+        (0.0, 120.0, 250.0, 100.0)
+    );
+    assert!(ecs_world.world().get_entity(items[1]).is_ok());
+
+    // 再表示で元の配置に戻る
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(items[1])
+        .unwrap()
+        .display = None;
+    ecs_world.try_tick_world();
+
+    assert_eq!(
+        arrangement(&ecs_world, items[1]),
+        (260.0, 0.0, 250.0, 100.0)
+    );
+    assert_eq!(
+        arrangement(&ecs_world, items[4]),
+        (260.0, 120.0, 250.0, 100.0)
+    );
+}
+
+#[test]
+fn test_display_none_subtree_is_not_drawn_or_hit() {
+    let mut ecs_world = EcsWorld::new();
+    let (root, container, label) = {
+        let world = ecs_world.world_mut();
+        let root = world
+            .spawn((
+                LayoutRoot,
+                BoxStyle {
+                    size: px_size(800.0, 600.0),
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let container = world
+            .spawn((
+                BoxStyle {
+                    size: px_size(200.0, 100.0),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(root),
+            ))
+            .id();
+        let label = world
+            .spawn((
+                Label {
+                    text: "hidden".to_string(),
+                    ..Default::default()
+                },
+                BoxStyle {
+                    size: px_size(100.0, 20.0),
+                    ..Default::default()
+                },
+                ChildOf(container),
+            ))
+            .id();
+        (root, container, label)
+    };
+    ecs_world.try_tick_world();
+
+    let point = PhysicalPoint::new(10.0, 10.0);
+    assert_eq!(hit_test(ecs_world.world(), root, point), Some(label));
+    assert!(ecs_world.world().get::<DisplayHidden>(label).is_none());
+
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(container)
+        .unwrap()
+        .display = Some(BoxDisplay::None);
+    ecs_world.try_tick_world();
+
+    // 子孫まで非表示マーカーが伝播し、render_surfaceとヒットテストの対象外になる
+    let world = ecs_world.world();
+    assert!(world.get::<DisplayHidden>(container).is_some());
+    assert!(world.get::<DisplayHidden>(label).is_some());
+    assert!(!hit_test_entity(world, label, point));
+    assert_ne!(hit_test(world, root, point), Some(label));
+
+    ecs_world
+        .world_mut()
+        .get_mut::<BoxStyle>(container)
+        .unwrap()
+        .display = None;
+    ecs_world.try_tick_world();
+
+    let world = ecs_world.world();
+    assert!(world.get::<DisplayHidden>(label).is_none());
+    assert_eq!(hit_test(world, root, point), Some(label));
+}
+
+#[test]
+fn test_aspect_ratio_layout() {
+    let mut ecs_world = EcsWorld::new();
+    let item = {
+        let world = ecs_world.world_mut();
+        let root = world
+            .spawn((
+                LayoutRoot,
+                BoxStyle {
+                    size: px_size(800.0, 600.0),
+                    flex_direction: Some(FlexDirection::Column),
+                    align_items: Some(AlignItems::FlexStart),
+                    ..Default::default()
+                },
+            ))
+            .id();
+        world
+            .spawn((
+                BoxStyle {
+                    size: Some(BoxSize {
+                        width: Some(Dimension::Px(200.0)),
+                        height: None,
+                    }),
+                    aspect_ratio: Some(2.0),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(root),
+            ))
+            .id()
+    };
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, item), (0.0, 0.0, 200.0, 100.0));
+}
@@ -0,0 +1,257 @@
+//! CSS Grid レイアウトのテスト
+//!
+//! 1. BoxStyle の Grid系プロパティが taffy::Style へ正しく変換されること
+//! 2. Grid コンテナ/アイテムの Arrangement が期待どおりに計算されること
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::ChildOf;
+
+fn root_style() -> BoxStyle {
+    BoxStyle {
+        size: Some(BoxSize {
+            width: Some(Dimension::Px(800.0)),
+            height: Some(Dimension::Px(600.0)),
+        }),
+        ..Default::default()
+    }
+}
+
+fn full_size() -> Option<BoxSize> {
+    Some(BoxSize {
+        width: Some(Dimension::Percent(100.0)),
+        height: Some(Dimension::Percent(100.0)),
+    })
+}
+
+fn arrangement(ecs_world: &EcsWorld, entity: Entity) -> (f32, f32, f32, f32) {
+    let arr = ecs_world
+        .world()
+        .get::<Arrangement>(entity)
+        .expect("Arrangement");
+    (arr.offset.x, arr.offset.y, arr.size.width, arr.size.height)
+}
+
+// ===== 変換テスト =====
+
+#[test]
+fn test_grid_container_sets_display_grid() {
+    let style = BoxStyle {
+        grid_template_columns: Some(vec![GridTrack::Px(100.0).into()]),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+    assert_eq!(taffy_style.display, taffy::Display::Grid);
+
+    // Grid指定はFlex指定より優先される
+    let style = BoxStyle {
+        flex_direction: Some(FlexDirection::Row),
+        grid_auto_flow: Some(GridAutoFlow::Column),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+    assert_eq!(taffy_style.display, taffy::Display::Grid);
+    assert_eq!(taffy_style.grid_auto_flow, taffy::GridAutoFlow::Column);
+}
+
+#[test]
+fn test_grid_tracks_to_taffy() {
+    use taffy::style_helpers::*;
+
+    let style = BoxStyle {
+        grid_template_columns: Some(vec![
+            GridTrack::Px(200.0).into(),
+            GridTrack::Fr(1.0).into(),
+            GridTrack::MinMax(TrackBreadth::Px(100.0), TrackBreadth::Fr(2.0)).into(),
+            GridTemplate::Repeat(GridRepeat::AutoFill, vec![GridTrack::Percent(25.0)]),
+        ]),
+        grid_auto_rows: Some(vec![GridTrack::Auto, GridTrack::MinContent]),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+
+    let expected: Vec<taffy::GridTemplateComponent<String>> = vec![
+        taffy::GridTemplateComponent::Single(length(200.0)),
+        taffy::GridTemplateComponent::Single(fr(1.0)),
+        taffy::GridTemplateComponent::Single(minmax(length(100.0), fr(2.0))),
+        repeat(taffy::RepetitionCount::AutoFill, vec![percent(0.25)]),
+    ];
+    assert_eq!(taffy_style.grid_template_columns, expected);
+
+    let expected_auto: Vec<taffy::TrackSizingFunction> = vec![auto(), min_content()];
+    assert_eq!(taffy_style.grid_auto_rows, expected_auto);
+}
+
+#[test]
+fn test_grid_item_placement_to_taffy() {
+    let style = BoxStyle {
+        grid_row: Some(GridPlacement::span(2, 3)),
+        grid_column: Some(GridPlacement::area("main")),
+        ..Default::default()
+    };
+    let taffy_style: taffy::Style = (&style).into();
+
+    // アイテム側のプロパティだけではdisplayは変わらない
+    assert_eq!(taffy_style.display, taffy::Display::default());
+    assert_eq!(
+        taffy_style.grid_row,
+        taffy::Line {
+            start: taffy::style_helpers::line(2),
+            end: taffy::GridPlacement::Span(3),
+        }
+    );
+    assert_eq!(
+        taffy_style.grid_column.start,
+        taffy::GridPlacement::NamedLine("main".to_string(), 0)
+    );
+}
+
+#[test]
+fn test_parse_grid_template_areas() {
+    let areas = parse_grid_template_areas(&[
+        "header header header".to_string(),
+        "side   main   main".to_string(),
+        "side   .      broken".to_string(),
+        "broken broken broken".to_string(),
+    ]);
+    let names: Vec<&str> = areas.iter().map(|a| a.name.as_str()).collect();
+    // "broken" は長方形でないため除外される
+    assert_eq!(names, vec!["header", "side", "main"]);
+
+    let side = &areas[1];
+    assert_eq!((side.row_start, side.row_end), (2, 4));
+    assert_eq!((side.column_start, side.column_end), (1, 2));
+    let main = &areas[2];
+    assert_eq!((main.row_start, main.row_end), (2, 3));
+    assert_eq!((main.column_start, main.column_end), (2, 4));
+
+    // 列数が揃わない場合は全体を無視
+    assert!(parse_grid_template_areas(&["a b".to_string(), "a".to_string()]).is_empty());
+}
+
+// ===== レイアウトテスト =====
+
+#[test]
+fn test_grid_named_areas_layout() {
+    let mut ecs_world = EcsWorld::new();
+
+    let (header, side, main) = {
+        let world = ecs_world.world_mut();
+        let root = world.spawn((LayoutRoot, root_style())).id();
+        let grid = world
+            .spawn((
+                BoxStyle {
+                    size: full_size(),
+                    grid_template_columns: Some(vec![
+                        GridTrack::Px(200.0).into(),
+                        GridTrack::Fr(1.0).into(),
+                    ]),
+                    grid_template_rows: Some(vec![
+                        GridTrack::Px(100.0).into(),
+                        GridTrack::Fr(1.0).into(),
+                    ]),
+                    grid_template_areas: Some(vec![
+                        "header header".to_string(),
+                        "side main".to_string(),
+                    ]),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(root),
+            ))
+            .id();
+        let mut area_item = |name: &str| {
+            world
+                .spawn((
+                    BoxStyle {
+                        grid_row: Some(GridPlacement::area(name)),
+                        grid_column: Some(GridPlacement::area(name)),
+                        ..Default::default()
+                    },
+                    Arrangement::default(),
+                    ChildOf(grid),
+                ))
+                .id()
+        };
+        // 宣言順とは逆に生成しても、エリア名で配置される
+        let main = area_item("main");
+        let side = area_item("side");
+        let header = area_item("header");
+        (header, side, main)
+    };
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, header), (0.0, 0.0, 800.0, 100.0));
+    assert_eq!(arrangement(&ecs_world, side), (0.0, 100.0, 200.0, 500.0));
+    assert_eq!(arrangement(&ecs_world, main), (200.0, 100.0, 600.0, 500.0));
+}
+
+#[test]
+fn test_grid_span_and_auto_fill_layout() {
+    let mut ecs_world = EcsWorld::new();
+
+    let (banner, cells) = {
+        let world = ecs_world.world_mut();
+        let root = world.spawn((LayoutRoot, root_style())).id();
+        let grid = world
+            .spawn((
+                BoxStyle {
+                    size: full_size(),
+                    // 800px / 100px = 8列
+                    grid_template_columns: Some(vec![GridTemplate::Repeat(
+                        GridRepeat::AutoFill,
+                        vec![GridTrack::Px(100.0)],
+                    )]),
+                    grid_auto_rows: Some(vec![GridTrack::Px(50.0)]),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(root),
+            ))
+            .id();
+        // 1行目の3列目から4列分
+        let banner = world
+            .spawn((
+                BoxStyle {
+                    grid_row: Some(GridPlacement::lines(1, 2)),
+                    grid_column: Some(GridPlacement::span(3, 4)),
+                    ..Default::default()
+                },
+                Arrangement::default(),
+                ChildOf(grid),
+            ))
+            .id();
+        let cells: Vec<Entity> = (0..6)
+            .map(|_| {
+                world
+                    .spawn((BoxStyle::default(), Arrangement::default(), ChildOf(grid)))
+                    .id()
+            })
+            .collect();
+        (banner, cells)
+    };
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, banner), (200.0, 0.0, 400.0, 50.0));
+    // 自動配置: 1行目の空き(列1,2,7,8) → 2行目
+    let offsets: Vec<(f32, f32)> = cells
+        .iter()
+        .map(|e| {
+            let (x, y, _, _) = arrangement(&ecs_world, *e);
+            (x, y)
+        })
+        .collect();
+    assert_eq!(
+        offsets,
+        vec![
+            (0.0, 0.0),
+            (100.0, 0.0),
+            (600.0, 0.0),
+            (700.0, 0.0),
+            (0.0, 50.0),
+            (100.0, 50.0),
+        ]
+    );
+}
@@ -0,0 +1,225 @@
+//! TextBoxの入力ハンドラのテスト
+//!
+//! キー・文字・IME変換メッセージをディスパッチし、フォーカス中のTextBoxの
+//! 編集モデルに反映されることを確認する（描画・ポインター入力は対象外）。
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::{VK_A, VK_C, VK_LEFT, VK_RETURN, VK_V, VK_Z};
+use wintf::ecs::Visual;
+use wintf::ecs::drag::{DragConfig, OnDrag, OnDragEnd};
+use wintf::ecs::keyboard::{
+    CandidateWindowRequest, Composition, FocusChangedEvent, Focusable, KeyEvent, KeyboardInput,
+    KeyboardState, Modifiers, OnKeyDown, TextInputMessage, dispatch_keyboard_events, set_focus,
+};
+use wintf::ecs::pointer::Phase;
+use wintf::ecs::widget::text::{ClipboardResource, MemoryClipboard, TextBox};
+
+/// ウィンドウまで伝播したキー
+#[derive(Resource, Default)]
+struct Unhandled(Vec<KeyEvent>);
+
+fn on_window_key_down(
+    world: &mut World,
+    _sender: Entity,
+    _entity: Entity,
+    ev: &Phase<KeyEvent>,
+) -> bool {
+    if let Phase::Bubble(event) = ev {
+        world.resource_mut::<Unhandled>().0.push(event.clone());
+    }
+    false
+}
+
+fn ctrl() -> Modifiers {
+    Modifiers {
+        ctrl: true,
+        ..Default::default()
+    }
+}
+
+fn setup(text_box: TextBox) -> (World, Entity, Entity) {
+    let mut world = World::new();
+    world.init_resource::<Unhandled>();
+    world.init_resource::<KeyboardInput>();
+    world.init_resource::<KeyboardState>();
+    world.init_resource::<Messages<FocusChangedEvent>>();
+    world.init_resource::<Messages<CandidateWindowRequest>>();
+    world.insert_resource(ClipboardResource::new(MemoryClipboard::default()));
+
+    let window = world.spawn(OnKeyDown(on_window_key_down)).id();
+    let field = world.spawn((text_box, ChildOf(window))).id();
+    assert!(set_focus(&mut world, field));
+    (world, window, field)
+}
+
+fn key(world: &mut World, window: Entity, event: KeyEvent) {
+    world.resource_mut::<KeyboardInput>().push(window, event);
+    dispatch_keyboard_events(world);
+}
+
+fn type_text(world: &mut World, window: Entity, text: &str) {
+    for unit in text.encode_utf16() {
+        world
+            .resource_mut::<KeyboardInput>()
+            .push_text(window, TextInputMessage::Char(unit));
+    }
+    dispatch_keyboard_events(world);
+}
+
+fn text(world: &World, field: Entity) -> String {
+    world.get::<TextBox>(field).unwrap().text().to_string()
+}
+
+fn unhandled(world: &mut World) -> Vec<KeyEvent> {
+    std::mem::take(&mut world.resource_mut::<Unhandled>().0)
+}
+
+#[test]
+fn test_text_box_auto_inserts_focusable_and_visual() {
+    let mut world = World::new();
+    let entity = world.spawn(TextBox::new("abc")).id();
+    assert!(world.get::<Focusable>(entity).is_some());
+    assert!(world.get::<Visual>(entity).is_some());
+    assert!(world.get::<OnKeyDown>(entity).is_some());
+    // ドラッグ選択はドラッグ状態で追跡し、ウィンドウは移動しない
+    assert!(world.get::<OnDrag>(entity).is_some());
+    assert!(world.get::<OnDragEnd>(entity).is_some());
+    assert!(!world.get::<DragConfig>(entity).unwrap().move_window);
+}
+
+#[test]
+fn test_typing_and_caret_keys() {
+    let (mut world, window, field) = setup(TextBox::new("ac"));
+
+    // 初期キャレットは末尾
+    key(&mut world, window, KeyEvent::pressed(VK_LEFT));
+    type_text(&mut world, window, "b😀");
+    assert_eq!(text(&world, field), "ab😀c");
+
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_Z).with_modifiers(ctrl()),
+    );
+    assert_eq!(text(&world, field), "ac");
+    assert!(unhandled(&mut world).is_empty());
+}
+
+#[test]
+fn test_copy_and_paste_use_clipboard_resource() {
+    let (mut world, window, field) = setup(TextBox::new("hello"));
+
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_A).with_modifiers(ctrl()),
+    );
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_C).with_modifiers(ctrl()),
+    );
+    assert_eq!(
+        world
+            .resource_mut::<ClipboardResource>()
+            .clipboard()
+            .get_text()
+            .as_deref(),
+        Some("hello")
+    );
+
+    key(&mut world, window, KeyEvent::pressed(VK_LEFT));
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_V).with_modifiers(ctrl()),
+    );
+    assert_eq!(text(&world, field), "hellohello");
+}
+
+#[test]
+fn test_single_line_enter_bubbles_to_window() {
+    let (mut world, window, field) = setup(TextBox::new("abc"));
+    key(&mut world, window, KeyEvent::pressed(VK_RETURN));
+    assert_eq!(text(&world, field), "abc");
+    assert_eq!(unhandled(&mut world).len(), 1);
+
+    let (mut world, window, field) = setup(TextBox::multiline("abc"));
+    key(&mut world, window, KeyEvent::pressed(VK_RETURN));
+    assert_eq!(text(&world, field), "abc\n");
+    assert!(unhandled(&mut world).is_empty());
+}
+
+#[test]
+fn test_composition_is_shown_but_not_committed_until_result() {
+    let (mut world, window, field) = setup(TextBox::new("ab"));
+    key(&mut world, window, KeyEvent::pressed(VK_LEFT));
+
+    let units: Vec<u16> = "かな".encode_utf16().collect();
+    let kana = Composition::from_utf16(&units, &vec![0; units.len()], &[], None);
+    for message in [
+        TextInputMessage::StartComposition,
+        TextInputMessage::Composition {
+            result: None,
+            composition: Some(kana),
+        },
+    ] {
+        world
+            .resource_mut::<KeyboardInput>()
+            .push_text(window, message);
+    }
+    dispatch_keyboard_events(&mut world);
+
+    let text_box = world.get::<TextBox>(field).unwrap();
+    assert_eq!(text_box.text(), "ab");
+    assert_eq!(text_box.display_text(), ("aかなb".to_string(), 1));
+
+    // 変換中の矢印キーはIMEに任せる
+    key(&mut world, window, KeyEvent::pressed(VK_LEFT));
+    assert_eq!(world.get::<TextBox>(field).unwrap().editor().caret(), 1);
+
+    for message in [
+        TextInputMessage::Composition {
+            result: Some("仮名".to_string()),
+            composition: None,
+        },
+        TextInputMessage::EndComposition,
+    ] {
+        world
+            .resource_mut::<KeyboardInput>()
+            .push_text(window, message);
+    }
+    dispatch_keyboard_events(&mut world);
+
+    let text_box = world.get::<TextBox>(field).unwrap();
+    assert_eq!(text_box.text(), "a仮名b");
+    assert!(text_box.composition().is_none());
+}
+
+#[test]
+fn test_read_only_allows_selection_but_not_edits() {
+    let (mut world, window, field) = setup(TextBox::new("fixed").read_only());
+
+    type_text(&mut world, window, "x");
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_V).with_modifiers(ctrl()),
+    );
+    assert_eq!(text(&world, field), "fixed");
+
+    key(
+        &mut world,
+        window,
+        KeyEvent::pressed(VK_A).with_modifiers(ctrl()),
+    );
+    assert_eq!(
+        world
+            .get::<TextBox>(field)
+            .unwrap()
+            .editor()
+            .selected_text(),
+        "fixed"
+    );
+    assert!(unhandled(&mut world).is_empty());
+}
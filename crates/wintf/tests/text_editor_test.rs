@@ -0,0 +1,547 @@
+//! TextBox編集モデル（TextEditor）のテスト
+//!
+//! DirectWriteに依存しない純粋な編集モデルを検証する。
+//! 1. 書記素クラスタ単位のキャレット移動・削除
+//! 2. 単語・行・文書単位の移動と選択
+//! 3. 文字入力・改行の正規化
+//! 4. 取り消し・やり直し（連続入力のまとめ）
+//! 5. コピー・切り取り・貼り付け
+//! 6. キー入力 → 編集コマンド（書字方向による読み替え）
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    VIRTUAL_KEY, VK_A, VK_BACK, VK_C, VK_DELETE, VK_DOWN, VK_END, VK_HOME, VK_LEFT, VK_RETURN,
+    VK_RIGHT, VK_TAB, VK_UP, VK_V, VK_Y, VK_Z,
+};
+use wintf::ecs::keyboard::{KeyEvent, Modifiers};
+use wintf::ecs::widget::text::text_box::segment::{
+    next_word_boundary, prev_word_boundary, word_range_at,
+};
+use wintf::ecs::widget::text::{
+    EditCommand, MemoryClipboard, Movement, Selection, TextDirection, TextEditor,
+};
+
+/// キャレット位置を`|`で表した文字列（選択中は`[`…`]`、キャレット側に`|`）
+fn show(editor: &TextEditor) -> String {
+    let text = editor.text();
+    let selection = editor.selection();
+    let range = selection.range();
+    if selection.is_empty() {
+        return format!("{}|{}", &text[..range.start], &text[range.start..]);
+    }
+    let (open, close) = if selection.caret < selection.anchor {
+        ("|[", "]")
+    } else {
+        ("[", "]|")
+    };
+    format!(
+        "{}{open}{}{close}{}",
+        &text[..range.start],
+        &text[range.clone()],
+        &text[range.end..]
+    )
+}
+
+/// 先頭にキャレットを置いた編集モデル
+fn at_start(editor: TextEditor) -> TextEditor {
+    let mut editor = editor;
+    editor.set_caret(0, false);
+    editor
+}
+
+// ============================================================================
+// 書記素クラスタ
+// ============================================================================
+
+#[test]
+fn test_caret_moves_by_grapheme_cluster() {
+    // 結合文字・異体字セレクタ・ZWJ連結絵文字・国旗は1文字として移動する
+    let mut editor = at_start(TextEditor::new(
+        "e\u{301}葛\u{E0100}👨\u{200D}👩\u{200D}👧🇯🇵",
+    ));
+    let mut stops = vec![editor.caret()];
+    for _ in 0..5 {
+        editor.move_caret(Movement::NextGrapheme, false);
+        stops.push(editor.caret());
+    }
+    let text = editor.text();
+    let clusters: Vec<&str> = stops.windows(2).map(|w| &text[w[0]..w[1]]).collect();
+    assert_eq!(
+        clusters,
+        [
+            "e\u{301}",
+            "葛\u{E0100}",
+            "👨\u{200D}👩\u{200D}👧",
+            "🇯🇵",
+            ""
+        ]
+    );
+
+    // 逆方向も同じ境界で止まる
+    let mut back = vec![editor.caret()];
+    for _ in 0..4 {
+        editor.move_caret(Movement::PrevGrapheme, false);
+        back.push(editor.caret());
+    }
+    back.reverse();
+    assert_eq!(back, stops[..5]);
+}
+
+#[test]
+fn test_backspace_and_delete_remove_whole_cluster() {
+    let mut editor = TextEditor::new("a👍\u{1F3FD}か\u{3099}");
+    assert!(editor.delete(Movement::PrevGrapheme));
+    assert_eq!(show(&editor), "a👍\u{1F3FD}|");
+    assert!(editor.delete(Movement::PrevGrapheme));
+    assert_eq!(show(&editor), "a|");
+
+    editor.set_caret(0, false);
+    assert!(editor.delete(Movement::NextGrapheme));
+    assert_eq!(show(&editor), "|");
+    // 何もない方向への削除は変更なし
+    assert!(!editor.delete(Movement::NextGrapheme));
+    assert!(!editor.delete(Movement::PrevGrapheme));
+}
+
+#[test]
+fn test_set_caret_snaps_to_cluster_boundary() {
+    let mut editor = TextEditor::new("x👍\u{1F3FD}y");
+    // 修飾子の途中・UTF-8の途中は書記素クラスタの先頭に丸める
+    editor.set_caret(6, false);
+    assert_eq!(editor.caret(), 1);
+    editor.set_caret(2, false);
+    assert_eq!(editor.caret(), 1);
+    editor.set_caret(100, false);
+    assert_eq!(editor.caret(), editor.text().len());
+}
+
+// ============================================================================
+// 単語・行・文書
+// ============================================================================
+
+#[test]
+fn test_word_boundaries() {
+    let text = "hello, world  foo_bar";
+    assert_eq!(next_word_boundary(text, 0), 5);
+    assert_eq!(next_word_boundary(text, 5), 7);
+    assert_eq!(next_word_boundary(text, 7), 14);
+    assert_eq!(next_word_boundary(text, 14), text.len());
+    assert_eq!(prev_word_boundary(text, text.len()), 14);
+    assert_eq!(prev_word_boundary(text, 14), 7);
+    assert_eq!(prev_word_boundary(text, 7), 5);
+    assert_eq!(prev_word_boundary(text, 5), 0);
+    assert_eq!(word_range_at(text, 16), 14..text.len());
+    assert_eq!(word_range_at(text, 12), 12..14);
+
+    // UAX #29: 語中のアポストロフィ・小数点で区切らない
+    let text = "can't 3.14";
+    assert_eq!(word_range_at(text, 2), 0..5);
+    assert_eq!(next_word_boundary(text, 0), 6);
+    assert_eq!(next_word_boundary(text, 6), text.len());
+}
+
+#[test]
+fn test_japanese_word_boundaries_by_script() {
+    // 漢字・ひらがな・カタカナ（長音記号を含む）・句読点で区切る
+    let text = "東京都でコーヒーを飲む。";
+    let mut stops = vec![0];
+    let mut offset = 0;
+    while offset < text.len() {
+        offset = next_word_boundary(text, offset);
+        stops.push(offset);
+    }
+    let words: Vec<&str> = stops.windows(2).map(|w| &text[w[0]..w[1]]).collect();
+    assert_eq!(words, ["東京都", "で", "コーヒー", "を", "飲", "む", "。"]);
+
+    assert_eq!(&text[word_range_at(text, "東京都で".len())], "コーヒー");
+}
+
+#[test]
+fn test_word_movement_stops_at_line_breaks() {
+    let mut editor = at_start(TextEditor::multiline("one\n  two"));
+    editor.move_caret(Movement::NextWord, false);
+    assert_eq!(show(&editor), "one|\n  two");
+    editor.move_caret(Movement::NextWord, false);
+    assert_eq!(show(&editor), "one\n|  two");
+    editor.move_caret(Movement::NextWord, false);
+    assert_eq!(show(&editor), "one\n  |two");
+
+    editor.move_caret(Movement::PrevWord, false);
+    assert_eq!(show(&editor), "one\n|  two");
+    editor.move_caret(Movement::PrevWord, false);
+    assert_eq!(show(&editor), "one|\n  two");
+}
+
+#[test]
+fn test_word_deletion() {
+    let mut editor = TextEditor::new("foo bar baz");
+    editor.delete(Movement::PrevWord);
+    assert_eq!(show(&editor), "foo bar |");
+    editor.set_caret(0, false);
+    editor.delete(Movement::NextWord);
+    assert_eq!(show(&editor), "|bar ");
+}
+
+#[test]
+fn test_line_movement_keeps_preferred_column() {
+    let mut editor = TextEditor::multiline("abcdef\nxy\n漢字かな交じり");
+    editor.set_caret(4, false);
+    editor.move_caret(Movement::NextLine, false);
+    // 短い行では行末に止まる
+    assert_eq!(show(&editor), "abcdef\nxy|\n漢字かな交じり");
+    editor.move_caret(Movement::NextLine, false);
+    // 元の桁（4文字目）に戻る
+    assert_eq!(show(&editor), "abcdef\nxy\n漢字かな|交じり");
+    editor.move_caret(Movement::PrevLine, false);
+    editor.move_caret(Movement::PrevLine, false);
+    assert_eq!(show(&editor), "abcd|ef\nxy\n漢字かな交じり");
+
+    // 先頭行から上は文書の先頭、最終行から下は文書の末尾
+    editor.move_caret(Movement::PrevLine, false);
+    assert_eq!(editor.caret(), 0);
+    editor.move_caret(Movement::DocumentEnd, false);
+    editor.move_caret(Movement::NextLine, false);
+    assert_eq!(editor.caret(), editor.text().len());
+
+    // 横移動で桁は更新される
+    editor.set_caret(1, false);
+    editor.move_caret(Movement::NextLine, false);
+    assert_eq!(show(&editor), "abcdef\nx|y\n漢字かな交じり");
+}
+
+#[test]
+fn test_line_and_document_boundaries() {
+    let mut editor = TextEditor::multiline("first\nsecond\nthird");
+    editor.set_caret(9, false);
+    editor.move_caret(Movement::LineStart, false);
+    assert_eq!(show(&editor), "first\n|second\nthird");
+    editor.move_caret(Movement::LineEnd, true);
+    assert_eq!(show(&editor), "first\n[second]|\nthird");
+    // 選択の起点は行頭のまま
+    editor.move_caret(Movement::DocumentStart, true);
+    assert_eq!(show(&editor), "|[first\n]second\nthird");
+    editor.move_caret(Movement::DocumentEnd, false);
+    assert_eq!(show(&editor), "first\nsecond\nthird|");
+}
+
+// ============================================================================
+// 選択
+// ============================================================================
+
+#[test]
+fn test_extend_and_collapse_selection() {
+    let mut editor = at_start(TextEditor::new("abcdef"));
+    editor.move_caret(Movement::NextGrapheme, true);
+    editor.move_caret(Movement::NextGrapheme, true);
+    assert_eq!(show(&editor), "[ab]|cdef");
+    assert_eq!(editor.selected_text(), "ab");
+
+    // 伸ばさずに←→すると選択の端へ
+    editor.move_caret(Movement::PrevGrapheme, false);
+    assert_eq!(show(&editor), "|abcdef");
+    editor.set_selection(Selection::new(4, 1));
+    editor.move_caret(Movement::NextGrapheme, false);
+    assert_eq!(show(&editor), "abcd|ef");
+
+    // 選択を反対側へ伸ばす
+    editor.set_caret(3, false);
+    editor.move_caret(Movement::NextGrapheme, true);
+    editor.move_caret(Movement::PrevWord, true);
+    assert_eq!(show(&editor), "|[abc]def");
+}
+
+#[test]
+fn test_select_all_and_word() {
+    let mut editor = TextEditor::new("quick brown fox");
+    editor.select_all();
+    assert_eq!(editor.selected_text(), "quick brown fox");
+    editor.select_word_at(8);
+    assert_eq!(show(&editor), "quick [brown]| fox");
+    // 末尾のダブルクリックは直前の単語
+    editor.select_word_at(editor.text().len());
+    assert_eq!(editor.selected_text(), "fox");
+}
+
+// ============================================================================
+// 入力
+// ============================================================================
+
+#[test]
+fn test_insert_replaces_selection() {
+    let mut editor = TextEditor::new("Hello World");
+    editor.select_word_at(0);
+    assert!(editor.insert("Goodbye"));
+    assert_eq!(show(&editor), "Goodbye| World");
+    // 空文字列の挿入は選択がなければ何もしない
+    assert!(!editor.insert(""));
+}
+
+#[test]
+fn test_newlines_are_normalized() {
+    let mut single = TextEditor::new("a\r\nb\rc\nd");
+    assert_eq!(single.text(), "a b c d");
+    assert!(!single.insert_newline());
+    single.paste("x\r\ny\u{7}");
+    assert_eq!(single.text(), "a b c dx y");
+
+    let mut multi = TextEditor::multiline("a\r\nb\rc");
+    assert_eq!(multi.text(), "a\nb\nc");
+    assert!(multi.insert_newline());
+    multi.paste("\tx\r\n");
+    assert_eq!(multi.text(), "a\nb\nc\n\tx\n");
+}
+
+// ============================================================================
+// 取り消し・やり直し
+// ============================================================================
+
+#[test]
+fn test_typing_is_undone_word_by_word() {
+    let mut editor = TextEditor::new("");
+    for c in "hello world".chars() {
+        editor.insert(&c.to_string());
+    }
+    assert!(editor.undo());
+    assert_eq!(show(&editor), "hello |");
+    assert!(editor.undo());
+    assert_eq!(show(&editor), "|");
+    assert!(!editor.undo());
+
+    assert!(editor.redo());
+    assert_eq!(show(&editor), "hello |");
+    assert!(editor.redo());
+    assert_eq!(show(&editor), "hello world|");
+    assert!(!editor.redo());
+}
+
+#[test]
+fn test_caret_movement_breaks_undo_group() {
+    let mut editor = TextEditor::new("");
+    editor.insert("a");
+    editor.insert("b");
+    editor.move_caret(Movement::PrevGrapheme, false);
+    editor.insert("c");
+    assert_eq!(editor.text(), "acb");
+    editor.undo();
+    assert_eq!(show(&editor), "a|b");
+    editor.undo();
+    assert_eq!(editor.text(), "");
+}
+
+#[test]
+fn test_consecutive_deletes_are_undone_together() {
+    let mut editor = TextEditor::new("abcdef");
+    editor.insert("X");
+    editor.delete(Movement::PrevGrapheme);
+    editor.delete(Movement::PrevGrapheme);
+    editor.delete(Movement::PrevGrapheme);
+    assert_eq!(show(&editor), "abcd|");
+    editor.undo();
+    assert_eq!(show(&editor), "abcdefX|");
+    editor.undo();
+    assert_eq!(show(&editor), "abcdef|");
+
+    let mut forward = at_start(TextEditor::new("abcdef"));
+    forward.delete(Movement::NextGrapheme);
+    forward.delete(Movement::NextGrapheme);
+    assert_eq!(show(&forward), "|cdef");
+    forward.undo();
+    assert_eq!(show(&forward), "|abcdef");
+}
+
+#[test]
+fn test_undo_restores_selection() {
+    let mut editor = TextEditor::new("one two three");
+    editor.select_word_at(4);
+    editor.insert("2");
+    assert_eq!(show(&editor), "one 2| three");
+    editor.undo();
+    assert_eq!(show(&editor), "one [two]| three");
+    editor.redo();
+    assert_eq!(show(&editor), "one 2| three");
+}
+
+#[test]
+fn test_new_edit_clears_redo_and_history_is_limited() {
+    let mut editor = TextEditor::new("").with_undo_limit(2);
+    editor.paste("a");
+    editor.paste("b");
+    editor.paste("c");
+    editor.undo();
+    assert!(editor.can_redo());
+    editor.paste("d");
+    assert!(!editor.can_redo());
+
+    assert!(editor.undo());
+    assert!(editor.undo());
+    assert!(!editor.undo());
+    assert_eq!(editor.text(), "a");
+}
+
+#[test]
+fn test_set_text_clears_history() {
+    let mut editor = TextEditor::new("abc");
+    editor.insert("d");
+    editor.set_text("xyz");
+    assert!(!editor.can_undo());
+    assert_eq!(show(&editor), "xyz|");
+}
+
+// ============================================================================
+// クリップボード
+// ============================================================================
+
+#[test]
+fn test_copy_cut_paste() {
+    let mut editor = TextEditor::new("copy me");
+    let mut clipboard = MemoryClipboard::default();
+
+    // 選択なしのコピーはクリップボードを変えない
+    EditCommand::Copy.execute(&mut editor, &mut clipboard);
+    assert_eq!(clipboard.text, None);
+
+    editor.select_word_at(0);
+    EditCommand::Copy.execute(&mut editor, &mut clipboard);
+    assert_eq!(clipboard.text.as_deref(), Some("copy"));
+
+    EditCommand::Cut.execute(&mut editor, &mut clipboard);
+    assert_eq!(show(&editor), "| me");
+
+    EditCommand::Move {
+        movement: Movement::DocumentEnd,
+        extend: false,
+    }
+    .execute(&mut editor, &mut clipboard);
+    EditCommand::Paste.execute(&mut editor, &mut clipboard);
+    assert_eq!(show(&editor), " mecopy|");
+
+    // 貼り付けは直前の入力とまとめない
+    EditCommand::Undo.execute(&mut editor, &mut clipboard);
+    assert_eq!(show(&editor), " me|");
+    EditCommand::Undo.execute(&mut editor, &mut clipboard);
+    assert_eq!(show(&editor), "[copy]| me");
+}
+
+// ============================================================================
+// キー入力 → 編集コマンド
+// ============================================================================
+
+fn key(vk: VIRTUAL_KEY) -> KeyEvent {
+    KeyEvent::pressed(vk)
+}
+
+fn with(vk: VIRTUAL_KEY, ctrl: bool, shift: bool) -> KeyEvent {
+    KeyEvent::pressed(vk).with_modifiers(Modifiers {
+        ctrl,
+        shift,
+        ..Default::default()
+    })
+}
+
+fn moving(movement: Movement, extend: bool) -> Option<EditCommand> {
+    Some(EditCommand::Move { movement, extend })
+}
+
+#[test]
+fn test_horizontal_key_mapping() {
+    let ltr = TextDirection::HorizontalLeftToRight;
+    let map = |event: KeyEvent| EditCommand::from_key_event(&event, ltr, true);
+
+    assert_eq!(map(key(VK_LEFT)), moving(Movement::PrevGrapheme, false));
+    assert_eq!(
+        map(with(VK_RIGHT, true, true)),
+        moving(Movement::NextWord, true)
+    );
+    assert_eq!(map(key(VK_UP)), moving(Movement::PrevLine, false));
+    assert_eq!(
+        map(with(VK_DOWN, false, true)),
+        moving(Movement::NextLine, true)
+    );
+    assert_eq!(map(key(VK_HOME)), moving(Movement::LineStart, false));
+    assert_eq!(
+        map(with(VK_END, true, false)),
+        moving(Movement::DocumentEnd, false)
+    );
+    assert_eq!(
+        map(key(VK_BACK)),
+        Some(EditCommand::Delete(Movement::PrevGrapheme))
+    );
+    assert_eq!(
+        map(with(VK_DELETE, true, false)),
+        Some(EditCommand::Delete(Movement::NextWord))
+    );
+    assert_eq!(map(key(VK_RETURN)), Some(EditCommand::InsertNewline));
+    assert_eq!(map(with(VK_A, true, false)), Some(EditCommand::SelectAll));
+    assert_eq!(map(with(VK_C, true, false)), Some(EditCommand::Copy));
+    assert_eq!(map(with(VK_V, true, false)), Some(EditCommand::Paste));
+    assert_eq!(map(with(VK_Z, true, false)), Some(EditCommand::Undo));
+    assert_eq!(map(with(VK_Z, true, true)), Some(EditCommand::Redo));
+    assert_eq!(map(with(VK_Y, true, false)), Some(EditCommand::Redo));
+    assert_eq!(map(with(VK_DELETE, false, true)), Some(EditCommand::Cut));
+
+    // Tab・文字キー・キー解放は扱わない
+    assert_eq!(map(key(VK_TAB)), None);
+    assert_eq!(map(key(VK_A)), None);
+    assert_eq!(map(KeyEvent::released(VK_LEFT)), None);
+
+    // 右から左では←→が反転
+    let rtl = TextDirection::HorizontalRightToLeft;
+    assert_eq!(
+        EditCommand::from_key_event(&key(VK_LEFT), rtl, false),
+        moving(Movement::NextGrapheme, false)
+    );
+}
+
+#[test]
+fn test_single_line_leaves_vertical_keys_and_enter_unhandled() {
+    let ltr = TextDirection::HorizontalLeftToRight;
+    let map = |event: KeyEvent| EditCommand::from_key_event(&event, ltr, false);
+    assert_eq!(map(key(VK_UP)), None);
+    assert_eq!(map(key(VK_DOWN)), None);
+    assert_eq!(map(key(VK_RETURN)), None);
+    assert_eq!(map(key(VK_LEFT)), moving(Movement::PrevGrapheme, false));
+}
+
+#[test]
+fn test_vertical_key_mapping() {
+    // 縦書き（右から左へ行が進む）: ↑↓が文字、→が前の行、←が次の行
+    let rl = TextDirection::VerticalRightToLeft;
+    let map = |event: KeyEvent| EditCommand::from_key_event(&event, rl, true);
+    assert_eq!(map(key(VK_UP)), moving(Movement::PrevGrapheme, false));
+    assert_eq!(
+        map(with(VK_DOWN, true, false)),
+        moving(Movement::NextWord, false)
+    );
+    assert_eq!(map(key(VK_RIGHT)), moving(Movement::PrevLine, false));
+    assert_eq!(
+        map(with(VK_LEFT, false, true)),
+        moving(Movement::NextLine, true)
+    );
+
+    // 縦書き（左から右へ行が進む）
+    let lr = TextDirection::VerticalLeftToRight;
+    assert_eq!(
+        EditCommand::from_key_event(&key(VK_LEFT), lr, true),
+        moving(Movement::PrevLine, false)
+    );
+    assert_eq!(
+        EditCommand::from_key_event(&key(VK_RIGHT), lr, true),
+        moving(Movement::NextLine, false)
+    );
+
+    // 単一行の縦書きでは行方向の←→は扱わない
+    assert_eq!(EditCommand::from_key_event(&key(VK_LEFT), rl, false), None);
+}
+
+#[test]
+fn test_mutating_commands() {
+    assert!(EditCommand::Paste.is_mutating());
+    assert!(EditCommand::Delete(Movement::PrevGrapheme).is_mutating());
+    assert!(!EditCommand::Copy.is_mutating());
+    assert!(!EditCommand::SelectAll.is_mutating());
+    assert!(
+        !EditCommand::Move {
+            movement: Movement::NextGrapheme,
+            extend: true,
+        }
+        .is_mutating()
+    );
+}
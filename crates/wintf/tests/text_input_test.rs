@@ -0,0 +1,383 @@
+//! テキスト入力・IME変換のテスト
+//!
+//! 1. `TextInputComposer`: 合成メッセージ列に対するイベント列
+//! 2. フォーカス中のエンティティへの配信（`OnTextInput`・`OnComposition`）
+//! 3. 候補ウィンドウの配置要求（`CandidateWindowRequest`）
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::keyboard::{
+    CandidateWindowRequest, ClauseAttribute, Composition, CompositionEvent, FocusChangedEvent,
+    Focusable, ImeCaretRect, KeyboardInput, KeyboardState, OnComposition, OnTextInput,
+    TextInputComposer, TextInputEvent, TextInputMessage, TextInputOutput, dispatch_keyboard_events,
+    set_focus,
+};
+use wintf::ecs::layout::GlobalArrangement;
+use wintf::ecs::pointer::Phase;
+
+fn rect(left: f32, top: f32, right: f32, bottom: f32) -> D2DRect {
+    D2DRect {
+        left,
+        top,
+        right,
+        bottom,
+    }
+}
+
+fn utf16(s: &str) -> Vec<u16> {
+    s.encode_utf16().collect()
+}
+
+/// 全体が1文節の変換中文字列
+fn composing(text: &str, attribute: u8) -> Composition {
+    let units = utf16(text);
+    Composition::from_utf16(&units, &vec![attribute; units.len()], &[], None)
+}
+
+fn update(composition: Option<Composition>, result: Option<&str>) -> TextInputMessage {
+    TextInputMessage::Composition {
+        result: result.map(str::to_string),
+        composition,
+    }
+}
+
+fn text(s: &str) -> TextInputOutput {
+    TextInputOutput::Text(TextInputEvent {
+        text: s.to_string(),
+    })
+}
+
+fn comp(event: CompositionEvent) -> TextInputOutput {
+    TextInputOutput::Composition(event)
+}
+
+// ============================================================================
+// 状態機械
+// ============================================================================
+
+#[test]
+fn test_typical_japanese_input_sequence() {
+    let mut composer = TextInputComposer::default();
+
+    assert_eq!(
+        composer.feed(TextInputMessage::StartComposition),
+        vec![comp(CompositionEvent::Started)]
+    );
+    assert!(composer.is_composing());
+
+    let kana = composing("かんじ", 0);
+    assert_eq!(
+        composer.feed(update(Some(kana.clone()), None)),
+        vec![comp(CompositionEvent::Updated(kana.clone()))]
+    );
+    // 同じ内容の更新は出力しない
+    assert!(composer.feed(update(Some(kana), None)).is_empty());
+
+    let converted = composing("漢字", 1);
+    assert_eq!(
+        composer.feed(update(Some(converted.clone()), None)),
+        vec![comp(CompositionEvent::Updated(converted.clone()))]
+    );
+    assert_eq!(composer.composition(), Some(&converted));
+
+    // 確定: 変換中文字列を空にしてから確定文字列
+    assert_eq!(
+        composer.feed(update(None, Some("漢字"))),
+        vec![
+            comp(CompositionEvent::Updated(Composition::default())),
+            text("漢字"),
+        ]
+    );
+    assert_eq!(
+        composer.feed(TextInputMessage::EndComposition),
+        vec![comp(CompositionEvent::Ended)]
+    );
+    assert!(!composer.is_composing());
+}
+
+#[test]
+fn test_partial_commit_keeps_remaining_clauses() {
+    let mut composer = TextInputComposer::default();
+    composer.feed(TextInputMessage::StartComposition);
+
+    // 「今日は」「いい天気」の2文節、2文節目が変換対象
+    let units = utf16("今日はいい天気");
+    let two_clauses = Composition::from_utf16(&units, &[2, 2, 2, 1, 1, 1, 1], &[0, 3, 7], Some(3));
+    assert_eq!(two_clauses.clauses.len(), 2);
+    assert_eq!(
+        two_clauses
+            .target_clause()
+            .map(|c| &two_clauses.text[c.range.clone()]),
+        Some("いい天気")
+    );
+    assert_eq!(two_clauses.clauses[0].attribute, ClauseAttribute::Converted);
+    composer.feed(update(Some(two_clauses), None));
+
+    // 1文節目だけ確定し、残りは変換中のまま
+    let rest = composing("いい天気", 1);
+    assert_eq!(
+        composer.feed(update(Some(rest.clone()), Some("今日は"))),
+        vec![comp(CompositionEvent::Updated(rest)), text("今日は")]
+    );
+    assert!(composer.is_composing());
+}
+
+#[test]
+fn test_implicit_start_and_stray_end() {
+    let mut composer = TextInputComposer::default();
+
+    // 開始していない終了は無視
+    assert!(composer.feed(TextInputMessage::EndComposition).is_empty());
+
+    // STARTCOMPOSITIONなしの更新で暗黙に開始
+    let kana = composing("あ", 0);
+    assert_eq!(
+        composer.feed(update(Some(kana.clone()), None)),
+        vec![
+            comp(CompositionEvent::Started),
+            comp(CompositionEvent::Updated(kana)),
+        ]
+    );
+
+    // 変換していない確定（直接入力IME等）は文字列のみ
+    let mut direct = TextInputComposer::default();
+    assert_eq!(direct.feed(update(None, Some("ａ"))), vec![text("ａ")]);
+    assert!(!direct.is_composing());
+}
+
+#[test]
+fn test_cancel_and_focus_lost() {
+    let mut composer = TextInputComposer::default();
+    composer.feed(TextInputMessage::StartComposition);
+    composer.feed(update(Some(composing("かな", 0)), None));
+
+    // Escで取り消し: 空の変換中文字列 → 終了（確定文字列なし）
+    assert_eq!(
+        composer.feed(update(Some(Composition::default()), None)),
+        vec![comp(CompositionEvent::Updated(Composition::default()))]
+    );
+    assert_eq!(
+        composer.feed(TextInputMessage::EndComposition),
+        vec![comp(CompositionEvent::Ended)]
+    );
+
+    // 変換中のフォーカス喪失
+    composer.feed(update(Some(composing("かな", 0)), None));
+    assert_eq!(
+        composer.feed(TextInputMessage::FocusLost),
+        vec![comp(CompositionEvent::Ended)]
+    );
+    assert!(composer.feed(TextInputMessage::FocusLost).is_empty());
+}
+
+// ============================================================================
+// 配信
+// ============================================================================
+
+#[derive(Resource, Default)]
+struct Received {
+    text: Vec<(Entity, String)>,
+    composition: Vec<(Entity, CompositionEvent)>,
+}
+
+fn on_text(world: &mut World, _: Entity, entity: Entity, ev: &Phase<TextInputEvent>) -> bool {
+    if let Phase::Bubble(event) = ev {
+        world
+            .resource_mut::<Received>()
+            .text
+            .push((entity, event.text.clone()));
+    }
+    false
+}
+
+fn on_composition(
+    world: &mut World,
+    _: Entity,
+    entity: Entity,
+    ev: &Phase<CompositionEvent>,
+) -> bool {
+    if let Phase::Bubble(event) = ev {
+        world
+            .resource_mut::<Received>()
+            .composition
+            .push((entity, event.clone()));
+    }
+    // 入力欄で処理済み
+    ev.is_bubble()
+}
+
+fn setup() -> (World, Entity, Entity) {
+    let mut world = World::new();
+    world.init_resource::<Received>();
+    world.init_resource::<KeyboardInput>();
+    world.init_resource::<KeyboardState>();
+    world.init_resource::<Messages<FocusChangedEvent>>();
+    world.init_resource::<Messages<CandidateWindowRequest>>();
+
+    let window = world
+        .spawn((OnTextInput(on_text), OnComposition(on_composition)))
+        .id();
+    let field = world
+        .spawn((
+            Focusable,
+            OnTextInput(on_text),
+            OnComposition(on_composition),
+            GlobalArrangement {
+                transform: Matrix3x2::translation(10.0, 20.0),
+                bounds: rect(10.0, 20.0, 210.0, 44.0),
+            },
+            ChildOf(window),
+        ))
+        .id();
+    (world, window, field)
+}
+
+fn send(world: &mut World, window: Entity, messages: impl IntoIterator<Item = TextInputMessage>) {
+    for message in messages {
+        world
+            .resource_mut::<KeyboardInput>()
+            .push_text(window, message);
+    }
+    dispatch_keyboard_events(world);
+}
+
+fn candidate_requests(world: &mut World) -> Vec<CandidateWindowRequest> {
+    world
+        .resource_mut::<Messages<CandidateWindowRequest>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn test_chars_route_to_focused_entity() {
+    let (mut world, window, field) = setup();
+
+    // フォーカスなし: ウィンドウへ
+    send(&mut world, window, [TextInputMessage::Char('a' as u16)]);
+    set_focus(&mut world, field);
+    let units = utf16("😀");
+    send(
+        &mut world,
+        window,
+        [
+            TextInputMessage::Char(units[0]),
+            TextInputMessage::Char(units[1]),
+            TextInputMessage::Char(0x08),
+        ],
+    );
+
+    let received = world.resource::<Received>();
+    assert_eq!(
+        received.text,
+        vec![
+            (window, "a".to_string()),
+            (field, "😀".to_string()),
+            (window, "😀".to_string()),
+        ]
+    );
+    // ウィンドウに状態機械が付与される
+    assert!(world.get::<TextInputComposer>(window).is_some());
+}
+
+#[test]
+fn test_composition_routes_and_requests_candidate_window() {
+    let (mut world, window, field) = setup();
+    set_focus(&mut world, field);
+
+    send(
+        &mut world,
+        window,
+        [
+            TextInputMessage::StartComposition,
+            update(Some(composing("にほん", 0)), None),
+        ],
+    );
+    // 矩形が指定されていなければ境界
+    assert_eq!(
+        candidate_requests(&mut world),
+        vec![
+            CandidateWindowRequest {
+                window,
+                exclude: rect(10.0, 20.0, 210.0, 44.0),
+            };
+            2
+        ]
+    );
+
+    // キャレット矩形を指定
+    let caret = rect(40.0, 22.0, 41.0, 42.0);
+    world.entity_mut(field).insert(ImeCaretRect(caret));
+    send(
+        &mut world,
+        window,
+        [
+            update(Some(composing("日本", 1)), None),
+            update(None, Some("日本")),
+            TextInputMessage::EndComposition,
+        ],
+    );
+    assert_eq!(
+        candidate_requests(&mut world),
+        vec![
+            CandidateWindowRequest {
+                window,
+                exclude: caret,
+            };
+            2
+        ]
+    );
+
+    let received = world.resource::<Received>();
+    // OnComposition は入力欄で処理済み（ウィンドウへは伝播しない）
+    assert!(received.composition.iter().all(|&(e, _)| e == field));
+    assert_eq!(
+        received
+            .composition
+            .iter()
+            .map(|(_, event)| event.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CompositionEvent::Started,
+            CompositionEvent::Updated(composing("にほん", 0)),
+            CompositionEvent::Updated(composing("日本", 1)),
+            CompositionEvent::Updated(Composition::default()),
+            CompositionEvent::Ended,
+        ]
+    );
+    assert_eq!(
+        received.text,
+        vec![(field, "日本".to_string()), (window, "日本".to_string())]
+    );
+}
+
+#[test]
+fn test_window_focus_lost_ends_composition() {
+    let (mut world, window, field) = setup();
+    set_focus(&mut world, field);
+    send(
+        &mut world,
+        window,
+        [
+            TextInputMessage::StartComposition,
+            update(Some(composing("か", 0)), None),
+        ],
+    );
+
+    world
+        .resource_mut::<KeyboardInput>()
+        .push_focus_lost(window);
+    dispatch_keyboard_events(&mut world);
+
+    let received = world.resource::<Received>();
+    assert_eq!(
+        received.composition.last(),
+        Some(&(field, CompositionEvent::Ended))
+    );
+    assert!(
+        !world
+            .get::<TextInputComposer>(window)
+            .unwrap()
+            .is_composing()
+    );
+}
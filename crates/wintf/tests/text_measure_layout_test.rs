@@ -0,0 +1,296 @@
+//! テキストの内在サイズ計測のテスト
+//!
+//! FixedAdvanceTextMeasurer（半角 = font_size * 0.5、全角 = font_size、行高 = font_size * 1.25）で
+//! Label / Typewriter のサイズ Auto がテキストから決まることを検証する。
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::widget::text::{Label, TextDirection, Typewriter, TypewriterTalk, TypewriterToken};
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::ChildOf;
+
+fn new_world() -> EcsWorld {
+    let mut ecs_world = EcsWorld::new();
+    ecs_world
+        .world_mut()
+        .insert_resource(TextMeasurerResource::new(
+            FixedAdvanceTextMeasurer::default(),
+        ));
+    ecs_world
+}
+
+/// 指定サイズ・縦並び・先頭寄せのルート
+fn spawn_root(ecs_world: &mut EcsWorld, width: f32, height: f32) -> Entity {
+    ecs_world
+        .world_mut()
+        .spawn((
+            LayoutRoot,
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Px(width)),
+                    height: Some(Dimension::Px(height)),
+                }),
+                flex_direction: Some(FlexDirection::Column),
+                align_items: Some(AlignItems::FlexStart),
+                ..Default::default()
+            },
+        ))
+        .id()
+}
+
+fn spawn_label(ecs_world: &mut EcsWorld, parent: Entity, label: Label, style: BoxStyle) -> Entity {
+    ecs_world
+        .world_mut()
+        .spawn((label, style, Arrangement::default(), ChildOf(parent)))
+        .id()
+}
+
+fn label(text: &str, font_size: f32, direction: TextDirection) -> Label {
+    Label {
+        text: text.to_string(),
+        font_size,
+        direction,
+        ..Default::default()
+    }
+}
+
+fn size_of(ecs_world: &EcsWorld, entity: Entity) -> (f32, f32) {
+    let arr = ecs_world
+        .world()
+        .get::<Arrangement>(entity)
+        .expect("Arrangement");
+    (arr.size.width, arr.size.height)
+}
+
+// ===== 計測器 =====
+
+#[test]
+fn test_fixed_advance_measurer() {
+    let measurer = FixedAdvanceTextMeasurer::default();
+    let text = TextMeasure {
+        text: "Hello wide world".to_string(),
+        font_family: "メイリオ".to_string(),
+        font_size: 20.0,
+        direction: TextDirection::HorizontalLeftToRight,
+    };
+    // max-content: 16文字 × 10
+    assert_eq!(
+        measurer.measure(&text, None),
+        TextExtent {
+            inline: 160.0,
+            block: 25.0
+        }
+    );
+    // min-content: 最長の単語
+    assert_eq!(
+        measurer.measure(&text, Some(0.0)),
+        TextExtent {
+            inline: 50.0,
+            block: 75.0
+        }
+    );
+    // "Hello wide" (100) / "world" (50)
+    assert_eq!(
+        measurer.measure(&text, Some(120.0)),
+        TextExtent {
+            inline: 100.0,
+            block: 50.0
+        }
+    );
+
+    // 全角文字は1文字ごとに折り返せる。改行は常に行を分ける
+    let text = TextMeasure {
+        text: "日本語\nab".to_string(),
+        ..text
+    };
+    assert_eq!(
+        measurer.measure(&text, Some(45.0)),
+        TextExtent {
+            inline: 40.0,
+            block: 75.0
+        }
+    );
+}
+
+#[test]
+fn test_vertical_extent_to_physical() {
+    let extent = TextExtent {
+        inline: 60.0,
+        block: 25.0,
+    };
+    assert_eq!(
+        extent.to_physical(TextDirection::VerticalRightToLeft),
+        taffy::Size {
+            width: 25.0,
+            height: 60.0
+        }
+    );
+    assert_eq!(
+        extent.to_physical(TextDirection::HorizontalRightToLeft),
+        taffy::Size {
+            width: 60.0,
+            height: 25.0
+        }
+    );
+}
+
+// ===== レイアウト =====
+
+#[test]
+fn test_auto_sized_label_uses_text_size() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("Hello world", 20.0, TextDirection::HorizontalLeftToRight),
+        BoxStyle::default(),
+    );
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (110.0, 25.0));
+}
+
+#[test]
+fn test_label_wraps_in_narrow_container() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 60.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("Hello world", 20.0, TextDirection::HorizontalLeftToRight),
+        BoxStyle::default(),
+    );
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (50.0, 50.0));
+}
+
+#[test]
+fn test_fixed_width_label_measures_height() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("Hello world", 20.0, TextDirection::HorizontalLeftToRight),
+        BoxStyle {
+            size: Some(BoxSize {
+                width: Some(Dimension::Px(70.0)),
+                height: None,
+            }),
+            ..Default::default()
+        },
+    );
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (70.0, 50.0));
+}
+
+#[test]
+fn test_vertical_label_inline_axis_is_height() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("日本語", 20.0, TextDirection::VerticalRightToLeft),
+        BoxStyle::default(),
+    );
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (25.0, 60.0));
+}
+
+#[test]
+fn test_vertical_label_wraps_by_height() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("日本語テキスト", 20.0, TextDirection::VerticalRightToLeft),
+        BoxStyle {
+            size: Some(BoxSize {
+                width: None,
+                height: Some(Dimension::Px(60.0)),
+            }),
+            ..Default::default()
+        },
+    );
+
+    ecs_world.try_tick_world();
+
+    // 3文字ごとに列を折り返す: 3列 × 25
+    assert_eq!(size_of(&ecs_world, entity), (75.0, 60.0));
+}
+
+#[test]
+fn test_label_text_change_relayouts() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("Hi", 20.0, TextDirection::HorizontalLeftToRight),
+        BoxStyle::default(),
+    );
+    ecs_world.try_tick_world();
+    assert_eq!(size_of(&ecs_world, entity), (20.0, 25.0));
+
+    ecs_world.world_mut().get_mut::<Label>(entity).unwrap().text = "Hello".to_string();
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (50.0, 25.0));
+}
+
+#[test]
+fn test_typewriter_measures_talk_text() {
+    let mut ecs_world = new_world();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = ecs_world
+        .world_mut()
+        .spawn((
+            Typewriter {
+                font_size: 16.0,
+                ..Default::default()
+            },
+            TypewriterTalk::new(
+                vec![
+                    TypewriterToken::Text("ab".to_string()),
+                    TypewriterToken::Text("c".to_string()),
+                ],
+                0.0,
+            ),
+            BoxStyle::default(),
+            Arrangement::default(),
+            ChildOf(root),
+        ))
+        .id();
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (24.0, 20.0));
+}
+
+#[test]
+fn test_without_measurer_text_is_zero_sized() {
+    let mut ecs_world = new_world();
+    ecs_world
+        .world_mut()
+        .remove_resource::<TextMeasurerResource>();
+    let root = spawn_root(&mut ecs_world, 800.0, 600.0);
+    let entity = spawn_label(
+        &mut ecs_world,
+        root,
+        label("Hello", 20.0, TextDirection::HorizontalLeftToRight),
+        BoxStyle::default(),
+    );
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(size_of(&ecs_world, entity), (0.0, 0.0));
+}
@@ -0,0 +1,259 @@
+//! Transform（回転・スキュー・スケール）を考慮したヒットテストのテスト
+//!
+//! `GlobalArrangement.bounds`（軸平行矩形）ではなく、累積変換行列の逆変換で得た
+//! ローカル座標で判定されることを検証する。
+use bevy_ecs::prelude::*;
+use windows::Win32::Graphics::Imaging::{
+    GUID_WICPixelFormat32bppPBGRA, IWICBitmapSource, WICBitmapCacheOnDemand,
+};
+use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx, CoUninitialize};
+use windows_core::Interface;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::layout::{
+    GlobalArrangement, HitTest, PhysicalPoint, hit_test, hit_test_entity, to_local_point,
+};
+use wintf::ecs::transform::{Rotate, Scale, Skew, Transform, TransformOrigin};
+use wintf::ecs::widget::bitmap_source::{AlphaMask, BitmapSourceResource, WicCore};
+use wintf::ecs::{ChildOf, D2DRect};
+
+/// スケール1で bounds に配置された GlobalArrangement
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+fn rotate(degrees: f32, origin: TransformOrigin) -> Transform {
+    Transform {
+        rotate: Rotate(degrees),
+        origin,
+        ..Default::default()
+    }
+}
+
+fn hits(world: &World, entity: Entity, x: f32, y: f32) -> bool {
+    hit_test_entity(world, entity, PhysicalPoint::new(x, y))
+}
+
+#[test]
+fn test_rotated_square_uses_rotated_shape() {
+    let mut world = World::new();
+    // 100x100 を中心で45度回転（ひし形）
+    let entity = world
+        .spawn((
+            global(100.0, 100.0, 200.0, 200.0),
+            rotate(45.0, TransformOrigin::center()),
+        ))
+        .id();
+
+    assert!(hits(&world, entity, 150.0, 150.0));
+    // 元の矩形の角はひし形の外
+    assert!(!hits(&world, entity, 105.0, 105.0));
+    assert!(!hits(&world, entity, 195.0, 195.0));
+    // 元の矩形の外でも、ひし形の頂点付近はヒット（上頂点 ≒ (150, 79.3)）
+    assert!(hits(&world, entity, 150.0, 85.0));
+    assert!(hits(&world, entity, 215.0, 150.0));
+}
+
+#[test]
+fn test_rotation_about_top_left_origin() {
+    let mut world = World::new();
+    // 100x50 を左上基準で90度回転 → x: -50..0, y: 0..100 を覆う
+    let entity = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 50.0),
+            rotate(90.0, TransformOrigin::top_left()),
+        ))
+        .id();
+
+    assert!(hits(&world, entity, -25.0, 80.0));
+    assert!(!hits(&world, entity, 25.0, 25.0));
+
+    let local = to_local_point(&world, entity, PhysicalPoint::new(-25.0, 80.0)).unwrap();
+    assert!((local.x - 80.0).abs() < 1e-3, "{local:?}");
+    assert!((local.y - 25.0).abs() < 1e-3, "{local:?}");
+}
+
+#[test]
+fn test_skewed_entity() {
+    let mut world = World::new();
+    // 100x50 を左上基準で x方向に45度スキュー → 下辺は右に50ずれる
+    let entity = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 50.0),
+            Transform {
+                skew: Skew::new(45.0, 0.0),
+                origin: TransformOrigin::top_left(),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    // 軸平行矩形の外だがスキュー後の平行四辺形の内側
+    assert!(hits(&world, entity, 130.0, 40.0));
+    // 軸平行矩形の内側だが平行四辺形の外
+    assert!(!hits(&world, entity, 10.0, 40.0));
+    assert!(hits(&world, entity, 10.0, 5.0));
+}
+
+#[test]
+fn test_scaled_entity() {
+    let mut world = World::new();
+    // 中心基準で2倍 → 50..250 を覆う
+    let entity = world
+        .spawn((
+            global(100.0, 100.0, 200.0, 200.0),
+            Transform {
+                scale: Scale::uniform(2.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+
+    assert!(hits(&world, entity, 60.0, 240.0));
+    assert!(!hits(&world, entity, 40.0, 150.0));
+}
+
+#[test]
+fn test_local_point_is_in_dip_under_layout_scale() {
+    let mut world = World::new();
+    // DPIスケール2.0: 100x50 DIP のボックスが物理ピクセル 100..300 x 100..200 に配置
+    let entity = world
+        .spawn(GlobalArrangement {
+            transform: Matrix3x2 {
+                M11: 2.0,
+                M12: 0.0,
+                M21: 0.0,
+                M22: 2.0,
+                M31: 100.0,
+                M32: 100.0,
+            },
+            bounds: D2DRect {
+                left: 100.0,
+                top: 100.0,
+                right: 300.0,
+                bottom: 200.0,
+            },
+        })
+        .id();
+
+    let local = to_local_point(&world, entity, PhysicalPoint::new(160.0, 130.0)).unwrap();
+    assert!((local.x - 30.0).abs() < 1e-3, "{local:?}");
+    assert!((local.y - 15.0).abs() < 1e-3, "{local:?}");
+    assert!((local.size.width - 100.0).abs() < 1e-3, "{local:?}");
+    assert!((local.size.height - 50.0).abs() < 1e-3, "{local:?}");
+}
+
+#[test]
+fn test_children_inherit_parent_rotation() {
+    let mut world = World::new();
+    // 親 200x200 を中心で180度回転、子は親の左上 50x50
+    let parent = world
+        .spawn((
+            global(0.0, 0.0, 200.0, 200.0),
+            rotate(180.0, TransformOrigin::center()),
+        ))
+        .id();
+    let child = world
+        .spawn((global(0.0, 0.0, 50.0, 50.0), ChildOf(parent)))
+        .id();
+
+    // 子は右下に表示される
+    assert!(hits(&world, child, 175.0, 175.0));
+    assert!(!hits(&world, child, 25.0, 25.0));
+
+    assert_eq!(
+        hit_test(&world, parent, PhysicalPoint::new(175.0, 175.0)),
+        Some(child)
+    );
+    assert_eq!(
+        hit_test(&world, parent, PhysicalPoint::new(25.0, 25.0)),
+        Some(parent)
+    );
+}
+
+#[test]
+fn test_degenerate_transform_is_not_hit() {
+    let mut world = World::new();
+    let entity = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 100.0),
+            Transform {
+                scale: Scale::new(0.0, 1.0),
+                ..Default::default()
+            },
+        ))
+        .id();
+    assert!(!hits(&world, entity, 50.0, 50.0));
+}
+
+#[test]
+fn test_without_transform_matches_bounds() {
+    let mut world = World::new();
+    let entity = world.spawn(global(10.0, 20.0, 110.0, 70.0)).id();
+    assert!(hits(&world, entity, 10.0, 20.0));
+    assert!(hits(&world, entity, 110.0, 70.0));
+    assert!(!hits(&world, entity, 111.0, 70.0));
+}
+
+// ===== αマスク =====
+
+/// COMを初期化するヘルパー
+fn with_com_initialized<T, F: FnOnce() -> T>(f: F) -> T {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+    let result = f();
+    unsafe {
+        CoUninitialize();
+    }
+    result
+}
+
+/// 2x2 マスク: 左列が不透明、右列が透明
+fn left_column_mask() -> AlphaMask {
+    let opaque = [0, 0, 0, 255];
+    let transparent = [0, 0, 0, 0];
+    let row = [opaque, transparent].concat();
+    let pixels = [row.clone(), row].concat();
+    AlphaMask::from_pbgra32(&pixels, 2, 2, 8)
+}
+
+fn bitmap_resource(wic: &WicCore, mask: AlphaMask) -> BitmapSourceResource {
+    let bitmap = unsafe {
+        wic.factory()
+            .CreateBitmap(1, 1, &GUID_WICPixelFormat32bppPBGRA, WICBitmapCacheOnDemand)
+            .expect("CreateBitmap failed")
+    };
+    let mut resource = BitmapSourceResource::new(bitmap.cast::<IWICBitmapSource>().unwrap());
+    resource.set_alpha_mask(mask);
+    resource
+}
+
+#[test]
+fn test_alpha_mask_is_sampled_in_local_coordinates() {
+    with_com_initialized(|| {
+        let wic = WicCore::new().expect("WicCore creation failed");
+        let mut world = World::new();
+        // 100x100 を中心で90度回転: ローカルの左列（不透明）は画面の上半分に来る
+        let entity = world
+            .spawn((
+                global(0.0, 0.0, 100.0, 100.0),
+                rotate(90.0, TransformOrigin::center()),
+                HitTest::alpha_mask(),
+                bitmap_resource(&wic, left_column_mask()),
+            ))
+            .id();
+
+        assert!(hits(&world, entity, 50.0, 25.0));
+        assert!(hits(&world, entity, 10.0, 10.0));
+        assert!(!hits(&world, entity, 50.0, 75.0));
+        assert!(!hits(&world, entity, 10.0, 90.0));
+    });
+}
@@ -0,0 +1,285 @@
+//! 書字方向（WritingMode）と論理プロパティのテスト
+//!
+//! 1. 論理プロパティが書字方向に応じて物理プロパティへ変換されること
+//! 2. WritingMode が子孫へ継承され、変更時に再レイアウトされること
+use bevy_ecs::prelude::*;
+use wintf::ecs::layout::*;
+use wintf::ecs::world::EcsWorld;
+use wintf::ecs::ChildOf;
+
+fn px(v: f32) -> LengthPercentageAuto {
+    LengthPercentageAuto::Px(v)
+}
+
+fn logical_item() -> BoxStyle {
+    BoxStyle {
+        logical_size: Some(LogicalSize {
+            inline_size: Some(Dimension::Px(100.0)),
+            block_size: Some(Dimension::Px(50.0)),
+        }),
+        flex_shrink: Some(0.0),
+        ..Default::default()
+    }
+}
+
+fn arrangement(ecs_world: &EcsWorld, entity: Entity) -> (f32, f32, f32, f32) {
+    let arr = ecs_world
+        .world()
+        .get::<Arrangement>(entity)
+        .expect("Arrangement");
+    (arr.offset.x, arr.offset.y, arr.size.width, arr.size.height)
+}
+
+// ===== 変換テスト =====
+
+#[test]
+fn test_logical_size_maps_to_physical() {
+    let style = logical_item();
+
+    let horizontal = style.to_taffy_style(WritingMode::HorizontalTb);
+    assert_eq!(horizontal.size.width, taffy::Dimension::length(100.0));
+    assert_eq!(horizontal.size.height, taffy::Dimension::length(50.0));
+
+    let vertical = style.to_taffy_style(WritingMode::VerticalRl);
+    assert_eq!(vertical.size.width, taffy::Dimension::length(50.0));
+    assert_eq!(vertical.size.height, taffy::Dimension::length(100.0));
+
+    // From は横書き扱い
+    let from: taffy::Style = (&style).into();
+    assert_eq!(from, horizontal);
+}
+
+#[test]
+fn test_logical_size_overrides_only_specified_axis() {
+    let style = BoxStyle {
+        size: Some(BoxSize {
+            width: Some(Dimension::Px(10.0)),
+            height: Some(Dimension::Px(20.0)),
+        }),
+        logical_size: Some(LogicalSize {
+            inline_size: Some(Dimension::Px(300.0)),
+            block_size: None,
+        }),
+        ..Default::default()
+    };
+    let taffy_style = style.to_taffy_style(WritingMode::VerticalLr);
+    assert_eq!(taffy_style.size.width, taffy::Dimension::length(10.0));
+    assert_eq!(taffy_style.size.height, taffy::Dimension::length(300.0));
+}
+
+#[test]
+fn test_logical_margin_block_start_per_writing_mode() {
+    let style = BoxStyle {
+        logical_margin: Some(LogicalRect {
+            block_start: Some(px(10.0)),
+            inline_start: Some(px(5.0)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let margin = |mode| style.to_taffy_style(mode).margin;
+
+    let tb = margin(WritingMode::HorizontalTb);
+    assert_eq!(tb.top, taffy::LengthPercentageAuto::length(10.0));
+    assert_eq!(tb.left, taffy::LengthPercentageAuto::length(5.0));
+
+    let rl = margin(WritingMode::VerticalRl);
+    assert_eq!(rl.right, taffy::LengthPercentageAuto::length(10.0));
+    assert_eq!(rl.top, taffy::LengthPercentageAuto::length(5.0));
+
+    let lr = margin(WritingMode::VerticalLr);
+    assert_eq!(lr.left, taffy::LengthPercentageAuto::length(10.0));
+    assert_eq!(lr.top, taffy::LengthPercentageAuto::length(5.0));
+}
+
+#[test]
+fn test_logical_rect_overrides_only_specified_sides() {
+    let style = BoxStyle {
+        margin: Some(BoxMargin(Rect {
+            left: px(1.0),
+            right: px(2.0),
+            top: px(3.0),
+            bottom: px(4.0),
+        })),
+        logical_margin: Some(LogicalRect {
+            block_start: Some(px(10.0)),
+            ..Default::default()
+        }),
+        logical_padding: Some(LogicalRect {
+            inline_end: Some(LengthPercentage::Px(7.0)),
+            ..Default::default()
+        }),
+        logical_inset: Some(LogicalRect {
+            inline_start: Some(px(8.0)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let taffy_style = style.to_taffy_style(WritingMode::VerticalRl);
+
+    // block_start は vertical-rl で right。他の辺は物理指定を維持
+    assert_eq!(
+        taffy_style.margin.left,
+        taffy::LengthPercentageAuto::length(1.0)
+    );
+    assert_eq!(
+        taffy_style.margin.right,
+        taffy::LengthPercentageAuto::length(10.0)
+    );
+    assert_eq!(
+        taffy_style.margin.top,
+        taffy::LengthPercentageAuto::length(3.0)
+    );
+    assert_eq!(
+        taffy_style.margin.bottom,
+        taffy::LengthPercentageAuto::length(4.0)
+    );
+
+    // 物理指定がない辺は taffy デフォルト（padding は 0、inset は auto）
+    assert_eq!(
+        taffy_style.padding.bottom,
+        taffy::LengthPercentage::length(7.0)
+    );
+    assert_eq!(
+        taffy_style.padding.top,
+        taffy::LengthPercentage::length(0.0)
+    );
+    assert_eq!(
+        taffy_style.inset.top,
+        taffy::LengthPercentageAuto::length(8.0)
+    );
+    assert_eq!(taffy_style.inset.left, taffy::LengthPercentageAuto::auto());
+}
+
+#[test]
+fn test_logical_flex_direction_mapping() {
+    use LogicalFlexDirection::*;
+    let cases = [
+        (WritingMode::HorizontalTb, Inline, FlexDirection::Row),
+        (WritingMode::HorizontalTb, Block, FlexDirection::Column),
+        (WritingMode::VerticalRl, Inline, FlexDirection::Column),
+        (WritingMode::VerticalRl, Block, FlexDirection::RowReverse),
+        (WritingMode::VerticalRl, BlockReverse, FlexDirection::Row),
+        (WritingMode::VerticalLr, Block, FlexDirection::Row),
+        (
+            WritingMode::VerticalLr,
+            InlineReverse,
+            FlexDirection::ColumnReverse,
+        ),
+    ];
+    for (mode, logical, expected) in cases {
+        let style = BoxStyle {
+            flex_direction: Some(FlexDirection::Row),
+            logical_flex_direction: Some(logical),
+            ..Default::default()
+        };
+        let taffy_style = style.to_taffy_style(mode);
+        assert_eq!(taffy_style.display, taffy::Display::Flex);
+        assert_eq!(
+            taffy_style.flex_direction, expected,
+            "{:?} {:?}",
+            mode, logical
+        );
+    }
+}
+
+// ===== レイアウトテスト =====
+
+/// WritingMode を持つルート → ブロック方向Flexコンテナー → 論理サイズの子2つ
+fn spawn_logical_tree(ecs_world: &mut EcsWorld, mode: WritingMode) -> (Entity, Entity, Entity) {
+    let world = ecs_world.world_mut();
+    let root = world
+        .spawn((
+            LayoutRoot,
+            mode,
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Px(800.0)),
+                    height: Some(Dimension::Px(600.0)),
+                }),
+                ..Default::default()
+            },
+        ))
+        .id();
+    let container = world
+        .spawn((
+            BoxStyle {
+                size: Some(BoxSize {
+                    width: Some(Dimension::Percent(100.0)),
+                    height: Some(Dimension::Percent(100.0)),
+                }),
+                logical_flex_direction: Some(LogicalFlexDirection::Block),
+                align_items: Some(AlignItems::FlexStart),
+                ..Default::default()
+            },
+            Arrangement::default(),
+            ChildOf(root),
+        ))
+        .id();
+    let first = world
+        .spawn((logical_item(), Arrangement::default(), ChildOf(container)))
+        .id();
+    let second = world
+        .spawn((logical_item(), Arrangement::default(), ChildOf(container)))
+        .id();
+    (root, first, second)
+}
+
+#[test]
+fn test_vertical_rl_inherited_layout() {
+    let mut ecs_world = EcsWorld::new();
+    let (_, first, second) = spawn_logical_tree(&mut ecs_world, WritingMode::VerticalRl);
+
+    ecs_world.try_tick_world();
+
+    // ブロック方向は右→左、インラインサイズは高さ
+    assert_eq!(arrangement(&ecs_world, first), (750.0, 0.0, 50.0, 100.0));
+    assert_eq!(arrangement(&ecs_world, second), (700.0, 0.0, 50.0, 100.0));
+}
+
+#[test]
+fn test_writing_mode_change_relayouts() {
+    let mut ecs_world = EcsWorld::new();
+    let (root, first, second) = spawn_logical_tree(&mut ecs_world, WritingMode::VerticalRl);
+    ecs_world.try_tick_world();
+
+    *ecs_world.world_mut().get_mut::<WritingMode>(root).unwrap() = WritingMode::HorizontalTb;
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, first), (0.0, 0.0, 100.0, 50.0));
+    assert_eq!(arrangement(&ecs_world, second), (0.0, 50.0, 100.0, 50.0));
+
+    // WritingMode を外すと既定の横書き（結果は同じ）
+    ecs_world
+        .world_mut()
+        .entity_mut(root)
+        .remove::<WritingMode>();
+    ecs_world.try_tick_world();
+    assert_eq!(arrangement(&ecs_world, second), (0.0, 50.0, 100.0, 50.0));
+}
+
+#[test]
+fn test_nested_writing_mode_overrides_ancestor() {
+    let mut ecs_world = EcsWorld::new();
+    let (_, first, _) = spawn_logical_tree(&mut ecs_world, WritingMode::VerticalRl);
+    let nested = ecs_world
+        .world_mut()
+        .spawn((
+            WritingMode::HorizontalTb,
+            logical_item(),
+            Arrangement::default(),
+            ChildOf(first),
+        ))
+        .id();
+
+    ecs_world.try_tick_world();
+
+    assert_eq!(arrangement(&ecs_world, first), (750.0, 0.0, 50.0, 100.0));
+    assert_eq!(
+        ecs_world.world().get::<Arrangement>(nested).unwrap().size,
+        Size {
+            width: 100.0,
+            height: 50.0
+        }
+    );
+}
@@ -0,0 +1,266 @@
+//! ZIndex（兄弟間の順序・トップレイヤー）のテスト
+//!
+//! 1. `stacking_order`による描画順
+//! 2. `hit_test`（線形走査・空間インデックス）の前後関係とクリップ
+//! 3. トップレイヤーからのバブリング経路
+//! 4. Visual挿入位置（`stack_position`）
+use bevy_ecs::prelude::*;
+use windows_numerics::Matrix3x2;
+use wintf::ecs::D2DRect;
+use wintf::ecs::layout::{
+    ClipToBounds, GlobalArrangement, HitTestIndex, PhysicalPoint, StackPosition, ZIndex, hit_test,
+    hit_test_linear, sorted_children, stack_position, stacking_order, update_hit_test_index_system,
+};
+use wintf::ecs::pointer::build_bubble_path;
+
+fn global(left: f32, top: f32, right: f32, bottom: f32) -> GlobalArrangement {
+    GlobalArrangement {
+        transform: Matrix3x2::translation(left, top),
+        bounds: D2DRect {
+            left,
+            top,
+            right,
+            bottom,
+        },
+    }
+}
+
+/// 同じ矩形を持つ子を順に追加
+fn spawn_stack(world: &mut World, parent: Entity, count: usize) -> Vec<Entity> {
+    (0..count)
+        .map(|_| {
+            world
+                .spawn((global(0.0, 0.0, 100.0, 100.0), ChildOf(parent)))
+                .id()
+        })
+        .collect()
+}
+
+fn point() -> PhysicalPoint {
+    PhysicalPoint::new(50.0, 50.0)
+}
+
+// ===== stacking_order =====
+
+#[test]
+fn test_default_order_follows_children() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let children = spawn_stack(&mut world, root, 3);
+    assert_eq!(
+        stacking_order(&world, root),
+        vec![root, children[0], children[1], children[2]]
+    );
+    assert_eq!(hit_test(&world, root, point()), Some(children[2]));
+}
+
+#[test]
+fn test_local_z_index_reorders_siblings() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let children = spawn_stack(&mut world, root, 3);
+    // 先頭の子を最前面へ、最後の子を最背面へ
+    world.entity_mut(children[0]).insert(ZIndex::Local(1));
+    world.entity_mut(children[2]).insert(ZIndex::Local(-1));
+
+    assert_eq!(
+        stacking_order(&world, root),
+        vec![root, children[2], children[1], children[0]]
+    );
+    assert_eq!(hit_test(&world, root, point()), Some(children[0]));
+}
+
+#[test]
+fn test_local_z_index_is_scoped_to_siblings() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let [back, front]: [Entity; 2] = spawn_stack(&mut world, root, 2).try_into().unwrap();
+    // 背面の親の子に大きな値を付けても、前面の兄弟の上には出ない
+    let grandchild = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 100.0),
+            ZIndex::Local(1000),
+            ChildOf(back),
+        ))
+        .id();
+
+    assert_eq!(
+        stacking_order(&world, root),
+        vec![root, back, grandchild, front]
+    );
+    assert_eq!(hit_test(&world, root, point()), Some(front));
+}
+
+#[test]
+fn test_equal_local_values_keep_children_order() {
+    let mut world = World::new();
+    let children: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+    let sorted = sorted_children(&children, |e| {
+        Some(if e == children[0] || e == children[2] {
+            ZIndex::Local(5)
+        } else {
+            ZIndex::Local(0)
+        })
+    });
+    assert_eq!(
+        sorted,
+        vec![children[1], children[3], children[0], children[2]]
+    );
+}
+
+// ===== トップレイヤー =====
+
+#[test]
+fn test_global_z_index_lifts_above_other_branches() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let [back, front]: [Entity; 2] = spawn_stack(&mut world, root, 2).try_into().unwrap();
+    let overlay = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 100.0),
+            ZIndex::Global(0),
+            ChildOf(back),
+        ))
+        .id();
+    let overlay_child = world
+        .spawn((global(40.0, 40.0, 60.0, 60.0), ChildOf(overlay)))
+        .id();
+
+    assert_eq!(
+        stacking_order(&world, root),
+        vec![root, back, front, overlay, overlay_child]
+    );
+    assert_eq!(hit_test(&world, root, point()), Some(overlay_child));
+    assert_eq!(
+        hit_test(&world, root, PhysicalPoint::new(10.0, 10.0)),
+        Some(overlay)
+    );
+}
+
+#[test]
+fn test_top_layer_is_ordered_by_global_value() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let children = spawn_stack(&mut world, root, 3);
+    world.entity_mut(children[0]).insert(ZIndex::Global(10));
+    world.entity_mut(children[1]).insert(ZIndex::Global(5));
+    // 負の値でもトップレイヤーは通常のコンテンツより前面
+    world.entity_mut(children[2]).insert(ZIndex::Global(-1));
+    let normal = spawn_stack(&mut world, root, 1)[0];
+
+    assert_eq!(
+        stacking_order(&world, root),
+        vec![root, normal, children[2], children[1], children[0]]
+    );
+    assert_eq!(hit_test(&world, root, point()), Some(children[0]));
+}
+
+#[test]
+fn test_top_layer_escapes_ancestor_clip() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 500.0, 500.0)).id();
+    // スクロール領域（クリップ）の外にはみ出すツールチップ
+    let viewport = world
+        .spawn((global(0.0, 0.0, 100.0, 100.0), ClipToBounds, ChildOf(root)))
+        .id();
+    let clipped = world
+        .spawn((global(80.0, 80.0, 200.0, 200.0), ChildOf(viewport)))
+        .id();
+    let tooltip = world
+        .spawn((
+            global(80.0, 80.0, 200.0, 200.0),
+            ZIndex::Global(0),
+            ChildOf(clipped),
+        ))
+        .id();
+    let tooltip_text = world
+        .spawn((global(150.0, 150.0, 190.0, 190.0), ChildOf(tooltip)))
+        .id();
+
+    let outside = PhysicalPoint::new(170.0, 170.0);
+    assert_eq!(hit_test(&world, root, outside), Some(tooltip_text));
+    world.entity_mut(tooltip).remove::<ZIndex>();
+    assert_eq!(hit_test(&world, root, outside), Some(root));
+}
+
+#[test]
+fn test_bubble_path_follows_logical_parents() {
+    let mut world = World::new();
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let [button, cover]: [Entity; 2] = spawn_stack(&mut world, root, 2).try_into().unwrap();
+    let popup = world
+        .spawn((
+            global(0.0, 0.0, 100.0, 100.0),
+            ZIndex::Global(1),
+            ChildOf(button),
+        ))
+        .id();
+
+    // 後ろの兄弟（cover）より前面でヒットし、論理的な親（button）へバブリングする
+    let hit = hit_test(&world, root, point()).unwrap();
+    assert_eq!(hit, popup);
+    assert_eq!(build_bubble_path(&world, hit), vec![popup, button, root]);
+    assert!(!build_bubble_path(&world, hit).contains(&cover));
+}
+
+// ===== 空間インデックスとの等価性 =====
+
+#[test]
+fn test_index_respects_z_index_and_follows_changes() {
+    let mut world = World::new();
+    world.insert_resource(HitTestIndex::default());
+    let root = world.spawn(global(0.0, 0.0, 100.0, 100.0)).id();
+    let [a, b]: [Entity; 2] = spawn_stack(&mut world, root, 2).try_into().unwrap();
+    let a_child = world
+        .spawn((global(20.0, 20.0, 80.0, 80.0), ChildOf(a)))
+        .id();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(update_hit_test_index_system);
+    schedule.run(&mut world);
+    assert_eq!(hit_test(&world, root, point()), Some(b));
+
+    world.entity_mut(a).insert(ZIndex::Local(1));
+    schedule.run(&mut world);
+    assert_eq!(hit_test(&world, root, point()), Some(a_child));
+
+    world.entity_mut(a).insert(ZIndex::Global(0));
+    world.entity_mut(b).insert(ZIndex::Global(1));
+    schedule.run(&mut world);
+    assert_eq!(hit_test(&world, root, point()), Some(b));
+    // サブツリーをルートにしても線形走査と一致
+    for entity in [root, a, b] {
+        for p in [point(), PhysicalPoint::new(5.0, 5.0)] {
+            assert_eq!(
+                hit_test(&world, entity, p),
+                hit_test_linear(&world, entity, p)
+            );
+        }
+    }
+
+    world.entity_mut(b).remove::<ZIndex>();
+    schedule.run(&mut world);
+    assert_eq!(hit_test(&world, root, point()), Some(a_child));
+}
+
+// ===== Visual挿入位置 =====
+
+#[test]
+fn test_stack_position() {
+    let mut world = World::new();
+    let e: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+    // 何も追加されていない
+    assert_eq!(stack_position(&e, e[1], |_| false), StackPosition::Top);
+    // 前面側の最も近い追加済み兄弟の背面
+    assert_eq!(
+        stack_position(&e, e[1], |x| x == e[3] || x == e[0]),
+        StackPosition::Behind(e[3])
+    );
+    // 前面側になければ背面側の兄弟の前面
+    assert_eq!(
+        stack_position(&e, e[2], |x| x == e[0]),
+        StackPosition::Above(e[0])
+    );
+    // 兄弟に含まれない
+    let stranger = world.spawn_empty().id();
+    assert_eq!(stack_position(&e, stranger, |_| true), StackPosition::Top);
+}